@@ -5,7 +5,7 @@ fn main() {
 }
 
 fn app(cx: Scope) -> Element {
-    let login = use_callback!(cx, move |_| async move {
+    let login = use_async_callback(cx, move |_| async move {
         let res = reqwest::get("https://dog.ceo/api/breeds/list/all")
             .await
             .unwrap()
@@ -17,6 +17,6 @@ fn app(cx: Scope) -> Element {
     });
 
     cx.render(rsx! {
-        button { onclick: login, "Click me!" }
+        button { onclick: move |evt| login.spawn(evt), "Click me!" }
     })
 }
@@ -3,6 +3,7 @@ use crate::assets::WebAssetConfigDropGuard;
 use crate::plugin::PluginManager;
 use crate::server::fullstack::FullstackServerEnvGuard;
 use crate::server::fullstack::FullstackWebEnvGuard;
+use crate::size_report::{SizeReport, SizeReportEnvGuard};
 use dioxus_cli_config::Platform;
 
 use super::*;
@@ -13,6 +14,10 @@ use super::*;
 pub struct Build {
     #[clap(flatten)]
     pub build: ConfigOptsBuild,
+
+    /// Write a JSON report of how much of the wasm binary each section, crate and component takes up to this file. Only supported for web and fullstack builds
+    #[clap(long)]
+    pub size_report: Option<PathBuf>,
 }
 
 impl Build {
@@ -52,6 +57,8 @@ impl Build {
         // #[cfg(feature = "plugin")]
         // let _ = PluginManager::on_build_start(&crate_config, &platform);
 
+        let _size_report_guard = self.size_report.as_ref().map(|_| SizeReportEnvGuard::new());
+
         let build_result = match platform {
             Platform::Web => crate::builder::build(&crate_config, false, self.build.skip_assets)?,
             Platform::Desktop => {
@@ -90,6 +97,27 @@ impl Build {
             }
         };
 
+        if let Some(report_path) = &self.size_report {
+            if platform == Platform::Desktop {
+                log::warn!("Size reports are only supported for web and fullstack builds");
+            } else {
+                let wasm_path = crate_config
+                    .out_dir()
+                    .join("assets")
+                    .join("dioxus")
+                    .join(format!(
+                        "{}_bg.wasm",
+                        crate_config.dioxus_config.application.name
+                    ));
+                let report = SizeReport::from_file(&wasm_path)?;
+                if report.crates.is_empty() {
+                    log::warn!("The wasm binary has no function names, so the size report only lists sections. wasm-opt removes the names unless it is run with `-g`");
+                }
+                report.write(report_path)?;
+                log::info!("📊 Wrote the size report to {}", report_path.display());
+            }
+        }
+
         let temp = gen_page(&crate_config, build_result.assets.as_ref(), false);
 
         let mut file = std::fs::File::create(
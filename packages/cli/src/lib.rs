@@ -7,6 +7,7 @@ pub const DIOXUS_CLI_VERSION: &str = "0.4.1";
 mod assets;
 pub mod builder;
 pub mod server;
+pub mod size_report;
 pub mod tools;
 
 pub use builder::*;
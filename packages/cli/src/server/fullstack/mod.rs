@@ -92,7 +92,11 @@ fn build_web(serve: ConfigOptsServe, target_directory: &std::path::Path) -> Resu
     web_config.platform = Some(dioxus_cli_config::Platform::Web);
 
     let _gaurd = FullstackWebEnvGuard::new(&web_config);
-    crate::cli::build::Build { build: web_config }.build(None, Some(target_directory))
+    crate::cli::build::Build {
+        build: web_config,
+        size_report: None,
+    }
+    .build(None, Some(target_directory))
 }
 
 // Debug mode web builds have a very large size by default. If debug mode is not enabled, we strip some of the debug info by default
@@ -0,0 +1,441 @@
+//! Attribute the size of a wasm binary to its sections, crates and components.
+//!
+//! Components built with `--cfg dioxus_size_report` record their path in the `dioxus_size_report` custom section.
+//! Functions are matched to components and crates through the names in the `name` section, which wasm-bindgen keeps
+//! and demangles in `dx build`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::{Error, Result};
+
+/// The name of the custom section components write their path to.
+pub const COMPONENT_SECTION: &str = "dioxus_size_report";
+
+/// The sizes of the parts of a wasm binary, in bytes.
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+pub struct SizeReport {
+    /// The size of the whole binary.
+    pub total: u64,
+    /// The size of each section, by section name.
+    pub sections: BTreeMap<String, u64>,
+    /// The size of the function bodies from each crate.
+    pub crates: BTreeMap<String, u64>,
+    /// The size of the function bodies of each component, including the closures inside it.
+    pub components: BTreeMap<String, u64>,
+    /// The size of the function bodies that have no name.
+    pub unnamed: u64,
+}
+
+impl SizeReport {
+    /// Read a wasm binary and attribute its size.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        Self::analyze(&std::fs::read(path)?)
+    }
+
+    /// Attribute the size of a wasm binary.
+    pub fn analyze(wasm: &[u8]) -> Result<Self> {
+        let module = Module::parse(wasm)?;
+
+        let mut report = SizeReport {
+            total: wasm.len() as u64,
+            ..Default::default()
+        };
+        for (name, size) in &module.sections {
+            *report.sections.entry(name.clone()).or_default() += size;
+        }
+
+        let components = module.components();
+        for (index, size) in module.function_sizes() {
+            let Some(name) = module.function_names.get(&index) else {
+                report.unnamed += size;
+                continue;
+            };
+            *report
+                .crates
+                .entry(crate_name(name).to_string())
+                .or_default() += size;
+            if let Some(component) = find_component(&components, name) {
+                *report.components.entry(component.to_string()).or_default() += size;
+            }
+        }
+        for component in components {
+            report.components.entry(component.to_string()).or_default();
+        }
+
+        Ok(report)
+    }
+
+    /// Write the report as JSON.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|err| Error::Unique(err.to_string()))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Builds with this guard alive pass `--cfg dioxus_size_report` to rustc, so components record their path in the binary.
+pub(crate) struct SizeReportEnvGuard {
+    old_rustflags: String,
+}
+
+impl SizeReportEnvGuard {
+    pub fn new() -> Self {
+        let old_rustflags = std::env::var("RUSTFLAGS").unwrap_or_default();
+        std::env::set_var(
+            "RUSTFLAGS",
+            format!("{old_rustflags} --cfg dioxus_size_report"),
+        );
+        Self { old_rustflags }
+    }
+}
+
+impl Drop for SizeReportEnvGuard {
+    fn drop(&mut self) {
+        std::env::set_var("RUSTFLAGS", &self.old_rustflags);
+    }
+}
+
+/// The crate a demangled function name belongs to. Trait impls like `<my_app::Foo as core::fmt::Debug>::fmt` belong
+/// to the crate of the type.
+fn crate_name(name: &str) -> &str {
+    let name = name.trim_start_matches(['<', '&', '*', ' ']);
+    let name = name.strip_prefix("mut ").unwrap_or(name);
+    let name = name.strip_prefix("dyn ").unwrap_or(name);
+    let end = name
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(name.len());
+    match &name[..end] {
+        "" => "unknown",
+        name => name,
+    }
+}
+
+/// The component a function is part of: the component function itself or a closure or item nested inside it.
+fn find_component<'a>(components: &[&'a str], name: &str) -> Option<&'a str> {
+    components
+        .iter()
+        .filter(|component| {
+            name.strip_prefix(**component)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+        .max_by_key(|component| component.len())
+        .copied()
+}
+
+#[derive(Default)]
+struct Module<'a> {
+    sections: Vec<(String, u64)>,
+    imported_functions: u32,
+    function_bodies: Vec<u64>,
+    function_names: BTreeMap<u32, String>,
+    component_section: Vec<&'a [u8]>,
+}
+
+impl<'a> Module<'a> {
+    fn parse(wasm: &'a [u8]) -> Result<Self> {
+        let mut reader = Reader::new(wasm);
+        if reader.bytes(4)? != b"\0asm" {
+            return Err(Error::ParseError("not a wasm binary".to_string()));
+        }
+        reader.bytes(4)?;
+
+        let mut module = Module::default();
+        while !reader.is_empty() {
+            let start = reader.position;
+            let id = reader.byte()?;
+            let len = reader.u32()? as usize;
+            let mut section = Reader::new(reader.bytes(len)?);
+            let size = (reader.position - start) as u64;
+
+            let name = match id {
+                0 => {
+                    let name = section.name()?;
+                    match name {
+                        "name" => module.parse_names(section)?,
+                        COMPONENT_SECTION => module.component_section.push(section.rest()),
+                        _ => {}
+                    }
+                    format!("custom:{name}")
+                }
+                2 => {
+                    module.parse_imports(section)?;
+                    "import".to_string()
+                }
+                10 => {
+                    module.parse_code(section)?;
+                    "code".to_string()
+                }
+                1 => "type".to_string(),
+                3 => "function".to_string(),
+                4 => "table".to_string(),
+                5 => "memory".to_string(),
+                6 => "global".to_string(),
+                7 => "export".to_string(),
+                8 => "start".to_string(),
+                9 => "element".to_string(),
+                11 => "data".to_string(),
+                12 => "datacount".to_string(),
+                13 => "tag".to_string(),
+                id => format!("unknown:{id}"),
+            };
+            module.sections.push((name, size));
+        }
+
+        Ok(module)
+    }
+
+    fn parse_imports(&mut self, mut section: Reader) -> Result<()> {
+        for _ in 0..section.u32()? {
+            section.name()?;
+            section.name()?;
+            match section.byte()? {
+                // function
+                0x00 => {
+                    section.u32()?;
+                    self.imported_functions += 1;
+                }
+                // table
+                0x01 => {
+                    section.byte()?;
+                    section.limits()?;
+                }
+                // memory
+                0x02 => section.limits()?,
+                // global
+                0x03 => {
+                    section.bytes(2)?;
+                }
+                // tag
+                0x04 => {
+                    section.byte()?;
+                    section.u32()?;
+                }
+                kind => {
+                    return Err(Error::ParseError(format!("unknown import kind {kind}")));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_code(&mut self, mut section: Reader) -> Result<()> {
+        for _ in 0..section.u32()? {
+            let start = section.position;
+            let len = section.u32()? as usize;
+            section.bytes(len)?;
+            self.function_bodies.push((section.position - start) as u64);
+        }
+        Ok(())
+    }
+
+    fn parse_names(&mut self, mut section: Reader) -> Result<()> {
+        while !section.is_empty() {
+            let id = section.byte()?;
+            let len = section.u32()? as usize;
+            let mut subsection = Reader::new(section.bytes(len)?);
+            // Only the function names are needed
+            if id != 1 {
+                continue;
+            }
+            for _ in 0..subsection.u32()? {
+                let index = subsection.u32()?;
+                let name = subsection.name()?;
+                self.function_names.insert(index, name.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// The size of the body of each function defined in the module, by function index.
+    fn function_sizes(&self) -> impl Iterator<Item = (u32, u64)> + '_ {
+        self.function_bodies
+            .iter()
+            .enumerate()
+            .map(|(index, size)| (self.imported_functions + index as u32, *size))
+    }
+
+    fn components(&self) -> Vec<&'a str> {
+        let mut components: Vec<&str> = self
+            .component_section
+            .iter()
+            .flat_map(|section| section.split(|byte| *byte == b'\n'))
+            .filter_map(|name| std::str::from_utf8(name).ok())
+            .filter(|name| !name.is_empty())
+            .collect();
+        components.sort_unstable();
+        components.dedup();
+        components
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.data[self.position..];
+        self.position = self.data.len();
+        rest
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| Error::ParseError("unexpected end of the wasm binary".to_string()))?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let mut result = 0u32;
+        for shift in (0..35).step_by(7) {
+            let byte = self.byte()?;
+            result |= ((byte & 0x7f) as u32) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        Err(Error::ParseError(
+            "invalid integer in the wasm binary".to_string(),
+        ))
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        let len = self.u32()? as usize;
+        std::str::from_utf8(self.bytes(len)?)
+            .map_err(|_| Error::ParseError("invalid name in the wasm binary".to_string()))
+    }
+
+    fn limits(&mut self) -> Result<()> {
+        let flags = self.byte()?;
+        self.u32()?;
+        if flags & 0x01 != 0 {
+            self.u32()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leb(mut value: u32, out: &mut Vec<u8>) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    fn name(name: &str, out: &mut Vec<u8>) {
+        leb(name.len() as u32, out);
+        out.extend_from_slice(name.as_bytes());
+    }
+
+    fn section(id: u8, contents: Vec<u8>, out: &mut Vec<u8>) {
+        out.push(id);
+        leb(contents.len() as u32, out);
+        out.extend(contents);
+    }
+
+    /// A module with one imported function and three function bodies of 4, 8 and 16 bytes
+    fn module() -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+
+        let mut imports = vec![1];
+        name("env", &mut imports);
+        name("log", &mut imports);
+        imports.extend([0x00, 0x00]);
+        section(2, imports, &mut wasm);
+
+        let mut code = vec![3];
+        for len in [3, 7, 15] {
+            leb(len, &mut code);
+            code.resize(code.len() + len as usize, 0x0b);
+        }
+        section(10, code, &mut wasm);
+
+        let mut names = Vec::new();
+        name("name", &mut names);
+        let mut functions = vec![4];
+        for (index, function) in [
+            "env::log",
+            "my_app::Counter",
+            "my_app::Counter::{{closure}}",
+            "<dioxus_core::VNode as core::ops::Drop>::drop",
+        ]
+        .iter()
+        .enumerate()
+        {
+            leb(index as u32, &mut functions);
+            name(function, &mut functions);
+        }
+        names.push(1);
+        leb(functions.len() as u32, &mut names);
+        names.extend(functions);
+        section(0, names, &mut wasm);
+
+        let mut components = Vec::new();
+        name(COMPONENT_SECTION, &mut components);
+        components.extend_from_slice(b"my_app::Counter\nmy_app::Unused\n");
+        section(0, components, &mut wasm);
+
+        wasm
+    }
+
+    #[test]
+    fn attributes_functions() {
+        let wasm = module();
+        let report = SizeReport::analyze(&wasm).unwrap();
+
+        assert_eq!(report.total, wasm.len() as u64);
+        // The section id, length and function count, then the bodies
+        assert_eq!(report.sections["code"], 3 + 4 + 8 + 16);
+        assert_eq!(report.crates["my_app"], 4 + 8);
+        assert_eq!(report.crates["dioxus_core"], 16);
+        assert_eq!(report.components["my_app::Counter"], 4 + 8);
+        assert_eq!(report.components["my_app::Unused"], 0);
+        assert_eq!(report.unnamed, 0);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(SizeReport::analyze(b"<html></html>").is_err());
+    }
+
+    #[test]
+    fn crate_names() {
+        assert_eq!(crate_name("my_app::Counter"), "my_app");
+        assert_eq!(
+            crate_name("<&mut dioxus_core::Scope as core::fmt::Debug>::fmt"),
+            "dioxus_core"
+        );
+        assert_eq!(crate_name("{{closure}}"), "unknown");
+    }
+}
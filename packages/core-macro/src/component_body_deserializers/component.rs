@@ -27,9 +27,12 @@ fn get_out_comp_fn(orig_comp_fn: &ItemFn, cx_pat: &Pat) -> ItemFn {
         ..orig_comp_fn.clone()
     };
 
+    let size_report_entry = size_report_entry(&orig_comp_fn.sig.ident);
+
     ItemFn {
         block: parse_quote! {
             {
+                #size_report_entry
                 #[warn(non_snake_case)]
                 #[allow(clippy::inline_always)]
                 #[inline(always)]
@@ -41,6 +44,32 @@ fn get_out_comp_fn(orig_comp_fn: &ItemFn, cx_pat: &Pat) -> ItemFn {
     }
 }
 
+/// Record the path of the component in the `dioxus_size_report` section of the binary when the app is built with
+/// `--cfg dioxus_size_report`. `dx build --size-report` reads the section to attribute code size to components.
+///
+/// The `allow` has to be on an item around the `cfg`, not on the item the `cfg` is on.
+fn size_report_entry(comp_ident: &Ident) -> TokenStream2 {
+    quote! {
+        #[allow(unknown_lints, unexpected_cfgs)]
+        const _: () = {
+            #[cfg(dioxus_size_report)]
+            const NAME: &str = concat!(module_path!(), "::", stringify!(#comp_ident));
+            #[cfg(dioxus_size_report)]
+            #[used]
+            #[link_section = "dioxus_size_report"]
+            static ENTRY: [u8; NAME.len() + 1] = {
+                let mut entry = [b'\n'; NAME.len() + 1];
+                let mut i = 0;
+                while i < NAME.len() {
+                    entry[i] = NAME.as_bytes()[i];
+                    i += 1;
+                }
+                entry
+            };
+        };
+    }
+}
+
 /// The args and deserializing implementation for the [`crate::component`] macro.
 #[derive(Clone)]
 pub struct ComponentDeserializerArgs {
@@ -89,6 +89,8 @@ impl VirtualDom {
             height: self.scopes[id.0].height(),
             id,
         });
+        self.urgent_scopes.remove(&id);
+        self.deferred_scopes.remove(&id);
 
         // Remove all VNode ids from the scope
         for id in self.scopes[id.0]
@@ -123,6 +125,14 @@ impl VirtualDom {
             }
         }
 
+        // Let the rest of the suspense list reveal if it was waiting on this scope
+        let suspense_list = self.scopes[id.0].context().suspense_list.take();
+        if let Some(list) = suspense_list {
+            for id in list.remove(id) {
+                self.mark_dirty(id);
+            }
+        }
+
         self.scopes.remove(id.0);
     }
 
@@ -0,0 +1,191 @@
+use crate::{
+    innerlude::spawn_at,
+    runtime::{with_current_scope, Runtime, ScopeGuard},
+    ScopeId, ScopeState,
+};
+use generational_box::{GenerationalBox, UnsyncStorage};
+use std::{future::Future, pin::Pin};
+
+/// A function that can be copied into async blocks, child components, and other hooks. Callbacks are owned by the scope that created them and are dropped when that scope is dropped.
+///
+/// Unlike [`crate::EventHandler`], a callback is `Copy` and `'static`, and it can return a value to the caller.
+///
+/// ```rust, ignore
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     let double = use_callback(cx, |value: i32| value * 2);
+///
+///     render! { Child { double: double } }
+/// }
+///
+/// #[component]
+/// fn Child(cx: Scope, double: Callback<i32, i32>) -> Element {
+///     render! { "{double.call(21)}" }
+/// }
+/// ```
+pub struct Callback<Args: 'static = (), Ret: 'static = ()> {
+    origin: ScopeId,
+    callback: GenerationalBox<Box<dyn FnMut(Args) -> Ret>, UnsyncStorage>,
+}
+
+impl<Args: 'static, Ret: 'static> Callback<Args, Ret> {
+    /// Create a new callback owned by the current scope.
+    ///
+    /// Panics if called outside of a component.
+    pub fn new(f: impl FnMut(Args) -> Ret + 'static) -> Self {
+        with_current_scope(|cx| Self {
+            origin: cx.id,
+            callback: cx.owner.insert(Box::new(f) as Box<dyn FnMut(Args) -> Ret>),
+        })
+        .expect("Callbacks must be created inside of a component")
+    }
+
+    /// Call the callback. The callback runs as if it were inside the scope that created it.
+    ///
+    /// Panics if the scope that created the callback has been dropped, or if the callback is called recursively.
+    pub fn call(&self, args: Args) -> Ret {
+        let runtime = Runtime::current();
+        let _scope = runtime
+            .as_ref()
+            .map(|runtime| ScopeGuard::new(runtime, self.origin));
+        let mut callback = self.callback.write();
+        callback(args)
+    }
+
+    /// Replace the function the callback calls. Every copy of the callback will call the new function.
+    pub fn replace(&self, f: impl FnMut(Args) -> Ret + 'static) {
+        self.callback.set(Box::new(f));
+    }
+
+    /// Get the scope that created the callback.
+    pub fn origin_scope(&self) -> ScopeId {
+        self.origin
+    }
+}
+
+impl<Args: 'static, Ret: 'static> Copy for Callback<Args, Ret> {}
+
+impl<Args: 'static, Ret: 'static> Clone for Callback<Args, Ret> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Args: 'static, Ret: 'static> PartialEq for Callback<Args, Ret> {
+    fn eq(&self, other: &Self) -> bool {
+        self.callback.ptr_eq(&other.callback)
+    }
+}
+
+impl<Args: 'static, Ret: 'static> std::fmt::Debug for Callback<Args, Ret> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Callback")
+            .field("origin", &self.origin)
+            .field("callback", &self.callback)
+            .finish()
+    }
+}
+
+/// A [`Callback`] that runs an async function. The caller can await the result, or spawn it in the scope that created the callback.
+pub struct AsyncCallback<Args: 'static = (), Ret: 'static = ()> {
+    inner: Callback<Args, Pin<Box<dyn Future<Output = Ret>>>>,
+}
+
+impl<Args: 'static, Ret: 'static> AsyncCallback<Args, Ret> {
+    /// Create a new async callback owned by the current scope.
+    ///
+    /// Panics if called outside of a component.
+    pub fn new<F: Future<Output = Ret> + 'static>(mut f: impl FnMut(Args) -> F + 'static) -> Self {
+        Self {
+            inner: Callback::new(move |args| {
+                Box::pin(f(args)) as Pin<Box<dyn Future<Output = Ret>>>
+            }),
+        }
+    }
+
+    /// Call the callback and return a future that resolves to its result.
+    pub fn call(&self, args: Args) -> impl Future<Output = Ret> {
+        self.inner.call(args)
+    }
+
+    /// Replace the function the callback calls. Every copy of the callback will call the new function.
+    pub fn replace<F: Future<Output = Ret> + 'static>(
+        &self,
+        mut f: impl FnMut(Args) -> F + 'static,
+    ) {
+        self.inner
+            .replace(move |args| Box::pin(f(args)) as Pin<Box<dyn Future<Output = Ret>>>);
+    }
+
+    /// Get the scope that created the callback.
+    pub fn origin_scope(&self) -> ScopeId {
+        self.inner.origin_scope()
+    }
+}
+
+impl<Args: 'static> AsyncCallback<Args> {
+    /// Call the callback and run the future it returns in the scope that created the callback. The future will be cancelled if that scope is dropped.
+    pub fn spawn(&self, args: Args) {
+        let future = self.inner.call(args);
+        spawn_at(future, self.inner.origin);
+    }
+}
+
+impl<Args: 'static, Ret: 'static> Copy for AsyncCallback<Args, Ret> {}
+
+impl<Args: 'static, Ret: 'static> Clone for AsyncCallback<Args, Ret> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Args: 'static, Ret: 'static> PartialEq for AsyncCallback<Args, Ret> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<Args: 'static, Ret: 'static> std::fmt::Debug for AsyncCallback<Args, Ret> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AsyncCallback").field(&self.inner).finish()
+    }
+}
+
+/// Create a [`Callback`] owned by the current component. The callback always calls the closure from the latest render, so it sees the latest props and state.
+pub fn use_callback<Args: 'static, Ret: 'static>(
+    cx: &ScopeState,
+    f: impl FnMut(Args) -> Ret + 'static,
+) -> Callback<Args, Ret> {
+    let mut f = Some(f);
+    let callback = *cx.use_hook(|| Callback::new(f.take().unwrap()));
+    if let Some(f) = f {
+        callback.replace(f);
+    }
+    callback
+}
+
+/// Create an [`AsyncCallback`] owned by the current component. The callback always calls the closure from the latest render, so it sees the latest props and state.
+///
+/// ```rust, ignore
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     let login = use_async_callback(cx, |_| async move {
+///         // log the user in
+///     });
+///
+///     render! {
+///         button { onclick: move |evt| login.spawn(evt), "Log in" }
+///     }
+/// }
+/// ```
+pub fn use_async_callback<Args: 'static, Ret: 'static, F: Future<Output = Ret> + 'static>(
+    cx: &ScopeState,
+    f: impl FnMut(Args) -> F + 'static,
+) -> AsyncCallback<Args, Ret> {
+    let mut f = Some(f);
+    let callback = *cx.use_hook(|| AsyncCallback::new(f.take().unwrap()));
+    if let Some(f) = f {
+        callback.replace(f);
+    }
+    callback
+}
@@ -727,30 +727,17 @@ impl<'b> VirtualDom {
             self.diff_node(&old[new_index_to_old_index[*idx]], &new[*idx]);
         }
 
-        let mut nodes_created = 0;
-
         // add mount instruction for the first items not covered by the lis
         let last = *lis_sequence.last().unwrap();
         if last < (new.len() - 1) {
-            for (idx, new_node) in new[(last + 1)..].iter().enumerate() {
-                let new_idx = idx + last + 1;
-                let old_index = new_index_to_old_index[new_idx];
-                if old_index == u32::MAX as usize {
-                    nodes_created += self.create(new_node);
-                } else {
-                    self.diff_node(&old[old_index], new_node);
-                    nodes_created += self.push_all_real_nodes(new_node);
-                }
-            }
-
             let id = self.find_last_element(&new[last]);
-            if nodes_created > 0 {
-                self.mutations.push(Mutation::InsertAfter {
-                    id,
-                    m: nodes_created,
-                })
-            }
-            nodes_created = 0;
+            self.mount_keyed_gap(
+                old,
+                new,
+                (last + 1)..new.len(),
+                &new_index_to_old_index,
+                KeyedAnchor::After(id),
+            );
         }
 
         // for each spacing, generate a mount instruction
@@ -758,26 +745,14 @@ impl<'b> VirtualDom {
         let mut last = *lis_iter.next().unwrap();
         for next in lis_iter {
             if last - next > 1 {
-                for (idx, new_node) in new[(next + 1)..last].iter().enumerate() {
-                    let new_idx = idx + next + 1;
-                    let old_index = new_index_to_old_index[new_idx];
-                    if old_index == u32::MAX as usize {
-                        nodes_created += self.create(new_node);
-                    } else {
-                        self.diff_node(&old[old_index], new_node);
-                        nodes_created += self.push_all_real_nodes(new_node);
-                    }
-                }
-
                 let id = self.find_first_element(&new[last]);
-                if nodes_created > 0 {
-                    self.mutations.push(Mutation::InsertBefore {
-                        id,
-                        m: nodes_created,
-                    });
-                }
-
-                nodes_created = 0;
+                self.mount_keyed_gap(
+                    old,
+                    new,
+                    (next + 1)..last,
+                    &new_index_to_old_index,
+                    KeyedAnchor::Before(id),
+                );
             }
             last = *next;
         }
@@ -785,26 +760,70 @@ impl<'b> VirtualDom {
         // add mount instruction for the last items not covered by the lis
         let first_lis = *lis_sequence.first().unwrap();
         if first_lis > 0 {
-            for (idx, new_node) in new[..first_lis].iter().enumerate() {
-                let old_index = new_index_to_old_index[idx];
-                if old_index == u32::MAX as usize {
-                    nodes_created += self.create(new_node);
-                } else {
-                    self.diff_node(&old[old_index], new_node);
-                    nodes_created += self.push_all_real_nodes(new_node);
+            let id = self.find_first_element(&new[first_lis]);
+            self.mount_keyed_gap(
+                old,
+                new,
+                0..first_lis,
+                &new_index_to_old_index,
+                KeyedAnchor::Before(id),
+            );
+        }
+    }
+
+    /// Mount the nodes in a gap between the items of the longest increasing subsequence next to an anchor node
+    ///
+    /// Nodes that were already mounted are moved instead of inserted so the renderer can keep their state. Runs of
+    /// moved and created nodes are mounted one after the other, which keeps the order of the gap.
+    fn mount_keyed_gap(
+        &mut self,
+        old: &'b [VNode<'b>],
+        new: &'b [VNode<'b>],
+        gap: std::ops::Range<usize>,
+        new_index_to_old_index: &[usize],
+        mut anchor: KeyedAnchor,
+    ) {
+        let mut run = 0;
+        let mut moving = false;
+
+        for new_idx in gap {
+            let new_node = &new[new_idx];
+            let old_index = new_index_to_old_index[new_idx];
+            let is_move = old_index != u32::MAX as usize;
+
+            if run > 0 && is_move != moving {
+                self.mount_keyed_run(anchor, run, moving);
+                run = 0;
+
+                // Nodes inserted after the anchor would end up in front of the run we just mounted
+                if let KeyedAnchor::After(_) = anchor {
+                    anchor = KeyedAnchor::After(self.find_last_element(&new[new_idx - 1]));
                 }
             }
 
-            let id = self.find_first_element(&new[first_lis]);
-            if nodes_created > 0 {
-                self.mutations.push(Mutation::InsertBefore {
-                    id,
-                    m: nodes_created,
-                });
-            }
+            moving = is_move;
+            run += if is_move {
+                self.diff_node(&old[old_index], new_node);
+                self.push_all_real_nodes(new_node)
+            } else {
+                self.create(new_node)
+            };
+        }
+
+        if run > 0 {
+            self.mount_keyed_run(anchor, run, moving);
         }
     }
 
+    fn mount_keyed_run(&mut self, anchor: KeyedAnchor, m: usize, moving: bool) {
+        self.mutations.push(match (anchor, moving) {
+            (KeyedAnchor::Before(id), false) => Mutation::InsertBefore { id, m },
+            (KeyedAnchor::Before(id), true) => Mutation::MoveBefore { id, m },
+            (KeyedAnchor::After(id), false) => Mutation::InsertAfter { id, m },
+            (KeyedAnchor::After(id), true) => Mutation::MoveAfter { id, m },
+        });
+    }
+
     /// Push all the real nodes on the stack
     fn push_all_real_nodes(&mut self, node: &'b VNode<'b>) -> usize {
         node.template
@@ -1152,6 +1171,13 @@ impl<'b> VirtualDom {
     }
 }
 
+/// Where the nodes in a gap of a keyed list are mounted, see [`VirtualDom::mount_keyed_gap`]
+#[derive(Clone, Copy)]
+enum KeyedAnchor {
+    Before(ElementId),
+    After(ElementId),
+}
+
 /// Are the templates the same?
 ///
 /// We need to check for the obvious case, and the non-obvious case where the template as cloned
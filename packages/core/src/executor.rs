@@ -0,0 +1,45 @@
+use std::{future::Future, pin::Pin, rc::Rc, time::Duration};
+
+use crate::runtime::with_runtime;
+
+/// The async runtime the tasks of a [`VirtualDom`](crate::VirtualDom) run on.
+///
+/// Dioxus polls component tasks itself, but tasks often need timers or IO from the runtime they run in. Setting an executor with [`VirtualDom::with_executor`](crate::VirtualDom::with_executor) lets an app embedded in an existing runtime (tokio, async-std, bevy or a custom executor on embedded devices) provide them, instead of the renderer assuming its own runtime.
+///
+/// ```rust, ignore
+/// struct AsyncStdExecutor;
+///
+/// impl Executor for AsyncStdExecutor {
+///     fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+///         Box::pin(async_std::task::sleep(duration))
+///     }
+/// }
+///
+/// let dom = VirtualDom::new(app).with_executor(AsyncStdExecutor);
+/// ```
+pub trait Executor {
+    /// Run `poll` in the context of the executor. The virtual dom polls its tasks inside this function.
+    ///
+    /// Executors that keep their timers and IO in a thread local context, like tokio, enter that context here. The default implementation just calls `poll`.
+    fn enter(&self, poll: &mut dyn FnMut()) {
+        poll()
+    }
+
+    /// Create a future that finishes after `duration` with the timer of the executor.
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>>;
+}
+
+impl<E: Executor + ?Sized> Executor for Rc<E> {
+    fn enter(&self, poll: &mut dyn FnMut()) {
+        (**self).enter(poll)
+    }
+
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+        (**self).sleep(duration)
+    }
+}
+
+/// Get the executor of the current virtual dom, if one was set with [`VirtualDom::with_executor`](crate::VirtualDom::with_executor).
+pub fn current_executor() -> Option<Rc<dyn Executor>> {
+    with_runtime(|rt| rt.executor()).flatten()
+}
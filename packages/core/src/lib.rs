@@ -6,11 +6,13 @@
 mod any_props;
 mod arena;
 mod bump_frame;
+mod callback;
 mod create;
 mod diff;
 mod dirty_scope;
 mod error_boundary;
 mod events;
+mod executor;
 mod fragment;
 mod lazynodes;
 mod mutations;
@@ -21,13 +23,16 @@ mod scheduler;
 mod scope_arena;
 mod scope_context;
 mod scopes;
+mod suspense_list;
 mod virtual_dom;
 
 pub(crate) mod innerlude {
     pub use crate::arena::*;
+    pub use crate::callback::*;
     pub use crate::dirty_scope::*;
     pub use crate::error_boundary::*;
     pub use crate::events::*;
+    pub use crate::executor::*;
     pub use crate::fragment::*;
     pub use crate::lazynodes::*;
     pub use crate::mutations::*;
@@ -38,6 +43,7 @@ pub(crate) mod innerlude {
     pub use crate::scheduler::*;
     pub use crate::scope_context::*;
     pub use crate::scopes::*;
+    pub use crate::suspense_list::*;
     pub use crate::virtual_dom::*;
 
     /// An [`Element`] is a possibly-none [`VNode`] created by calling `render` on [`Scope`] or [`ScopeState`].
@@ -76,11 +82,12 @@ pub(crate) mod innerlude {
 }
 
 pub use crate::innerlude::{
-    fc_to_builder, vdom_is_rendering, AnyValue, Attribute, AttributeType, AttributeValue,
-    BorrowedAttributeValue, CapturedError, Component, DynamicNode, Element, ElementId, Event,
-    Fragment, HasAttributes, IntoDynNode, LazyNodes, MountedAttribute, Mutation, Mutations,
-    Properties, RenderReturn, Scope, ScopeId, ScopeState, Scoped, TaskId, Template,
-    TemplateAttribute, TemplateNode, VComponent, VNode, VPlaceholder, VText, VirtualDom,
+    fc_to_builder, vdom_is_rendering, AnyValue, AsyncCallback, Attribute, AttributeType,
+    AttributeValue, BorrowedAttributeValue, Callback, CapturedError, Component, DynamicNode,
+    Element, ElementId, Event, Executor, Fragment, HasAttributes, IntoDynNode, LazyNodes,
+    MountedAttribute, Mutation, Mutations, PendingWork, Properties, RenderReturn, Scope, ScopeId,
+    ScopeState, Scoped, TaskId, Template, TemplateAttribute, TemplateNode, VComponent, VNode,
+    VPlaceholder, VText, VirtualDom,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -88,13 +95,16 @@ pub use crate::innerlude::{
 /// This includes types like [`Scope`], [`Element`], and [`Component`].
 pub mod prelude {
     pub use crate::innerlude::{
-        consume_context, consume_context_from_scope, current_scope_id, fc_to_builder, has_context,
-        provide_context, provide_context_to_scope, provide_root_context, push_future,
-        remove_future, schedule_update_any, spawn, spawn_forever, suspend, use_error_boundary,
-        AnyValue, Attribute, AttributeType, Component, Element, ErrorBoundary, Event, EventHandler,
-        Fragment, HasAttributes, IntoAttributeValue, IntoDynNode, LazyNodes, MountedAttribute,
-        Properties, Runtime, RuntimeGuard, Scope, ScopeId, ScopeState, Scoped, TaskId, Template,
-        TemplateAttribute, TemplateNode, Throw, VNode, VirtualDom,
+        consume_context, consume_context_from_scope, current_executor, current_scope_id,
+        fc_to_builder, has_context, has_context_in_scope, provide_context,
+        provide_context_to_scope, provide_root_context, push_future, remove_future,
+        schedule_update_any, schedule_urgent_update_any, spawn, spawn_at, spawn_forever, suspend,
+        use_async_callback, use_callback, use_error_boundary, use_suspense_list, AnyValue,
+        AsyncCallback, Attribute, AttributeType, Callback, Component, Element, ErrorBoundary,
+        Event, EventHandler, Executor, Fragment, HasAttributes, IntoAttributeValue, IntoDynNode,
+        LazyNodes, MountedAttribute, Properties, RevealOrder, Runtime, RuntimeGuard, Scope,
+        ScopeId, ScopeState, Scoped, SuspenseList, TaskId, Template, TemplateAttribute,
+        TemplateNode, Throw, VNode, VirtualDom,
     };
 }
 
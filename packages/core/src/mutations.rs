@@ -189,6 +189,30 @@ pub enum Mutation<'a> {
         m: usize,
     },
 
+    /// Move a number of nodes that are already mounted after a given node.
+    ///
+    /// This is emitted when keyed children are reordered. Renderers should move the nodes without unmounting them,
+    /// so they keep state like running animations, focus, and the document of an iframe.
+    MoveAfter {
+        /// The ID of the node to move the nodes after.
+        id: ElementId,
+
+        /// The number of nodes on the stack to move after the target node.
+        m: usize,
+    },
+
+    /// Move a number of nodes that are already mounted before a given node.
+    ///
+    /// This is emitted when keyed children are reordered. Renderers should move the nodes without unmounting them,
+    /// so they keep state like running animations, focus, and the document of an iframe.
+    MoveBefore {
+        /// The ID of the node to move the nodes before.
+        id: ElementId,
+
+        /// The number of nodes on the stack to move before the target node.
+        m: usize,
+    },
+
     /// Set the value of a node's attribute.
     SetAttribute {
         /// The name of the attribute to set.
@@ -1,6 +1,8 @@
 use std::cell::{Cell, Ref, RefCell};
 
-use crate::{innerlude::Scheduler, scope_context::ScopeContext, scopes::ScopeId};
+use crate::{
+    executor::Executor, innerlude::Scheduler, scope_context::ScopeContext, scopes::ScopeId,
+};
 use std::rc::Rc;
 
 thread_local! {
@@ -49,6 +51,14 @@ pub struct Runtime {
     // We use this to track the current scope
     pub(crate) scope_stack: RefCell<Vec<ScopeId>>,
     pub(crate) rendering: Cell<bool>,
+
+    pub(crate) executor: RefCell<Option<Rc<dyn Executor>>>,
+
+    // Set while the updates of a transition are scheduled
+    pub(crate) in_transition: Cell<bool>,
+
+    // Called once every deferred update of the current transitions is rendered
+    pub(crate) finished_transitions: RefCell<Vec<Box<dyn FnOnce()>>>,
 }
 
 impl Runtime {
@@ -61,6 +71,12 @@ impl Runtime {
             scope_stack: Default::default(),
 
             rendering: Cell::new(true),
+
+            executor: Default::default(),
+
+            in_transition: Cell::new(false),
+
+            finished_transitions: Default::default(),
         })
     }
 
@@ -69,6 +85,28 @@ impl Runtime {
         RUNTIMES.with(|stack| stack.borrow().last().cloned())
     }
 
+    /// Get the executor the tasks of this runtime run on, if one was set
+    pub fn executor(&self) -> Option<Rc<dyn Executor>> {
+        self.executor.borrow().clone()
+    }
+
+    /// Run `update` as a transition. Components that are marked dirty inside `update` are rendered after every other dirty component, so expensive updates don't delay the response to input.
+    ///
+    /// Renderers with a frame budget handle input between the chunks of a transition. `on_finished` is called once every update of the transition is rendered.
+    pub fn start_transition(
+        self: &Rc<Self>,
+        update: impl FnOnce(),
+        on_finished: impl FnOnce() + 'static,
+    ) {
+        RuntimeGuard::with(self.clone(), None, || {
+            let _transition = TransitionGuard::new(&self.in_transition);
+            update();
+        });
+        self.finished_transitions
+            .borrow_mut()
+            .push(Box::new(on_finished));
+    }
+
     /// Create a scope context. This slab is synchronized with the scope slab.
     pub(crate) fn create_context_at(&self, id: ScopeId, context: ScopeContext) {
         let mut contexts = self.scope_contexts.borrow_mut();
@@ -79,7 +117,9 @@ impl Runtime {
     }
 
     pub(crate) fn remove_context(&self, id: ScopeId) {
-        self.scope_contexts.borrow_mut()[id.0] = None;
+        // Drop the context after the borrow is released so callbacks that are dropped with it can access the runtime
+        let context = self.scope_contexts.borrow_mut()[id.0].take();
+        drop(context);
     }
 
     /// Get the current scope id
@@ -91,10 +131,8 @@ impl Runtime {
     ///
     /// Useful in a limited number of scenarios, not public.
     pub(crate) fn with_scope<O>(&self, id: ScopeId, f: impl FnOnce() -> O) -> O {
-        self.scope_stack.borrow_mut().push(id);
-        let o = f();
-        self.scope_stack.borrow_mut().pop();
-        o
+        let _scope = ScopeGuard::new(self, id);
+        f()
     }
 
     /// Get the context for any scope given its ID
@@ -108,6 +146,46 @@ impl Runtime {
     }
 }
 
+/// Makes a scope the current scope of the runtime until it is dropped, even if the code inside of it panics
+pub(crate) struct ScopeGuard<'a> {
+    runtime: &'a Runtime,
+}
+
+impl<'a> ScopeGuard<'a> {
+    pub(crate) fn new(runtime: &'a Runtime, id: ScopeId) -> Self {
+        runtime.scope_stack.borrow_mut().push(id);
+        Self { runtime }
+    }
+}
+
+impl Drop for ScopeGuard<'_> {
+    fn drop(&mut self) {
+        self.runtime.scope_stack.borrow_mut().pop();
+    }
+}
+
+/// Marks the runtime as inside of a transition until it is dropped, even if the update panics
+struct TransitionGuard<'a> {
+    in_transition: &'a Cell<bool>,
+    nested: bool,
+}
+
+impl<'a> TransitionGuard<'a> {
+    fn new(in_transition: &'a Cell<bool>) -> Self {
+        let nested = in_transition.replace(true);
+        Self {
+            in_transition,
+            nested,
+        }
+    }
+}
+
+impl Drop for TransitionGuard<'_> {
+    fn drop(&mut self) {
+        self.in_transition.set(self.nested);
+    }
+}
+
 /// A guard for a new runtime. This must be used to override the current runtime when importing components from a dynamic library that has it's own runtime.
 ///
 /// ```rust
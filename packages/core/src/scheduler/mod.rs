@@ -1,4 +1,4 @@
-use crate::ScopeId;
+use crate::{runtime::with_runtime, ScopeId};
 use slab::Slab;
 
 mod task;
@@ -14,10 +14,26 @@ pub(crate) enum SchedulerMsg {
     /// Immediate updates from Components that mark them as dirty
     Immediate(ScopeId),
 
+    /// Urgent updates that are rendered before any other dirty components
+    Urgent(ScopeId),
+
+    /// Low priority updates from transitions that are rendered after every other dirty component
+    Deferred(ScopeId),
+
     /// A task has woken and needs to be progressed
     TaskNotified(TaskId),
 }
 
+impl SchedulerMsg {
+    /// An update for a component, which is deferred if it was scheduled inside [`crate::Runtime::start_transition`]
+    pub(crate) fn update(id: ScopeId) -> Self {
+        match with_runtime(|runtime| runtime.in_transition.get()) {
+            Some(true) => SchedulerMsg::Deferred(id),
+            _ => SchedulerMsg::Immediate(id),
+        }
+    }
+}
+
 use std::{cell::RefCell, rc::Rc};
 
 pub(crate) struct Scheduler {
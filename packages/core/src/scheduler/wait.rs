@@ -8,6 +8,15 @@ impl VirtualDom {
     /// queue
     pub(crate) fn handle_task_wakeup(&mut self, id: TaskId) {
         let _runtime = RuntimeGuard::new(self.runtime.clone());
+
+        // Poll inside the executor so the task can use its timers and IO
+        match self.runtime.executor() {
+            Some(executor) => executor.enter(&mut || self.poll_task(id)),
+            None => self.poll_task(id),
+        }
+    }
+
+    fn poll_task(&self, id: TaskId) {
         let mut tasks = self.runtime.scheduler.tasks.borrow_mut();
 
         let task = match tasks.get(id.0) {
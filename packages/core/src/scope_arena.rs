@@ -53,7 +53,7 @@ impl VirtualDom {
         // Remove all the outdated listeners
         self.ensure_drop_safety(scope_id);
 
-        let new_nodes = unsafe {
+        let mut new_nodes = unsafe {
             let scope = &self.scopes[scope_id.0];
             scope.previous_frame().reset();
 
@@ -69,6 +69,25 @@ impl VirtualDom {
             props.render(scope).extend_lifetime()
         };
 
+        // Keep components in a suspense list hidden until the list reveals them
+        let suspense_list = self.scopes[scope_id.0]
+            .context()
+            .suspense_list
+            .borrow()
+            .clone();
+        if let Some(list) = suspense_list {
+            let ready = !self.scopes[scope_id.0].context().suspended.get();
+            for id in list.set_ready(scope_id, ready) {
+                if id != scope_id {
+                    self.mark_dirty(id);
+                }
+            }
+            if ready && !list.is_revealed(scope_id) {
+                self.scopes[scope_id.0].context().suspended.set(true);
+                new_nodes = RenderReturn::default();
+            }
+        }
+
         let scope = &self.scopes[scope_id.0];
 
         // We write on top of the previous frame and then make it the current by pushing the generation forward
@@ -87,6 +106,8 @@ impl VirtualDom {
             height: context.height,
             id: context.id,
         });
+        self.urgent_scopes.remove(&context.id);
+        self.deferred_scopes.remove(&context.id);
 
         if context.suspended.get() {
             if matches!(allocated, RenderReturn::Aborted(_)) {
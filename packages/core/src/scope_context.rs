@@ -1,8 +1,9 @@
 use crate::{
     innerlude::{Scheduler, SchedulerMsg},
     runtime::{with_current_scope, with_runtime},
-    Element, ScopeId, TaskId,
+    Element, ScopeId, SuspenseList, TaskId,
 };
+use generational_box::{Owner, UnsyncStorage};
 use rustc_hash::FxHashSet;
 use std::{
     any::Any,
@@ -23,11 +24,16 @@ pub(crate) struct ScopeContext {
 
     pub(crate) height: u32,
     pub(crate) suspended: Cell<bool>,
+    // The suspense list this scope joined the first time it suspended
+    pub(crate) suspense_list: RefCell<Option<SuspenseList>>,
 
     pub(crate) shared_contexts: RefCell<Vec<Box<dyn Any>>>,
 
     pub(crate) tasks: Rc<Scheduler>,
     pub(crate) spawned_tasks: RefCell<FxHashSet<TaskId>>,
+
+    // Owns the callbacks created in this scope
+    pub(crate) owner: Owner<UnsyncStorage>,
 }
 
 impl ScopeContext {
@@ -44,9 +50,11 @@ impl ScopeContext {
             parent_id,
             height,
             suspended: Cell::new(false),
+            suspense_list: RefCell::new(None),
             shared_contexts: RefCell::new(vec![]),
             tasks,
             spawned_tasks: RefCell::new(FxHashSet::default()),
+            owner: UnsyncStorage::owner(),
         }
     }
 
@@ -63,7 +71,7 @@ impl ScopeContext {
     /// ## Notice: you should prefer using [`Self::schedule_update_any`] and [`Self::scope_id`]
     pub fn schedule_update(&self) -> Arc<dyn Fn() + Send + Sync + 'static> {
         let (chan, id) = (self.tasks.sender.clone(), self.scope_id());
-        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::Immediate(id))))
+        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::update(id))))
     }
 
     /// Schedule an update for any component given its [`ScopeId`].
@@ -74,7 +82,17 @@ impl ScopeContext {
     pub fn schedule_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
         let chan = self.tasks.sender.clone();
         Arc::new(move |id| {
-            chan.unbounded_send(SchedulerMsg::Immediate(id)).unwrap();
+            chan.unbounded_send(SchedulerMsg::update(id)).unwrap();
+        })
+    }
+
+    /// Schedule an urgent update for any component given its [`ScopeId`].
+    ///
+    /// Urgent updates are rendered before every other dirty component, so they are not delayed by large updates that are rendered across several frames with [`crate::VirtualDom::render_with_budget`].
+    pub fn schedule_urgent_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
+        let chan = self.tasks.sender.clone();
+        Arc::new(move |id| {
+            chan.unbounded_send(SchedulerMsg::Urgent(id)).unwrap();
         })
     }
 
@@ -89,7 +107,7 @@ impl ScopeContext {
     pub fn needs_update_any(&self, id: ScopeId) {
         self.tasks
             .sender
-            .unbounded_send(SchedulerMsg::Immediate(id))
+            .unbounded_send(SchedulerMsg::update(id))
             .expect("Scheduler to exist if scope exists");
     }
 
@@ -242,6 +260,12 @@ impl ScopeContext {
     /// Mark this component as suspended and then return None
     pub fn suspend(&self) -> Option<Element> {
         self.suspended.set(true);
+        if self.suspense_list.borrow().is_none() {
+            if let Some(list) = self.consume_context::<SuspenseList>() {
+                list.register(self.id);
+                *self.suspense_list.borrow_mut() = Some(list);
+            }
+        }
         None
     }
 }
@@ -255,6 +279,11 @@ pub fn schedule_update_any() -> Option<Arc<dyn Fn(ScopeId) + Send + Sync>> {
     with_current_scope(|cx| cx.schedule_update_any())
 }
 
+/// Schedule an urgent update for any component given its [`ScopeId`]. Urgent updates are rendered before every other dirty component.
+pub fn schedule_urgent_update_any() -> Option<Arc<dyn Fn(ScopeId) + Send + Sync>> {
+    with_current_scope(|cx| cx.schedule_urgent_update_any())
+}
+
 /// Get the current scope id
 pub fn current_scope_id() -> Option<ScopeId> {
     with_runtime(|rt| rt.current_scope_id()).flatten()
@@ -285,6 +314,15 @@ pub fn has_context<T: 'static + Clone>() -> Option<T> {
     with_current_scope(|cx| cx.has_context::<T>()).flatten()
 }
 
+/// Check if the given scope has a context. Unlike [`consume_context_from_scope`], this does not look at parent scopes
+pub fn has_context_in_scope<T: 'static + Clone>(scope_id: ScopeId) -> Option<T> {
+    with_runtime(|rt| {
+        rt.get_context(scope_id)
+            .and_then(|cx| cx.has_context::<T>())
+    })
+    .flatten()
+}
+
 /// Provide context to the current scope
 pub fn provide_context<T: 'static + Clone>(value: T) -> Option<T> {
     with_current_scope(|cx| cx.provide_context(value))
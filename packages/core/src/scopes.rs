@@ -250,6 +250,13 @@ impl<'src> ScopeState {
         self.context().schedule_update_any()
     }
 
+    /// Schedule an urgent update for any component given its [`ScopeId`].
+    ///
+    /// Urgent updates are rendered before every other dirty component, so they are not delayed by large updates that are rendered across several frames with [`crate::VirtualDom::render_with_budget`].
+    pub fn schedule_urgent_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
+        self.context().schedule_urgent_update_any()
+    }
+
     /// Mark this scope as dirty, and schedule a render for it.
     pub fn needs_update(&self) {
         self.context().needs_update()
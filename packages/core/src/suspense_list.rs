@@ -0,0 +1,190 @@
+use crate::innerlude::*;
+use rustc_hash::FxHashSet;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+/// The order a [`SuspenseList`] reveals the components below it in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RevealOrder {
+    /// Reveal components from first to last. A component that is ready stays hidden until every component before it is revealed.
+    #[default]
+    Forwards,
+    /// Reveal components from last to first. A component that is ready stays hidden until every component after it is revealed.
+    Backwards,
+    /// Reveal every component at once, when all of them are ready.
+    Together,
+}
+
+/// Provide a suspense list that coordinates when the suspended components below it are revealed
+pub fn use_suspense_list(cx: &ScopeState, order: RevealOrder) -> &SuspenseList {
+    let list = cx.use_hook(|| cx.provide_context(SuspenseList::new(order)));
+    list.inner.order.set(order);
+    list
+}
+
+/// Coordinates the order that suspended components are revealed in, so content doesn't pop in at random as each component resolves.
+///
+/// A component joins the closest suspense list above it the first time it suspends. Components are ordered by when they first suspend, which is the order they appear in when they all suspend in the same render.
+#[derive(Debug, Clone, Default)]
+pub struct SuspenseList {
+    inner: Rc<SuspenseListInner>,
+}
+
+#[derive(Debug, Default)]
+struct SuspenseListInner {
+    order: Cell<RevealOrder>,
+    items: RefCell<Vec<ScopeId>>,
+    ready: RefCell<FxHashSet<ScopeId>>,
+    revealed: RefCell<FxHashSet<ScopeId>>,
+}
+
+impl SuspenseList {
+    /// Create a new suspense list
+    pub fn new(order: RevealOrder) -> Self {
+        let list = Self::default();
+        list.inner.order.set(order);
+        list
+    }
+
+    /// The order this list reveals components in
+    pub fn order(&self) -> RevealOrder {
+        self.inner.order.get()
+    }
+
+    /// Returns true if the component is part of this list and is still waiting to be revealed
+    pub fn is_pending(&self, scope: ScopeId) -> bool {
+        self.inner.items.borrow().contains(&scope) && !self.is_revealed(scope)
+    }
+
+    pub(crate) fn register(&self, scope: ScopeId) {
+        let mut items = self.inner.items.borrow_mut();
+        if !items.contains(&scope) {
+            items.push(scope);
+        }
+    }
+
+    pub(crate) fn is_revealed(&self, scope: ScopeId) -> bool {
+        self.inner.revealed.borrow().contains(&scope)
+    }
+
+    /// Record whether a component finished loading. Returns the components that can be revealed now.
+    pub(crate) fn set_ready(&self, scope: ScopeId, ready: bool) -> Vec<ScopeId> {
+        let mut ready_scopes = self.inner.ready.borrow_mut();
+        if ready {
+            ready_scopes.insert(scope);
+        } else {
+            ready_scopes.remove(&scope);
+        }
+        drop(ready_scopes);
+        self.reveal()
+    }
+
+    /// Remove a component that was unmounted. Returns the components that can be revealed now.
+    pub(crate) fn remove(&self, scope: ScopeId) -> Vec<ScopeId> {
+        self.inner.items.borrow_mut().retain(|id| *id != scope);
+        self.inner.ready.borrow_mut().remove(&scope);
+        self.inner.revealed.borrow_mut().remove(&scope);
+        self.reveal()
+    }
+
+    fn reveal(&self) -> Vec<ScopeId> {
+        let items = self.inner.items.borrow();
+        let ready = self.inner.ready.borrow();
+        let mut revealed = self.inner.revealed.borrow_mut();
+        // A component that was revealed once never blocks the components after it again
+        let can_show = |id: &ScopeId| ready.contains(id) || revealed.contains(id);
+
+        let newly_revealed: Vec<ScopeId> = match self.order() {
+            RevealOrder::Forwards => items
+                .iter()
+                .take_while(|id| can_show(id))
+                .copied()
+                .collect(),
+            RevealOrder::Backwards => items
+                .iter()
+                .rev()
+                .take_while(|id| can_show(id))
+                .copied()
+                .collect(),
+            RevealOrder::Together if items.iter().all(can_show) => items.clone(),
+            RevealOrder::Together => Vec::new(),
+        };
+
+        newly_revealed
+            .into_iter()
+            .filter(|id| revealed.insert(*id))
+            .collect()
+    }
+}
+
+/// Coordinate the order that the suspended components inside of this component are revealed in.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// rsx! {
+///     SuspenseList {
+///         reveal_order: RevealOrder::Forwards,
+///         // The comments are never shown before the post, even if they load first
+///         Post {}
+///         Comments {}
+///     }
+/// }
+/// ```
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn SuspenseList<'a>(cx: Scope<'a, SuspenseListProps<'a>>) -> Element {
+    use_suspense_list(cx, cx.props.reveal_order);
+    let children = cx.props.children.as_ref()?;
+    Some(cx.vnode(
+        children.parent.clone(),
+        children.key,
+        children.template.clone(),
+        children.root_ids.clone(),
+        children.dynamic_nodes,
+        children.dynamic_attrs,
+    ))
+}
+
+/// The props for [`SuspenseList`]
+pub struct SuspenseListProps<'a> {
+    reveal_order: RevealOrder,
+    children: Element<'a>,
+}
+
+/// The builder for [`SuspenseListProps`]
+pub struct SuspenseListBuilder<'a>(SuspenseListProps<'a>);
+
+impl<'a> SuspenseListBuilder<'a> {
+    /// Set the order the components are revealed in. Defaults to [`RevealOrder::Forwards`].
+    pub fn reveal_order(mut self, reveal_order: RevealOrder) -> Self {
+        self.0.reveal_order = reveal_order;
+        self
+    }
+
+    /// Set the children of the suspense list
+    pub fn children(mut self, children: Element<'a>) -> Self {
+        self.0.children = children;
+        self
+    }
+
+    /// Build the props
+    pub fn build(self) -> SuspenseListProps<'a> {
+        self.0
+    }
+}
+
+impl<'a> Properties<'_> for SuspenseListProps<'a> {
+    type Builder = SuspenseListBuilder<'a>;
+    const IS_STATIC: bool = false;
+    fn builder(_cx: &ScopeState) -> Self::Builder {
+        SuspenseListBuilder(SuspenseListProps {
+            reveal_order: RevealOrder::default(),
+            children: None,
+        })
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
@@ -179,11 +179,35 @@ use std::{
 ///    real_dom.apply(dom.render_immediate());
 /// }
 /// ```
+///
+/// ## Custom frame pacing
+///
+/// Renderers with their own frame loop can render one scope at a time with [`VirtualDom::render_next_scope`], collect
+/// the mutations with [`VirtualDom::take_mutations`], and check how much work is left with
+/// [`VirtualDom::pending_work`].
+///
+/// ```rust, ignore
+/// loop {
+///     let frame_deadline = Instant::now() + Duration::from_millis(16);
+///     while Instant::now() < frame_deadline && dom.render_next_scope() {}
+///     gpu_dom.apply(dom.take_mutations());
+///
+///     if dom.pending_work().dirty_scopes == 0 {
+///         dom.wait_for_work().await;
+///     }
+/// }
+/// ```
 pub struct VirtualDom {
     pub(crate) scopes: Slab<Box<ScopeState>>,
 
     pub(crate) dirty_scopes: BTreeSet<DirtyScope>,
 
+    // Dirty scopes that are rendered before the rest
+    pub(crate) urgent_scopes: FxHashSet<ScopeId>,
+
+    // Dirty scopes from transitions that are rendered after the rest
+    pub(crate) deferred_scopes: FxHashSet<ScopeId>,
+
     // Maps a template path to a map of byteindexes to templates
     pub(crate) templates: FxHashMap<TemplateId, FxHashMap<usize, Template<'static>>>,
 
@@ -266,6 +290,8 @@ impl VirtualDom {
             runtime: Runtime::new(scheduler),
             scopes: Default::default(),
             dirty_scopes: Default::default(),
+            urgent_scopes: Default::default(),
+            deferred_scopes: Default::default(),
             templates: Default::default(),
             elements: Default::default(),
             element_refs: Default::default(),
@@ -312,6 +338,14 @@ impl VirtualDom {
         self
     }
 
+    /// Run the tasks of the virtualdom on an [`Executor`]
+    ///
+    /// Tasks are polled inside [`Executor::enter`], and hooks that need a timer use [`Executor::sleep`]. Use this when the app is embedded in a runtime it didn't create.
+    pub fn with_executor(self, executor: impl Executor + 'static) -> Self {
+        *self.runtime.executor.borrow_mut() = Some(Rc::new(executor));
+        self
+    }
+
     /// Manually mark a scope as requiring a re-render
     ///
     /// Whenever the Runtime "works", it will re-render this scope
@@ -320,6 +354,56 @@ impl VirtualDom {
             let height = scope.height();
             tracing::trace!("Marking scope {:?} ({}) as dirty", id, scope.context().name);
             self.dirty_scopes.insert(DirtyScope { height, id });
+            self.deferred_scopes.remove(&id);
+        }
+    }
+
+    /// Mark a scope as requiring a re-render before any other dirty scope
+    ///
+    /// Urgent scopes preempt the dirty scopes that are left over from a render that ran out of budget in [`VirtualDom::render_with_budget`].
+    pub fn mark_urgent(&mut self, id: ScopeId) {
+        if self.scopes.contains(id.0) {
+            self.mark_dirty(id);
+            self.urgent_scopes.insert(id);
+        }
+    }
+
+    /// Mark a scope as requiring a re-render after every other dirty scope
+    ///
+    /// This is how updates inside [`Runtime::start_transition`] are scheduled. If the scope is already dirty, it keeps its priority.
+    pub fn mark_deferred(&mut self, id: ScopeId) {
+        if let Some(scope) = self.get_scope(id) {
+            let dirty = DirtyScope {
+                height: scope.height(),
+                id,
+            };
+            if !self.dirty_scopes.contains(&dirty) {
+                self.dirty_scopes.insert(dirty);
+                self.deferred_scopes.insert(id);
+            }
+        }
+    }
+
+    /// Check if there are any scopes waiting to be rendered
+    pub fn has_dirty_scopes(&self) -> bool {
+        !self.dirty_scopes.is_empty()
+    }
+
+    /// Check if there are any suspended scopes waiting for their futures to resolve
+    pub fn has_suspended_work(&self) -> bool {
+        !self.suspended_scopes.is_empty()
+    }
+
+    /// Get how much work is waiting to be rendered
+    ///
+    /// Events that were scheduled since the last render are not counted until they are processed by
+    /// [`VirtualDom::process_events`] or one of the render methods.
+    pub fn pending_work(&self) -> PendingWork {
+        PendingWork {
+            dirty_scopes: self.dirty_scopes.len(),
+            urgent_scopes: self.urgent_scopes.len(),
+            deferred_scopes: self.deferred_scopes.len(),
+            suspended_scopes: self.suspended_scopes.len(),
         }
     }
 
@@ -492,6 +576,8 @@ impl VirtualDom {
                 // If a bunch of messages are ready in a sequence, try to pop them off synchronously
                 Some(msg) => match msg {
                     SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                    SchedulerMsg::Urgent(id) => self.mark_urgent(id),
+                    SchedulerMsg::Deferred(id) => self.mark_deferred(id),
                     SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
                 },
 
@@ -519,9 +605,12 @@ impl VirtualDom {
         while let Ok(Some(msg)) = self.rx.try_next() {
             match msg {
                 SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                SchedulerMsg::Urgent(id) => self.mark_urgent(id),
+                SchedulerMsg::Deferred(id) => self.mark_deferred(id),
                 SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
             }
         }
+        self.finish_transitions();
     }
 
     /// Replace a template at runtime. This will re-render all components that use this template.
@@ -638,20 +727,8 @@ impl VirtualDom {
         loop {
             // Next, diff any dirty scopes
             // We choose not to poll the deadline since we complete pretty quickly anyways
-            if let Some(dirty) = self.dirty_scopes.iter().next().cloned() {
-                self.dirty_scopes.remove(&dirty);
-
-                // If the scope doesn't exist for whatever reason, then we should skip it
-                if !self.scopes.contains(dirty.id.0) {
-                    continue;
-                }
-
-                {
-                    let _runtime = RuntimeGuard::new(self.runtime.clone());
-                    // Run the scope and get the mutations
-                    self.run_scope(dirty.id);
-                    self.diff_scope(dirty.id);
-                }
+            if let Some(dirty) = self.next_dirty_scope() {
+                self.render_dirty_scope(dirty);
             }
 
             // If there's more work, then just continue, plenty of work to do
@@ -675,6 +752,125 @@ impl VirtualDom {
         }
     }
 
+    /// Render dirty scopes until `out_of_time` returns true, and leave the rest for the next call
+    ///
+    /// Large updates can take longer than a frame to render. Rendering them in chunks lets the renderer apply the
+    /// mutations and handle input between the chunks, so the app stays responsive. Scopes marked with
+    /// [`VirtualDom::mark_urgent`] or [`ScopeState::schedule_urgent_update_any`] are rendered before the scopes that
+    /// are left over from the last call. A single scope is never split, and at least one dirty scope is rendered on
+    /// every call.
+    ///
+    /// Unlike [`VirtualDom::render_with_deadline`], this does not wait for suspense. The renderer should keep calling
+    /// this, yielding to its event loop in between, until [`VirtualDom::has_dirty_scopes`] returns false.
+    ///
+    /// ```rust, ignore
+    /// loop {
+    ///     dom.wait_for_work().await;
+    ///
+    ///     let frame_start = Instant::now();
+    ///     let mutations = dom.render_with_budget(|| frame_start.elapsed() > Duration::from_millis(8));
+    ///     real_dom.apply(mutations);
+    ///
+    ///     if dom.has_dirty_scopes() {
+    ///         yield_to_event_loop().await;
+    ///     }
+    /// }
+    /// ```
+    pub fn render_with_budget(&mut self, mut out_of_time: impl FnMut() -> bool) -> Mutations {
+        self.process_events();
+
+        while let Some(dirty) = self.next_dirty_scope() {
+            if self.render_dirty_scope(dirty) && out_of_time() {
+                break;
+            }
+        }
+
+        self.finalize()
+    }
+
+    /// Render a single dirty scope and keep its mutations until [`VirtualDom::take_mutations`] is called
+    ///
+    /// This is the building block of [`VirtualDom::render_with_budget`] for renderers that pace frames themselves, like
+    /// GPU and terminal renderers, and for benchmarks that measure each scope. Urgent scopes are rendered first. Returns
+    /// false if there was no dirty scope to render.
+    ///
+    /// ```rust, ignore
+    /// dom.process_events();
+    /// while Instant::now() < frame_deadline && dom.render_next_scope() {}
+    /// gpu_dom.apply(dom.take_mutations());
+    /// ```
+    pub fn render_next_scope(&mut self) -> bool {
+        self.process_events();
+
+        while let Some(dirty) = self.next_dirty_scope() {
+            if self.render_dirty_scope(dirty) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Take the mutations that were created by [`VirtualDom::render_next_scope`] since the last call
+    pub fn take_mutations(&mut self) -> Mutations {
+        self.finalize()
+    }
+
+    /// Take the next scope to render. Urgent scopes come first, then scopes in order of their height, then deferred scopes
+    fn next_dirty_scope(&mut self) -> Option<DirtyScope> {
+        let urgent = if self.urgent_scopes.is_empty() {
+            None
+        } else {
+            self.dirty_scopes
+                .iter()
+                .find(|dirty| self.urgent_scopes.contains(&dirty.id))
+                .cloned()
+        };
+        let normal = || {
+            self.dirty_scopes
+                .iter()
+                .find(|dirty| !self.deferred_scopes.contains(&dirty.id))
+                .cloned()
+        };
+        let dirty = urgent
+            .or_else(normal)
+            .or_else(|| self.dirty_scopes.iter().next().cloned())?;
+        self.dirty_scopes.remove(&dirty);
+        self.urgent_scopes.remove(&dirty.id);
+        self.deferred_scopes.remove(&dirty.id);
+        Some(dirty)
+    }
+
+    /// Run and diff a dirty scope. Returns false if the scope no longer exists
+    fn render_dirty_scope(&mut self, dirty: DirtyScope) -> bool {
+        // If the scope doesn't exist for whatever reason, then we should skip it
+        if !self.scopes.contains(dirty.id.0) {
+            return false;
+        }
+
+        let _runtime = RuntimeGuard::new(self.runtime.clone());
+        // Run the scope and get the mutations
+        self.run_scope(dirty.id);
+        self.diff_scope(dirty.id);
+        self.finish_transitions();
+        true
+    }
+
+    /// Tell the transitions that they are finished once none of their scopes are waiting to be rendered
+    fn finish_transitions(&mut self) {
+        if !self.deferred_scopes.is_empty() {
+            return;
+        }
+        let finished = std::mem::take(&mut *self.runtime.finished_transitions.borrow_mut());
+        if finished.is_empty() {
+            return;
+        }
+        let _runtime = RuntimeGuard::new(self.runtime.clone());
+        for on_finished in finished {
+            on_finished();
+        }
+    }
+
     /// Swap the current mutations with a new
     fn finalize(&mut self) -> Mutations {
         std::mem::take(&mut self.mutations)
@@ -692,3 +888,16 @@ impl Drop for VirtualDom {
         self.drop_scope(ScopeId::ROOT, true);
     }
 }
+
+/// How much work a [`VirtualDom`] has left, from [`VirtualDom::pending_work`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PendingWork {
+    /// The number of scopes waiting to be rendered
+    pub dirty_scopes: usize,
+    /// The number of dirty scopes that will be rendered before the rest
+    pub urgent_scopes: usize,
+    /// The number of dirty scopes from transitions that will be rendered after the rest
+    pub deferred_scopes: usize,
+    /// The number of scopes waiting for a suspended future
+    pub suspended_scopes: usize,
+}
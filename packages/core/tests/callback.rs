@@ -0,0 +1,88 @@
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn callbacks_return_values_to_children() {
+    fn app(cx: Scope) -> Element {
+        let offset = cx.generation() as i32;
+        let add = use_callback(cx, move |value: i32| value + offset);
+
+        render! { child { add: add } }
+    }
+
+    #[component]
+    fn child(cx: Scope, add: Callback<i32, i32>) -> Element {
+        let value = add.call(1);
+        render! { "{value}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "1");
+
+    // The callback calls the closure from the latest render
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.mark_dirty(ScopeId(1));
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "2");
+}
+
+#[test]
+fn callbacks_are_dropped_with_their_scope() {
+    let dropped = Rc::new(Cell::new(false));
+
+    struct DropGuard(Rc<Cell<bool>>);
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    fn app(cx: Scope<Rc<Cell<bool>>>) -> Element {
+        let show = cx.generation() == 0;
+        render! {
+            if show {
+                child { dropped: cx.props.clone() }
+            }
+        }
+    }
+
+    #[component]
+    fn child(cx: Scope, dropped: Rc<Cell<bool>>) -> Element {
+        let guard = DropGuard(dropped.clone());
+        use_callback(cx, move |_: ()| {
+            let _ = &guard;
+        });
+        render! { "child" }
+    }
+
+    let mut dom = VirtualDom::new_with_props(app, dropped.clone());
+    _ = dom.rebuild();
+    assert!(!dropped.get());
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+    assert!(dropped.get());
+}
+
+#[test]
+fn panicking_callbacks_restore_the_current_scope() {
+    fn app(cx: Scope) -> Element {
+        let fail = use_callback(cx, |_: ()| panic!("the callback failed"));
+
+        render! { child { fail: fail } }
+    }
+
+    #[component]
+    fn child(cx: Scope, fail: Callback) -> Element {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| fail.call(())));
+        assert!(result.is_err());
+        assert_eq!(current_scope_id(), Some(cx.scope_id()));
+        render! { "child" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "child");
+}
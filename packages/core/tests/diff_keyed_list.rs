@@ -44,7 +44,7 @@ fn keyed_diffing_out_of_order() {
         dom.render_immediate().edits,
         [
             PushRoot { id: ElementId(7,) },
-            InsertBefore { id: ElementId(5,), m: 1 },
+            MoveBefore { id: ElementId(5,), m: 1 },
         ]
     );
 }
@@ -70,7 +70,7 @@ fn keyed_diffing_out_of_order_adds() {
         [
             PushRoot { id: ElementId(5,) },
             PushRoot { id: ElementId(4,) },
-            InsertBefore { id: ElementId(1,), m: 2 },
+            MoveBefore { id: ElementId(1,), m: 2 },
         ]
     );
 }
@@ -96,7 +96,7 @@ fn keyed_diffing_out_of_order_adds_3() {
         [
             PushRoot { id: ElementId(5,) },
             PushRoot { id: ElementId(4,) },
-            InsertBefore { id: ElementId(2,), m: 2 },
+            MoveBefore { id: ElementId(2,), m: 2 },
         ]
     );
 }
@@ -122,7 +122,7 @@ fn keyed_diffing_out_of_order_adds_4() {
         [
             PushRoot { id: ElementId(5,) },
             PushRoot { id: ElementId(4,) },
-            InsertBefore { id: ElementId(3,), m: 2 },
+            MoveBefore { id: ElementId(3,), m: 2 },
         ]
     );
 }
@@ -147,7 +147,7 @@ fn keyed_diffing_out_of_order_adds_5() {
         dom.render_immediate().edits,
         [
             PushRoot { id: ElementId(5,) },
-            InsertBefore { id: ElementId(4,), m: 1 },
+            MoveBefore { id: ElementId(4,), m: 1 },
         ]
     );
 }
@@ -202,7 +202,7 @@ fn keyed_diffing_additions_and_moves_on_ends() {
             InsertAfter { id: ElementId(3), m: 2 },
             // move 7 to the front
             PushRoot { id: ElementId(4) },
-            InsertBefore { id: ElementId(1), m: 1 }
+            MoveBefore { id: ElementId(1), m: 1 }
         ]
     );
 }
@@ -236,7 +236,7 @@ fn keyed_diffing_additions_and_moves_in_middle() {
             InsertBefore { id: ElementId(2), m: 2 },
             // move 7
             PushRoot { id: ElementId(4) },
-            InsertBefore { id: ElementId(1), m: 1 }
+            MoveBefore { id: ElementId(1), m: 1 }
         ]
     );
 }
@@ -264,7 +264,7 @@ fn controlled_keyed_diffing_out_of_order() {
             Remove { id: ElementId(4,) },
             // move 4 to after 6
             PushRoot { id: ElementId(1) },
-            InsertAfter { id: ElementId(3,), m: 1 },
+            MoveAfter { id: ElementId(3,), m: 1 },
             // create 9 and insert before 6
             LoadTemplate { name: "template", index: 0, id: ElementId(4) },
             InsertBefore { id: ElementId(3,), m: 1 },
@@ -297,7 +297,66 @@ fn controlled_keyed_diffing_out_of_order_max_test() {
             LoadTemplate { name: "template", index: 0, id: ElementId(5) },
             InsertBefore { id: ElementId(3,), m: 1 },
             PushRoot { id: ElementId(4) },
-            InsertBefore { id: ElementId(1,), m: 1 },
+            MoveBefore { id: ElementId(1,), m: 1 },
+        ]
+    );
+}
+
+/// Moved and created nodes in the same gap are mounted in order, but only the existing nodes are moved
+#[test]
+fn keyed_diffing_moves_and_creates_in_one_gap() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[1, 2, 3, 4],
+            1 => &[4, 9, 1, 2, 3],
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx!({ order.iter().map(|i| rsx!(div { key: "{i}" })) }))
+    });
+
+    _ = dom.rebuild();
+
+    // LIS: 1, 2, 3
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            // move 4 to the front
+            PushRoot { id: ElementId(4) },
+            MoveBefore { id: ElementId(1), m: 1 },
+            // create 9 and insert it between 4 and 1
+            LoadTemplate { name: "template", index: 0, id: ElementId(5) },
+            InsertBefore { id: ElementId(1), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn keyed_diffing_moves_and_creates_at_the_end() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[1, 2, 3, 4],
+            1 => &[2, 3, 4, 1, 9],
+            _ => unreachable!(),
+        };
+
+        cx.render(rsx!({ order.iter().map(|i| rsx!(div { key: "{i}" })) }))
+    });
+
+    _ = dom.rebuild();
+
+    // LIS: 2, 3, 4
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            // move 1 after 4
+            PushRoot { id: ElementId(1) },
+            MoveAfter { id: ElementId(4), m: 1 },
+            // create 9 and insert it after 1
+            LoadTemplate { name: "template", index: 0, id: ElementId(5) },
+            InsertAfter { id: ElementId(1), m: 1 },
         ]
     );
 }
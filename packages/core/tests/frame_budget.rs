@@ -0,0 +1,139 @@
+#![allow(non_snake_case)]
+
+//! Verify that rendering with a budget or one scope at a time splits the work, that urgent scopes are rendered first, and that transitions are rendered last
+use dioxus::core::{PendingWork, ScopeId};
+use dioxus::prelude::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+thread_local! {
+    static RENDERED: RefCell<Vec<ScopeId>> = RefCell::new(Vec::new());
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        Child {}
+        Child {}
+        Child {}
+    }
+}
+
+fn Child(cx: Scope) -> Element {
+    RENDERED.with(|rendered| rendered.borrow_mut().push(cx.scope_id()));
+    render! { div {} }
+}
+
+fn take_rendered() -> Vec<ScopeId> {
+    RENDERED.with(|rendered| std::mem::take(&mut *rendered.borrow_mut()))
+}
+
+#[test]
+fn budget_splits_rendering() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    take_rendered();
+
+    for id in 1..=3 {
+        dom.mark_dirty(ScopeId(id));
+    }
+
+    // An exhausted budget still renders one scope per call
+    _ = dom.render_with_budget(|| true);
+    assert_eq!(take_rendered(), [ScopeId(1)]);
+    assert!(dom.has_dirty_scopes());
+
+    _ = dom.render_with_budget(|| false);
+    assert_eq!(take_rendered(), [ScopeId(2), ScopeId(3)]);
+    assert!(!dom.has_dirty_scopes());
+}
+
+#[test]
+fn urgent_scopes_preempt_leftover_work() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    take_rendered();
+
+    for id in 1..=3 {
+        dom.mark_dirty(ScopeId(id));
+    }
+    _ = dom.render_with_budget(|| true);
+    take_rendered();
+
+    dom.base_scope().schedule_urgent_update_any()(ScopeId(3));
+    _ = dom.render_with_budget(|| true);
+    assert_eq!(take_rendered(), [ScopeId(3)]);
+
+    _ = dom.render_with_budget(|| false);
+    assert_eq!(take_rendered(), [ScopeId(2)]);
+}
+
+#[test]
+fn render_one_scope_at_a_time() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    take_rendered();
+
+    for id in 1..=3 {
+        dom.mark_dirty(ScopeId(id));
+    }
+    dom.mark_urgent(ScopeId(2));
+    assert_eq!(
+        dom.pending_work(),
+        PendingWork { dirty_scopes: 3, urgent_scopes: 1, deferred_scopes: 0, suspended_scopes: 0 }
+    );
+
+    assert!(dom.render_next_scope());
+    assert_eq!(take_rendered(), [ScopeId(2)]);
+    assert_eq!(dom.pending_work().dirty_scopes, 2);
+
+    while dom.render_next_scope() {}
+    assert_eq!(take_rendered(), [ScopeId(1), ScopeId(3)]);
+    assert_eq!(dom.pending_work(), PendingWork::default());
+    _ = dom.take_mutations();
+}
+
+#[test]
+fn transitions_render_after_other_updates() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    take_rendered();
+
+    let finished = Rc::new(Cell::new(false));
+    dom.runtime()
+        .start_transition(|| dom.base_scope().needs_update_any(ScopeId(1)), {
+            let finished = finished.clone();
+            move || finished.set(true)
+        });
+    dom.mark_dirty(ScopeId(3));
+    dom.process_events();
+    assert_eq!(dom.pending_work().deferred_scopes, 1);
+
+    assert!(dom.render_next_scope());
+    assert_eq!(take_rendered(), [ScopeId(3)]);
+    assert!(!finished.get());
+
+    assert!(dom.render_next_scope());
+    assert_eq!(take_rendered(), [ScopeId(1)]);
+    assert!(finished.get());
+    _ = dom.take_mutations();
+}
+
+#[test]
+fn panicking_transitions_end() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    take_rendered();
+
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        dom.runtime()
+            .start_transition(|| panic!("the update failed"), || {})
+    }));
+    assert!(panicked.is_err());
+
+    // Updates after the transition are not deferred
+    dom.base_scope().needs_update_any(ScopeId(1));
+    dom.process_events();
+    assert_eq!(dom.pending_work().deferred_scopes, 0);
+    assert_eq!(dom.pending_work().dirty_scopes, 1);
+    _ = dom.take_mutations();
+}
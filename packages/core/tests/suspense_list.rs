@@ -0,0 +1,51 @@
+#![allow(non_snake_case)]
+use std::cell::RefCell;
+
+use dioxus::prelude::*;
+
+thread_local! {
+    static READY: RefCell<[bool; 2]> = RefCell::new([false; 2]);
+    static ITEMS: RefCell<[Option<ScopeId>; 2]> = RefCell::new([None; 2]);
+}
+
+#[test]
+fn suspense_list_reveals_forwards() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    assert_eq!(dioxus_ssr::render(&dom), "");
+
+    // The second item is ready, but it waits for the first one
+    resolve(&mut dom, 1);
+    assert_eq!(dioxus_ssr::render(&dom), "");
+
+    // Once the first item is ready, both are revealed
+    resolve(&mut dom, 0);
+    assert_eq!(dioxus_ssr::render(&dom), "item 0item 1");
+}
+
+fn resolve(dom: &mut VirtualDom, index: usize) {
+    READY.with(|ready| ready.borrow_mut()[index] = true);
+    let scope = ITEMS.with(|items| items.borrow()[index]).unwrap();
+    dom.mark_dirty(scope);
+    _ = dom.render_immediate();
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        SuspenseList {
+            reveal_order: RevealOrder::Forwards,
+            Item { index: 0 }
+            Item { index: 1 }
+        }
+    }
+}
+
+#[component]
+fn Item(cx: Scope, index: usize) -> Element {
+    ITEMS.with(|items| items.borrow_mut()[*index] = Some(cx.scope_id()));
+    if !READY.with(|ready| ready.borrow()[*index]) {
+        cx.suspend()?;
+    }
+
+    render!("item {index}")
+}
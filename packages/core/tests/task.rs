@@ -44,3 +44,57 @@ async fn it_works() {
         135
     );
 }
+
+#[cfg(not(miri))]
+#[tokio::test]
+async fn tasks_run_on_the_executor() {
+    use dioxus::prelude::*;
+    use dioxus_core::prelude::current_executor;
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    static ENTERED: AtomicUsize = AtomicUsize::new(0);
+    static SLEPT: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountingExecutor;
+
+    impl Executor for CountingExecutor {
+        fn enter(&self, poll: &mut dyn FnMut()) {
+            ENTERED.fetch_add(1, Ordering::Relaxed);
+            poll()
+        }
+
+        fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+            SLEPT.fetch_add(1, Ordering::Relaxed);
+            Box::pin(tokio::time::sleep(duration))
+        }
+    }
+
+    fn app(cx: Scope) -> Element {
+        cx.use_hook(|| {
+            cx.spawn(async {
+                let executor = current_executor().unwrap();
+                executor.sleep(Duration::from_micros(50)).await;
+            });
+        });
+
+        None
+    }
+
+    let mut dom = VirtualDom::new(app).with_executor(CountingExecutor);
+
+    let _ = dom.rebuild();
+
+    tokio::select! {
+        _ = dom.wait_for_work() => {}
+        _ = tokio::time::sleep(Duration::from_millis(100)) => {}
+    };
+
+    assert_eq!(SLEPT.load(Ordering::Relaxed), 1);
+    // The task is polled once before the sleep and once after it
+    assert_eq!(ENTERED.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,46 @@
+//! This benchmark measures the cost of encoding the edits for a 10k row table update before they are sent to the webview.
+//!
+//! The edits are written with the same binary protocol the desktop renderer uses, then compressed with the LZ4 framing
+//! enabled by the `compress-edits` feature. Each run also prints the raw and compressed size of the batch.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use dioxus_interpreter_js::binary_protocol::Channel;
+
+criterion_group!(mbenches, table_update);
+criterion_main!(mbenches);
+
+const ROWS: u32 = 10_000;
+
+fn table_update_bytes() -> Vec<u8> {
+    let mut channel = Channel::default();
+    for row in 0..ROWS {
+        // Every row has an id, a label and a class that changes when the row is selected
+        let id = row * 3 + 1;
+        channel.set_text(id, &format!("{row}"));
+        channel.set_text(id + 1, &format!("pretty large green table row {row}"));
+        channel.set_attribute(id + 2, "class", "danger", "");
+    }
+    let bytes = channel.export_memory().collect();
+    channel.reset();
+    bytes
+}
+
+fn table_update(c: &mut Criterion) {
+    let bytes = table_update_bytes();
+    let compressed = lz4_flex::compress_prepend_size(&bytes);
+    println!(
+        "10k row update: {} bytes raw, {} bytes compressed",
+        bytes.len(),
+        compressed.len()
+    );
+
+    c.bench_function("encode 10k row update", |b| b.iter(table_update_bytes));
+
+    c.bench_function("compress 10k row update", |b| {
+        b.iter(|| lz4_flex::compress_prepend_size(&bytes))
+    });
+
+    c.bench_function("decompress 10k row update", |b| {
+        b.iter(|| lz4_flex::decompress_size_prepended(&compressed).unwrap())
+    });
+}
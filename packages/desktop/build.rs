@@ -56,23 +56,96 @@ fn main() {
           .then(response => {{
               response.arrayBuffer()
                   .then(bytes => {{
+                      const edits = decode_edits(bytes);
                       // In headless mode, the requestAnimationFrame callback is never called, so we need to run the bytes directly
-                      if (headless) {{
-                        run_from_bytes(bytes);
+                      // The same goes for the first render if the window is hidden until it was applied
+                      if (headless || !window.interpreter.rendered) {{
+                        run_from_bytes(edits);
+                        if (!window.interpreter.rendered) {{
+                          window.interpreter.rendered = true;
+                          window.interpreter.notify_rendered();
+                        }}
                       }}
                       else {{
                         requestAnimationFrame(() => {{
-                          run_from_bytes(bytes);
+                          run_from_bytes(edits);
                         }});
                       }}
                       window.interpreter.wait_for_request(headless);
                   }});
           }})
+    }}
+    // Tell the window that the first render was applied once its stylesheets and fonts have loaded
+    window.interpreter.notify_rendered = () => {{
+      const sheets = Array.from(document.querySelectorAll("link[rel=stylesheet]"))
+          .filter(link => !link.sheet)
+          .map(link => new Promise(resolve => {{
+              link.addEventListener("load", resolve, {{ once: true }});
+              link.addEventListener("error", resolve, {{ once: true }});
+          }}));
+      const loaded = Promise.all([...sheets, document.fonts.ready]);
+      const timeout = new Promise(resolve => setTimeout(resolve, 3000));
+      Promise.race([loaded, timeout]).then(() => {{
+          window.ipc.postMessage(window.interpreter.serializeIpcMessage("rendered"));
+      }});
     }}"#
     );
+    // Keep this in sync with `encode_edits` in src/edits.rs
+    let decode_edits = include_str!("src/decode_edits.js");
+    // Keep this in sync with src/js_channel.rs
+    let js_channels = r#"// Typed channels to `use_js_channel` in rust
+    window.interpreter.channels = {
+      next_id: 0,
+      pending: {},
+      listeners: {},
+      receive(name, id, data, error) {
+        if (id === null) {
+          const listeners = this.listeners[name];
+          if (listeners) {
+            listeners.forEach((listener) => listener(data));
+          }
+          return;
+        }
+        const request = this.pending[id];
+        delete this.pending[id];
+        if (!request) {
+          return;
+        }
+        if (error === null) {
+          request.resolve(data);
+        } else {
+          request.reject(new Error(error));
+        }
+      },
+    };
+    window.dioxusChannel = function (name) {
+      const channels = window.interpreter.channels;
+      const post = (id, data) => window.ipc.postMessage(window.interpreter.serializeIpcMessage("channel", { name, id, data }));
+      return {
+        send(data) {
+          post(null, data);
+        },
+        request(data) {
+          return new Promise((resolve, reject) => {
+            const id = channels.next_id++;
+            channels.pending[id] = { resolve, reject };
+            post(id, data);
+          });
+        },
+        subscribe(listener) {
+          if (!channels.listeners[name]) {
+            channels.listeners[name] = new Set();
+          }
+          channels.listeners[name].add(listener);
+          return () => channels.listeners[name].delete(listener);
+        },
+      };
+    };"#;
     let mut interpreter = SLEDGEHAMMER_JS
         .replace("/*POST_HANDLE_EDITS*/", prevent_file_upload)
         .replace("export", "")
+        + decode_edits
+        + js_channels
         + &polling_request;
     while let Some(import_start) = interpreter.find("import") {
         let import_end = interpreter[import_start..]
@@ -0,0 +1,44 @@
+use dioxus_core::{prelude::spawn_at, Executor, ScopeId};
+use dioxus_html::animation::AnimationFrameProvider;
+use std::{
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+/// The webview does not tell the app when it draws, so frames are scheduled with a timer at 60 frames per second.
+const FRAME: Duration = Duration::from_micros(16_667);
+
+/// Schedules frames with the timer of the app's executor.
+pub(crate) struct DesktopAnimationFrameProvider {
+    executor: Option<Rc<dyn Executor>>,
+    start: Instant,
+}
+
+impl DesktopAnimationFrameProvider {
+    pub(crate) fn new(executor: Option<Rc<dyn Executor>>) -> Self {
+        Self {
+            executor,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl AnimationFrameProvider for DesktopAnimationFrameProvider {
+    fn request_frame(&self, callback: Box<dyn FnOnce(f64)>) {
+        let executor = match &self.executor {
+            Some(executor) => executor.clone(),
+            None => {
+                tracing::error!("Animation frames need a timer, but the app has no executor");
+                return;
+            }
+        };
+        let start = self.start;
+        spawn_at(
+            async move {
+                executor.sleep(FRAME).await;
+                callback(start.elapsed().as_secs_f64() * 1000.0);
+            },
+            ScopeId::ROOT,
+        );
+    }
+}
@@ -1,23 +1,31 @@
 use crate::{
     config::{Config, WindowCloseBehaviour},
+    context_menu::ContextMenus,
+    deep_link::{DeepLink, DeepLinks},
     desktop_context::WindowEventHandlers,
     element::DesktopElement,
+    events::DesktopDragData,
     file_upload::FileDialogRequest,
     ipc::IpcMessage,
     ipc::{EventData, UserWindowEvent},
+    js_channel::ChannelMessage,
+    native_menu::MenuHandlers,
+    notification::NotificationScheduler,
     query::QueryResult,
     shortcut::{GlobalHotKeyEvent, ShortcutRegistry},
+    tray::Tray,
     webview::WebviewInstance,
 };
 use crossbeam_channel::Receiver;
-use dioxus_core::{Component, ElementId, VirtualDom};
+use dioxus_core::{Component, ElementId, Executor, ScopeId, VirtualDom};
 use dioxus_html::{
-    native_bind::NativeFileEngine, FileEngine, HasFileData, HasFormData, HtmlEvent,
-    PlatformEventData,
+    native_bind::NativeFileEngine, notification::NotificationEvent, FileEngine, HasFileData,
+    HasFormData, HtmlEvent, PlatformEventData,
 };
 use std::{
     cell::{Cell, RefCell},
     collections::HashMap,
+    path::PathBuf,
     rc::Rc,
     sync::Arc,
 };
@@ -41,6 +49,10 @@ pub(crate) struct App<P> {
     pub(crate) window_behavior: WindowCloseBehaviour,
     pub(crate) webviews: HashMap<WindowId, WebviewInstance>,
 
+    /// The app started in the tray. The first window is created when the tray icon is used, and closing it hides it again
+    pub(crate) in_tray: bool,
+    pub(crate) tray_window: Option<WindowId>,
+
     /// This single blob of state is shared between all the windows so they have access to the runtime state
     ///
     /// This includes stuff like the event handlers, shortcuts, etc as well as ways to modify *other* windows
@@ -57,17 +69,28 @@ pub struct SharedContext {
     pub(crate) global_hotkey_channel: Receiver<GlobalHotKeyEvent>,
     pub(crate) proxy: EventLoopProxy<UserWindowEvent>,
     pub(crate) target: EventLoopWindowTarget<UserWindowEvent>,
+    pub(crate) notifications: NotificationScheduler,
+    pub(crate) tray: Tray,
+    pub(crate) menu_handlers: MenuHandlers,
+    pub(crate) context_menus: ContextMenus,
+    pub(crate) deep_links: DeepLinks,
+    pub(crate) executor: Option<Rc<dyn Executor>>,
 }
 
 impl<P: 'static> App<P> {
     pub fn new(cfg: Config, props: P, root: Component<P>) -> (EventLoop<UserWindowEvent>, Self) {
         let event_loop = EventLoopBuilder::<UserWindowEvent>::with_user_event().build();
+        let notifications =
+            NotificationScheduler::new(cfg.data_dir.clone(), event_loop.create_proxy());
+        let deep_links = DeepLinks::new(cfg.url_scheme.as_deref(), &event_loop.create_proxy());
 
         let app = Self {
             root,
             window_behavior: cfg.last_window_close_behaviour,
             is_visible_before_start: true,
             webviews: HashMap::new(),
+            in_tray: false,
+            tray_window: None,
             control_flow: ControlFlow::Wait,
             props: Cell::new(Some(props)),
             cfg: Cell::new(Some(cfg)),
@@ -78,6 +101,12 @@ impl<P: 'static> App<P> {
                 global_hotkey_channel: GlobalHotKeyEvent::receiver().clone(),
                 proxy: event_loop.create_proxy(),
                 target: event_loop.clone(),
+                notifications,
+                tray: Tray::default(),
+                menu_handlers: MenuHandlers::default(),
+                context_menus: ContextMenus::default(),
+                deep_links,
+                executor: cfg.executor.clone(),
             }),
         };
 
@@ -101,6 +130,28 @@ impl<P: 'static> App<P> {
         if let Ok(event) = self.shared.global_hotkey_channel.try_recv() {
             self.shared.shortcut_manager.call_handlers(event);
         }
+
+        self.shared.tray.poll();
+        self.poll_menu_events();
+
+        if let Some(show) = self.shared.tray.take_interaction() {
+            self.handle_tray_interaction(show);
+        }
+    }
+
+    /// Send clicks on native menus to the handlers of the tray or the open context menu if the item is in their menu, and to the menu handlers otherwise.
+    fn poll_menu_events(&self) {
+        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        while let Ok(event) = muda::MenuEvent::receiver().try_recv() {
+            let id = event.id.0;
+            if !self.shared.tray.handle_menu_event(&id)
+                && !self.shared.context_menus.handle_menu_event(&id)
+            {
+                self.shared
+                    .menu_handlers
+                    .emit(crate::native_menu::MenuEvent { id });
+            }
+        }
     }
 
     #[cfg(all(feature = "hot-reload", debug_assertions))]
@@ -128,10 +179,33 @@ impl<P: 'static> App<P> {
     }
 
     pub fn handle_close_requested(&mut self, id: WindowId) {
+        let Some(webview) = self.webviews.get(&id) else {
+            return;
+        };
+
+        // The app keeps running in the tray
+        if self.tray_window == Some(id) {
+            webview.desktop_context.window.set_visible(false);
+            return;
+        }
+
+        // Before quit handlers can veto or delay the close. They send a ConfirmClose event when they are done
+        let can_close = webview.desktop_context.quit_handlers.request_close(
+            webview.dom.runtime(),
+            self.shared.proxy.clone(),
+            id,
+        );
+        if can_close {
+            self.handle_confirm_close(id);
+        }
+    }
+
+    pub fn handle_confirm_close(&mut self, id: WindowId) {
         use WindowCloseBehaviour::*;
 
         match self.window_behavior {
             LastWindowExitsApp => {
+                self.will_quit_if_last(id);
                 self.webviews.remove(&id);
                 if self.webviews.is_empty() {
                     self.control_flow = ControlFlow::Exit
@@ -152,21 +226,78 @@ impl<P: 'static> App<P> {
     }
 
     pub fn window_destroyed(&mut self, id: WindowId) {
-        self.webviews.remove(&id);
-
-        if matches!(
+        let exits = matches!(
             self.window_behavior,
             WindowCloseBehaviour::LastWindowExitsApp
-        ) && self.webviews.is_empty()
-        {
+        );
+        if exits {
+            self.will_quit_if_last(id);
+        }
+
+        self.webviews.remove(&id);
+
+        if exits && self.webviews.is_empty() {
             self.control_flow = ControlFlow::Exit
         }
     }
 
+    /// Call the will quit handlers of every window if `id` is the last window, so closing it exits the app
+    fn will_quit_if_last(&self, id: WindowId) {
+        if self.webviews.len() != 1 || !self.webviews.contains_key(&id) {
+            return;
+        }
+        for webview in self.webviews.values() {
+            webview
+                .desktop_context
+                .quit_handlers
+                .will_quit(webview.dom.runtime());
+        }
+    }
+
     pub fn handle_start_cause_init(&mut self) {
         let props = self.props.take().unwrap();
-        let cfg = self.cfg.take().unwrap();
+        let mut cfg = self.cfg.take().unwrap();
+
+        let has_tray = match cfg.tray.take() {
+            Some(tray) => self.shared.tray.create(tray),
+            None => false,
+        };
 
+        if cfg.start_hidden_in_tray && has_tray {
+            self.in_tray = true;
+            self.shared.tray.keep_missed_events();
+            self.props.set(Some(props));
+            self.cfg.set(Some(cfg));
+            return;
+        }
+
+        self.open_first_window(cfg, props);
+    }
+
+    /// Create the window of an app that started in the tray, or show it if it exists.
+    fn handle_tray_interaction(&mut self, show: bool) {
+        if !self.in_tray {
+            return;
+        }
+
+        if let (Some(mut cfg), Some(props)) = (self.cfg.take(), self.props.take()) {
+            cfg.window = cfg.window.with_visible(show);
+            let id = self.open_first_window(cfg, props);
+            self.tray_window = Some(id);
+            return;
+        }
+
+        let window = self
+            .tray_window
+            .and_then(|id| self.webviews.get(&id))
+            .map(|webview| &webview.desktop_context.window);
+        if let (true, Some(window)) = (show, window) {
+            window.set_visible(true);
+            window.set_focus();
+        }
+    }
+
+    fn open_first_window(&mut self, cfg: Config, props: P) -> WindowId {
         self.is_visible_before_start = cfg.window.window.visible;
 
         let webview = WebviewInstance::new(
@@ -182,15 +313,36 @@ impl<P: 'static> App<P> {
             .shared
             .proxy
             .send_event(UserWindowEvent(EventData::Poll, id));
+        id
+    }
+
+    pub fn handle_mark_dirty(&mut self, id: WindowId, scope: ScopeId) {
+        let Some(view) = self.webviews.get_mut(&id) else {
+            return;
+        };
+
+        view.dom.mark_dirty(scope);
+        view.poll_vdom();
+    }
+
+    pub fn handle_notification_event(&mut self, event: NotificationEvent) {
+        self.shared.notifications.emit(event);
+    }
+
+    pub fn handle_open_url(&mut self, url: String) {
+        self.shared.deep_links.emit(DeepLink { url });
+    }
+
+    pub fn handle_file_drop(&mut self, id: WindowId, paths: Vec<PathBuf>) {
+        if let Some(view) = self.webviews.get_mut(&id) {
+            view.dragged_files = paths;
+        }
     }
 
     pub fn handle_browser_open(&mut self, msg: IpcMessage) {
         if let Some(temp) = msg.params().as_object() {
-            if temp.contains_key("href") {
-                let open = webbrowser::open(temp["href"].as_str().unwrap());
-                if let Err(e) = open {
-                    tracing::error!("Open Browser error: {:?}", e);
-                }
+            if let Some(href) = temp.get("href").and_then(|href| href.as_str()) {
+                crate::open_external::open_link(href);
             }
         }
     }
@@ -198,12 +350,29 @@ impl<P: 'static> App<P> {
     pub fn handle_initialize_msg(&mut self, id: WindowId) {
         let view = self.webviews.get_mut(&id).unwrap();
         view.desktop_context.send_edits(view.dom.rebuild());
-        view.desktop_context
-            .window
-            .set_visible(self.is_visible_before_start);
+        if !view.show_on_render {
+            view.desktop_context
+                .window
+                .set_visible(self.is_visible_before_start);
+            view.splash = None;
+        }
+    }
+
+    /// The first render of the window was applied and its stylesheets have loaded.
+    pub fn handle_rendered_msg(&mut self, id: WindowId) {
+        let Some(view) = self.webviews.get_mut(&id) else {
+            return;
+        };
+        if view.show_on_render {
+            view.show_on_render = false;
+            view.desktop_context.window.set_visible(true);
+            view.desktop_context.window.set_focus();
+            view.splash = None;
+        }
     }
 
     pub fn handle_close_msg(&mut self, id: WindowId) {
+        self.will_quit_if_last(id);
         self.webviews.remove(&id);
 
         if self.webviews.is_empty() {
@@ -211,6 +380,16 @@ impl<P: 'static> App<P> {
         }
     }
 
+    pub fn handle_channel_msg(&mut self, msg: IpcMessage, id: WindowId) {
+        let Ok(message) = serde_json::from_value::<ChannelMessage>(msg.params()) else {
+            return;
+        };
+        if let Some(view) = self.webviews.get(&id) {
+            let desktop = &view.desktop_context;
+            desktop.js_channels.dispatch(desktop, message);
+        }
+    }
+
     pub fn handle_query_msg(&mut self, msg: IpcMessage, id: WindowId) {
         let Ok(result) = serde_json::from_value::<QueryResult>(msg.params()) else {
             return;
@@ -245,10 +424,18 @@ impl<P: 'static> App<P> {
                 let element = DesktopElement::new(element, view.desktop_context.clone(), query);
                 Rc::new(PlatformEventData::new(Box::new(element)))
             }
+            // The webview doesn't know the paths of files dragged in from outside, so add the ones wry reported
+            dioxus_html::EventData::Drag(drag) if !view.dragged_files.is_empty() => {
+                let drag = DesktopDragData::new(drag, view.dragged_files.clone());
+                Rc::new(PlatformEventData::new(Box::new(drag)))
+            }
             _ => data.into_any(),
         };
 
         view.dom.handle_event(&name, as_any, element, bubbles);
+        if name == "drop" {
+            view.dragged_files.clear();
+        }
         view.desktop_context.send_edits(view.dom.render_immediate());
     }
 
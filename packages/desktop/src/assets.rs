@@ -1,4 +1,7 @@
-use dioxus_core::prelude::{Runtime, RuntimeGuard, ScopeId};
+use dioxus_core::{
+    prelude::{Runtime, RuntimeGuard, ScopeId},
+    ScopeState,
+};
 use rustc_hash::FxHashMap;
 use std::{cell::RefCell, rc::Rc};
 use wry::{http::Request, RequestAsyncResponder};
@@ -59,3 +62,48 @@ impl AssetHandlerRegistry {
         self.handlers.borrow_mut().remove(name)
     }
 }
+
+/// The url of a file in the assets of the app, like `images/logo.png`.
+///
+/// The file is found in the resources of the bundled app, in the asset directory of `Dioxus.toml` when the app runs with `dx serve`, and in the crate directory when it runs with `cargo run`. It is served through the custom protocol of the webview, so the url works the same in all of them.
+///
+/// ```rust, ignore
+/// render! { img { src: asset!("images/logo.png") } }
+/// ```
+#[macro_export]
+macro_rules! asset {
+    ($path:expr) => {
+        $crate::asset_url($path)
+    };
+}
+
+/// Get the url of a file in the assets of the app. See [`asset!`](crate::asset) for where the file is found.
+///
+/// Debug builds log a warning if the file doesn't exist.
+pub fn asset_url(path: &str) -> String {
+    let path = path.trim_start_matches("./").trim_start_matches('/');
+
+    #[cfg(debug_assertions)]
+    if crate::protocol::resolve_asset(std::path::Path::new(path)).is_none() {
+        tracing::warn!("The asset {path} was not found in the asset directories of the app");
+    }
+
+    let encoded: Vec<_> = path.split('/').map(urlencoding::encode).collect();
+    format!("{ASSET_ORIGIN}/{}", encoded.join("/"))
+}
+
+/// Get the url of a file in the assets of the app once, when the component is created. See [`asset!`](crate::asset) for where the file is found.
+///
+/// ```rust, ignore
+/// let logo = use_asset(cx, "images/logo.png");
+/// render! { img { src: "{logo}" } }
+/// ```
+pub fn use_asset<'a>(cx: &'a ScopeState, path: &str) -> &'a str {
+    cx.use_hook(|| asset_url(path))
+}
+
+/// The origin of the pages of the webview. Keep this in sync with `EDITS_PATH` in build.rs
+#[cfg(any(target_os = "android", target_os = "windows"))]
+const ASSET_ORIGIN: &str = "http://dioxus.index.html";
+#[cfg(not(any(target_os = "android", target_os = "windows")))]
+const ASSET_ORIGIN: &str = "dioxus://index.html";
@@ -0,0 +1,107 @@
+//! Platform specific implementations of the dock/taskbar badge and progress bar.
+
+use tao::window::{ProgressBarState, ProgressState, Window};
+
+/// Show `count` on the app icon. A count of zero removes the badge.
+///
+/// The count is shown on the dock icon on macOS, and in Linux launchers that support the Unity launcher API if the app has an installed desktop entry named after the executable.
+///
+/// Windows is not supported. The taskbar has no badge, only overlay icons, so the count is ignored and a warning is logged the first time it is set.
+pub(crate) fn set_badge_count(count: usize) {
+    #[cfg(target_os = "macos")]
+    {
+        use objc::runtime::Object;
+        use objc::{msg_send, sel, sel_impl};
+
+        let label = (count > 0).then(|| std::ffi::CString::new(count.to_string()).unwrap());
+        objc::rc::autoreleasepool(|| unsafe {
+            let app: *mut Object = msg_send![objc::class!(NSApplication), sharedApplication];
+            let dock_tile: *mut Object = msg_send![app, dockTile];
+            let label: *mut Object = match &label {
+                Some(label) => {
+                    msg_send![objc::class!(NSString), stringWithUTF8String: label.as_ptr()]
+                }
+                None => std::ptr::null_mut(),
+            };
+            let _: () = msg_send![dock_tile, setBadgeLabel: label];
+        });
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    {
+        use gtk::{gio, glib, glib::ToVariant};
+
+        let properties = glib::VariantDict::new(None);
+        properties.insert("count", count as i64);
+        properties.insert("count-visible", count > 0);
+        let app_uri = format!("application://{}", desktop_filename());
+        let parameters = glib::Variant::tuple_from_iter([app_uri.to_variant(), properties.end()]);
+
+        let session = gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>);
+        let result = session.and_then(|connection| {
+            connection.emit_signal(
+                None,
+                "/com/canonical/unity/launcherentry",
+                "com.canonical.Unity.LauncherEntry",
+                "Update",
+                Some(&parameters),
+            )
+        });
+        if let Err(err) = result {
+            tracing::warn!("Failed to set the badge count: {err}");
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        static WARNED: std::sync::Once = std::sync::Once::new();
+        WARNED.call_once(|| {
+            tracing::warn!("Badge counts are not supported on Windows, ignoring {count}");
+        });
+    }
+
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    tracing::trace!("Badge counts are not supported on this platform, ignoring {count}");
+}
+
+/// Show `progress` (between 0.0 and 1.0) on the app's taskbar or dock icon. `None` hides the progress bar.
+///
+/// The progress bar is shown in the Windows taskbar and on the macOS dock icon. On Linux it is shown by launchers that support the Unity launcher API, and only if the app has an installed desktop entry named after the executable.
+pub(crate) fn set_progress(window: &Window, progress: Option<f64>) {
+    let state = match progress {
+        Some(progress) => ProgressBarState {
+            state: Some(ProgressState::Normal),
+            progress: Some((progress.clamp(0.0, 1.0) * 100.0).round() as u64),
+            desktop_filename: Some(desktop_filename()),
+        },
+        None => ProgressBarState {
+            state: Some(ProgressState::None),
+            progress: None,
+            desktop_filename: Some(desktop_filename()),
+        },
+    };
+    window.set_progress_bar(state);
+}
+
+/// The name of the desktop entry the Unity launcher api uses to find the icon of the app. Bundles name it after the executable.
+fn desktop_filename() -> String {
+    let name = std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.file_stem()?.to_string_lossy().into_owned()))
+        .unwrap_or_default();
+    format!("{name}.desktop")
+}
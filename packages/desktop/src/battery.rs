@@ -0,0 +1,31 @@
+use crate::{element::observe, DesktopContext};
+use dioxus_html::battery::{BatteryProvider, BatteryStatus};
+use std::any::Any;
+
+/// Reads the battery with `navigator.getBattery()` in the webview, and listens for changes to it.
+///
+/// Webviews without the battery status API report no battery.
+pub(crate) struct DesktopBatteryProvider {
+    desktop_ctx: DesktopContext,
+}
+
+impl DesktopBatteryProvider {
+    pub(crate) fn new(desktop_ctx: DesktopContext) -> Self {
+        Self { desktop_ctx }
+    }
+}
+
+impl BatteryProvider for DesktopBatteryProvider {
+    fn subscribe(&self, mut on_change: Box<dyn FnMut(Option<BatteryStatus>)>) -> Box<dyn Any> {
+        Box::new(observe(
+            &self.desktop_ctx,
+            &self.desktop_ctx.query,
+            "observeBattery",
+            "",
+            move |value| match serde_json::from_value(value) {
+                Ok(status) => on_change(status),
+                Err(err) => tracing::error!("Failed to read the battery status: {err}"),
+            },
+        ))
+    }
+}
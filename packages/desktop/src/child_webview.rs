@@ -0,0 +1,262 @@
+//! Webviews embedded in a window on top of an element of the page, created with [`use_child_webview`].
+
+use crate::{
+    element::{DesktopElement, DesktopObserver},
+    window, DesktopContext,
+};
+use dioxus_core::ScopeState;
+use dioxus_html::{geometry::euclid::Rect, prelude::MountedData};
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use futures_util::StreamExt;
+use std::{cell::RefCell, rc::Rc};
+use tao::window::Window;
+use wry::{PageLoadEvent, WebView, WebViewBuilder};
+
+/// The page and settings of a [`ChildWebView`].
+pub struct ChildWebViewBuilder {
+    url: String,
+    navigation_filter: Option<Box<dyn Fn(&str) -> bool>>,
+    transparent: bool,
+}
+
+impl ChildWebViewBuilder {
+    /// Load `url` in the child webview.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            navigation_filter: None,
+            transparent: false,
+        }
+    }
+
+    /// Only let the child webview navigate to urls `filter` returns true for, like the pages of an OAuth provider. Blocked navigations are still reported to the app as [`ChildWebViewEvent::Blocked`].
+    pub fn with_navigation_filter(mut self, filter: impl Fn(&str) -> bool + 'static) -> Self {
+        self.navigation_filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Let the page below show through the transparent parts of the child webview.
+    pub fn with_transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+}
+
+/// What happened in a [`ChildWebView`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChildWebViewEvent {
+    /// The child webview started loading a url.
+    Started(String),
+
+    /// The child webview finished loading a url.
+    Finished(String),
+
+    /// The navigation filter blocked the child webview from loading a url.
+    Blocked(String),
+
+    /// The page in the child webview tried to open a url in a new window. Only the app can open new windows, like with [`open_external`](crate::open_external).
+    NewWindow(String),
+}
+
+/// Embed a webview in the current window, on top of `element`. The child webview follows the element when it moves or is resized, and is hidden while `element` is `None`.
+///
+/// `on_event` is called with the navigation events of the child webview. The child webview is closed when the component is dropped.
+///
+/// Child webviews are drawn above the page, so the page can't show anything on top of them. On Linux they are only supported on X11.
+///
+/// ```rust, ignore
+/// let frame = use_signal(cx, || None);
+/// let browser = use_child_webview(
+///     cx,
+///     frame(),
+///     || ChildWebViewBuilder::new("https://dioxuslabs.com"),
+///     |event| println!("{event:?}"),
+/// );
+///
+/// render! {
+///     button { onclick: move |_| browser.load_url("https://docs.rs"), "Docs" }
+///     div {
+///         style: "width: 100%; height: 400px;",
+///         onmounted: move |event| frame.set(Some(event.inner().clone())),
+///     }
+/// }
+/// ```
+pub fn use_child_webview(
+    cx: &ScopeState,
+    element: Option<Rc<MountedData>>,
+    build: impl FnOnce() -> ChildWebViewBuilder,
+    mut on_event: impl FnMut(ChildWebViewEvent) + 'static,
+) -> &ChildWebView {
+    let child = cx.use_hook(|| {
+        let desktop = window();
+        let (sender, mut receiver) = unbounded();
+        let webview = build_webview(&desktop.window, build(), sender);
+
+        // The handlers of the webview run inside the event loop, so the events are passed to the app in a task
+        cx.spawn(async move {
+            while let Some(event) = receiver.next().await {
+                on_event(event);
+            }
+        });
+
+        ChildWebView {
+            inner: Rc::new(ChildInner {
+                desktop,
+                webview,
+                observed: RefCell::new(None),
+            }),
+        }
+    });
+
+    let changed = match (&*child.inner.observed.borrow(), &element) {
+        (Some((old, _)), Some(new)) => !Rc::ptr_eq(old, new),
+        (None, None) => false,
+        _ => true,
+    };
+    if changed {
+        child.inner.observed.borrow_mut().take();
+        child.set_visible(false);
+        if let Some(element) = element {
+            let observer = element.downcast::<DesktopElement>().map(|target| {
+                let inner = Rc::downgrade(&child.inner);
+                target.observe_bounds(move |bounds| {
+                    if let Some(inner) = inner.upgrade() {
+                        inner.set_bounds(bounds);
+                    }
+                })
+            });
+            *child.inner.observed.borrow_mut() = Some((element, observer));
+        }
+    }
+
+    child
+}
+
+/// A handle to a webview embedded in a window, created with [`use_child_webview`].
+#[derive(Clone)]
+pub struct ChildWebView {
+    inner: Rc<ChildInner>,
+}
+
+impl ChildWebView {
+    /// Navigate the child webview to `url`.
+    pub fn load_url(&self, url: &str) {
+        if let Some(webview) = &self.inner.webview {
+            webview.load_url(url);
+        }
+    }
+
+    /// Run javascript in the page of the child webview.
+    pub fn evaluate_script(&self, script: &str) {
+        if let Some(webview) = &self.inner.webview {
+            if let Err(err) = webview.evaluate_script(script) {
+                tracing::error!("Failed to run a script in a child webview: {err}");
+            }
+        }
+    }
+
+    /// Get the wry webview, or `None` if it could not be created on this platform.
+    pub fn webview(&self) -> Option<&WebView> {
+        self.inner.webview.as_ref()
+    }
+
+    fn set_visible(&self, visible: bool) {
+        if let Some(webview) = &self.inner.webview {
+            _ = webview.set_visible(visible);
+        }
+    }
+}
+
+struct ChildInner {
+    desktop: DesktopContext,
+    webview: Option<WebView>,
+    observed: RefCell<Option<(Rc<MountedData>, Option<DesktopObserver>)>>,
+}
+
+impl ChildInner {
+    fn set_bounds(&self, bounds: Rect<f64, f64>) {
+        let Some(webview) = &self.webview else {
+            return;
+        };
+        let rect = to_wry_rect(&self.desktop.window, bounds);
+        // An element that isn't rendered, like one with display: none, has no size
+        let visible = rect.width > 0 && rect.height > 0;
+        webview.set_bounds(rect);
+        _ = webview.set_visible(visible);
+    }
+}
+
+fn build_webview(
+    window: &Window,
+    builder: ChildWebViewBuilder,
+    sender: UnboundedSender<ChildWebViewEvent>,
+) -> Option<WebView> {
+    let ChildWebViewBuilder {
+        url,
+        navigation_filter,
+        transparent,
+    } = builder;
+
+    let navigation_sender = sender.clone();
+    let new_window_sender = sender.clone();
+    let webview = WebViewBuilder::new_as_child(window)
+        .with_bounds(wry::Rect {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+        })
+        .with_visible(false)
+        .with_transparent(transparent)
+        .with_url(&url)
+        .and_then(|webview| {
+            webview
+                .with_navigation_handler(move |url| {
+                    let allowed = navigation_filter
+                        .as_ref()
+                        .map_or(true, |filter| filter(&url));
+                    if !allowed {
+                        _ = navigation_sender.unbounded_send(ChildWebViewEvent::Blocked(url));
+                    }
+                    allowed
+                })
+                .with_on_page_load_handler(move |event, url| {
+                    _ = sender.unbounded_send(match event {
+                        PageLoadEvent::Started => ChildWebViewEvent::Started(url),
+                        PageLoadEvent::Finished => ChildWebViewEvent::Finished(url),
+                    });
+                })
+                .with_new_window_req_handler(move |url| {
+                    _ = new_window_sender.unbounded_send(ChildWebViewEvent::NewWindow(url));
+                    false
+                })
+                .build()
+        });
+
+    match webview {
+        Ok(webview) => Some(webview),
+        Err(err) => {
+            tracing::error!("Failed to create a child webview: {err}");
+            None
+        }
+    }
+}
+
+/// Convert bounds in the viewport of the page to the bounds of a child webview in the window.
+fn to_wry_rect(window: &Window, bounds: Rect<f64, f64>) -> wry::Rect {
+    // Windows places child webviews in physical pixels, other platforms in logical pixels
+    #[cfg(target_os = "windows")]
+    let scale = window.scale_factor();
+    #[cfg(not(target_os = "windows"))]
+    let scale = {
+        let _ = window;
+        1.0
+    };
+
+    wry::Rect {
+        x: (bounds.origin.x * scale).round() as i32,
+        y: (bounds.origin.y * scale).round() as i32,
+        width: (bounds.size.width * scale).round().max(0.0) as u32,
+        height: (bounds.size.height * scale).round().max(0.0) as u32,
+    }
+}
@@ -0,0 +1,191 @@
+use async_trait::async_trait;
+use dioxus_html::clipboard::{ClipboardError, ClipboardProvider};
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub(crate) use native::DesktopClipboardProvider;
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+pub(crate) use webview::DesktopClipboardProvider;
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+mod native {
+    use super::*;
+    use arboard::{Clipboard, ImageData};
+    use dioxus_html::clipboard::ClipboardImage;
+    use std::cell::RefCell;
+
+    thread_local! {
+        // The clipboard is kept open for the lifetime of the app, because on Linux the contents we wrote are lost once it is closed
+        static CLIPBOARD: RefCell<Option<Clipboard>> = RefCell::new(None);
+    }
+
+    /// Reads and writes the system clipboard with arboard.
+    pub(crate) struct DesktopClipboardProvider;
+
+    impl DesktopClipboardProvider {
+        pub(crate) fn new(_desktop_ctx: crate::DesktopContext) -> Self {
+            Self
+        }
+
+        fn with_clipboard<T>(
+            &self,
+            f: impl FnOnce(&mut Clipboard) -> Result<T, arboard::Error>,
+        ) -> Result<T, ClipboardError> {
+            CLIPBOARD.with(|clipboard| {
+                let mut clipboard = clipboard.borrow_mut();
+                if clipboard.is_none() {
+                    *clipboard = Some(Clipboard::new().map_err(clipboard_error)?);
+                }
+                f(clipboard.as_mut().unwrap()).map_err(clipboard_error)
+            })
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl ClipboardProvider for DesktopClipboardProvider {
+        async fn read_text(&self) -> Result<String, ClipboardError> {
+            self.with_clipboard(|clipboard| clipboard.get_text())
+        }
+
+        async fn write_text(&self, text: String) -> Result<(), ClipboardError> {
+            self.with_clipboard(|clipboard| clipboard.set_text(text))
+        }
+
+        async fn read_image(&self) -> Result<ClipboardImage, ClipboardError> {
+            let image = self.with_clipboard(|clipboard| clipboard.get_image())?;
+            Ok(ClipboardImage {
+                width: image.width,
+                height: image.height,
+                rgba: image.bytes.into_owned(),
+            })
+        }
+
+        async fn write_image(&self, image: ClipboardImage) -> Result<(), ClipboardError> {
+            if image.rgba.len() != image.width * image.height * 4 {
+                return Err(ClipboardError::Other(format!(
+                    "expected {} bytes of RGBA pixels for a {}x{} image, got {}",
+                    image.width * image.height * 4,
+                    image.width,
+                    image.height,
+                    image.rgba.len()
+                )));
+            }
+            self.with_clipboard(|clipboard| {
+                clipboard.set_image(ImageData {
+                    width: image.width,
+                    height: image.height,
+                    bytes: image.rgba.into(),
+                })
+            })
+        }
+
+        async fn read_html(&self) -> Result<String, ClipboardError> {
+            self.with_clipboard(|clipboard| clipboard.get().html())
+        }
+
+        async fn write_html(
+            &self,
+            html: String,
+            alt_text: Option<String>,
+        ) -> Result<(), ClipboardError> {
+            self.with_clipboard(|clipboard| clipboard.set_html(html, alt_text))
+        }
+    }
+
+    fn clipboard_error(err: arboard::Error) -> ClipboardError {
+        match err {
+            arboard::Error::ContentNotAvailable => ClipboardError::Empty,
+            arboard::Error::ClipboardNotSupported => ClipboardError::Unsupported,
+            err => ClipboardError::Other(err.to_string()),
+        }
+    }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+mod webview {
+    use super::*;
+    use crate::DesktopContext;
+
+    /// Reads and writes the clipboard with `navigator.clipboard` inside the webview. Images are not supported.
+    pub(crate) struct DesktopClipboardProvider {
+        desktop_ctx: DesktopContext,
+    }
+
+    impl DesktopClipboardProvider {
+        pub(crate) fn new(desktop_ctx: DesktopContext) -> Self {
+            Self { desktop_ctx }
+        }
+
+        async fn run(&self, js: &str) -> Result<serde_json::Value, ClipboardError> {
+            let ctx = self.desktop_ctx.clone();
+            let mut query = self
+                .desktop_ctx
+                .query
+                .new_query::<serde_json::Value>(js, ctx);
+            let result = query
+                .result()
+                .await
+                .map_err(|err| ClipboardError::Other(err.to_string()))?;
+            match result {
+                serde_json::Value::Object(mut object) => match object.remove("error") {
+                    Some(serde_json::Value::String(error)) => Err(ClipboardError::Denied(error)),
+                    _ => Ok(object.remove("value").unwrap_or_default()),
+                },
+                _ => Ok(serde_json::Value::Null),
+            }
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl ClipboardProvider for DesktopClipboardProvider {
+        async fn read_text(&self) -> Result<String, ClipboardError> {
+            let value = self
+                .run(
+                    r#"try { return { value: await navigator.clipboard.readText() }; }
+                    catch (e) { return { error: String(e) }; }"#,
+                )
+                .await?;
+            match value {
+                serde_json::Value::String(text) => Ok(text),
+                _ => Err(ClipboardError::Empty),
+            }
+        }
+
+        async fn write_text(&self, text: String) -> Result<(), ClipboardError> {
+            let text = serde_json::to_string(&text).unwrap();
+            self.run(&format!(
+                r#"try {{ await navigator.clipboard.writeText({text}); return {{}}; }}
+                catch (e) {{ return {{ error: String(e) }}; }}"#
+            ))
+            .await?;
+            Ok(())
+        }
+    }
+}
+
+/// A number that changes whenever the contents of the system clipboard change, or `None` if the OS doesn't keep one.
+#[cfg(target_os = "windows")]
+pub(crate) fn change_count() -> Option<u64> {
+    let count = unsafe { windows::Win32::System::DataExchange::GetClipboardSequenceNumber() };
+    // Zero means the window station has no access to the clipboard
+    (count != 0).then_some(count as u64)
+}
+
+/// A number that changes whenever the contents of the system clipboard change, or `None` if the OS doesn't keep one.
+#[cfg(target_os = "macos")]
+pub(crate) fn change_count() -> Option<u64> {
+    use objc::runtime::Object;
+    use objc::{msg_send, sel, sel_impl};
+
+    objc::rc::autoreleasepool(|| unsafe {
+        let pasteboard: *mut Object = msg_send![objc::class!(NSPasteboard), generalPasteboard];
+        let count: isize = msg_send![pasteboard, changeCount];
+        Some(count as u64)
+    })
+}
+
+/// A number that changes whenever the contents of the system clipboard change, or `None` if the OS doesn't keep one.
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub(crate) fn change_count() -> Option<u64> {
+    None
+}
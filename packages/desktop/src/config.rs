@@ -1,7 +1,16 @@
 use std::borrow::Cow;
+use std::future::Future;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
 
-use dioxus_core::prelude::Component;
+use crate::native_menu::NativeMenu;
+use crate::splash::SplashScreen;
+use crate::tray::TrayBuilder;
+use crate::window_effect::WindowEffect;
+use crate::window_level::WindowLevel;
+use dioxus_core::{prelude::Component, Executor};
 use tao::window::{Icon, WindowBuilder, WindowId};
 use wry::{
     http::{Request as HttpRequest, Response as HttpResponse},
@@ -24,16 +33,31 @@ pub struct Config {
     pub(crate) window: WindowBuilder,
     pub(crate) file_drop_handler: Option<DropHandler>,
     pub(crate) protocols: Vec<WryProtocol>,
+    pub(crate) async_protocols: Vec<AsyncProtocol>,
     pub(crate) pre_rendered: Option<String>,
     pub(crate) disable_context_menu: bool,
+    pub(crate) devtools: bool,
     pub(crate) resource_dir: Option<PathBuf>,
     pub(crate) data_dir: Option<PathBuf>,
     pub(crate) custom_head: Option<String>,
     pub(crate) custom_index: Option<String>,
     pub(crate) root_name: String,
     pub(crate) background_color: Option<(u8, u8, u8, u8)>,
+    pub(crate) transparent_titlebar: bool,
+    pub(crate) window_effect: Option<WindowEffect>,
+    pub(crate) window_level: Option<WindowLevel>,
+    pub(crate) skip_taskbar: bool,
     pub(crate) last_window_close_behaviour: WindowCloseBehaviour,
     pub(crate) enable_default_menu_bar: bool,
+    pub(crate) menu: Option<NativeMenu>,
+    pub(crate) executor: Option<Rc<dyn Executor>>,
+    pub(crate) frame_budget: Option<Duration>,
+    pub(crate) tray: Option<TrayBuilder>,
+    pub(crate) start_hidden_in_tray: bool,
+    pub(crate) window_state: Option<PathBuf>,
+    pub(crate) url_scheme: Option<String>,
+    pub(crate) show_on_first_render: bool,
+    pub(crate) splash_screen: Option<SplashScreen>,
 }
 
 type DropHandler = Box<dyn Fn(WindowId, FileDropEvent) -> bool>;
@@ -43,6 +67,13 @@ pub(crate) type WryProtocol = (
     Box<dyn Fn(HttpRequest<Vec<u8>>) -> HttpResponse<Cow<'static, [u8]>> + 'static>,
 );
 
+type ProtocolResponse = Pin<Box<dyn Future<Output = HttpResponse<Cow<'static, [u8]>>>>>;
+
+pub(crate) type AsyncProtocol = (
+    String,
+    Box<dyn Fn(HttpRequest<Vec<u8>>) -> ProtocolResponse + 'static>,
+);
+
 impl Config {
     /// Initializes a new `WindowBuilder` with default values.
     #[inline]
@@ -57,17 +88,32 @@ impl Config {
         Self {
             window,
             protocols: Vec::new(),
+            async_protocols: Vec::new(),
             file_drop_handler: None,
             pre_rendered: None,
             disable_context_menu: !cfg!(debug_assertions),
+            devtools: cfg!(debug_assertions),
             resource_dir: None,
             data_dir: None,
             custom_head: None,
             custom_index: None,
             root_name: "main".to_string(),
             background_color: None,
+            transparent_titlebar: false,
+            window_effect: None,
+            window_level: None,
+            skip_taskbar: false,
             last_window_close_behaviour: WindowCloseBehaviour::LastWindowExitsApp,
             enable_default_menu_bar: true,
+            menu: None,
+            executor: None,
+            frame_budget: None,
+            tray: None,
+            start_hidden_in_tray: false,
+            window_state: None,
+            url_scheme: None,
+            show_on_first_render: false,
+            splash_screen: None,
         }
     }
 
@@ -93,6 +139,14 @@ impl Config {
         self
     }
 
+    /// Set the menubar of the window, replacing the default menu bar. The top level items should be submenus, like "File" and "Edit".
+    ///
+    /// Clicks on the items are reported to [`use_menu_event_handler`](crate::use_menu_event_handler), and [`use_menubar`](crate::use_menubar) can change the menu while the app runs.
+    pub fn with_menu(mut self, menu: NativeMenu) -> Self {
+        self.menu = Some(menu);
+        self
+    }
+
     /// set the directory from which assets will be searched in release mode
     pub fn with_resource_directory(mut self, path: impl Into<PathBuf>) -> Self {
         self.resource_dir = Some(path.into());
@@ -107,12 +161,54 @@ impl Config {
         self
     }
 
+    /// Save the size, position and maximized state of the window to the json file at `path` when it closes, and open it with the saved geometry on the next launch.
+    ///
+    /// If the monitor the window was on is no longer connected, the window keeps its size and the platform picks its position. Windows that would not fit on their monitor anymore are shrunk and moved onto it. Give every window its own path.
+    pub fn with_window_state_persistence(mut self, path: impl Into<PathBuf>) -> Self {
+        self.window_state = Some(path.into());
+        self
+    }
+
+    /// Open urls with this scheme, like `myapp://settings`, with the app. Listen for them with [`use_deep_link_handler`](crate::use_deep_link_handler).
+    ///
+    /// On Windows and Linux the scheme is registered for the current user when the app starts. The app becomes single instance: when it is started again, the new process forwards the urls it was started with to the running app and exits. On macOS the scheme has to be declared under `CFBundleURLTypes` in the `Info.plist` of the app bundle.
+    pub fn with_url_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.url_scheme = Some(scheme.into());
+        self
+    }
+
+    /// Keep the window hidden until the first render of the app was applied and its stylesheets have loaded, so the window doesn't flash white while the page loads.
+    ///
+    /// The window is shown after at most three seconds, even if a stylesheet is still loading. Windows that are not visible in their [`WindowBuilder`] stay hidden.
+    pub fn with_show_on_first_render(mut self, show_on_first_render: bool) -> Self {
+        self.show_on_first_render = show_on_first_render;
+        self
+    }
+
+    /// Show a splash screen until the window is shown. Combine it with [`Config::with_show_on_first_render`] to show it until the app has rendered.
+    pub fn with_splash_screen(mut self, splash_screen: SplashScreen) -> Self {
+        self.splash_screen = Some(splash_screen);
+        self
+    }
+
     /// Set whether or not the right-click context menu should be disabled.
     pub fn with_disable_context_menu(mut self, disable: bool) -> Self {
         self.disable_context_menu = disable;
         self
     }
 
+    /// Let the devtools of the webview be opened with [`DesktopService::open_devtools`](crate::DesktopService::open_devtools) and the context menu. Enabled by default in debug builds.
+    ///
+    /// Release builds also need the `devtools` feature. This lets an app ship devtools to beta users behind a flag:
+    ///
+    /// ```rust, ignore
+    /// Config::new().with_devtools(std::env::var("MY_APP_BETA").is_ok())
+    /// ```
+    pub fn with_devtools(mut self, devtools: bool) -> Self {
+        self.devtools = devtools;
+        self
+    }
+
     /// Set the pre-rendered HTML content
     pub fn with_prerendered(mut self, content: String) -> Self {
         self.pre_rendered = Some(content);
@@ -133,7 +229,7 @@ impl Config {
         self
     }
 
-    /// Set a file drop handler. If this is enabled, html drag events will be disabled.
+    /// Set a file drop handler. If it returns true, the webview ignores the drop and html drag events are not fired for it.
     pub fn with_file_drop_handler(
         mut self,
         handler: impl Fn(WindowId, FileDropEvent) -> bool + 'static,
@@ -143,11 +239,40 @@ impl Config {
     }
 
     /// Set a custom protocol
-    pub fn with_custom_protocol<F>(mut self, name: String, handler: F) -> Self
+    pub fn with_custom_protocol<F>(mut self, name: impl Into<String>, handler: F) -> Self
     where
         F: Fn(HttpRequest<Vec<u8>>) -> HttpResponse<Cow<'static, [u8]>> + 'static,
     {
-        self.protocols.push((name, Box::new(handler)));
+        self.protocols.push((name.into(), Box::new(handler)));
+        self
+    }
+
+    /// Serve the requests the webview makes to `name://` with an async handler, like the bundled assets of the app or files that need authentication.
+    ///
+    /// The response is awaited as a task of the window on the executor of the app, so the handler doesn't block the window and can do IO. On Windows and Android the webview reaches the protocol at `http://name.localhost/` instead.
+    ///
+    /// ```rust, ignore
+    /// let config = Config::new().with_asynchronous_custom_protocol("thumbnails", |request| async move {
+    ///     let path = request.uri().path().trim_start_matches('/').to_string();
+    ///     match tokio::fs::read(thumbnail_path(&path)).await {
+    ///         Ok(bytes) => Response::new(Cow::Owned(bytes)),
+    ///         Err(_) => Response::builder().status(404).body(Cow::Borrowed(&[][..])).unwrap(),
+    ///     }
+    /// });
+    /// ```
+    pub fn with_asynchronous_custom_protocol<F, R>(
+        mut self,
+        name: impl Into<String>,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(HttpRequest<Vec<u8>>) -> R + 'static,
+        R: Future<Output = HttpResponse<Cow<'static, [u8]>>> + 'static,
+    {
+        self.async_protocols.push((
+            name.into(),
+            Box::new(move |request| Box::pin(handler(request))),
+        ));
         self
     }
 
@@ -191,6 +316,69 @@ impl Config {
         self.background_color = Some(color);
         self
     }
+
+    /// Hide the title bar behind the content of the window, leaving only the window buttons. The page should leave room for them at the top left. Only supported on macOS.
+    pub fn with_transparent_titlebar(mut self, transparent: bool) -> Self {
+        self.transparent_titlebar = transparent;
+        self
+    }
+
+    /// Show the desktop blurred behind the transparent parts of the page. This makes the window and the webview transparent.
+    ///
+    /// If the platform doesn't support the effect, a warning is logged and the window is created without it.
+    pub fn with_window_effect(mut self, effect: WindowEffect) -> Self {
+        self.window_effect = Some(effect);
+        self
+    }
+
+    /// Stack the window above or below the windows of other apps.
+    pub fn with_window_level(mut self, level: WindowLevel) -> Self {
+        self.window_level = Some(level);
+        self
+    }
+
+    /// Keep the window above the windows of other apps. This is the same as [`Config::with_window_level`] with [`WindowLevel::AlwaysOnTop`].
+    pub fn with_always_on_top(self, always_on_top: bool) -> Self {
+        self.with_window_level(always_on_top.into())
+    }
+
+    /// Hide the window from the taskbar and the alt-tab switcher, for palettes and overlays. Only supported on Windows and Linux.
+    pub fn with_skip_taskbar(mut self, skip: bool) -> Self {
+        self.skip_taskbar = skip;
+        self
+    }
+
+    /// Show an icon in the system tray. Use [`use_tray_event_handler`](crate::use_tray_event_handler) to react to clicks on the icon and its menu.
+    pub fn with_tray(mut self, tray: TrayBuilder) -> Self {
+        self.tray = Some(tray);
+        self
+    }
+
+    /// Start with only the tray icon of [`Config::with_tray`], for apps that run in the background. The window and its app are created the first time the icon is used.
+    ///
+    /// Clicking the icon shows the window. Clicking an item of the tray menu creates the window without showing it, and passes the click to the [`use_tray_event_handler`](crate::use_tray_event_handler) handlers of its components. Closing the window hides it until the icon is clicked again. Call [`DesktopContext::close`](crate::DesktopContext::close) to quit.
+    ///
+    /// The window is shown right away if the tray icon can't be created, like on mobile.
+    pub fn start_hidden_in_tray(mut self) -> Self {
+        self.start_hidden_in_tray = true;
+        self
+    }
+
+    /// Render large updates across several frames, spending at most `budget` rendering before the window handles input again.
+    ///
+    /// By default the whole update is rendered at once, which can freeze the window while thousands of components render. Components that read signals marked with `Signal::mark_urgent` are rendered first. A budget around 8ms leaves time for the webview to paint at 60fps.
+    pub fn with_frame_budget(mut self, budget: Duration) -> Self {
+        self.frame_budget = Some(budget);
+        self
+    }
+
+    /// Run the tasks of the app on a custom [`Executor`] instead of a tokio runtime.
+    ///
+    /// With an executor, [`launch_with_props`](crate::launch_with_props) does not start a tokio runtime. Every window of the app uses the executor of the config the app was launched with.
+    pub fn with_executor(mut self, executor: impl Executor + 'static) -> Self {
+        self.executor = Some(Rc::new(executor));
+        self
+    }
 }
 
 impl Default for Config {
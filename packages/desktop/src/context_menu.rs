@@ -0,0 +1,119 @@
+//! Native menus that open when an element is right clicked, shown with [`use_context_menu`](crate::use_context_menu).
+
+use crate::native_menu::{MenuEvent, NativeMenu};
+use slab::Slab;
+use std::cell::{Cell, RefCell};
+use tao::window::Window;
+
+type ContextMenuHandler = Box<dyn FnMut(&MenuEvent)>;
+
+struct ContextMenu {
+    handler: ContextMenuHandler,
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    built: Option<crate::native_menu::BuiltMenu>,
+}
+
+/// The context menus of every window, and the handlers for clicks on their items.
+#[derive(Default)]
+pub(crate) struct ContextMenus {
+    menus: RefCell<Slab<ContextMenu>>,
+    /// The menu that was shown last. Only one context menu can be open at a time, so its clicks belong to it.
+    open: Cell<Option<usize>>,
+}
+
+impl ContextMenus {
+    pub(crate) fn add(&self, handler: ContextMenuHandler) -> usize {
+        self.menus.borrow_mut().insert(ContextMenu {
+            handler,
+            #[cfg(not(any(target_os = "ios", target_os = "android")))]
+            built: None,
+        })
+    }
+
+    pub(crate) fn remove(&self, id: usize) {
+        self.menus.borrow_mut().try_remove(id);
+        if self.open.get() == Some(id) {
+            self.open.set(None);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl ContextMenus {
+    /// Show `menu` at the cursor. Clicks on its items are sent to the handler of the context menu with this id.
+    pub(crate) fn show(&self, id: usize, window: &Window, menu: &NativeMenu) {
+        use crate::native_menu::BuiltMenu;
+
+        let native = {
+            let mut menus = self.menus.borrow_mut();
+            let Some(state) = menus.get_mut(id) else {
+                return;
+            };
+            match &mut state.built {
+                Some(built) => built.update(menu),
+                None => state.built = Some(BuiltMenu::new(menu)),
+            }
+            state.built.as_ref().map(|built| built.menu.clone())
+        };
+        self.open.set(Some(id));
+
+        // The popup blocks until it is closed on some platforms, so the menus can't stay borrowed
+        if let Some(native) = native {
+            show_context_menu(&native, window);
+        }
+    }
+
+    /// Send a click on a menu item to the handler of the open context menu if the item is in it. Returns false if it belongs to another menu.
+    pub(crate) fn handle_menu_event(&self, id: &str) -> bool {
+        let Some(open) = self.open.get() else {
+            return false;
+        };
+        let mut menus = self.menus.borrow_mut();
+        match menus.get_mut(open) {
+            Some(menu)
+                if menu
+                    .built
+                    .as_ref()
+                    .map_or(false, |built| built.contains(id)) =>
+            {
+                (menu.handler)(&MenuEvent { id: id.to_string() });
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+impl ContextMenus {
+    pub(crate) fn show(&self, _id: usize, _window: &Window, _menu: &NativeMenu) {}
+
+    pub(crate) fn handle_menu_event(&self, _id: &str) -> bool {
+        false
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+#[allow(unused)]
+fn show_context_menu(menu: &muda::Menu, window: &Window) {
+    use muda::ContextMenu;
+
+    // Without a position the menu opens at the cursor
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        menu.show_context_menu_for_hwnd(window.hwnd(), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use tao::platform::unix::WindowExtUnix;
+        menu.show_context_menu_for_gtk_window(window.gtk_window(), None);
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use tao::platform::macos::WindowExtMacOS;
+        menu.show_context_menu_for_nsview(window.ns_view() as _, None);
+    }
+}
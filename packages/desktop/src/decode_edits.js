@@ -0,0 +1,51 @@
+// Decode a batch of edits framed by the desktop renderer
+function decode_edits(buffer) {
+  const bytes = new Uint8Array(buffer);
+  const encoding = bytes[bytes.length - 1];
+  const body = bytes.subarray(0, bytes.length - 1);
+  if (encoding === 1) {
+    return decompress_lz4(body);
+  }
+  return body;
+}
+// Decompress a LZ4 block prefixed with the little endian uncompressed size
+function decompress_lz4(input) {
+  const size = input[0] | (input[1] << 8) | (input[2] << 16) | (input[3] << 24);
+  const output = new Uint8Array(size);
+  let i = 4;
+  let o = 0;
+  while (i < input.length) {
+    const token = input[i++];
+    let literals = token >> 4;
+    if (literals === 15) {
+      let extra;
+      do {
+        extra = input[i++];
+        literals += extra;
+      } while (extra === 255);
+    }
+    output.set(input.subarray(i, i + literals), o);
+    i += literals;
+    o += literals;
+    // The last sequence only contains literals
+    if (i >= input.length) {
+      break;
+    }
+    const offset = input[i] | (input[i + 1] << 8);
+    i += 2;
+    let length = token & 15;
+    if (length === 15) {
+      let extra;
+      do {
+        extra = input[i++];
+        length += extra;
+      } while (extra === 255);
+    }
+    length += 4;
+    // Matches may overlap the bytes they are copying, so copy one byte at a time
+    for (let m = o - offset; length > 0; length--) {
+      output[o++] = output[m++];
+    }
+  }
+  return output;
+}
@@ -0,0 +1,251 @@
+//! Urls with the custom scheme of the app, like `myapp://settings`, that the OS opens with the app. Enabled with [`Config::with_url_scheme`](crate::Config::with_url_scheme).
+//!
+//! On Windows and Linux the scheme is registered for the current user when the app starts, and the OS starts a new process with the url as an argument. Only the first process opens windows: the others forward their urls to it and exit. On macOS the scheme has to be declared in the `Info.plist` of the app bundle, and the OS sends the url to the running app.
+
+use crate::ipc::UserWindowEvent;
+use slab::Slab;
+use std::cell::RefCell;
+use tao::event_loop::EventLoopProxy;
+
+/// A url with the scheme of the app that the app was asked to open.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeepLink {
+    /// The full url, like `myapp://settings/profile?tab=1`.
+    pub url: String,
+}
+
+impl DeepLink {
+    /// The url without the scheme as an absolute path, like `/settings/profile?tab=1`. Pass it to the router to navigate to the page the url points to.
+    pub fn path(&self) -> String {
+        let rest = match self.url.split_once(':') {
+            Some((_, rest)) => rest,
+            None => &self.url,
+        };
+        format!("/{}", rest.trim_start_matches('/'))
+    }
+}
+
+type DeepLinkHandler = Box<dyn FnMut(&DeepLink)>;
+
+#[derive(Default)]
+pub(crate) struct DeepLinks {
+    handlers: RefCell<Slab<DeepLinkHandler>>,
+    // Links that were opened before any component listened for them, like the one the app was started with
+    missed: RefCell<Vec<DeepLink>>,
+}
+
+impl DeepLinks {
+    /// Start handling urls with `scheme`. If another process of the app is running, this forwards the urls to it and exits.
+    pub(crate) fn new(scheme: Option<&str>, proxy: &EventLoopProxy<UserWindowEvent>) -> Self {
+        let links = Self::default();
+        let Some(scheme) = scheme else {
+            return links;
+        };
+
+        let prefix = format!("{}:", scheme.to_lowercase());
+        let urls: Vec<String> = std::env::args()
+            .skip(1)
+            .filter(|arg| arg.to_lowercase().starts_with(&prefix))
+            .collect();
+
+        #[cfg(not(any(target_os = "ios", target_os = "android")))]
+        {
+            if single_instance::forward(scheme, &urls) {
+                std::process::exit(0);
+            }
+            single_instance::listen(scheme, proxy.clone());
+            register(scheme);
+        }
+        #[cfg(any(target_os = "ios", target_os = "android"))]
+        let _ = proxy;
+
+        links
+            .missed
+            .borrow_mut()
+            .extend(urls.into_iter().map(|url| DeepLink { url }));
+        links
+    }
+
+    pub(crate) fn add_handler(&self, mut handler: DeepLinkHandler) -> usize {
+        for link in self.missed.borrow_mut().drain(..) {
+            handler(&link);
+        }
+        self.handlers.borrow_mut().insert(handler)
+    }
+
+    pub(crate) fn remove_handler(&self, id: usize) {
+        self.handlers.borrow_mut().try_remove(id);
+    }
+
+    pub(crate) fn emit(&self, link: DeepLink) {
+        let mut handlers = self.handlers.borrow_mut();
+        if handlers.is_empty() {
+            self.missed.borrow_mut().push(link);
+            return;
+        }
+        for (_, handler) in handlers.iter_mut() {
+            handler(&link);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+mod single_instance {
+    use super::*;
+    use crate::ipc::EventData;
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+        path::PathBuf,
+        time::Duration,
+    };
+    use tao::window::WindowId;
+
+    /// The running process writes the port it listens on to this file.
+    fn port_file(scheme: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("dioxus-{scheme}.port"))
+    }
+
+    /// The running process greets with this line, so the urls are not sent to another program that reused the port.
+    fn greeting(scheme: &str) -> String {
+        format!("dioxus-deep-link {scheme}")
+    }
+
+    /// Send the urls to the process that is already running. Returns false if there is none.
+    pub(super) fn forward(scheme: &str, urls: &[String]) -> bool {
+        let Some(port) = std::fs::read_to_string(port_file(scheme))
+            .ok()
+            .and_then(|port| port.trim().parse::<u16>().ok())
+        else {
+            return false;
+        };
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let Ok(mut stream) = TcpStream::connect_timeout(&address, Duration::from_millis(500))
+        else {
+            return false;
+        };
+        _ = stream.set_read_timeout(Some(Duration::from_millis(500)));
+
+        let mut line = String::new();
+        if BufReader::new(&stream).read_line(&mut line).is_err() || line.trim() != greeting(scheme)
+        {
+            return false;
+        }
+        for url in urls {
+            if writeln!(stream, "{url}").is_err() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Accept the urls of processes that are started later on a background thread.
+    pub(super) fn listen(scheme: &str, proxy: EventLoopProxy<UserWindowEvent>) {
+        let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, 0)) {
+            Ok(listener) => listener,
+            Err(err) => {
+                tracing::error!("Failed to listen for deep links from other processes: {err}");
+                return;
+            }
+        };
+        let Ok(address) = listener.local_addr() else {
+            return;
+        };
+        if let Err(err) = std::fs::write(port_file(scheme), address.port().to_string()) {
+            tracing::error!("Failed to listen for deep links from other processes: {err}");
+            return;
+        }
+
+        let greeting = greeting(scheme);
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                if writeln!(stream, "{greeting}").is_err() {
+                    continue;
+                }
+                for url in BufReader::new(stream).lines().map_while(Result::ok) {
+                    // Links are not tied to a window
+                    _ = proxy.send_event(UserWindowEvent(EventData::OpenUrl(url), unsafe {
+                        WindowId::dummy()
+                    }));
+                }
+            }
+        });
+    }
+}
+
+/// Make the OS open urls with `scheme` with this executable.
+#[cfg(target_os = "windows")]
+fn register(scheme: &str) {
+    use std::process::Command;
+
+    let Ok(exe) = std::env::current_exe() else {
+        return;
+    };
+    let key = format!("HKCU\\Software\\Classes\\{scheme}");
+    let command = format!("\"{}\" \"%1\"", exe.display());
+    let entries = [
+        (key.clone(), None, format!("URL:{scheme}")),
+        (key.clone(), Some("URL Protocol"), String::new()),
+        (format!("{key}\\shell\\open\\command"), None, command),
+    ];
+    for (key, name, value) in entries {
+        let mut reg = Command::new("reg");
+        reg.args(["add", key.as_str(), "/f", "/d", value.as_str()]);
+        match name {
+            Some(name) => reg.args(["/v", name]),
+            None => reg.arg("/ve"),
+        };
+        if let Err(err) = reg.output() {
+            tracing::error!("Failed to register the {scheme} url scheme: {err}");
+            return;
+        }
+    }
+}
+
+/// Make the OS open urls with `scheme` with this executable.
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "android"
+)))]
+fn register(scheme: &str) {
+    use std::process::Command;
+
+    let (Ok(exe), Some(dir)) = (std::env::current_exe(), applications_dir()) else {
+        return;
+    };
+    let name = format!("dioxus-{scheme}-handler.desktop");
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName={scheme}\nExec=\"{}\" %u\nMimeType=x-scheme-handler/{scheme};\nNoDisplay=true\n",
+        exe.display()
+    );
+    let result = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(dir.join(&name), entry))
+        .and_then(|_| {
+            Command::new("xdg-mime")
+                .args(["default", &name, &format!("x-scheme-handler/{scheme}")])
+                .output()
+        });
+    if let Err(err) = result {
+        tracing::error!("Failed to register the {scheme} url scheme: {err}");
+    }
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "android"
+)))]
+fn applications_dir() -> Option<std::path::PathBuf> {
+    let data = match std::env::var_os("XDG_DATA_HOME") {
+        Some(dir) => dir.into(),
+        None => std::path::PathBuf::from(std::env::var_os("HOME")?).join(".local/share"),
+    };
+    Some(data.join("applications"))
+}
+
+/// The scheme is declared in the `Info.plist` of the app bundle on macOS.
+#[cfg(target_os = "macos")]
+fn register(_scheme: &str) {}
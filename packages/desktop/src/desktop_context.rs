@@ -1,25 +1,47 @@
 use crate::{
     app::SharedContext,
     assets::AssetHandlerRegistry,
+    clipboard::DesktopClipboardProvider,
     edits::EditQueue,
+    fullscreen::FullscreenMode,
     ipc::{EventData, UserWindowEvent},
+    js_channel::JsChannels,
+    keep_awake::KeepAwakeGuard,
+    menubar::MenuBar,
+    native_menu::NativeMenu,
     query::QueryEngine,
+    quit::QuitHandlers,
     shortcut::{HotKey, ShortcutId, ShortcutRegistryError},
+    tray::TrayImage,
     webview::WebviewInstance,
+    window_effect::{WindowEffect, WindowEffectError},
+    window_handle::WindowHandle,
+    window_level::WindowLevel,
     AssetRequest, Config,
 };
 use dioxus_core::{
     prelude::{current_scope_id, ScopeId},
-    Mutations, VirtualDom,
+    Component, Mutations, VirtualDom,
+};
+use dioxus_html::{
+    clipboard::UseClipboard,
+    notification::{NotificationContent, NotificationId, ScheduleAt},
 };
 use dioxus_interpreter_js::binary_protocol::Channel;
+use dioxus_signals::{Effect, Readable};
 use rustc_hash::FxHashMap;
 use slab::Slab;
-use std::{cell::RefCell, fmt::Debug, rc::Rc, rc::Weak, sync::atomic::AtomicU16};
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    rc::Rc,
+    rc::Weak,
+    sync::atomic::AtomicU16,
+};
 use tao::{
     event::Event,
     event_loop::EventLoopWindowTarget,
-    window::{Fullscreen as WryFullscreen, Window, WindowId},
+    window::{Window, WindowId},
 };
 use wry::{RequestAsyncResponder, WebView};
 
@@ -35,6 +57,74 @@ pub fn window() -> DesktopContext {
     dioxus_core::prelude::consume_context().unwrap()
 }
 
+/// Schedule a local notification to be shown at a later time.
+///
+/// The notification is saved in the data directory of the app, so it is still shown if the app restarts before it is due. On Linux it is handed to the systemd user session and shown even while the app is closed. On other platforms, a notification that comes due while the app is closed is shown the next time the app launches.
+///
+/// Use [`use_notification_events`](crate::use_notification_events) to find out when the notification was shown or clicked. Clicks are only reported on Linux while the app is running.
+///
+/// ## Panics
+///
+/// This function will panic if it is called outside of the context of a Dioxus App.
+///
+/// ```rust, ignore
+/// let id = schedule_notification(
+///     Duration::from_secs(60 * 5),
+///     NotificationContent::new("Standup").body("The daily standup starts in 5 minutes"),
+/// );
+/// ```
+pub fn schedule_notification(
+    at: impl Into<ScheduleAt>,
+    content: NotificationContent,
+) -> NotificationId {
+    window().schedule_notification(at, content)
+}
+
+/// Cancel a notification created with [`schedule_notification`] that has not been shown yet.
+///
+/// ## Panics
+///
+/// This function will panic if it is called outside of the context of a Dioxus App.
+pub fn cancel_notification(id: NotificationId) {
+    window().cancel_notification(id)
+}
+
+/// Open a new window that renders `component`. The window runs its own [`VirtualDom`] on the event loop of the app, so it can be opened from any window.
+///
+/// Pass values between windows with a [`SharedSignal`](crate::SharedSignal). Signals from [`dioxus_signals`] belong to the window that created them.
+///
+/// ## Panics
+///
+/// This function will panic if it is called outside of the context of a Dioxus App.
+///
+/// ```rust, ignore
+/// let settings = new_window(Settings, Config::new().with_window(WindowBuilder::new().with_title("Settings")));
+/// // later
+/// settings.focus();
+/// ```
+pub fn new_window(component: Component, cfg: Config) -> WindowHandle {
+    new_window_with_props(component, (), cfg)
+}
+
+/// Open a new window that renders `component` with `props`, like [`new_window`].
+///
+/// ## Panics
+///
+/// This function will panic if it is called outside of the context of a Dioxus App.
+pub fn new_window_with_props<P: 'static>(
+    component: Component<P>,
+    props: P,
+    cfg: Config,
+) -> WindowHandle {
+    let desktop = window();
+    let handle = desktop.new_window(VirtualDom::new_with_props(component, props), cfg);
+    let id = handle
+        .upgrade()
+        .expect("the new window is pending until the event loop opens it")
+        .id();
+    WindowHandle::new(handle, id, desktop.shared.proxy.clone())
+}
+
 /// A handle to the [`DesktopService`] that can be passed around.
 pub type DesktopContext = Rc<DesktopService>;
 
@@ -65,7 +155,15 @@ pub struct DesktopService {
     pub(crate) templates: RefCell<FxHashMap<String, u16>>,
     pub(crate) max_template_count: AtomicU16,
     pub(crate) channel: RefCell<Channel>,
+    /// The number of listeners of each bubbling event type, which the webview listens for at the root
+    pub(crate) delegated_events: RefCell<FxHashMap<String, usize>>,
     pub(crate) asset_handlers: AssetHandlerRegistry,
+    pub(crate) quit_handlers: QuitHandlers,
+    pub(crate) menubar: MenuBar,
+    pub(crate) kiosk: Cell<bool>,
+    pub(crate) window_effect: Cell<Option<WindowEffect>>,
+    pub(crate) window_level: Cell<WindowLevel>,
+    pub(crate) js_channels: JsChannels,
 
     #[cfg(target_os = "ios")]
     pub(crate) views: Rc<RefCell<Vec<*mut objc::runtime::Object>>>,
@@ -87,6 +185,7 @@ impl DesktopService {
         shared: Rc<SharedContext>,
         edit_queue: EditQueue,
         asset_handlers: AssetHandlerRegistry,
+        menubar: MenuBar,
     ) -> Self {
         Self {
             window,
@@ -94,10 +193,17 @@ impl DesktopService {
             shared,
             edit_queue,
             asset_handlers,
+            quit_handlers: Default::default(),
+            menubar,
+            kiosk: Default::default(),
+            window_effect: Default::default(),
+            window_level: Default::default(),
+            js_channels: Default::default(),
             query: Default::default(),
             templates: Default::default(),
             max_template_count: Default::default(),
             channel: Default::default(),
+            delegated_events: Default::default(),
             #[cfg(target_os = "ios")]
             views: Default::default(),
         }
@@ -110,6 +216,7 @@ impl DesktopService {
             &mut self.channel.borrow_mut(),
             &mut self.templates.borrow_mut(),
             &self.max_template_count,
+            &mut self.delegated_events.borrow_mut(),
         ) {
             self.edit_queue.add_edits(bytes)
         }
@@ -177,13 +284,90 @@ impl DesktopService {
             .send_event(UserWindowEvent(EventData::CloseWindow, id));
     }
 
-    /// change window to fullscreen
-    pub fn set_fullscreen(&self, fullscreen: bool) {
-        if let Some(handle) = &self.window.current_monitor() {
-            self.window.set_fullscreen(
-                fullscreen.then_some(WryFullscreen::Borderless(Some(handle.clone()))),
-            );
+    /// Change how the window fills the screen. Pass `true` or `false` to switch between a borderless fullscreen window and a normal window.
+    ///
+    /// ```rust, ignore
+    /// let monitor = window().available_monitors().nth(1);
+    /// window().set_fullscreen(FullscreenMode::Exclusive(monitor));
+    /// ```
+    pub fn set_fullscreen(&self, mode: impl Into<FullscreenMode>) {
+        let mode = mode.into();
+        self.window.set_fullscreen(mode.to_tao(&self.window));
+
+        let kiosk = mode.is_kiosk();
+        if kiosk != self.kiosk.replace(kiosk) {
+            match kiosk {
+                true => WindowLevel::AlwaysOnTop.apply(&self.window),
+                false => self.window_level.get().apply(&self.window),
+            }
+            crate::fullscreen::set_kiosk(kiosk);
+        }
+    }
+
+    /// Keep the window above the windows of other apps. This is the same as [`DesktopService::set_window_level`] with [`WindowLevel::AlwaysOnTop`].
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        self.set_window_level(always_on_top.into());
+    }
+
+    /// Stack the window above or below the windows of other apps.
+    ///
+    /// A window in [`FullscreenMode::Kiosk`] stays on top, and gets this level when it leaves kiosk mode.
+    pub fn set_window_level(&self, level: WindowLevel) {
+        self.window_level.set(level);
+        if !self.kiosk.get() {
+            level.apply(&self.window);
+        }
+    }
+
+    /// Get the level set with [`DesktopService::set_window_level`].
+    pub fn window_level(&self) -> WindowLevel {
+        self.window_level.get()
+    }
+
+    /// Hide the window from the taskbar and the alt-tab switcher. Only supported on Windows and Linux.
+    pub fn set_skip_taskbar(&self, skip: bool) {
+        crate::window_level::set_skip_taskbar(&self.window, skip);
+    }
+
+    /// Replace the background effect of the window, or remove it with `None`. The window has to be transparent, which [`Config::with_window_effect`] makes it.
+    ///
+    /// The previous effect is restored if the new one is not supported.
+    pub fn set_window_effect(&self, effect: Option<WindowEffect>) -> Result<(), WindowEffectError> {
+        // Effects share the backdrop of the window, so the previous one has to be cleared first
+        let previous = self.window_effect.take();
+        if let Some(previous) = previous {
+            previous.clear(&self.window)?;
         }
+        if let Some(effect) = effect {
+            if let Err(err) = effect.apply(&self.window) {
+                if let Some(previous) = previous {
+                    self.window_effect
+                        .set(previous.apply(&self.window).ok().map(|_| previous));
+                }
+                return Err(err);
+            }
+        }
+        self.window_effect.set(effect);
+        Ok(())
+    }
+
+    /// Hide the title bar behind the content of the window, like [`Config::with_transparent_titlebar`]. Only supported on macOS.
+    pub fn set_transparent_titlebar(&self, transparent: bool) {
+        crate::window_effect::set_transparent_titlebar(&self.window, transparent);
+    }
+
+    /// Keep the system from sleeping and the screensaver from starting until the guard is dropped, like while a video plays. `reason` may be shown to the user in the power settings of the system.
+    ///
+    /// On Linux this needs `systemd-inhibit`, and does nothing on mobile.
+    ///
+    /// ```rust, ignore
+    /// let exporting = use_signal(cx, || None);
+    /// exporting.set(Some(window().keep_awake("Exporting the video")));
+    /// // ...
+    /// exporting.set(None);
+    /// ```
+    pub fn keep_awake(&self, reason: impl Into<String>) -> KeepAwakeGuard {
+        KeepAwakeGuard::new(&reason.into())
     }
 
     /// launch print modal
@@ -200,11 +384,98 @@ impl DesktopService {
 
     /// opens DevTool window
     pub fn devtool(&self) {
-        #[cfg(debug_assertions)]
+        self.open_devtools();
+    }
+
+    /// Open the devtools of the webview. The window needs devtools enabled with [`Config::with_devtools`], which release builds only support with the `devtools` feature.
+    pub fn open_devtools(&self) {
+        #[cfg(any(debug_assertions, feature = "devtools"))]
         self.webview.open_devtools();
 
-        #[cfg(not(debug_assertions))]
-        tracing::warn!("Devtools are disabled in release builds");
+        #[cfg(not(any(debug_assertions, feature = "devtools")))]
+        tracing::warn!("Devtools are disabled in release builds without the devtools feature");
+    }
+
+    /// Close the devtools of the webview, on platforms where the webview supports it.
+    pub fn close_devtools(&self) {
+        #[cfg(any(debug_assertions, feature = "devtools"))]
+        self.webview.close_devtools();
+    }
+
+    /// Check if the devtools of the webview are open. Platforms where the webview can't tell return `false`.
+    ///
+    /// ```rust, ignore
+    /// let desktop = window();
+    /// match desktop.devtools_opened() {
+    ///     true => desktop.close_devtools(),
+    ///     false => desktop.open_devtools(),
+    /// }
+    /// ```
+    pub fn devtools_opened(&self) -> bool {
+        #[cfg(any(debug_assertions, feature = "devtools"))]
+        {
+            self.webview.is_devtools_open()
+        }
+
+        #[cfg(not(any(debug_assertions, feature = "devtools")))]
+        false
+    }
+
+    /// Show a count on the app icon and keep it in sync with `count`. A count of zero removes the badge.
+    ///
+    /// This must be called from inside a component. The binding is removed when that component is dropped.
+    ///
+    /// Badges are shown on the dock icon on macOS, and in Linux launchers that support the Unity launcher API if the app has an installed `<executable name>.desktop` entry. Windows has no taskbar badges, so the count is ignored there and a warning is logged.
+    ///
+    /// ```rust, ignore
+    /// let unread = use_signal(cx, || 0);
+    /// cx.use_hook(|| window().set_badge_count(unread));
+    /// ```
+    pub fn set_badge_count(&self, count: impl Readable<Target = usize> + 'static) {
+        Effect::new(move || crate::badge::set_badge_count(*count.read()));
+    }
+
+    /// Show `count` on the app icon once, for counts that are not kept in a signal. A count of zero removes the badge. See [`DesktopService::set_badge_count`] for the supported platforms.
+    pub fn set_badge(&self, count: usize) {
+        crate::badge::set_badge_count(count);
+    }
+
+    /// Show `progress` on the app's taskbar or dock icon once, for progress that is not kept in a signal. The progress should be between 0.0 and 1.0, and `None` hides the progress bar.
+    ///
+    /// ```rust, ignore
+    /// for (i, file) in files.iter().enumerate() {
+    ///     export(file).await;
+    ///     window().set_progress((i + 1) as f64 / files.len() as f64);
+    /// }
+    /// window().set_progress(None);
+    /// ```
+    pub fn set_progress(&self, progress: impl Into<Option<f64>>) {
+        crate::badge::set_progress(&self.window, progress.into());
+    }
+
+    /// Show a progress bar on the app's taskbar or dock icon and keep it in sync with `progress`. The progress should be between 0.0 and 1.0, and `None` hides the progress bar.
+    ///
+    /// This must be called from inside a component. The binding is removed when that component is dropped.
+    ///
+    /// The progress bar is shown in the Windows taskbar and on the macOS dock icon. On Linux it is only shown by launchers that support the Unity launcher API, and only if the app has an installed `<executable name>.desktop` entry.
+    pub fn bind_progress_bar(
+        self: &Rc<Self>,
+        progress: impl Readable<Target = Option<f64>> + 'static,
+    ) {
+        let window = Rc::downgrade(self);
+        Effect::new(move || {
+            let progress = *progress.read();
+            if let Some(window) = window.upgrade() {
+                crate::badge::set_progress(&window.window, progress);
+            }
+        });
+    }
+
+    /// Get a handle to the system clipboard, which can read and write text, html and images.
+    ///
+    /// Unlike the clipboard api of the webview, this doesn't need the window to be focused or a permission from the user. Use [`use_clipboard_change_handler`](crate::use_clipboard_change_handler) to listen for changes.
+    pub fn clipboard(self: &Rc<Self>) -> UseClipboard {
+        UseClipboard::new(Rc::new(DesktopClipboardProvider::new(self.clone())))
     }
 
     /// Create a wry event handler that listens for wry events.
@@ -236,16 +507,107 @@ impl DesktopService {
             .add_shortcut(hotkey, Box::new(callback))
     }
 
+    /// Create a shortcut that is only triggered while this window is focused. Unlike [`DesktopContext::create_shortcut`], the key press still reaches the webview.
+    ///
+    /// Keys typed into inputs, textareas and contenteditable elements don't trigger the shortcut unless it opts in with [`DesktopContext::set_shortcut_in_editable`].
+    pub fn create_window_shortcut(
+        self: &Rc<Self>,
+        hotkey: HotKey,
+        callback: impl FnMut() + 'static,
+    ) -> Result<ShortcutId, ShortcutRegistryError> {
+        self.shared
+            .shortcut_manager
+            .add_window_shortcut(self, hotkey, Box::new(callback))
+    }
+
     /// Remove a global shortcut
     pub fn remove_shortcut(&self, id: ShortcutId) {
         self.shared.shortcut_manager.remove_shortcut(id)
     }
 
+    /// Enable or disable a global shortcut without removing it. Disabled shortcuts do not call their handler.
+    pub fn set_shortcut_enabled(
+        &self,
+        id: ShortcutId,
+        enabled: bool,
+    ) -> Result<(), ShortcutRegistryError> {
+        self.shared.shortcut_manager.set_enabled(id, enabled)
+    }
+
+    /// Also trigger a window shortcut for keys typed into inputs, textareas and contenteditable elements.
+    pub fn set_shortcut_in_editable(&self, id: ShortcutId, in_editable: bool) {
+        self.shared
+            .shortcut_manager
+            .set_in_editable(id, in_editable)
+    }
+
+    /// Move a shortcut to a different accelerator without changing its id or handler. See [`ShortcutHandle::rebind`](crate::ShortcutHandle::rebind) for more information.
+    pub fn rebind_shortcut(
+        &self,
+        id: ShortcutId,
+        hotkey: HotKey,
+    ) -> Result<(), ShortcutRegistryError> {
+        self.shared.shortcut_manager.rebind(id, hotkey)
+    }
+
+    /// Get the accelerator a shortcut is bound to
+    pub fn shortcut_accelerator(&self, id: ShortcutId) -> Option<HotKey> {
+        self.shared.shortcut_manager.accelerator(id)
+    }
+
+    /// Check if a global shortcut is enabled
+    pub fn is_shortcut_enabled(&self, id: ShortcutId) -> bool {
+        self.shared.shortcut_manager.is_enabled(id)
+    }
+
+    /// Get the accelerators of every global shortcut this app registered with the OS. Use it to show which shortcuts are taken, for example in a rebinding UI.
+    pub fn registered_accelerators(&self) -> Vec<HotKey> {
+        self.shared.shortcut_manager.registered_accelerators()
+    }
+
     /// Remove all global shortcuts
     pub fn remove_all_shortcuts(&self) {
         self.shared.shortcut_manager.remove_all()
     }
 
+    /// Replace the menubar of this window. Items that kept their place are updated in place, so an open menu stays open.
+    pub fn set_menubar(&self, menu: &NativeMenu) {
+        self.menubar.set(menu)
+    }
+
+    /// Replace the menu of the tray icon created with [`Config::with_tray`](crate::Config::with_tray).
+    pub fn set_tray_menu(&self, menu: &NativeMenu) {
+        self.shared.tray.set_menu(menu)
+    }
+
+    /// Change the text shown when the pointer hovers over the tray icon.
+    pub fn set_tray_tooltip(&self, tooltip: Option<String>) {
+        self.shared.tray.set_tooltip(tooltip)
+    }
+
+    /// Change the image of the tray icon to RGBA pixels.
+    pub fn set_tray_icon(&self, rgba: Vec<u8>, width: u32, height: u32) {
+        self.shared.tray.set_icon(TrayImage {
+            rgba,
+            width,
+            height,
+        })
+    }
+
+    /// Schedule a local notification to be shown at a later time. See [`schedule_notification`](crate::schedule_notification) for more information.
+    pub fn schedule_notification(
+        &self,
+        at: impl Into<ScheduleAt>,
+        content: NotificationContent,
+    ) -> NotificationId {
+        self.shared.notifications.schedule(at.into(), content)
+    }
+
+    /// Cancel a notification created with [`DesktopContext::schedule_notification`] that has not been shown yet.
+    pub fn cancel_notification(&self, id: NotificationId) {
+        self.shared.notifications.cancel(id)
+    }
+
     /// Provide a callback to handle asset loading yourself.
     /// If the ScopeId isn't provided, defaults to a global handler.
     /// Note that the handler is namespaced by name, not ScopeId.
@@ -42,11 +42,15 @@ impl EditQueue {
     }
 }
 
+/// Apply a batch of mutations to the interpreter's channel and return the framed edits to send to the webview.
+///
+/// Bubbling events are delegated to a single listener at the root per event type, so `delegated` counts the listeners of each event type and the webview only hears about the first and the last one.
 pub(crate) fn apply_edits(
     mutations: Mutations,
     channel: &mut Channel,
     templates: &mut FxHashMap<String, u16>,
     max_template_count: &AtomicU16,
+    delegated: &mut FxHashMap<String, usize>,
 ) -> Option<Vec<u8>> {
     if mutations.templates.is_empty() && mutations.edits.is_empty() {
         return None;
@@ -57,6 +61,7 @@ pub(crate) fn apply_edits(
     }
 
     use dioxus_core::Mutation::*;
+    channel.before_mutations();
     for edit in mutations.edits {
         match edit {
             AppendChildren { id, m } => channel.append_children(id.0 as u32, m as u16),
@@ -73,6 +78,8 @@ pub(crate) fn apply_edits(
             ReplacePlaceholder { path, m } => channel.replace_placeholder(path, m as u16),
             InsertAfter { id, m } => channel.insert_after(id.0 as u32, m as u16),
             InsertBefore { id, m } => channel.insert_before(id.0 as u32, m as u16),
+            MoveAfter { id, m } => channel.move_after(id.0 as u32, m as u16),
+            MoveBefore { id, m } => channel.move_before(id.0 as u32, m as u16),
             SetAttribute {
                 name,
                 value,
@@ -101,19 +108,61 @@ pub(crate) fn apply_edits(
             },
             SetText { value, id } => channel.set_text(id.0 as u32, value),
             NewEventListener { name, id, .. } => {
-                channel.new_event_listener(name, id.0 as u32, event_bubbles(name) as u8)
+                if event_bubbles(name) {
+                    let listeners = delegated.entry(name.to_string()).or_default();
+                    *listeners += 1;
+                    if *listeners == 1 {
+                        channel.listen_at_root(name);
+                    }
+                } else {
+                    channel.new_event_listener(name, id.0 as u32, false as u8)
+                }
             }
             RemoveEventListener { name, id } => {
-                channel.remove_event_listener(name, id.0 as u32, event_bubbles(name) as u8)
+                if event_bubbles(name) {
+                    if let Some(listeners) = delegated.get_mut(name) {
+                        *listeners -= 1;
+                        if *listeners == 0 {
+                            delegated.remove(name);
+                            channel.unlisten_at_root(name);
+                        }
+                    }
+                } else {
+                    channel.remove_event_listener(name, id.0 as u32, false as u8)
+                }
             }
             Remove { id } => channel.remove(id.0 as u32),
             PushRoot { id } => channel.push_root(id.0 as u32),
         }
     }
 
+    channel.after_mutations();
+
     let bytes: Vec<_> = channel.export_memory().collect();
     channel.reset();
-    Some(bytes)
+    Some(encode_edits(bytes))
+}
+
+/// Edit batches smaller than this are sent as is because compressing them costs more than it saves
+#[cfg(feature = "compress-edits")]
+const COMPRESSION_THRESHOLD: usize = 4 * 1024;
+
+/// The last byte of every batch sent to the interpreter marks how the rest of the batch is encoded
+const RAW_EDITS: u8 = 0;
+#[cfg(feature = "compress-edits")]
+const LZ4_EDITS: u8 = 1;
+
+/// Frame a batch of edits for the interpreter, compressing it with LZ4 if the `compress-edits` feature is enabled and the batch is large enough
+pub(crate) fn encode_edits(mut bytes: Vec<u8>) -> Vec<u8> {
+    #[cfg(feature = "compress-edits")]
+    if bytes.len() >= COMPRESSION_THRESHOLD {
+        let mut compressed = lz4_flex::compress_prepend_size(&bytes);
+        compressed.push(LZ4_EDITS);
+        return compressed;
+    }
+
+    bytes.push(RAW_EDITS);
+    bytes
 }
 
 pub fn add_template(
@@ -170,3 +219,115 @@ pub fn create_template_node(channel: &mut Channel, node: &'static TemplateNode<'
         Dynamic { .. } => channel.add_placeholder(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    /// Decode a framed batch with the same decoder the interpreter uses
+    fn decode_with_interpreter(framed: &[u8]) -> Vec<u8> {
+        let script = format!(
+            "{}\nprocess.stdout.write(decode_edits(require('fs').readFileSync(0)));",
+            include_str!("decode_edits.js")
+        );
+        let mut node = Command::new("node")
+            .arg("-e")
+            .arg(script)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("node must be installed to run the interpreter's decoder");
+        node.stdin.take().unwrap().write_all(framed).unwrap();
+        let output = node.wait_with_output().unwrap();
+        assert!(output.status.success(), "the decoder failed");
+        output.stdout
+    }
+
+    #[test]
+    #[ignore = "needs node, run with `cargo test -- --ignored`"]
+    fn interpreter_decodes_encoded_edits() {
+        // A small batch, a repetitive batch with long overlapping matches, and a batch with long literal runs
+        let small = b"a few edits".to_vec();
+        let repetitive = b"create_element div; set_attribute class item; "
+            .iter()
+            .copied()
+            .cycle()
+            .take(64 * 1024)
+            .collect::<Vec<_>>();
+        let mut seed = 0x2545_f491_u32;
+        let random = (0..16 * 1024)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 24) as u8
+            })
+            .collect::<Vec<_>>();
+
+        for edits in [small, repetitive, random] {
+            let framed = encode_edits(edits.clone());
+            #[cfg(feature = "compress-edits")]
+            assert_eq!(
+                framed.last(),
+                Some(if edits.len() >= COMPRESSION_THRESHOLD {
+                    &LZ4_EDITS
+                } else {
+                    &RAW_EDITS
+                })
+            );
+            assert_eq!(decode_with_interpreter(&framed), edits);
+        }
+    }
+
+    /// Apply a batch of edits without templates and return the size of the framed batch
+    fn apply(
+        edits: Vec<dioxus_core::Mutation<'static>>,
+        channel: &mut Channel,
+        delegated: &mut FxHashMap<String, usize>,
+    ) -> usize {
+        let mutations = Mutations {
+            edits,
+            ..Default::default()
+        };
+        apply_edits(
+            mutations,
+            channel,
+            &mut FxHashMap::default(),
+            &AtomicU16::new(0),
+            delegated,
+        )
+        .map_or(0, |bytes| bytes.len())
+    }
+
+    #[test]
+    fn bubbling_listeners_are_delegated_to_the_root() {
+        use dioxus_core::{ElementId, Mutation::*};
+
+        let mut channel = Channel::default();
+        let mut delegated = FxHashMap::default();
+        let listen = |name, id| NewEventListener {
+            name,
+            id: ElementId(id),
+        };
+        let unlisten = |name, id| RemoveEventListener {
+            name,
+            id: ElementId(id),
+        };
+
+        // Only the first listener of an event type reaches the webview
+        let first = apply(vec![listen("click", 1)], &mut channel, &mut delegated);
+        let second = apply(vec![listen("click", 2)], &mut channel, &mut delegated);
+        assert!(second < first);
+        assert_eq!(delegated.get("click"), Some(&2));
+
+        // Events that don't bubble still listen on their element
+        let focus = apply(vec![listen("focus", 3)], &mut channel, &mut delegated);
+        assert!(focus > second);
+        assert!(!delegated.contains_key("focus"));
+
+        apply(vec![unlisten("click", 1)], &mut channel, &mut delegated);
+        assert_eq!(delegated.get("click"), Some(&1));
+        apply(vec![unlisten("click", 2)], &mut channel, &mut delegated);
+        assert!(delegated.is_empty());
+    }
+}
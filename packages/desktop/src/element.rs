@@ -1,5 +1,11 @@
-use dioxus_core::ElementId;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use dioxus_core::{
+    prelude::{push_future, remove_future},
+    ElementId, TaskId,
+};
 use dioxus_html::{geometry::euclid::Rect, MountedResult, RenderedElementBacking};
+use serde::Deserialize;
 
 use crate::{desktop_context::DesktopContext, query::QueryEngine};
 
@@ -15,6 +21,10 @@ impl DesktopElement {
     pub(crate) fn new(id: ElementId, webview: DesktopContext, query: QueryEngine) -> Self {
         Self { id, webview, query }
     }
+
+    pub(crate) fn id(&self) -> ElementId {
+        self.id
+    }
 }
 
 impl RenderedElementBacking for DesktopElement {
@@ -103,6 +113,151 @@ impl RenderedElementBacking for DesktopElement {
             }
         })
     }
+
+    fn observe_resize(
+        &self,
+        mut on_resize: Box<dyn FnMut(Rect<f64, f64>)>,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        Ok(Box::new(self.observe(
+            "observeResize",
+            move |value| match Rect::deserialize(value) {
+                Ok(rect) => on_resize(rect),
+                Err(err) => tracing::error!("Failed to read the size of an element: {err}"),
+            },
+        )))
+    }
+
+    fn observe_intersection(
+        &self,
+        mut on_change: Box<dyn FnMut(bool)>,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        Ok(Box::new(self.observe(
+            "observeIntersection",
+            move |value| {
+                if let serde_json::Value::Bool(intersecting) = value {
+                    on_change(intersecting)
+                }
+            },
+        )))
+    }
+
+    fn observe_focus(
+        &self,
+        mut on_change: Box<dyn FnMut(bool)>,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        Ok(Box::new(self.observe("observeFocus", move |value| {
+            if let serde_json::Value::Bool(focused) = value {
+                on_change(focused)
+            }
+        })))
+    }
+
+    fn trap_focus(&self) -> MountedResult<Box<dyn std::any::Any>> {
+        // The trap never sends anything back, it only needs to be released when dropped
+        Ok(Box::new(self.observe("trapFocus", |_| {})))
+    }
+
+    fn observe_scroll(
+        &self,
+        mut on_scroll: Box<dyn FnMut()>,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        Ok(Box::new(
+            self.observe("observeScroll", move |_| on_scroll()),
+        ))
+    }
+}
+
+impl DesktopElement {
+    /// Call `on_open` whenever the element is right clicked, instead of opening the context menu of the webview.
+    pub(crate) fn observe_context_menu(
+        &self,
+        mut on_open: impl FnMut() + 'static,
+    ) -> DesktopObserver {
+        self.observe("observeContextMenu", move |_| on_open())
+    }
+
+    /// Call `on_change` with the bounds of the element in the viewport whenever it moves or is resized.
+    pub(crate) fn observe_bounds(
+        &self,
+        mut on_change: impl FnMut(Rect<f64, f64>) + 'static,
+    ) -> DesktopObserver {
+        self.observe("observeBounds", move |value| {
+            match Rect::deserialize(value) {
+                Ok(rect) => on_change(rect),
+                Err(err) => tracing::error!("Failed to read the bounds of an element: {err}"),
+            }
+        })
+    }
+
+    /// Start an observer in the webview with `window.interpreter[function](id, key, callback)`, and call `on_value` with every value it passes to the callback.
+    fn observe(
+        &self,
+        function: &str,
+        on_value: impl FnMut(serde_json::Value) + 'static,
+    ) -> DesktopObserver {
+        observe(
+            &self.webview,
+            &self.query,
+            function,
+            &self.id.0.to_string(),
+            on_value,
+        )
+    }
+}
+
+/// Start an observer in the webview with `window.interpreter[function](...args, key, callback)`, and call `on_value` with every value it passes to the callback.
+///
+/// `args` are inserted into the call before the key, so they must be valid javascript.
+pub(crate) fn observe(
+    webview: &DesktopContext,
+    query: &QueryEngine,
+    function: &str,
+    args: &str,
+    mut on_value: impl FnMut(serde_json::Value) + 'static,
+) -> DesktopObserver {
+    // Identifies the observer in the webview, so it can be disconnected when the handle is dropped
+    static NEXT_OBSERVER: AtomicUsize = AtomicUsize::new(0);
+    let key = NEXT_OBSERVER.fetch_add(1, Ordering::Relaxed);
+
+    let args = if args.is_empty() {
+        key.to_string()
+    } else {
+        format!("{args}, {key}")
+    };
+    let script =
+        format!("return window.interpreter.{function}({args}, (value) => dioxus.send(value));");
+    let mut query = query.new_query::<bool>(&script, webview.clone());
+
+    let task = push_future(async move {
+        while let Ok(value) = query.recv().await {
+            on_value(value);
+        }
+    });
+
+    DesktopObserver {
+        key,
+        task,
+        webview: webview.clone(),
+    }
+}
+
+/// Disconnects an observer in the webview and stops listening for its messages when dropped.
+pub(crate) struct DesktopObserver {
+    key: usize,
+    task: Option<TaskId>,
+    webview: DesktopContext,
+}
+
+impl Drop for DesktopObserver {
+    fn drop(&mut self) {
+        if let Some(task) = self.task {
+            remove_future(task);
+        }
+        _ = self
+            .webview
+            .webview
+            .evaluate_script(&format!("window.interpreter.unobserve({});", self.key));
+    }
 }
 
 #[derive(Debug)]
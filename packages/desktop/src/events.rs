@@ -1,7 +1,17 @@
 //! Convert a serialized event to an event trigger
 
 use crate::element::DesktopElement;
-use dioxus_html::*;
+use dioxus_html::{
+    geometry::{ClientPoint, Coordinates, ElementPoint, PagePoint, ScreenPoint},
+    input_data::{MouseButton, MouseButtonSet},
+    native_bind::NativeFileEngine,
+    point_interaction::{
+        InteractionElementOffset, InteractionLocation, ModifiersInteraction, PointerInteraction,
+    },
+    prelude::Modifiers,
+    *,
+};
+use std::{path::PathBuf, sync::Arc};
 
 pub(crate) struct SerializedHtmlEventConverter;
 
@@ -31,6 +41,9 @@ impl HtmlEventConverter for SerializedHtmlEventConverter {
     }
 
     fn convert_drag_data(&self, event: &PlatformEventData) -> DragData {
+        if let Some(data) = event.downcast::<DesktopDragData>() {
+            return data.clone().into();
+        }
         event
             .downcast::<SerializedDragData>()
             .cloned()
@@ -146,3 +159,92 @@ impl HtmlEventConverter for SerializedHtmlEventConverter {
             .into()
     }
 }
+
+/// The data of a drag event while files from outside the app are dragged over the window.
+///
+/// The files are available through [`HasFileData::files`] like on the web, but the file names are full paths. Downcast the event to get the paths directly:
+///
+/// ```rust, ignore
+/// div {
+///     ondragover: |evt| evt.stop_propagation(),
+///     ondrop: |evt| {
+///         if let Some(drop) = evt.data.downcast::<DesktopDragData>() {
+///             println!("dropped {:?}", drop.paths());
+///         }
+///     },
+/// }
+/// ```
+#[derive(Clone)]
+pub struct DesktopDragData {
+    data: SerializedDragData,
+    paths: Vec<PathBuf>,
+}
+
+impl DesktopDragData {
+    pub(crate) fn new(data: SerializedDragData, paths: Vec<PathBuf>) -> Self {
+        Self { data, paths }
+    }
+
+    /// The paths of the files that are dragged.
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+impl HasDragData for DesktopDragData {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl HasFileData for DesktopDragData {
+    fn files(&self) -> Option<Arc<dyn FileEngine>> {
+        Some(Arc::new(NativeFileEngine::new(self.paths.clone())))
+    }
+}
+
+impl HasMouseData for DesktopDragData {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl InteractionLocation for DesktopDragData {
+    fn client_coordinates(&self) -> ClientPoint {
+        self.data.client_coordinates()
+    }
+
+    fn page_coordinates(&self) -> PagePoint {
+        self.data.page_coordinates()
+    }
+
+    fn screen_coordinates(&self) -> ScreenPoint {
+        self.data.screen_coordinates()
+    }
+}
+
+impl InteractionElementOffset for DesktopDragData {
+    fn element_coordinates(&self) -> ElementPoint {
+        self.data.element_coordinates()
+    }
+
+    fn coordinates(&self) -> Coordinates {
+        self.data.coordinates()
+    }
+}
+
+impl ModifiersInteraction for DesktopDragData {
+    fn modifiers(&self) -> Modifiers {
+        self.data.modifiers()
+    }
+}
+
+impl PointerInteraction for DesktopDragData {
+    fn held_buttons(&self) -> MouseButtonSet {
+        self.data.held_buttons()
+    }
+
+    fn trigger_button(&self) -> Option<MouseButton> {
+        self.data.trigger_button()
+    }
+}
@@ -0,0 +1,40 @@
+use dioxus_core::Executor;
+use std::{future::Future, pin::Pin, time::Duration};
+use tokio::runtime::Handle;
+
+/// An [`Executor`] that runs the tasks of the app on a tokio runtime.
+///
+/// [`launch_with_props`](crate::launch_with_props) uses this executor automatically. If it is called inside a tokio runtime, the app runs on that runtime instead of starting its own.
+#[derive(Debug, Clone)]
+pub struct TokioExecutor {
+    handle: Handle,
+}
+
+impl TokioExecutor {
+    /// Create an executor that runs on the runtime of the handle.
+    pub fn new(handle: Handle) -> Self {
+        Self { handle }
+    }
+
+    /// Create an executor that runs on the current tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it is not called inside a tokio runtime.
+    pub fn current() -> Self {
+        Self::new(Handle::current())
+    }
+}
+
+impl Executor for TokioExecutor {
+    fn enter(&self, poll: &mut dyn FnMut()) {
+        let _guard = self.handle.enter();
+        poll()
+    }
+
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()>>> {
+        // Timers are registered with the runtime they are created in
+        let _guard = self.handle.enter();
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
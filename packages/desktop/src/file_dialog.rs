@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use dioxus_html::{
+    file_dialog::{FileDialogError, FileDialogOptions, FileDialogProvider},
+    FileEngine,
+};
+use std::sync::Arc;
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub(crate) use native::DesktopFileDialogProvider;
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+pub(crate) use mobile::DesktopFileDialogProvider;
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+mod native {
+    use super::*;
+    use crate::DesktopContext;
+    use dioxus_html::native_bind::NativeFileEngine;
+    use rfd::AsyncFileDialog;
+    use std::path::{Path, PathBuf};
+
+    /// Shows the native file dialogs of the platform with rfd.
+    pub(crate) struct DesktopFileDialogProvider {
+        desktop_ctx: DesktopContext,
+    }
+
+    impl DesktopFileDialogProvider {
+        pub(crate) fn new(desktop_ctx: DesktopContext) -> Self {
+            Self { desktop_ctx }
+        }
+
+        /// Create a dialog that is modal to the window of this provider.
+        fn dialog(&self, options: &FileDialogOptions) -> AsyncFileDialog {
+            let mut dialog = AsyncFileDialog::new().set_parent(&*self.desktop_ctx.window);
+            if let Some(title) = &options.title {
+                dialog = dialog.set_title(title);
+            }
+            for filter in &options.filters {
+                dialog = dialog.add_filter(&filter.name, &filter.extensions);
+            }
+            if let Some(directory) = &options.directory {
+                dialog = dialog.set_directory(directory);
+            }
+            if let Some(file_name) = &options.file_name {
+                dialog = dialog.set_file_name(file_name);
+            }
+            dialog
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl FileDialogProvider for DesktopFileDialogProvider {
+        async fn pick_files(
+            &self,
+            options: FileDialogOptions,
+            multiple: bool,
+        ) -> Option<Arc<dyn FileEngine>> {
+            let dialog = self.dialog(&options);
+            let files: Vec<_> = if multiple {
+                dialog.pick_files().await?
+            } else {
+                vec![dialog.pick_file().await?]
+            };
+            let paths = files.iter().map(|file| file.path().to_path_buf()).collect();
+            Some(Arc::new(NativeFileEngine::new(paths)))
+        }
+
+        async fn pick_folder(&self, options: FileDialogOptions) -> Option<Arc<dyn FileEngine>> {
+            let folder = self.dialog(&options).pick_folder().await?;
+            let mut paths = Vec::new();
+            collect_files(folder.path(), &mut paths);
+            Some(Arc::new(NativeFileEngine::new(paths)))
+        }
+
+        async fn save_file(
+            &self,
+            options: FileDialogOptions,
+            contents: Vec<u8>,
+        ) -> Result<Option<String>, FileDialogError> {
+            let Some(file) = self.dialog(&options).save_file().await else {
+                return Ok(None);
+            };
+            std::fs::write(file.path(), contents)
+                .map_err(|err| FileDialogError::Write(err.to_string()))?;
+            Ok(Some(file.path().to_string_lossy().into_owned()))
+        }
+    }
+
+    /// Add the paths of the files in `folder` and its subfolders to `paths`. Entries that can't be read are skipped.
+    fn collect_files(folder: &Path, paths: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(folder) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            match entry.file_type() {
+                Ok(kind) if kind.is_dir() => collect_files(&path, paths),
+                Ok(_) => paths.push(path),
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+mod mobile {
+    use super::*;
+
+    /// Mobile platforms don't have file dialogs, so every dialog is cancelled right away.
+    pub(crate) struct DesktopFileDialogProvider;
+
+    impl DesktopFileDialogProvider {
+        pub(crate) fn new(_desktop_ctx: crate::DesktopContext) -> Self {
+            Self
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl FileDialogProvider for DesktopFileDialogProvider {
+        async fn pick_files(
+            &self,
+            _options: FileDialogOptions,
+            _multiple: bool,
+        ) -> Option<Arc<dyn FileEngine>> {
+            None
+        }
+
+        async fn pick_folder(&self, _options: FileDialogOptions) -> Option<Arc<dyn FileEngine>> {
+            None
+        }
+
+        async fn save_file(
+            &self,
+            _options: FileDialogOptions,
+            _contents: Vec<u8>,
+        ) -> Result<Option<String>, FileDialogError> {
+            Err(FileDialogError::Unsupported)
+        }
+    }
+}
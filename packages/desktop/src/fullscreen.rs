@@ -0,0 +1,102 @@
+//! The fullscreen modes of a window, set with [`DesktopContext::set_fullscreen`](crate::DesktopContext::set_fullscreen).
+
+use tao::{
+    monitor::MonitorHandle,
+    window::{Fullscreen, Window},
+};
+
+/// How a window fills the screen. Monitors are listed with [`Window::available_monitors`].
+#[derive(Debug, Clone, Default)]
+pub enum FullscreenMode {
+    /// A normal window.
+    #[default]
+    Windowed,
+
+    /// A borderless window that covers the monitor, or the monitor the window is on if it is `None`.
+    Borderless(Option<MonitorHandle>),
+
+    /// Take exclusive control of the monitor, or the monitor the window is on if it is `None`, with its highest resolution and refresh rate.
+    ///
+    /// This is only supported on Windows and macOS. Other platforms use [`FullscreenMode::Borderless`] instead.
+    Exclusive(Option<MonitorHandle>),
+
+    /// A borderless fullscreen window that stays on top of other windows, for apps that run on a kiosk or a display.
+    ///
+    /// On macOS the dock and menu bar are hidden, and switching apps, force quitting and logging out are disabled until the window leaves kiosk mode. Other platforms keep their system shortcuts.
+    Kiosk(Option<MonitorHandle>),
+}
+
+impl From<bool> for FullscreenMode {
+    fn from(fullscreen: bool) -> Self {
+        match fullscreen {
+            true => FullscreenMode::Borderless(None),
+            false => FullscreenMode::Windowed,
+        }
+    }
+}
+
+impl FullscreenMode {
+    pub(crate) fn is_kiosk(&self) -> bool {
+        matches!(self, FullscreenMode::Kiosk(_))
+    }
+
+    pub(crate) fn to_tao(&self, window: &Window) -> Option<Fullscreen> {
+        match self {
+            FullscreenMode::Windowed => None,
+            FullscreenMode::Borderless(monitor) | FullscreenMode::Kiosk(monitor) => Some(
+                Fullscreen::Borderless(monitor.clone().or_else(|| window.current_monitor())),
+            ),
+            FullscreenMode::Exclusive(monitor) => {
+                let monitor = monitor.clone().or_else(|| window.current_monitor())?;
+                let best_mode = monitor.video_modes().max_by_key(|mode| {
+                    let size = mode.size();
+                    (
+                        size.width * size.height,
+                        mode.refresh_rate(),
+                        mode.bit_depth(),
+                    )
+                });
+                match best_mode {
+                    Some(mode) => Some(Fullscreen::Exclusive(mode)),
+                    None => Some(Fullscreen::Borderless(Some(monitor))),
+                }
+            }
+        }
+    }
+}
+
+/// Disable the system shortcuts that leave the app while `kiosk` is true.
+pub(crate) fn set_kiosk(kiosk: bool) {
+    #[cfg(target_os = "macos")]
+    {
+        use objc::runtime::Object;
+        use objc::{msg_send, sel, sel_impl};
+
+        // NSApplicationPresentationOptions
+        const HIDE_DOCK: usize = 1 << 1;
+        const HIDE_MENU_BAR: usize = 1 << 3;
+        const DISABLE_PROCESS_SWITCHING: usize = 1 << 5;
+        const DISABLE_FORCE_QUIT: usize = 1 << 6;
+        const DISABLE_SESSION_TERMINATION: usize = 1 << 7;
+        const DISABLE_HIDE_APPLICATION: usize = 1 << 8;
+
+        let options = match kiosk {
+            true => {
+                HIDE_DOCK
+                    | HIDE_MENU_BAR
+                    | DISABLE_PROCESS_SWITCHING
+                    | DISABLE_FORCE_QUIT
+                    | DISABLE_SESSION_TERMINATION
+                    | DISABLE_HIDE_APPLICATION
+            }
+            false => 0,
+        };
+        objc::rc::autoreleasepool(|| unsafe {
+            let app: *mut Object = msg_send![objc::class!(NSApplication), sharedApplication];
+            let _: () = msg_send![app, setPresentationOptions: options];
+        });
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    tracing::trace!("System shortcuts can't be disabled on this platform, ignoring kiosk {kiosk}");
+}
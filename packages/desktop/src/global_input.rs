@@ -0,0 +1,327 @@
+//! Mouse and keyboard input from the whole desktop, including input outside of the windows of the app. Enabled with the `global-input` feature.
+//!
+//! This is meant for utilities like screen recorder overlays. To react to a key combination, use a global shortcut instead: it doesn't need to see every key the user presses.
+//!
+//! On macOS the app needs the accessibility permission, which the user grants in the system settings. On Linux only X11 is supported.
+
+use dioxus_html::input_data::{keyboard_types::Code, MouseButton};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{Stream, StreamExt};
+use std::{
+    pin::Pin,
+    sync::{Mutex, Once},
+    task::{Context, Poll},
+};
+use thiserror::Error;
+
+/// Which input a [`GlobalInput`] stream reports. Nothing is reported by default.
+///
+/// Keys are only reported if [`GlobalInputConfig::with_keyboard`] is set, because they include everything the user types in other apps, like passwords.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GlobalInputConfig {
+    mouse: bool,
+    keyboard: bool,
+}
+
+impl GlobalInputConfig {
+    /// Create a config that doesn't report any input yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report mouse movement, buttons and scrolling.
+    pub fn with_mouse(mut self, mouse: bool) -> Self {
+        self.mouse = mouse;
+        self
+    }
+
+    /// Report every key that is pressed and released, in any app. Only set this if the user explicitly allowed it.
+    pub fn with_keyboard(mut self, keyboard: bool) -> Self {
+        self.keyboard = keyboard;
+        self
+    }
+}
+
+/// Input that happened anywhere on the desktop.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GlobalInputEvent {
+    /// The mouse moved to this position on the screen.
+    MouseMove {
+        /// The horizontal position in screen coordinates.
+        x: f64,
+        /// The vertical position in screen coordinates.
+        y: f64,
+    },
+
+    /// A mouse button was pressed.
+    MouseDown(MouseButton),
+
+    /// A mouse button was released.
+    MouseUp(MouseButton),
+
+    /// The mouse wheel was scrolled.
+    Wheel {
+        /// The horizontal distance in lines.
+        delta_x: i64,
+        /// The vertical distance in lines.
+        delta_y: i64,
+    },
+
+    /// A key was pressed.
+    KeyDown(Code),
+
+    /// A key was released.
+    KeyUp(Code),
+}
+
+/// An error from [`global_input`].
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum GlobalInputError {
+    /// The config doesn't report any input.
+    #[error("The config doesn't enable any input")]
+    NothingEnabled,
+
+    /// The user hasn't granted the app the permission to read global input. On macOS this is the accessibility permission.
+    #[error("The app is not allowed to read global input")]
+    PermissionDenied,
+
+    /// The platform can't read global input, like on Wayland. Listening is only tried once, so every later call returns this error too.
+    #[error("Failed to listen for global input: {0}")]
+    Unavailable(String),
+}
+
+/// Start reporting the input of the whole desktop that `config` enables.
+///
+/// Listening starts on the first call. If the platform can't read global input, like on Wayland, the streams that were already created end and later calls return [`GlobalInputError::Unavailable`].
+///
+/// ```rust, ignore
+/// let position = use_signal(cx, || (0.0, 0.0));
+/// use_on_create(cx, move || async move {
+///     let Ok(mut input) = global_input(GlobalInputConfig::new().with_mouse(true)) else {
+///         return;
+///     };
+///     while let Some(event) = input.next().await {
+///         if let GlobalInputEvent::MouseMove { x, y } = event {
+///             position.set((x, y));
+///         }
+///     }
+/// });
+/// ```
+pub fn global_input(config: GlobalInputConfig) -> Result<GlobalInput, GlobalInputError> {
+    if !config.mouse && !config.keyboard {
+        return Err(GlobalInputError::NothingEnabled);
+    }
+    if !has_permission() {
+        return Err(GlobalInputError::PermissionDenied);
+    }
+
+    start_listening();
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    if let Some(err) = FAILURE.lock().unwrap().clone() {
+        return Err(GlobalInputError::Unavailable(err));
+    }
+    let (sender, receiver) = unbounded();
+    subscribers.push((config, sender));
+    Ok(GlobalInput { receiver })
+}
+
+/// A stream of [`GlobalInputEvent`]s created with [`global_input`]. Input is no longer reported once it is dropped.
+pub struct GlobalInput {
+    receiver: UnboundedReceiver<GlobalInputEvent>,
+}
+
+impl Stream for GlobalInput {
+    type Item = GlobalInputEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+static SUBSCRIBERS: Mutex<Vec<(GlobalInputConfig, UnboundedSender<GlobalInputEvent>)>> =
+    Mutex::new(Vec::new());
+
+/// Why listening stopped. It is set while [`SUBSCRIBERS`] is locked, so no stream is added after the others end.
+static FAILURE: Mutex<Option<String>> = Mutex::new(None);
+
+/// The platform can only listen once per process, so one thread sends the input to every stream.
+fn start_listening() {
+    static LISTENING: Once = Once::new();
+    LISTENING.call_once(|| {
+        std::thread::spawn(|| {
+            let result = rdev::listen(|event| {
+                let event = convert_event(event.event_type);
+                let keyboard = matches!(
+                    event,
+                    GlobalInputEvent::KeyDown(_) | GlobalInputEvent::KeyUp(_)
+                );
+                SUBSCRIBERS.lock().unwrap().retain(|(config, sender)| {
+                    let enabled = if keyboard {
+                        config.keyboard
+                    } else {
+                        config.mouse
+                    };
+                    !enabled || sender.unbounded_send(event.clone()).is_ok()
+                });
+            });
+            let err = match result {
+                Ok(()) => "the listener stopped".to_string(),
+                Err(err) => format!("{err:?}"),
+            };
+            tracing::error!("Failed to listen for global input: {err}");
+            // End all streams
+            let mut subscribers = SUBSCRIBERS.lock().unwrap();
+            *FAILURE.lock().unwrap() = Some(err);
+            subscribers.clear();
+        });
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn has_permission() -> bool {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+    unsafe { AXIsProcessTrusted() }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn has_permission() -> bool {
+    true
+}
+
+fn convert_event(event: rdev::EventType) -> GlobalInputEvent {
+    use rdev::EventType;
+
+    match event {
+        EventType::MouseMove { x, y } => GlobalInputEvent::MouseMove { x, y },
+        EventType::ButtonPress(button) => GlobalInputEvent::MouseDown(convert_button(button)),
+        EventType::ButtonRelease(button) => GlobalInputEvent::MouseUp(convert_button(button)),
+        EventType::Wheel { delta_x, delta_y } => GlobalInputEvent::Wheel { delta_x, delta_y },
+        EventType::KeyPress(key) => GlobalInputEvent::KeyDown(convert_key(key)),
+        EventType::KeyRelease(key) => GlobalInputEvent::KeyUp(convert_key(key)),
+    }
+}
+
+fn convert_button(button: rdev::Button) -> MouseButton {
+    match button {
+        rdev::Button::Left => MouseButton::Primary,
+        rdev::Button::Right => MouseButton::Secondary,
+        rdev::Button::Middle => MouseButton::Auxiliary,
+        rdev::Button::Unknown(_) => MouseButton::Unknown,
+    }
+}
+
+fn convert_key(key: rdev::Key) -> Code {
+    use rdev::Key;
+
+    match key {
+        Key::Alt => Code::AltLeft,
+        Key::AltGr => Code::AltRight,
+        Key::Backspace => Code::Backspace,
+        Key::CapsLock => Code::CapsLock,
+        Key::ControlLeft => Code::ControlLeft,
+        Key::ControlRight => Code::ControlRight,
+        Key::Delete => Code::Delete,
+        Key::DownArrow => Code::ArrowDown,
+        Key::End => Code::End,
+        Key::Escape => Code::Escape,
+        Key::F1 => Code::F1,
+        Key::F2 => Code::F2,
+        Key::F3 => Code::F3,
+        Key::F4 => Code::F4,
+        Key::F5 => Code::F5,
+        Key::F6 => Code::F6,
+        Key::F7 => Code::F7,
+        Key::F8 => Code::F8,
+        Key::F9 => Code::F9,
+        Key::F10 => Code::F10,
+        Key::F11 => Code::F11,
+        Key::F12 => Code::F12,
+        Key::Home => Code::Home,
+        Key::LeftArrow => Code::ArrowLeft,
+        Key::MetaLeft => Code::MetaLeft,
+        Key::MetaRight => Code::MetaRight,
+        Key::PageDown => Code::PageDown,
+        Key::PageUp => Code::PageUp,
+        Key::Return => Code::Enter,
+        Key::RightArrow => Code::ArrowRight,
+        Key::ShiftLeft => Code::ShiftLeft,
+        Key::ShiftRight => Code::ShiftRight,
+        Key::Space => Code::Space,
+        Key::Tab => Code::Tab,
+        Key::UpArrow => Code::ArrowUp,
+        Key::PrintScreen => Code::PrintScreen,
+        Key::ScrollLock => Code::ScrollLock,
+        Key::Pause => Code::Pause,
+        Key::NumLock => Code::NumLock,
+        Key::BackQuote => Code::Backquote,
+        Key::Num1 => Code::Digit1,
+        Key::Num2 => Code::Digit2,
+        Key::Num3 => Code::Digit3,
+        Key::Num4 => Code::Digit4,
+        Key::Num5 => Code::Digit5,
+        Key::Num6 => Code::Digit6,
+        Key::Num7 => Code::Digit7,
+        Key::Num8 => Code::Digit8,
+        Key::Num9 => Code::Digit9,
+        Key::Num0 => Code::Digit0,
+        Key::Minus => Code::Minus,
+        Key::Equal => Code::Equal,
+        Key::KeyQ => Code::KeyQ,
+        Key::KeyW => Code::KeyW,
+        Key::KeyE => Code::KeyE,
+        Key::KeyR => Code::KeyR,
+        Key::KeyT => Code::KeyT,
+        Key::KeyY => Code::KeyY,
+        Key::KeyU => Code::KeyU,
+        Key::KeyI => Code::KeyI,
+        Key::KeyO => Code::KeyO,
+        Key::KeyP => Code::KeyP,
+        Key::LeftBracket => Code::BracketLeft,
+        Key::RightBracket => Code::BracketRight,
+        Key::KeyA => Code::KeyA,
+        Key::KeyS => Code::KeyS,
+        Key::KeyD => Code::KeyD,
+        Key::KeyF => Code::KeyF,
+        Key::KeyG => Code::KeyG,
+        Key::KeyH => Code::KeyH,
+        Key::KeyJ => Code::KeyJ,
+        Key::KeyK => Code::KeyK,
+        Key::KeyL => Code::KeyL,
+        Key::SemiColon => Code::Semicolon,
+        Key::Quote => Code::Quote,
+        Key::BackSlash => Code::Backslash,
+        Key::IntlBackslash => Code::IntlBackslash,
+        Key::KeyZ => Code::KeyZ,
+        Key::KeyX => Code::KeyX,
+        Key::KeyC => Code::KeyC,
+        Key::KeyV => Code::KeyV,
+        Key::KeyB => Code::KeyB,
+        Key::KeyN => Code::KeyN,
+        Key::KeyM => Code::KeyM,
+        Key::Comma => Code::Comma,
+        Key::Dot => Code::Period,
+        Key::Slash => Code::Slash,
+        Key::Insert => Code::Insert,
+        Key::KpReturn => Code::NumpadEnter,
+        Key::KpMinus => Code::NumpadSubtract,
+        Key::KpPlus => Code::NumpadAdd,
+        Key::KpMultiply => Code::NumpadMultiply,
+        Key::KpDivide => Code::NumpadDivide,
+        Key::Kp0 => Code::Numpad0,
+        Key::Kp1 => Code::Numpad1,
+        Key::Kp2 => Code::Numpad2,
+        Key::Kp3 => Code::Numpad3,
+        Key::Kp4 => Code::Numpad4,
+        Key::Kp5 => Code::Numpad5,
+        Key::Kp6 => Code::Numpad6,
+        Key::Kp7 => Code::Numpad7,
+        Key::Kp8 => Code::Numpad8,
+        Key::Kp9 => Code::Numpad9,
+        Key::KpDelete => Code::NumpadDecimal,
+        Key::Function => Code::Fn,
+        _ => Code::Unidentified,
+    }
+}
@@ -1,9 +1,26 @@
 use crate::{
-    assets::*, ipc::UserWindowEvent, shortcut::IntoAccelerator, window, DesktopContext,
-    ShortcutHandle, ShortcutRegistryError, WryEventHandler,
+    assets::*,
+    element::{DesktopElement, DesktopObserver},
+    ipc::UserWindowEvent,
+    monitors::Monitor,
+    shortcut::IntoAccelerator,
+    window, DeepLink, DesktopContext, MenuEvent, NativeMenu, ShortcutHandle, ShortcutRegistryError,
+    TrayEvent, WryEventHandler,
+};
+use dioxus_core::{prelude::current_executor, ScopeState};
+use dioxus_html::{
+    notification::NotificationEvent,
+    prelude::MountedData,
+    theme::{AccentColor, ColorScheme},
+};
+use dioxus_signals::{
+    use_effect, Autosave, AutosaveConfig, AutosaveTrigger, ReadOnlySignal, Signal,
+};
+use std::{cell::RefCell, future::Future, rc::Rc, time::Duration};
+use tao::{
+    event::{Event, WindowEvent},
+    event_loop::EventLoopWindowTarget,
 };
-use dioxus_core::ScopeState;
-use tao::{event::Event, event_loop::EventLoopWindowTarget};
 use wry::RequestAsyncResponder;
 
 /// Get an imperative handle to the current window
@@ -30,6 +47,353 @@ pub fn use_wry_event_handler(
     })
 }
 
+/// Listen for [`NotificationEvent`]s of notifications created with [`schedule_notification`](crate::schedule_notification).
+///
+/// Events of notifications that were shown while the app was closed, or before any component listened, are passed to the first handler that is added.
+///
+/// ```rust, ignore
+/// use_notification_events(cx, |event| {
+///     if event.kind == NotificationEventKind::Clicked {
+///         println!("Opened from notification {:?}", event.data);
+///     }
+/// });
+/// ```
+pub fn use_notification_events(cx: &ScopeState, handler: impl FnMut(&NotificationEvent) + 'static) {
+    cx.use_hook(|| {
+        let desktop = window();
+        let id = desktop.shared.notifications.add_handler(Box::new(handler));
+        NotificationHandler { desktop, id }
+    });
+
+    struct NotificationHandler {
+        desktop: DesktopContext,
+        id: usize,
+    }
+    impl Drop for NotificationHandler {
+        fn drop(&mut self) {
+            self.desktop.shared.notifications.remove_handler(self.id);
+        }
+    }
+}
+
+/// Listen for urls with the scheme set with [`Config::with_url_scheme`](crate::Config::with_url_scheme) that the app is asked to open.
+///
+/// The urls the app was started with, or that were opened before any component listened, are passed to the first handler that is added. Navigate the router to the page of a link with its [`path`](DeepLink::path):
+///
+/// ```rust, ignore
+/// let navigator = use_navigator(cx).clone();
+/// use_deep_link_handler(cx, move |link| {
+///     navigator.push(link.path());
+/// });
+/// ```
+pub fn use_deep_link_handler(cx: &ScopeState, handler: impl FnMut(&DeepLink) + 'static) {
+    cx.use_hook(|| {
+        let desktop = window();
+        let id = desktop.shared.deep_links.add_handler(Box::new(handler));
+        DeepLinkHandler { desktop, id }
+    });
+
+    struct DeepLinkHandler {
+        desktop: DesktopContext,
+        id: usize,
+    }
+    impl Drop for DeepLinkHandler {
+        fn drop(&mut self) {
+            self.desktop.shared.deep_links.remove_handler(self.id);
+        }
+    }
+}
+
+/// Listen for clicks on the tray icon created with [`Config::with_tray`](crate::Config::with_tray) and on the items of its menu.
+///
+/// ```rust, ignore
+/// use_tray_event_handler(cx, |event| match event {
+///     TrayEvent::Click => window().set_visible(true),
+///     TrayEvent::MenuItem(id) if id == "quit" => window().close(),
+///     _ => {}
+/// });
+/// ```
+pub fn use_tray_event_handler(cx: &ScopeState, handler: impl FnMut(&TrayEvent) + 'static) {
+    cx.use_hook(|| {
+        let desktop = window();
+        let id = desktop.shared.tray.add_handler(Box::new(handler));
+        TrayHandler { desktop, id }
+    });
+
+    struct TrayHandler {
+        desktop: DesktopContext,
+        id: usize,
+    }
+    impl Drop for TrayHandler {
+        fn drop(&mut self) {
+            self.desktop.shared.tray.remove_handler(self.id);
+        }
+    }
+}
+
+/// Keep the menu of the tray icon in sync with signals. `build` runs right away and again whenever a signal it reads changes, and its menu replaces the current menu of the tray icon.
+///
+/// ```rust, ignore
+/// let paused = use_signal(cx, || false);
+/// use_tray_menu(cx, move || {
+///     NativeMenu::new()
+///         .checkbox("pause", "Pause syncing", *paused.read())
+///         .separator()
+///         .item("quit", "Quit")
+/// });
+/// ```
+pub fn use_tray_menu(cx: &ScopeState, mut build: impl FnMut() -> NativeMenu + 'static) {
+    let desktop = use_window(cx).clone();
+    use_effect(cx, move || desktop.set_tray_menu(&build()));
+}
+
+/// Keep the menubar of the current window in sync with signals. `build` runs right away and again whenever a signal it reads changes, and its menu replaces the menubar.
+///
+/// Labels, enabled and checked states are updated in place, so bind them to signals freely. Adding or removing items rebuilds the menubar.
+///
+/// ```rust, ignore
+/// let document = use_signal(cx, || None::<Document>);
+/// use_menubar(cx, move || {
+///     let open = document.read().is_some();
+///     NativeMenu::new().submenu(
+///         "File",
+///         NativeMenu::new()
+///             .item_with_accelerator("open", "Open...", "CmdOrCtrl+O")
+///             .with_item(NativeMenuItem::Item {
+///                 id: "save".into(),
+///                 label: "Save".into(),
+///                 enabled: open,
+///                 accelerator: Some("CmdOrCtrl+S".into()),
+///             })
+///             .separator()
+///             .predefined(PredefinedMenuItem::Quit),
+///     )
+/// });
+/// ```
+pub fn use_menubar(cx: &ScopeState, mut build: impl FnMut() -> NativeMenu + 'static) {
+    let desktop = use_window(cx).clone();
+    use_effect(cx, move || desktop.set_menubar(&build()));
+}
+
+/// Listen for clicks on the items of the menubar and other native menus. Clicks on the tray menu and on context menus go to [`use_tray_event_handler`] and [`use_context_menu`] instead.
+///
+/// ```rust, ignore
+/// use_menu_event_handler(cx, move |event| match event.id.as_str() {
+///     "open" => open_document(),
+///     "save" => save_document(),
+///     _ => {}
+/// });
+/// ```
+pub fn use_menu_event_handler(cx: &ScopeState, handler: impl FnMut(&MenuEvent) + 'static) {
+    cx.use_hook(|| {
+        let desktop = window();
+        let id = desktop.shared.menu_handlers.add_handler(Box::new(handler));
+        MenuHandler { desktop, id }
+    });
+
+    struct MenuHandler {
+        desktop: DesktopContext,
+        id: usize,
+    }
+    impl Drop for MenuHandler {
+        fn drop(&mut self) {
+            self.desktop.shared.menu_handlers.remove_handler(self.id);
+        }
+    }
+}
+
+/// Show a native menu when an element is right clicked, instead of the context menu of the webview.
+///
+/// Pass the element you got from its `onmounted` event. `build` runs every time the menu opens, so the menu can depend on the current state. Clicks on its items are passed to `on_click`.
+///
+/// ```rust, ignore
+/// let element = use_state(cx, || None);
+/// use_context_menu(
+///     cx,
+///     element.get().clone(),
+///     || NativeMenu::new().item("rename", "Rename").item("delete", "Delete"),
+///     move |event| match event.id.as_str() {
+///         "rename" => start_rename(),
+///         "delete" => delete_file(),
+///         _ => {}
+///     },
+/// );
+///
+/// render! {
+///     div { onmounted: move |event| element.set(Some(event.inner().clone())), "notes.txt" }
+/// }
+/// ```
+pub fn use_context_menu(
+    cx: &ScopeState,
+    element: Option<Rc<MountedData>>,
+    build: impl FnMut() -> NativeMenu + 'static,
+    on_click: impl FnMut(&MenuEvent) + 'static,
+) {
+    let menu = cx.use_hook(|| {
+        let desktop = window();
+        let id = desktop.shared.context_menus.add(Box::new(on_click));
+        let build: Rc<RefCell<dyn FnMut() -> NativeMenu>> = Rc::new(RefCell::new(build));
+        ContextMenu {
+            desktop,
+            id,
+            build,
+            observed: RefCell::new(None),
+        }
+    });
+
+    let changed = match (&*menu.observed.borrow(), &element) {
+        (Some((old, _)), Some(new)) => !Rc::ptr_eq(old, new),
+        (None, None) => false,
+        _ => true,
+    };
+    if changed {
+        // Stop listening to the old element before listening to the new one
+        menu.observed.borrow_mut().take();
+        if let Some(element) = element {
+            let observer = element.downcast::<DesktopElement>().map(|target| {
+                let desktop = menu.desktop.clone();
+                let build = menu.build.clone();
+                let id = menu.id;
+                target.observe_context_menu(move || {
+                    let native = (&mut *build.borrow_mut())();
+                    desktop
+                        .shared
+                        .context_menus
+                        .show(id, &desktop.window, &native);
+                })
+            });
+            *menu.observed.borrow_mut() = Some((element, observer));
+        }
+    }
+
+    struct ContextMenu {
+        desktop: DesktopContext,
+        id: usize,
+        build: Rc<RefCell<dyn FnMut() -> NativeMenu>>,
+        observed: RefCell<Option<(Rc<MountedData>, Option<DesktopObserver>)>>,
+    }
+    impl Drop for ContextMenu {
+        fn drop(&mut self) {
+            self.desktop.shared.context_menus.remove(self.id);
+        }
+    }
+}
+
+/// Run an async check before the window this component is in closes, like asking the user to save their changes. Return `false` to keep the window open.
+///
+/// The window stays open until every before quit handler of the window has finished, so a handler can also delay the close to finish some work. Closing the window again while the handlers run does not ask again.
+///
+/// Closing a window with [`DesktopService::close`](crate::DesktopService::close) or [`DesktopService::close_window`](crate::DesktopService::close_window) does not ask the handlers.
+///
+/// ```rust, ignore
+/// let unsaved = use_signal(cx, || false);
+/// use_before_quit(cx, move || async move {
+///     !*unsaved.read() || ask_to_discard_changes().await
+/// });
+/// ```
+pub fn use_before_quit<F>(cx: &ScopeState, mut handler: impl FnMut() -> F + 'static)
+where
+    F: Future<Output = bool> + 'static,
+{
+    cx.use_hook(|| {
+        let desktop = window();
+        let id = desktop
+            .quit_handlers
+            .add_before_quit(cx.scope_id(), Box::new(move || Box::pin(handler())));
+        BeforeQuitHandler { desktop, id }
+    });
+
+    struct BeforeQuitHandler {
+        desktop: DesktopContext,
+        id: usize,
+    }
+    impl Drop for BeforeQuitHandler {
+        fn drop(&mut self) {
+            self.desktop.quit_handlers.remove_before_quit(self.id);
+        }
+    }
+}
+
+/// Run a callback right before the app exits because its last window closed. Use this to flush state to disk.
+///
+/// The app exits as soon as the callback returns, so any work has to finish inside of it. Tasks spawned from the callback will not run.
+///
+/// ```rust, ignore
+/// let document = use_signal(cx, String::new);
+/// use_app_will_quit(cx, move || {
+///     std::fs::write("draft.txt", &*document.read()).unwrap();
+/// });
+/// ```
+pub fn use_app_will_quit(cx: &ScopeState, handler: impl FnMut() + 'static) {
+    cx.use_hook(|| {
+        let desktop = window();
+        let id = desktop
+            .quit_handlers
+            .add_will_quit(cx.scope_id(), Box::new(handler));
+        WillQuitHandler { desktop, id }
+    });
+
+    struct WillQuitHandler {
+        desktop: DesktopContext,
+        id: usize,
+    }
+    impl Drop for WillQuitHandler {
+        fn drop(&mut self) {
+            self.desktop.quit_handlers.remove_will_quit(self.id);
+        }
+    }
+}
+
+/// Create an [`Autosave`] that flushes when the window loses focus, before the window closes, and on the interval of the config.
+///
+/// Windows do not report when they are hidden, so [`AutosaveTrigger::Hidden`] never fires on desktop. The window waits for the flush before it closes, even if saving fails. To keep the window open when saving fails, check the [`SaveStatus`](dioxus_signals::SaveStatus) in your own [`use_before_quit`] handler.
+///
+/// ```rust, ignore
+/// let autosave = use_autosave(cx, AutosaveConfig::default());
+/// let document = use_signal(cx, String::new);
+/// cx.use_hook(|| {
+///     autosave.watch(document, |text| async move { tokio::fs::write("draft.txt", text).await })
+/// });
+/// ```
+pub fn use_autosave(cx: &ScopeState, config: AutosaveConfig) -> Autosave {
+    let autosave = *cx.use_hook(|| {
+        let autosave = Autosave::new(config);
+        // The interval needs a timer, so it only runs if the app has an executor
+        if let (Some(interval), Some(executor)) = (config.interval, current_executor()) {
+            cx.spawn(async move {
+                loop {
+                    executor.sleep(interval).await;
+                    autosave.trigger(AutosaveTrigger::Interval);
+                }
+            });
+        }
+        autosave
+    });
+
+    let window_id = use_window(cx).window.id();
+    use_wry_event_handler(cx, move |event, _| {
+        if let Event::WindowEvent {
+            window_id: id,
+            event: WindowEvent::Focused(false),
+            ..
+        } = event
+        {
+            if *id == window_id {
+                autosave.trigger(AutosaveTrigger::Blur);
+            }
+        }
+    });
+
+    use_before_quit(cx, move || async move {
+        if autosave.config().flushes_on(AutosaveTrigger::BeforeQuit) {
+            _ = autosave.flush().await;
+        }
+        true
+    });
+
+    autosave
+}
+
 /// Provide a callback to handle asset loading yourself.
 ///
 /// The callback takes a path as requested by the web view, and it should return `Some(response)`
@@ -67,7 +431,37 @@ pub fn use_global_shortcut(
     cx.use_hook(move || {
         let desktop = window();
 
-        let id = desktop.create_shortcut(accelerator.accelerator(), handler);
+        let id = desktop.create_shortcut(accelerator.accelerator()?, handler);
+
+        Ok(ShortcutHandle {
+            desktop,
+            shortcut_id: id?,
+        })
+    })
+}
+
+/// Register a shortcut that is only triggered while the current window is focused. It is removed when the component is dropped.
+///
+/// Accelerators are parsed the same way as in [`use_global_shortcut`], but the key presses are read from the webview, so the shortcut does not fire when the app is in the background and does not take the keys away from other apps.
+///
+/// Keys typed into inputs, textareas and contenteditable elements are ignored unless the shortcut opts in with [`ShortcutHandle::set_in_editable`].
+///
+/// ```rust, ignore
+/// let saved = use_state(cx, || 0);
+/// _ = use_window_shortcut(cx, "ctrl+s", {
+///     to_owned![saved];
+///     move || saved.modify(|count| count + 1)
+/// });
+/// ```
+pub fn use_window_shortcut(
+    cx: &ScopeState,
+    accelerator: impl IntoAccelerator,
+    handler: impl FnMut() + 'static,
+) -> &Result<ShortcutHandle, ShortcutRegistryError> {
+    cx.use_hook(move || {
+        let desktop = window();
+
+        let id = desktop.create_window_shortcut(accelerator.accelerator()?, handler);
 
         Ok(ShortcutHandle {
             desktop,
@@ -75,3 +469,145 @@ pub fn use_global_shortcut(
         })
     })
 }
+
+/// Get the color scheme of the current window as a signal. The signal is updated when the user switches between light and dark mode in their OS settings.
+///
+/// ```rust, ignore
+/// let scheme = use_color_scheme(cx);
+/// let background = if scheme.read().is_dark() { "black" } else { "white" };
+/// ```
+pub fn use_color_scheme(cx: &ScopeState) -> ReadOnlySignal<ColorScheme> {
+    let scheme = *cx.use_hook(|| Signal::new(crate::theme::current_color_scheme(&window().window)));
+
+    use_wry_event_handler(cx, move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::ThemeChanged(theme),
+            ..
+        } = event
+        {
+            let new = crate::theme::color_scheme(*theme);
+            if *scheme.peek() != new {
+                scheme.set(new);
+            }
+        }
+    });
+
+    ReadOnlySignal::new(scheme)
+}
+
+/// Get the monitors connected to the computer as a signal.
+///
+/// The OS does not tell us when a monitor is connected, so the monitors are read again every second, and whenever the scale factor of the window changes.
+///
+/// ```rust, ignore
+/// let monitors = use_monitors(cx);
+/// let handle = use_signal(cx, || None::<WindowHandle>);
+///
+/// render! {
+///     for monitor in monitors.read().iter().cloned() {
+///         button {
+///             onclick: move |_| {
+///                 if let Some(handle) = handle.read().as_ref() {
+///                     handle.move_to_monitor(&monitor.id);
+///                 }
+///             },
+///             "{monitor.name:?}"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_monitors(cx: &ScopeState) -> ReadOnlySignal<Vec<Monitor>> {
+    let monitors = *cx.use_hook(|| {
+        let desktop = window();
+        let monitors = Signal::new(crate::monitors::available_monitors(&desktop.window));
+        // Polling needs a timer, so it only runs if the app has an executor
+        if let Some(executor) = current_executor() {
+            cx.spawn(async move {
+                loop {
+                    executor.sleep(Duration::from_secs(1)).await;
+                    let new = crate::monitors::available_monitors(&desktop.window);
+                    if *monitors.peek() != new {
+                        monitors.set(new);
+                    }
+                }
+            });
+        }
+        monitors
+    });
+
+    use_wry_event_handler(cx, move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::ScaleFactorChanged { .. },
+            ..
+        } = event
+        {
+            let new = crate::monitors::available_monitors(&window().window);
+            if *monitors.peek() != new {
+                monitors.set(new);
+            }
+        }
+    });
+
+    ReadOnlySignal::new(monitors)
+}
+
+/// Get the accent color the user picked in their OS settings as a signal.
+///
+/// The OS does not tell us when the accent color changes, so it is read again whenever the color scheme changes or the window regains focus.
+///
+/// The accent color is only available on macOS. On other platforms the signal is always `None`.
+pub fn use_accent_color(cx: &ScopeState) -> ReadOnlySignal<Option<AccentColor>> {
+    let accent = *cx.use_hook(|| Signal::new(crate::theme::current_accent_color()));
+
+    use_wry_event_handler(cx, move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::ThemeChanged(_) | WindowEvent::Focused(true),
+            ..
+        } = event
+        {
+            let new = crate::theme::current_accent_color();
+            if *accent.peek() != new {
+                accent.set(new);
+            }
+        }
+    });
+
+    ReadOnlySignal::new(accent)
+}
+
+/// Call `handler` whenever the contents of the system clipboard change, in this app or in another one.
+///
+/// The clipboard is checked a few times per second on Windows and macOS. Other platforms don't report changes, so the handler is never called there.
+///
+/// ```rust, ignore
+/// use_clipboard_change_handler(cx, move || {
+///     let clipboard = window().clipboard();
+///     cx.spawn(async move {
+///         if let Ok(text) = clipboard.read_text().await {
+///             println!("Copied {text}");
+///         }
+///     });
+/// });
+/// ```
+pub fn use_clipboard_change_handler(cx: &ScopeState, mut handler: impl FnMut() + 'static) {
+    cx.use_hook(|| {
+        // Checking the clipboard needs a timer, so it only runs if the app has an executor
+        let (Some(mut last), Some(executor)) =
+            (crate::clipboard::change_count(), current_executor())
+        else {
+            return;
+        };
+        cx.spawn(async move {
+            loop {
+                executor.sleep(Duration::from_millis(250)).await;
+                let Some(count) = crate::clipboard::change_count() else {
+                    continue;
+                };
+                if count != last {
+                    last = count;
+                    handler();
+                }
+            }
+        });
+    });
+}
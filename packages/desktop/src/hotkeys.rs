@@ -0,0 +1,63 @@
+use crate::{webview_listener::WindowListener, DesktopContext};
+use dioxus_html::{hotkeys::HotkeyProvider, KeyboardData, SerializedKeyboardData};
+use serde::Deserialize;
+use std::any::Any;
+
+/// Listens for `keydown` events in the webview and sends them to the virtual dom.
+///
+/// Key presses reach the app after the webview already handled them, so the default action of key presses a hotkey handled can't be prevented.
+pub(crate) struct DesktopHotkeyProvider {
+    desktop_ctx: DesktopContext,
+}
+
+impl DesktopHotkeyProvider {
+    pub(crate) fn new(desktop_ctx: DesktopContext) -> Self {
+        Self { desktop_ctx }
+    }
+}
+
+#[derive(Deserialize)]
+struct Keydown {
+    event: SerializedKeyboardData,
+    editable: bool,
+}
+
+impl HotkeyProvider for DesktopHotkeyProvider {
+    fn subscribe(
+        &self,
+        mut on_keydown: Box<dyn FnMut(KeyboardData, bool) -> bool>,
+    ) -> Box<dyn Any> {
+        Box::new(WindowListener::new(
+            self.desktop_ctx.clone(),
+            "keydown",
+            r#"
+            const target = event.target;
+            const editable = target instanceof HTMLElement &&
+                (target.isContentEditable || ["INPUT", "TEXTAREA", "SELECT"].includes(target.tagName));
+            return {
+                event: {
+                    char_code: event.charCode,
+                    is_composing: event.isComposing,
+                    key: event.key,
+                    key_code: event.keyCode,
+                    code: event.code,
+                    alt_key: event.altKey,
+                    ctrl_key: event.ctrlKey,
+                    meta_key: event.metaKey,
+                    shift_key: event.shiftKey,
+                    location: event.location,
+                    repeat: event.repeat,
+                    which: event.which,
+                },
+                editable,
+            };
+            "#,
+            move |value| match Keydown::deserialize(value) {
+                Ok(keydown) => {
+                    on_keydown(keydown.event.into(), keydown.editable);
+                }
+                Err(err) => tracing::error!("Failed to read a key press: {err}"),
+            },
+        ))
+    }
+}
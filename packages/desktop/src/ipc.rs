@@ -23,6 +23,21 @@ pub enum EventData {
 
     /// Close a given window (could be any window!)
     CloseWindow,
+
+    /// The before quit handlers of a window allowed it to close
+    ConfirmClose,
+
+    /// A [`SharedSignal`](crate::SharedSignal) read by this scope of the window changed
+    MarkDirty(dioxus_core::ScopeId),
+
+    /// A scheduled notification was delivered or clicked
+    Notification(dioxus_html::notification::NotificationEvent),
+
+    /// Files from outside the app are dragged over the window, or no files are dragged anymore if empty
+    FileDrop(Vec<std::path::PathBuf>),
+
+    /// Another process of the app was asked to open a url with the scheme of the app
+    OpenUrl(String),
 }
 
 /// A message struct that manages the communication between the webview and the eventloop code
@@ -42,6 +57,8 @@ pub enum IpcMethod<'a> {
     Query,
     BrowserOpen,
     Initialize,
+    Rendered,
+    Channel,
     Other(&'a str),
 }
 
@@ -54,6 +71,8 @@ impl IpcMessage {
             "query" => IpcMethod::Query,
             "browser_open" => IpcMethod::BrowserOpen,
             "initialize" => IpcMethod::Initialize,
+            "rendered" => IpcMethod::Rendered,
+            "channel" => IpcMethod::Channel,
             _ => IpcMethod::Other(&self.method),
         }
     }
@@ -0,0 +1,255 @@
+//! Typed channels between the javascript of the page and rust, created with [`use_js_channel`].
+
+use crate::{window, DesktopContext};
+use dioxus_core::ScopeState;
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::StreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{cell::RefCell, collections::HashMap, marker::PhantomData, rc::Rc};
+use thiserror::Error;
+
+/// An error from sending a message over a [`JsChannel`].
+#[derive(Debug, Error)]
+pub enum JsChannelError {
+    /// The message could not be serialized to json.
+    #[error("Failed to serialize the message: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    /// The message could not be sent to the webview.
+    #[error("Failed to send the message: {0}")]
+    Send(String),
+}
+
+/// Open a channel named `name` to the javascript of the page in the current window. Messages from the page are deserialized to `Req`, and messages to the page are serialized from `Resp`.
+///
+/// The page gets the other end with `window.dioxusChannel(name)`, which has three methods:
+/// - `request(data)` sends a message and returns a promise of the response.
+/// - `send(data)` sends a message without waiting for a response.
+/// - `subscribe(listener)` calls `listener` with every message that [`JsChannel::send`] pushes, and returns a function that unsubscribes it.
+///
+/// ```rust, ignore
+/// #[derive(Deserialize)]
+/// enum Request { Add(i32, i32) }
+///
+/// let channel = use_js_channel::<Request, i32>(cx, "math");
+/// use_on_create(cx, || {
+///     to_owned![channel];
+///     async move {
+///         while let Some(request) = channel.recv().await {
+///             let Request::Add(a, b) = request.data;
+///             _ = request.respond(&(a + b));
+///         }
+///     }
+/// });
+/// ```
+///
+/// ```js
+/// const sum = await window.dioxusChannel("math").request({ Add: [1, 2] });
+/// ```
+pub fn use_js_channel<Req, Resp>(cx: &ScopeState, name: impl Into<String>) -> &JsChannel<Req, Resp>
+where
+    Req: DeserializeOwned + 'static,
+    Resp: Serialize + 'static,
+{
+    cx.use_hook(|| {
+        let name = name.into();
+        let desktop = window();
+        let receiver = desktop.js_channels.open(&name);
+        JsChannel {
+            inner: Rc::new(ChannelInner {
+                name,
+                desktop,
+                receiver: RefCell::new(receiver),
+            }),
+            _marker: PhantomData,
+        }
+    })
+}
+
+/// A typed channel to the javascript of the page, created with [`use_js_channel`]. The channel is closed when the component that created it is dropped.
+pub struct JsChannel<Req, Resp> {
+    inner: Rc<ChannelInner>,
+    _marker: PhantomData<fn(Req) -> Resp>,
+}
+
+impl<Req, Resp> Clone for JsChannel<Req, Resp> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Req: DeserializeOwned, Resp: Serialize> JsChannel<Req, Resp> {
+    /// Push a message to the listeners the page subscribed to the channel.
+    pub fn send(&self, message: &Resp) -> Result<(), JsChannelError> {
+        let data = serde_json::to_value(message)?;
+        send_to_page(&self.inner.desktop, &self.inner.name, None, Ok(data))
+    }
+
+    /// Wait for the next message from the page. Messages that can't be deserialized to `Req` are rejected and skipped.
+    pub async fn recv(&self) -> Option<JsRequest<Req, Resp>> {
+        loop {
+            let message =
+                std::future::poll_fn(|cx| self.inner.receiver.borrow_mut().poll_next_unpin(cx))
+                    .await?;
+
+            let responder = Responder {
+                desktop: self.inner.desktop.clone(),
+                name: self.inner.name.clone(),
+                id: message.id,
+            };
+            match serde_json::from_value(message.data) {
+                Ok(data) => {
+                    return Some(JsRequest {
+                        data,
+                        responder,
+                        _marker: PhantomData,
+                    })
+                }
+                Err(err) => responder.reject(err.to_string()),
+            }
+        }
+    }
+}
+
+/// A message the page sent over a [`JsChannel`].
+///
+/// If the page sent it with `request`, it waits for [`JsRequest::respond`]. The request is rejected if this is dropped without a response.
+pub struct JsRequest<Req, Resp> {
+    /// The message from the page.
+    pub data: Req,
+    responder: Responder,
+    _marker: PhantomData<fn(Resp)>,
+}
+
+impl<Req, Resp: Serialize> JsRequest<Req, Resp> {
+    /// Resolve the promise of the page with `response`. Does nothing if the page sent the message without waiting for a response.
+    pub fn respond(self, response: &Resp) -> Result<(), JsChannelError> {
+        let data = serde_json::to_value(response)?;
+        self.responder.respond(Ok(data))
+    }
+
+    /// Reject the promise of the page with `error`.
+    pub fn reject(self, error: impl Into<String>) {
+        self.responder.reject(error.into())
+    }
+}
+
+/// Answers a message of the page exactly once.
+struct Responder {
+    desktop: DesktopContext,
+    name: String,
+    // The page only waits for a response if the message has an id
+    id: Option<u64>,
+}
+
+impl Responder {
+    fn respond(mut self, result: Result<serde_json::Value, String>) -> Result<(), JsChannelError> {
+        match self.id.take() {
+            Some(id) => send_to_page(&self.desktop, &self.name, Some(id), result),
+            None => Ok(()),
+        }
+    }
+
+    fn reject(self, error: String) {
+        let name = self.name.clone();
+        if let Err(err) = self.respond(Err(error)) {
+            tracing::error!("Failed to reject a message of the {name} channel: {err}");
+        }
+    }
+}
+
+impl Drop for Responder {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            let error = Err("The request was dropped without a response".to_string());
+            _ = send_to_page(&self.desktop, &self.name, Some(id), error);
+        }
+    }
+}
+
+/// Resolve or reject the request `id` of the page, or push a message to its listeners if there is no id.
+fn send_to_page(
+    desktop: &DesktopContext,
+    name: &str,
+    id: Option<u64>,
+    result: Result<serde_json::Value, String>,
+) -> Result<(), JsChannelError> {
+    let (data, error) = match result {
+        Ok(data) => (data, serde_json::Value::Null),
+        Err(error) => (serde_json::Value::Null, serde_json::Value::String(error)),
+    };
+    let script = format!(
+        "window.interpreter.channels.receive({}, {}, {data}, {error});",
+        serde_json::to_string(name)?,
+        serde_json::to_string(&id)?,
+    );
+    desktop
+        .webview
+        .evaluate_script(&script)
+        .map_err(|err| JsChannelError::Send(err.to_string()))
+}
+
+struct ChannelInner {
+    name: String,
+    desktop: DesktopContext,
+    receiver: RefCell<UnboundedReceiver<ChannelMessage>>,
+}
+
+impl Drop for ChannelInner {
+    fn drop(&mut self) {
+        let receiver = self.receiver.borrow();
+        self.desktop.js_channels.close(&self.name, &receiver);
+    }
+}
+
+/// A message from `window.dioxusChannel(name)` in the page.
+#[derive(Deserialize)]
+pub(crate) struct ChannelMessage {
+    name: String,
+    id: Option<u64>,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+/// The open channels of a window.
+#[derive(Default)]
+pub(crate) struct JsChannels {
+    channels: RefCell<HashMap<String, UnboundedSender<ChannelMessage>>>,
+}
+
+impl JsChannels {
+    fn open(&self, name: &str) -> UnboundedReceiver<ChannelMessage> {
+        let (sender, receiver) = unbounded();
+        let previous = self.channels.borrow_mut().insert(name.to_string(), sender);
+        if previous.is_some() {
+            tracing::warn!("The {name} channel was opened twice, only the last one is used");
+        }
+        receiver
+    }
+
+    fn close(&self, name: &str, receiver: &UnboundedReceiver<ChannelMessage>) {
+        let mut channels = self.channels.borrow_mut();
+        // Another channel may have been opened with the same name since
+        let open = channels.get(name);
+        if open.map_or(false, |sender| sender.is_connected_to(receiver)) {
+            channels.remove(name);
+        }
+    }
+
+    /// Pass a message of the page to the channel it was sent to.
+    pub(crate) fn dispatch(&self, desktop: &DesktopContext, message: ChannelMessage) {
+        let sender = self.channels.borrow().get(&message.name).cloned();
+        let (name, id) = (message.name.clone(), message.id);
+        let sent = match sender {
+            Some(sender) => sender.unbounded_send(message).is_ok(),
+            None => false,
+        };
+        if !sent && id.is_some() {
+            let error = Err(format!("There is no channel named {name}"));
+            _ = send_to_page(desktop, &name, id, error);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Keep the system and the display awake, with [`DesktopContext::keep_awake`](crate::DesktopContext::keep_awake).
+
+use std::{marker::PhantomData, rc::Rc};
+
+/// Keeps the system from sleeping and the screensaver from starting until it is dropped. Created with [`DesktopContext::keep_awake`](crate::DesktopContext::keep_awake).
+///
+/// The system can sleep again once every guard is dropped.
+#[must_use = "The system can sleep again as soon as the guard is dropped"]
+pub struct KeepAwakeGuard {
+    _inhibitor: Option<Inhibitor>,
+    // The inhibitor has to be released on the thread that created it
+    _not_send: PhantomData<Rc<()>>,
+}
+
+impl KeepAwakeGuard {
+    pub(crate) fn new(reason: &str) -> Self {
+        let inhibitor = Inhibitor::new(reason);
+        if inhibitor.is_none() {
+            tracing::warn!("Failed to keep the system awake for {reason}");
+        }
+        Self {
+            _inhibitor: inhibitor,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+struct Inhibitor;
+
+#[cfg(target_os = "windows")]
+thread_local! {
+    // The execution state belongs to the thread, so the guards of the thread share it
+    static GUARDS: std::cell::Cell<usize> = std::cell::Cell::new(0);
+}
+
+#[cfg(target_os = "windows")]
+impl Inhibitor {
+    fn new(_reason: &str) -> Option<Self> {
+        use windows::Win32::System::Power::{
+            SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED,
+        };
+
+        if GUARDS.with(|guards| guards.get()) == 0 {
+            let state = unsafe {
+                SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED)
+            };
+            if state.0 == 0 {
+                return None;
+            }
+        }
+        GUARDS.with(|guards| guards.set(guards.get() + 1));
+        Some(Inhibitor)
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        use windows::Win32::System::Power::{SetThreadExecutionState, ES_CONTINUOUS};
+
+        let remaining = GUARDS.with(|guards| {
+            guards.set(guards.get() - 1);
+            guards.get()
+        });
+        if remaining == 0 {
+            unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+struct Inhibitor(u32);
+
+#[cfg(target_os = "macos")]
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOPMAssertionCreateWithName(
+        assertion_type: core_foundation::string::CFStringRef,
+        level: u32,
+        name: core_foundation::string::CFStringRef,
+        id: *mut u32,
+    ) -> i32;
+    fn IOPMAssertionRelease(id: u32) -> i32;
+}
+
+#[cfg(target_os = "macos")]
+impl Inhibitor {
+    fn new(reason: &str) -> Option<Self> {
+        use core_foundation::{base::TCFType, string::CFString};
+
+        // kIOPMAssertionTypePreventUserIdleDisplaySleep also keeps the system awake
+        let assertion_type = CFString::from_static_string("PreventUserIdleDisplaySleep");
+        let name = CFString::new(reason);
+        const ASSERTION_LEVEL_ON: u32 = 255;
+
+        let mut id = 0;
+        let result = unsafe {
+            IOPMAssertionCreateWithName(
+                assertion_type.as_concrete_TypeRef(),
+                ASSERTION_LEVEL_ON,
+                name.as_concrete_TypeRef(),
+                &mut id,
+            )
+        };
+        (result == 0).then_some(Inhibitor(id))
+    }
+}
+
+#[cfg(target_os = "macos")]
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        unsafe { IOPMAssertionRelease(self.0) };
+    }
+}
+
+/// Linux has no common api to keep the system awake, so this holds a lock of systemd while the child process runs. The child exits when its input is closed, even if the app crashes.
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+struct Inhibitor(std::process::Child);
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+impl Inhibitor {
+    fn new(reason: &str) -> Option<Self> {
+        let who = std::env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.file_stem()?.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "dioxus".to_string());
+        std::process::Command::new("systemd-inhibit")
+            .arg("--what=idle:sleep")
+            .arg(format!("--who={who}"))
+            .arg(format!("--why={reason}"))
+            .arg("cat")
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .ok()
+            .map(Inhibitor)
+    }
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        drop(self.0.stdin.take());
+        _ = self.0.wait();
+    }
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+enum Inhibitor {}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+impl Inhibitor {
+    fn new(_reason: &str) -> Option<Self> {
+        None
+    }
+}
@@ -53,8 +53,7 @@ pub fn launch_cfg(root: Component, config_builder: Config) {
 
 /// Launch the WebView and run the event loop, with configuration and root props.
 ///
-/// If the [`tokio`] feature is enabled, this will also startup and block a tokio runtime using the unconstrained task.
-/// This function will start a multithreaded Tokio runtime as well the WebView event loop. This will block the current thread.
+/// If the [`tokio`] feature is enabled, the tasks of the app run on a [`TokioExecutor`](crate::TokioExecutor). When this is called inside a tokio runtime, the app uses that runtime. Otherwise it starts a multithreaded tokio runtime and blocks it using the unconstrained task. Set an executor with [`Config::with_executor`] to run the app on another runtime instead. This will block the current thread.
 ///
 /// You can configure the WebView window with a configuration closure
 ///
@@ -76,24 +75,33 @@ pub fn launch_cfg(root: Component, config_builder: Config) {
 ///     })
 /// }
 /// ```
-pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config) {
+#[allow(unused_mut)]
+pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, mut cfg: Config) {
     #[cfg(feature = "tokio")]
-    tokio::runtime::Builder::new_multi_thread()
-        .enable_all()
-        .build()
-        .unwrap()
-        .block_on(tokio::task::unconstrained(async move {
-            launch_with_props_blocking(root, props, cfg);
-        }));
+    if cfg.executor.is_none() {
+        // Reuse the runtime the app is embedded in. Starting another runtime inside of it would panic
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            cfg = cfg.with_executor(crate::TokioExecutor::new(handle));
+        } else {
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            cfg = cfg.with_executor(crate::TokioExecutor::new(runtime.handle().clone()));
+            runtime.block_on(tokio::task::unconstrained(async move {
+                launch_with_props_blocking(root, props, cfg);
+            }));
+            return;
+        }
+    }
 
-    #[cfg(not(feature = "tokio"))]
     launch_with_props_blocking(root, props, cfg);
 }
 
 /// Launch the WebView and run the event loop, with configuration and root props.
 ///
 /// This will block the main thread, and *must* be spawned on the main thread. This function does not assume any runtime
-/// and is equivalent to calling launch_with_props with the tokio feature disabled.
+/// and is equivalent to calling launch_with_props with the tokio feature disabled. Tasks only get a runtime if one is set with [`Config::with_executor`].
 pub fn launch_with_props_blocking<P: 'static>(root: Component<P>, props: P, cfg: Config) {
     let (event_loop, mut app) = App::new(cfg, props, root);
 
@@ -109,10 +117,22 @@ pub fn launch_with_props_blocking<P: 'static>(root: Component<P>, props: P, cfg:
                 WindowEvent::Destroyed { .. } => app.window_destroyed(window_id),
                 _ => {}
             },
+            // The OS sends the urls of the app's scheme to the running app on macOS and iOS
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            Event::Opened { urls } => {
+                for url in urls {
+                    app.handle_open_url(url.to_string());
+                }
+            }
             Event::UserEvent(UserWindowEvent(event, id)) => match event {
                 EventData::Poll => app.poll_vdom(id),
                 EventData::NewWindow => app.handle_new_window(),
                 EventData::CloseWindow => app.handle_close_msg(id),
+                EventData::ConfirmClose => app.handle_confirm_close(id),
+                EventData::MarkDirty(scope) => app.handle_mark_dirty(id, scope),
+                EventData::Notification(event) => app.handle_notification_event(event),
+                EventData::FileDrop(paths) => app.handle_file_drop(id, paths),
+                EventData::OpenUrl(url) => app.handle_open_url(url),
                 #[cfg(all(feature = "hot-reload", debug_assertions))]
                 EventData::HotReloadEvent(msg) => app.handle_hot_reload_msg(msg),
                 EventData::Ipc(msg) => match msg.method() {
@@ -121,6 +141,8 @@ pub fn launch_with_props_blocking<P: 'static>(root: Component<P>, props: P, cfg:
                     IpcMethod::Query => app.handle_query_msg(msg, id),
                     IpcMethod::BrowserOpen => app.handle_browser_open(msg),
                     IpcMethod::Initialize => app.handle_initialize_msg(id),
+                    IpcMethod::Rendered => app.handle_rendered_msg(id),
+                    IpcMethod::Channel => app.handle_channel_msg(msg, id),
                     IpcMethod::Other(_) => {}
                 },
             },
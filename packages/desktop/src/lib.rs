@@ -3,24 +3,64 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 #![deny(missing_docs)]
 
+mod animation;
 mod app;
 mod assets;
+mod badge;
+mod battery;
+mod child_webview;
+mod clipboard;
 mod config;
+mod context_menu;
+mod deep_link;
 mod desktop_context;
 mod edits;
 mod element;
 mod escape;
 mod eval;
 mod events;
+#[cfg(feature = "tokio")]
+mod executor;
+mod file_dialog;
 mod file_upload;
+mod fullscreen;
+#[cfg(all(
+    feature = "global-input",
+    not(any(target_os = "ios", target_os = "android"))
+))]
+mod global_input;
 mod hooks;
+mod hotkeys;
 mod ipc;
+mod js_channel;
+mod keep_awake;
 mod menubar;
+mod monitors;
+mod native_menu;
+mod network;
+mod notification;
+mod open_external;
+mod printer;
 mod protocol;
 mod query;
+mod quit;
+mod shared_signal;
 mod shortcut;
+mod splash;
+#[cfg(feature = "text-metrics")]
+mod text_metrics;
+mod theme;
+mod tray;
+#[cfg(feature = "updater")]
+pub mod updater;
 mod waker;
 mod webview;
+mod webview_listener;
+mod window_effect;
+mod window_handle;
+mod window_level;
+mod window_size;
+mod window_state;
 
 // mobile shortcut is only supported on mobile platforms
 #[cfg(any(target_os = "ios", target_os = "android"))]
@@ -38,11 +78,45 @@ pub use tao::window::WindowBuilder;
 pub use wry;
 
 // Public exports
-pub use assets::AssetRequest;
+pub use assets::{asset_url, use_asset, AssetRequest};
+pub use child_webview::{use_child_webview, ChildWebView, ChildWebViewBuilder, ChildWebViewEvent};
 pub use config::{Config, WindowCloseBehaviour};
+pub use deep_link::DeepLink;
 pub use desktop_context::{
-    window, DesktopContext, DesktopService, WryEventHandler, WryEventHandlerId,
+    cancel_notification, new_window, new_window_with_props, schedule_notification, window,
+    DesktopContext, DesktopService, WryEventHandler, WryEventHandlerId,
 };
-pub use hooks::{use_asset_handler, use_global_shortcut, use_window, use_wry_event_handler};
+pub use events::DesktopDragData;
+#[cfg(feature = "tokio")]
+pub use executor::TokioExecutor;
+pub use fullscreen::FullscreenMode;
+#[cfg(all(
+    feature = "global-input",
+    not(any(target_os = "ios", target_os = "android"))
+))]
+pub use global_input::{
+    global_input, GlobalInput, GlobalInputConfig, GlobalInputError, GlobalInputEvent,
+};
+pub use hooks::{
+    use_accent_color, use_app_will_quit, use_asset_handler, use_autosave, use_before_quit,
+    use_clipboard_change_handler, use_color_scheme, use_context_menu, use_deep_link_handler,
+    use_global_shortcut, use_menu_event_handler, use_menubar, use_monitors,
+    use_notification_events, use_tray_event_handler, use_tray_menu, use_window,
+    use_window_shortcut, use_wry_event_handler,
+};
+pub use js_channel::{use_js_channel, JsChannel, JsChannelError, JsRequest};
+pub use keep_awake::KeepAwakeGuard;
+pub use monitors::{Monitor, MonitorId};
+pub use native_menu::{MenuEvent, NativeMenu, NativeMenuItem, PredefinedMenuItem};
+pub use open_external::{open_external, OpenExternal, OpenExternalError};
+pub use printer::{use_printer, PrintError, UsePrinter};
+pub use shared_signal::{use_shared_signal, SharedSignal, SharedSignalWrite};
 pub use shortcut::{ShortcutHandle, ShortcutId, ShortcutRegistryError};
+pub use splash::SplashScreen;
+#[cfg(feature = "text-metrics")]
+pub use text_metrics::{measure_text, use_text_metrics};
+pub use tray::{TrayBuilder, TrayEvent, TrayImage};
+pub use window_effect::{VibrancyMaterial, WindowEffect, WindowEffectError};
+pub use window_handle::WindowHandle;
+pub use window_level::WindowLevel;
 pub use wry::RequestAsyncResponder;
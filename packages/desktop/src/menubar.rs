@@ -1,15 +1,44 @@
-use std::any::Any;
-
+use crate::native_menu::NativeMenu;
 use tao::window::Window;
 
-#[allow(unused)]
-pub fn build_menu(window: &Window, default_menu_bar: bool) -> Option<Box<dyn Any>> {
+/// The menubar of a window. The default menubar of the platform is shown until the app sets its own menu.
+pub(crate) struct MenuBar {
     #[cfg(not(any(target_os = "ios", target_os = "android")))]
-    {
-        return Some(Box::new(impl_::build_menu_bar(default_menu_bar, window)) as Box<dyn Any>);
+    menu: std::cell::RefCell<crate::native_menu::BuiltMenu>,
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl MenuBar {
+    pub(crate) fn new(window: &Window, default_menu_bar: bool, menu: Option<&NativeMenu>) -> Self {
+        use crate::native_menu::BuiltMenu;
+
+        let (bar, defaults) = impl_::build_menu_bar(default_menu_bar && menu.is_none(), window);
+        let menu = match menu {
+            Some(menu) => BuiltMenu::with_menu(bar, menu),
+            None => BuiltMenu::with_existing(bar, defaults),
+        };
+        Self {
+            menu: std::cell::RefCell::new(menu),
+        }
     }
 
-    None
+    /// Replace the items of the menubar, updating the existing items in place if only their labels or states changed.
+    pub(crate) fn set(&self, menu: &NativeMenu) {
+        self.menu.borrow_mut().update(menu);
+    }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+impl MenuBar {
+    pub(crate) fn new(
+        _window: &Window,
+        _default_menu_bar: bool,
+        _menu: Option<&NativeMenu>,
+    ) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn set(&self, _menu: &NativeMenu) {}
 }
 
 #[cfg(not(any(target_os = "ios", target_os = "android")))]
@@ -22,8 +51,10 @@ mod impl_ {
     /// > Note: The default menu bar enables macOS shortcuts like cut/copy/paste.
     /// > The menu bar differs per platform because of constraints introduced
     /// > by [`MenuItem`](tao::menu::MenuItem).
+    ///
+    /// Returns the menu and the submenus of the default menu bar.
     #[allow(unused)]
-    pub fn build_menu_bar(default: bool, window: &Window) -> Menu {
+    pub fn build_menu_bar(default: bool, window: &Window) -> (Menu, Vec<Submenu>) {
         let menu = Menu::new();
 
         #[cfg(target_os = "windows")]
@@ -45,54 +76,56 @@ mod impl_ {
             menu.init_for_nsapp();
         }
 
-        if default {
-            // since it is uncommon on windows to have an "application menu"
-            // we add a "window" menu to be more consistent across platforms with the standard menu
-            let window_menu = Submenu::new("Window", true);
-            window_menu
-                .append_items(&[
-                    &PredefinedMenuItem::fullscreen(None),
-                    &PredefinedMenuItem::separator(),
-                    &PredefinedMenuItem::hide(None),
-                    &PredefinedMenuItem::hide_others(None),
-                    &PredefinedMenuItem::show_all(None),
-                    &PredefinedMenuItem::maximize(None),
-                    &PredefinedMenuItem::minimize(None),
-                    &PredefinedMenuItem::close_window(None),
-                    &PredefinedMenuItem::separator(),
-                    &PredefinedMenuItem::quit(None),
-                ])
-                .unwrap();
+        if !default {
+            return (menu, Vec::new());
+        }
 
-            let edit_menu = Submenu::new("Edit", true);
-            edit_menu
-                .append_items(&[
-                    &PredefinedMenuItem::undo(None),
-                    &PredefinedMenuItem::redo(None),
-                    &PredefinedMenuItem::separator(),
-                    &PredefinedMenuItem::cut(None),
-                    &PredefinedMenuItem::copy(None),
-                    &PredefinedMenuItem::paste(None),
-                    &PredefinedMenuItem::separator(),
-                    &PredefinedMenuItem::select_all(None),
-                ])
-                .unwrap();
+        // since it is uncommon on windows to have an "application menu"
+        // we add a "window" menu to be more consistent across platforms with the standard menu
+        let window_menu = Submenu::new("Window", true);
+        window_menu
+            .append_items(&[
+                &PredefinedMenuItem::fullscreen(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::hide(None),
+                &PredefinedMenuItem::hide_others(None),
+                &PredefinedMenuItem::show_all(None),
+                &PredefinedMenuItem::maximize(None),
+                &PredefinedMenuItem::minimize(None),
+                &PredefinedMenuItem::close_window(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::quit(None),
+            ])
+            .unwrap();
 
-            let help_menu = Submenu::new("Help", true);
-            help_menu
-                .append_items(&[&MenuItem::new("Toggle Developer Tools", true, None)])
-                .unwrap();
+        let edit_menu = Submenu::new("Edit", true);
+        edit_menu
+            .append_items(&[
+                &PredefinedMenuItem::undo(None),
+                &PredefinedMenuItem::redo(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::cut(None),
+                &PredefinedMenuItem::copy(None),
+                &PredefinedMenuItem::paste(None),
+                &PredefinedMenuItem::separator(),
+                &PredefinedMenuItem::select_all(None),
+            ])
+            .unwrap();
 
-            menu.append_items(&[&window_menu, &edit_menu, &help_menu])
-                .unwrap();
+        let help_menu = Submenu::new("Help", true);
+        help_menu
+            .append_items(&[&MenuItem::new("Toggle Developer Tools", true, None)])
+            .unwrap();
+
+        menu.append_items(&[&window_menu, &edit_menu, &help_menu])
+            .unwrap();
 
-            #[cfg(target_os = "macos")]
-            {
-                window_menu.set_as_windows_menu_for_nsapp();
-                help_menu.set_as_help_menu_for_nsapp();
-            }
+        #[cfg(target_os = "macos")]
+        {
+            window_menu.set_as_windows_menu_for_nsapp();
+            help_menu.set_as_help_menu_for_nsapp();
         }
 
-        menu
+        (menu, vec![window_menu, edit_menu, help_menu])
     }
 }
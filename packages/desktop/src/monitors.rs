@@ -0,0 +1,82 @@
+//! The monitors connected to the computer, read with [`use_monitors`](crate::use_monitors).
+
+use tao::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    monitor::MonitorHandle,
+    window::{Fullscreen, Window},
+};
+
+/// Identifies a monitor while it stays connected at the same place on the desktop.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MonitorId(String);
+
+impl MonitorId {
+    // Monitor handles are not stable across platforms, so the name and position identify the monitor
+    fn of(monitor: &MonitorHandle) -> Self {
+        let position = monitor.position();
+        let name = monitor.name().unwrap_or_default();
+        MonitorId(format!("{name}@{},{}", position.x, position.y))
+    }
+}
+
+/// A monitor connected to the computer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Monitor {
+    /// The id to pass to [`WindowHandle::move_to_monitor`](crate::WindowHandle::move_to_monitor).
+    pub id: MonitorId,
+
+    /// The name of the monitor, if the platform reports it.
+    pub name: Option<String>,
+
+    /// The top left corner of the monitor on the desktop.
+    pub position: PhysicalPosition<i32>,
+
+    /// The resolution of the monitor.
+    pub size: PhysicalSize<u32>,
+
+    /// The number of physical pixels per logical pixel.
+    pub scale_factor: f64,
+
+    /// If this is the primary monitor of the system.
+    pub primary: bool,
+}
+
+/// List the monitors that are connected right now.
+pub(crate) fn available_monitors(window: &Window) -> Vec<Monitor> {
+    let primary = window.primary_monitor();
+    window
+        .available_monitors()
+        .map(|monitor| Monitor {
+            id: MonitorId::of(&monitor),
+            name: monitor.name(),
+            position: monitor.position(),
+            size: monitor.size(),
+            scale_factor: monitor.scale_factor(),
+            primary: primary.as_ref() == Some(&monitor),
+        })
+        .collect()
+}
+
+/// Center the window on the monitor with `id`, keeping it fullscreen if it is. Returns false if the monitor is not connected.
+pub(crate) fn move_to_monitor(window: &Window, id: &MonitorId) -> bool {
+    let Some(monitor) = window
+        .available_monitors()
+        .find(|monitor| MonitorId::of(monitor) == *id)
+    else {
+        return false;
+    };
+
+    if let Some(Fullscreen::Borderless(_)) = window.fullscreen() {
+        window.set_fullscreen(Some(Fullscreen::Borderless(Some(monitor))));
+        return true;
+    }
+
+    let (area, size) = (monitor.size(), window.outer_size());
+    let offset = |area: u32, size: u32| (area.saturating_sub(size) / 2) as i32;
+    let position = monitor.position();
+    window.set_outer_position(PhysicalPosition::new(
+        position.x + offset(area.width, size.width),
+        position.y + offset(area.height, size.height),
+    ));
+    true
+}
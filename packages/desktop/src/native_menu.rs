@@ -0,0 +1,516 @@
+//! Native menus defined in rust, shared by the menubar, the tray icon and context menus.
+//!
+//! A [`NativeMenu`] only describes the menu. It is turned into a muda menu by [`BuiltMenu`], which keeps the native items around so later versions of the menu can update them in place.
+
+use slab::Slab;
+use std::cell::RefCell;
+
+/// A native menu, like the menubar of a window or the menu of the tray icon.
+///
+/// Every item has an id that is reported in a [`MenuEvent`] when it is clicked.
+///
+/// ```rust, ignore
+/// let menu = NativeMenu::new()
+///     .submenu(
+///         "File",
+///         NativeMenu::new()
+///             .item_with_accelerator("save", "Save", "CmdOrCtrl+S")
+///             .separator()
+///             .predefined(PredefinedMenuItem::Quit),
+///     )
+///     .submenu(
+///         "Edit",
+///         NativeMenu::new()
+///             .predefined(PredefinedMenuItem::Copy)
+///             .predefined(PredefinedMenuItem::Paste),
+///     );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NativeMenu {
+    items: Vec<NativeMenuItem>,
+}
+
+/// An item in a [`NativeMenu`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NativeMenuItem {
+    /// An item with a label.
+    Item {
+        /// The id reported when the item is clicked.
+        id: String,
+        /// The text of the item.
+        label: String,
+        /// Disabled items are greyed out and can't be clicked.
+        enabled: bool,
+        /// A key combination that clicks the item while the menu is active, like `CmdOrCtrl+S`.
+        accelerator: Option<String>,
+    },
+    /// An item with a check mark. The check mark is toggled when the item is clicked.
+    Checkbox {
+        /// The id reported when the item is clicked.
+        id: String,
+        /// The text of the item.
+        label: String,
+        /// Whether the check mark is shown.
+        checked: bool,
+        /// Disabled items are greyed out and can't be clicked.
+        enabled: bool,
+    },
+    /// A line between groups of items.
+    Separator,
+    /// An item that opens another menu.
+    Submenu {
+        /// The text of the item.
+        label: String,
+        /// The items of the nested menu.
+        menu: NativeMenu,
+    },
+    /// An item with a standard action of the platform, like copy or quit.
+    Predefined(PredefinedMenuItem),
+}
+
+/// Items with a standard action of the platform. Their labels and shortcuts follow the platform's conventions, and clicking them is not reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PredefinedMenuItem {
+    /// Cut the selection.
+    Cut,
+    /// Copy the selection.
+    Copy,
+    /// Paste the clipboard.
+    Paste,
+    /// Select everything.
+    SelectAll,
+    /// Undo the last edit.
+    Undo,
+    /// Redo the last undone edit.
+    Redo,
+    /// Minimize the window.
+    Minimize,
+    /// Maximize the window.
+    Maximize,
+    /// Toggle fullscreen. Only supported on macOS.
+    Fullscreen,
+    /// Hide the app. Only supported on macOS.
+    Hide,
+    /// Hide the other apps. Only supported on macOS.
+    HideOthers,
+    /// Show every app. Only supported on macOS.
+    ShowAll,
+    /// Close the window.
+    CloseWindow,
+    /// Quit the app.
+    Quit,
+}
+
+impl NativeMenu {
+    /// Create an empty menu.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an item that reports `id` when it is clicked.
+    pub fn item(self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.with_item(NativeMenuItem::Item {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+            accelerator: None,
+        })
+    }
+
+    /// Add an item that reports `id` when it is clicked or `accelerator` is pressed, like `CmdOrCtrl+S`.
+    pub fn item_with_accelerator(
+        self,
+        id: impl Into<String>,
+        label: impl Into<String>,
+        accelerator: impl Into<String>,
+    ) -> Self {
+        self.with_item(NativeMenuItem::Item {
+            id: id.into(),
+            label: label.into(),
+            enabled: true,
+            accelerator: Some(accelerator.into()),
+        })
+    }
+
+    /// Add an item with a check mark that reports `id` when it is clicked.
+    pub fn checkbox(self, id: impl Into<String>, label: impl Into<String>, checked: bool) -> Self {
+        self.with_item(NativeMenuItem::Checkbox {
+            id: id.into(),
+            label: label.into(),
+            checked,
+            enabled: true,
+        })
+    }
+
+    /// Add a line between groups of items.
+    pub fn separator(self) -> Self {
+        self.with_item(NativeMenuItem::Separator)
+    }
+
+    /// Add an item that opens a nested menu.
+    pub fn submenu(self, label: impl Into<String>, menu: NativeMenu) -> Self {
+        self.with_item(NativeMenuItem::Submenu {
+            label: label.into(),
+            menu,
+        })
+    }
+
+    /// Add an item with a standard action of the platform.
+    pub fn predefined(self, item: PredefinedMenuItem) -> Self {
+        self.with_item(NativeMenuItem::Predefined(item))
+    }
+
+    /// Add any kind of item, like a disabled one.
+    pub fn with_item(mut self, item: NativeMenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// The items of the menu.
+    pub fn items(&self) -> &[NativeMenuItem] {
+        &self.items
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl NativeMenuItem {
+    /// Whether `other` can be shown by updating the native item of `self`, without rebuilding the menu.
+    fn same_shape(&self, other: &NativeMenuItem) -> bool {
+        match (self, other) {
+            (NativeMenuItem::Item { id, .. }, NativeMenuItem::Item { id: other, .. }) => {
+                id == other
+            }
+            (NativeMenuItem::Checkbox { id, .. }, NativeMenuItem::Checkbox { id: other, .. }) => {
+                id == other
+            }
+            (NativeMenuItem::Separator, NativeMenuItem::Separator) => true,
+            (NativeMenuItem::Submenu { menu, .. }, NativeMenuItem::Submenu { menu: other, .. }) => {
+                menu.same_shape(other)
+            }
+            (NativeMenuItem::Predefined(item), NativeMenuItem::Predefined(other)) => item == other,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl NativeMenu {
+    fn same_shape(&self, other: &NativeMenu) -> bool {
+        self.items.len() == other.items.len()
+            && self
+                .items
+                .iter()
+                .zip(&other.items)
+                .all(|(item, other)| item.same_shape(other))
+    }
+
+    /// Every item of the menu and its submenus, each submenu directly followed by its own items.
+    fn flatten(&self) -> Vec<&NativeMenuItem> {
+        let mut items = Vec::new();
+        for item in &self.items {
+            items.push(item);
+            if let NativeMenuItem::Submenu { menu, .. } = item {
+                items.extend(menu.flatten());
+            }
+        }
+        items
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.flatten().into_iter().any(|item| match item {
+            NativeMenuItem::Item { id: item, .. } | NativeMenuItem::Checkbox { id: item, .. } => {
+                item == id
+            }
+            _ => false,
+        })
+    }
+}
+
+/// A click on an item of a native menu, passed to the handlers of [`use_menu_event_handler`](crate::use_menu_event_handler).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuEvent {
+    /// The id of the item that was clicked.
+    pub id: String,
+}
+
+type MenuHandler = Box<dyn FnMut(&MenuEvent)>;
+
+/// The handlers for clicks on the menubar and context menus.
+#[derive(Default)]
+pub(crate) struct MenuHandlers {
+    handlers: RefCell<Slab<MenuHandler>>,
+}
+
+impl MenuHandlers {
+    pub(crate) fn add_handler(&self, handler: MenuHandler) -> usize {
+        self.handlers.borrow_mut().insert(handler)
+    }
+
+    pub(crate) fn remove_handler(&self, id: usize) {
+        self.handlers.borrow_mut().try_remove(id);
+    }
+
+    pub(crate) fn emit(&self, event: MenuEvent) {
+        for (_, handler) in self.handlers.borrow_mut().iter_mut() {
+            handler(&event);
+        }
+    }
+}
+
+/// A muda menu built from a [`NativeMenu`].
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+pub(crate) struct BuiltMenu {
+    pub(crate) menu: muda::Menu,
+    model: NativeMenu,
+    /// The native items of the top level of the menu.
+    top: Vec<NativeItem>,
+    /// The native items in the order of [`NativeMenu::flatten`].
+    items: Vec<NativeItem>,
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+#[derive(Clone)]
+enum NativeItem {
+    Item(muda::MenuItem),
+    Checkbox(muda::CheckMenuItem),
+    Submenu(muda::Submenu),
+    Predefined(muda::PredefinedMenuItem),
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl NativeItem {
+    fn as_item(&self) -> &dyn muda::IsMenuItem {
+        match self {
+            NativeItem::Item(item) => item,
+            NativeItem::Checkbox(item) => item,
+            NativeItem::Submenu(item) => item,
+            NativeItem::Predefined(item) => item,
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl BuiltMenu {
+    pub(crate) fn new(model: &NativeMenu) -> Self {
+        Self::with_menu(muda::Menu::new(), model)
+    }
+
+    /// Add the items of `model` to an existing muda menu, like a menubar that was already attached to a window.
+    pub(crate) fn with_menu(menu: muda::Menu, model: &NativeMenu) -> Self {
+        let mut built = Self {
+            menu,
+            model: NativeMenu::new(),
+            top: Vec::new(),
+            items: Vec::new(),
+        };
+        built.rebuild(model);
+        built
+    }
+
+    /// Track items that were added to `menu` without a [`NativeMenu`], like the default menubar. They are removed by the first update.
+    pub(crate) fn with_existing(menu: muda::Menu, existing: Vec<muda::Submenu>) -> Self {
+        Self {
+            menu,
+            model: NativeMenu::new(),
+            top: existing.into_iter().map(NativeItem::Submenu).collect(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Whether clicking an item with this id should be reported by this menu.
+    pub(crate) fn contains(&self, id: &str) -> bool {
+        self.model.contains(id)
+    }
+
+    /// Show a new version of the menu. Labels, enabled and checked states are updated in place, so an open menu stays open. The menu is only rebuilt if items were added, removed or moved.
+    pub(crate) fn update(&mut self, model: &NativeMenu) {
+        if self.top.len() != self.model.items.len() || !self.model.same_shape(model) {
+            self.rebuild(model);
+            return;
+        }
+
+        for ((old, new), native) in self
+            .model
+            .flatten()
+            .into_iter()
+            .zip(model.flatten())
+            .zip(&self.items)
+        {
+            update_item(old, new, native);
+        }
+        self.model = model.clone();
+    }
+
+    fn rebuild(&mut self, model: &NativeMenu) {
+        for item in self.top.drain(..) {
+            if let Err(err) = self.menu.remove(item.as_item()) {
+                tracing::error!("Failed to remove an item from a menu: {err}");
+            }
+        }
+        self.items.clear();
+
+        let menu = &self.menu;
+        self.top = build_items(&|item| menu.append(item), &model.items, &mut self.items);
+        self.model = model.clone();
+    }
+}
+
+/// Create the native items of `items`, add them with `append`, and return them. Every native item is also pushed to `all` in the order of [`NativeMenu::flatten`].
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+fn build_items(
+    append: &dyn Fn(&dyn muda::IsMenuItem) -> muda::Result<()>,
+    items: &[NativeMenuItem],
+    all: &mut Vec<NativeItem>,
+) -> Vec<NativeItem> {
+    let mut level = Vec::new();
+    for item in items {
+        let native = match item {
+            NativeMenuItem::Item {
+                id,
+                label,
+                enabled,
+                accelerator,
+            } => NativeItem::Item(muda::MenuItem::with_id(
+                id.clone(),
+                label,
+                *enabled,
+                accelerator.as_deref().and_then(parse_accelerator),
+            )),
+            NativeMenuItem::Checkbox {
+                id,
+                label,
+                checked,
+                enabled,
+            } => NativeItem::Checkbox(muda::CheckMenuItem::with_id(
+                id.clone(),
+                label,
+                *enabled,
+                *checked,
+                None,
+            )),
+            NativeMenuItem::Separator => {
+                NativeItem::Predefined(muda::PredefinedMenuItem::separator())
+            }
+            NativeMenuItem::Submenu { label, .. } => {
+                NativeItem::Submenu(muda::Submenu::new(label, true))
+            }
+            NativeMenuItem::Predefined(item) => NativeItem::Predefined(predefined(*item)),
+        };
+
+        if let Err(err) = append(native.as_item()) {
+            tracing::error!("Failed to add an item to a menu: {err}");
+        }
+        // The submenu's own items follow it, so the order matches NativeMenu::flatten
+        all.push(native.clone());
+        if let (NativeMenuItem::Submenu { menu, .. }, NativeItem::Submenu(submenu)) =
+            (item, &native)
+        {
+            build_items(&|item| submenu.append(item), &menu.items, all);
+        }
+        level.push(native);
+    }
+    level
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+fn update_item(old: &NativeMenuItem, new: &NativeMenuItem, native: &NativeItem) {
+    match (old, new, native) {
+        (
+            NativeMenuItem::Item {
+                label: old_label,
+                enabled: old_enabled,
+                accelerator: old_accelerator,
+                ..
+            },
+            NativeMenuItem::Item {
+                label,
+                enabled,
+                accelerator,
+                ..
+            },
+            NativeItem::Item(item),
+        ) => {
+            if old_label != label {
+                item.set_text(label);
+            }
+            if old_enabled != enabled {
+                item.set_enabled(*enabled);
+            }
+            if old_accelerator != accelerator {
+                if let Err(err) =
+                    item.set_accelerator(accelerator.as_deref().and_then(parse_accelerator))
+                {
+                    tracing::error!("Failed to change the accelerator of a menu item: {err}");
+                }
+            }
+        }
+        (
+            NativeMenuItem::Checkbox {
+                label: old_label,
+                enabled: old_enabled,
+                ..
+            },
+            NativeMenuItem::Checkbox {
+                label,
+                checked,
+                enabled,
+                ..
+            },
+            NativeItem::Checkbox(item),
+        ) => {
+            if old_label != label {
+                item.set_text(label);
+            }
+            if old_enabled != enabled {
+                item.set_enabled(*enabled);
+            }
+            // Clicks toggle the native check mark, so compare against it instead of the old menu
+            if item.is_checked() != *checked {
+                item.set_checked(*checked);
+            }
+        }
+        (
+            NativeMenuItem::Submenu {
+                label: old_label, ..
+            },
+            NativeMenuItem::Submenu { label, .. },
+            NativeItem::Submenu(item),
+        ) => {
+            if old_label != label {
+                item.set_text(label);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+fn parse_accelerator(accelerator: &str) -> Option<muda::accelerator::Accelerator> {
+    match accelerator.parse() {
+        Ok(accelerator) => Some(accelerator),
+        Err(err) => {
+            tracing::error!("Invalid menu accelerator {accelerator:?}: {err}");
+            None
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+fn predefined(item: PredefinedMenuItem) -> muda::PredefinedMenuItem {
+    match item {
+        PredefinedMenuItem::Cut => muda::PredefinedMenuItem::cut(None),
+        PredefinedMenuItem::Copy => muda::PredefinedMenuItem::copy(None),
+        PredefinedMenuItem::Paste => muda::PredefinedMenuItem::paste(None),
+        PredefinedMenuItem::SelectAll => muda::PredefinedMenuItem::select_all(None),
+        PredefinedMenuItem::Undo => muda::PredefinedMenuItem::undo(None),
+        PredefinedMenuItem::Redo => muda::PredefinedMenuItem::redo(None),
+        PredefinedMenuItem::Minimize => muda::PredefinedMenuItem::minimize(None),
+        PredefinedMenuItem::Maximize => muda::PredefinedMenuItem::maximize(None),
+        PredefinedMenuItem::Fullscreen => muda::PredefinedMenuItem::fullscreen(None),
+        PredefinedMenuItem::Hide => muda::PredefinedMenuItem::hide(None),
+        PredefinedMenuItem::HideOthers => muda::PredefinedMenuItem::hide_others(None),
+        PredefinedMenuItem::ShowAll => muda::PredefinedMenuItem::show_all(None),
+        PredefinedMenuItem::CloseWindow => muda::PredefinedMenuItem::close_window(None),
+        PredefinedMenuItem::Quit => muda::PredefinedMenuItem::quit(None),
+    }
+}
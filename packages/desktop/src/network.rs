@@ -0,0 +1,54 @@
+use crate::{element::observe, DesktopContext};
+use dioxus_html::network::{ConnectionType, NetworkStatus, NetworkStatusProvider};
+use serde::Deserialize;
+use std::{any::Any, cell::Cell, rc::Rc};
+
+/// Reads `navigator.onLine` and `navigator.connection` in the webview, and listens for changes to them.
+pub(crate) struct DesktopNetworkStatusProvider {
+    desktop_ctx: DesktopContext,
+    /// The status is only known once the webview reports it, so the last reported status is kept here
+    last: Rc<Cell<NetworkStatus>>,
+}
+
+impl DesktopNetworkStatusProvider {
+    pub(crate) fn new(desktop_ctx: DesktopContext) -> Self {
+        Self {
+            desktop_ctx,
+            last: Default::default(),
+        }
+    }
+}
+
+/// The status sent by `window.interpreter.observeNetwork`.
+#[derive(Deserialize)]
+struct WebviewNetworkStatus {
+    online: bool,
+    connection: String,
+}
+
+impl NetworkStatusProvider for DesktopNetworkStatusProvider {
+    fn status(&self) -> NetworkStatus {
+        self.last.get()
+    }
+
+    fn subscribe(&self, mut on_change: Box<dyn FnMut(NetworkStatus)>) -> Box<dyn Any> {
+        let last = self.last.clone();
+        Box::new(observe(
+            &self.desktop_ctx,
+            &self.desktop_ctx.query,
+            "observeNetwork",
+            "",
+            move |value| match WebviewNetworkStatus::deserialize(value) {
+                Ok(status) => {
+                    let status = NetworkStatus {
+                        online: status.online,
+                        connection: ConnectionType::from_web(&status.connection),
+                    };
+                    last.set(status);
+                    on_change(status);
+                }
+                Err(err) => tracing::error!("Failed to read the network status: {err}"),
+            },
+        ))
+    }
+}
@@ -0,0 +1,473 @@
+//! Local notifications that are shown right away or scheduled to be shown at a later time.
+//!
+//! Scheduled notifications are saved to `notifications.json` in the data directory so they survive a restart. On Linux they are also handed to the systemd user session with `systemd-run`, which shows them even while the app is closed. On other platforms, notifications that came due while the app was closed are shown the next time it launches.
+
+use crate::{
+    ipc::{EventData, UserWindowEvent},
+    DesktopContext,
+};
+use dioxus_html::notification::{
+    NotificationContent, NotificationEvent, NotificationEventKind, NotificationId,
+    NotificationProvider, ScheduleAt,
+};
+use serde::{Deserialize, Serialize};
+use slab::Slab;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    process::Command,
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tao::{event_loop::EventLoopProxy, window::WindowId};
+
+/// A notification that has been scheduled, but not delivered yet.
+#[derive(Serialize, Deserialize, Clone)]
+struct Scheduled {
+    id: NotificationId,
+    /// Milliseconds since the unix epoch
+    at: u64,
+    content: NotificationContent,
+    /// The OS shows this notification, even if the app is closed
+    #[serde(default)]
+    system: bool,
+}
+
+type NotificationHandler = Box<dyn FnMut(&NotificationEvent)>;
+
+/// The action that is reported when the body of a notification is clicked.
+const DEFAULT_ACTION: &str = "default";
+
+pub(crate) struct NotificationScheduler {
+    path: Option<PathBuf>,
+    scheduled: RefCell<Vec<Scheduled>>,
+    timers: Sender<TimerCommand>,
+    handlers: RefCell<Slab<NotificationHandler>>,
+    // Events that happened before any component listened for them
+    missed: RefCell<Vec<NotificationEvent>>,
+    /// The callbacks of notifications that were shown right away. Their events only go to their callback.
+    shown: RefCell<HashMap<NotificationId, Box<dyn FnMut(NotificationEvent)>>>,
+    last_id: Cell<u64>,
+}
+
+impl NotificationScheduler {
+    /// Load the notifications that were scheduled in a previous run and start their timers.
+    pub(crate) fn new(data_dir: Option<PathBuf>, proxy: EventLoopProxy<UserWindowEvent>) -> Self {
+        let path = data_dir
+            .or_else(default_data_dir)
+            .map(|dir| dir.join("notifications.json"));
+        let saved: Vec<Scheduled> = path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .unwrap_or_default();
+
+        let scheduler = Self {
+            path,
+            scheduled: Default::default(),
+            timers: spawn_timer_thread(proxy),
+            handlers: Default::default(),
+            missed: Default::default(),
+            shown: Default::default(),
+            last_id: Cell::new(saved.iter().map(|saved| saved.id.0).max().unwrap_or(0)),
+        };
+
+        let now = now_millis();
+        for notification in saved {
+            if notification.system && notification.at <= now {
+                // The OS already showed it while we were closed
+                scheduler.missed.borrow_mut().push(NotificationEvent {
+                    id: notification.id,
+                    kind: NotificationEventKind::Delivered,
+                    data: notification.content.data,
+                    action: None,
+                });
+            } else {
+                scheduler.start_timer(&notification);
+                scheduler.scheduled.borrow_mut().push(notification);
+            }
+        }
+        scheduler.save();
+
+        scheduler
+    }
+
+    pub(crate) fn schedule(&self, at: ScheduleAt, content: NotificationContent) -> NotificationId {
+        let id = self.next_id();
+
+        let mut notification = Scheduled {
+            id,
+            at: at.unix_millis(now_millis()),
+            content,
+            system: false,
+        };
+        notification.system = schedule_with_system(&notification);
+
+        self.start_timer(&notification);
+        self.scheduled.borrow_mut().push(notification);
+        self.save();
+
+        id
+    }
+
+    /// Show a notification right away and pass its events to `on_event` instead of the notification handlers.
+    pub(crate) fn show(
+        &self,
+        content: NotificationContent,
+        on_event: Box<dyn FnMut(NotificationEvent)>,
+    ) -> NotificationId {
+        let id = self.next_id();
+        self.shown.borrow_mut().insert(id, on_event);
+
+        let notification = Scheduled {
+            id,
+            at: now_millis(),
+            content,
+            system: false,
+        };
+        self.start_timer(&notification);
+
+        id
+    }
+
+    fn next_id(&self) -> NotificationId {
+        let id = NotificationId(now_millis().max(self.last_id.get() + 1));
+        self.last_id.set(id.0);
+        id
+    }
+
+    pub(crate) fn cancel(&self, id: NotificationId) {
+        let mut scheduled = self.scheduled.borrow_mut();
+        let Some(index) = scheduled
+            .iter()
+            .position(|notification| notification.id == id)
+        else {
+            return;
+        };
+        let notification = scheduled.remove(index);
+        drop(scheduled);
+
+        _ = self.timers.send(TimerCommand::Cancel(id));
+        if notification.system {
+            cancel_with_system(id);
+        }
+        self.save();
+    }
+
+    /// Add a handler for notification events. Events that happened before the first handler was added are passed to it right away.
+    pub(crate) fn add_handler(&self, mut handler: NotificationHandler) -> usize {
+        for event in self.missed.borrow_mut().drain(..) {
+            handler(&event);
+        }
+        self.handlers.borrow_mut().insert(handler)
+    }
+
+    pub(crate) fn remove_handler(&self, id: usize) {
+        self.handlers.borrow_mut().try_remove(id);
+    }
+
+    /// Handle an event sent by one of the timers.
+    pub(crate) fn emit(&self, event: NotificationEvent) {
+        let callback = self.shown.borrow_mut().remove(&event.id);
+        if let Some(mut callback) = callback {
+            // Nothing happens to a notification after it is clicked
+            let done = event.kind != NotificationEventKind::Delivered;
+            let id = event.id;
+            callback(event);
+            if !done {
+                self.shown.borrow_mut().insert(id, callback);
+            }
+            return;
+        }
+
+        if event.kind == NotificationEventKind::Delivered {
+            self.scheduled
+                .borrow_mut()
+                .retain(|notification| notification.id != event.id);
+            self.save();
+        }
+
+        let mut handlers = self.handlers.borrow_mut();
+        if handlers.is_empty() {
+            self.missed.borrow_mut().push(event);
+            return;
+        }
+        for (_, handler) in handlers.iter_mut() {
+            handler(&event);
+        }
+    }
+
+    /// Hand the notification to the timer thread, which shows it once it is due.
+    fn start_timer(&self, notification: &Scheduled) {
+        _ = self.timers.send(TimerCommand::Start(notification.clone()));
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = (|| {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let json = serde_json::to_vec(&*self.scheduled.borrow())?;
+            std::fs::write(path, json)
+        })();
+        if let Err(err) = result {
+            tracing::error!("Failed to save scheduled notifications to {path:?}: {err}");
+        }
+    }
+}
+
+enum TimerCommand {
+    Start(Scheduled),
+    Cancel(NotificationId),
+}
+
+/// Start the thread that waits for every scheduled notification. It sleeps until the earliest notification is due or a command arrives, so cancelled notifications are dropped right away.
+fn spawn_timer_thread(proxy: EventLoopProxy<UserWindowEvent>) -> Sender<TimerCommand> {
+    let (sender, commands) = mpsc::channel();
+    std::thread::spawn(move || {
+        // Ordered by when the notification is due, then by id so notifications due at the same time don't replace each other
+        let mut queue = BTreeMap::<(u64, NotificationId), Scheduled>::new();
+        loop {
+            let command = match queue.keys().next() {
+                Some((at, _)) => {
+                    commands.recv_timeout(Duration::from_millis(at.saturating_sub(now_millis())))
+                }
+                None => commands.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match command {
+                Ok(TimerCommand::Start(notification)) => {
+                    queue.insert((notification.at, notification.id), notification);
+                }
+                Ok(TimerCommand::Cancel(id)) => {
+                    queue.retain(|&(_, queued), _| queued != id);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                // The scheduler was dropped
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            let now = now_millis();
+            while let Some(&key) = queue.keys().next().filter(|(at, _)| *at <= now) {
+                if let Some(notification) = queue.remove(&key) {
+                    deliver(notification, proxy.clone());
+                }
+            }
+        }
+    });
+    sender
+}
+
+/// Show a notification that is due if the OS doesn't, and report what happened to the event loop.
+fn deliver(notification: Scheduled, proxy: EventLoopProxy<UserWindowEvent>) {
+    let (id, data) = (notification.id, notification.content.data.clone());
+    let send = move |kind, action| {
+        let event = NotificationEvent {
+            id,
+            kind,
+            data: data.clone(),
+            action,
+        };
+        // Notification events are not tied to a window
+        _ = proxy.send_event(UserWindowEvent(EventData::Notification(event), unsafe {
+            WindowId::dummy()
+        }));
+    };
+
+    if notification.system {
+        send(NotificationEventKind::Delivered, None);
+        return;
+    }
+
+    // Showing the notification blocks until it is closed, so it can't hold up the timers of the other notifications
+    std::thread::spawn(move || {
+        let action = show(&notification.content, || {
+            send(NotificationEventKind::Delivered, None)
+        });
+        match action {
+            Some(action) if action == DEFAULT_ACTION => send(NotificationEventKind::Clicked, None),
+            Some(action) => send(NotificationEventKind::Action, Some(action)),
+            None => {}
+        }
+    });
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64)
+}
+
+/// The directory apps store their data in by default, with a folder named after the executable.
+fn default_data_dir() -> Option<PathBuf> {
+    let name = std::env::current_exe().ok()?.file_stem()?.to_owned();
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".local/share")))
+    };
+    Some(base?.join(name))
+}
+
+fn app_name() -> String {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.file_stem()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "dioxus".to_string())
+}
+
+fn notify_send_args(content: &NotificationContent) -> Vec<String> {
+    let mut args = vec![format!("--app-name={}", app_name())];
+    if let Some(icon) = &content.icon {
+        args.push(format!("--icon={icon}"));
+    }
+    args.push(content.title.clone());
+    args.extend(content.body.clone());
+    args
+}
+
+/// Hand the notification to a scheduler in the OS so it is shown even if the app is closed. Returns false if the platform has no scheduler we can use.
+fn schedule_with_system(notification: &Scheduled) -> bool {
+    if cfg!(target_os = "linux") {
+        let delay = notification.at.saturating_sub(now_millis()) / 1000;
+        return Command::new("systemd-run")
+            .args(["--user", "--quiet", "--timer-property=AccuracySec=1s"])
+            .arg(format!("--unit={}", unit_name(notification.id)))
+            .arg(format!("--on-active={}s", delay.max(1)))
+            .arg("notify-send")
+            .args(notify_send_args(&notification.content))
+            .status()
+            .map_or(false, |status| status.success());
+    }
+
+    false
+}
+
+fn cancel_with_system(id: NotificationId) {
+    if cfg!(target_os = "linux") {
+        _ = Command::new("systemctl")
+            .args(["--user", "stop"])
+            .arg(format!("{}.timer", unit_name(id)))
+            .status();
+    }
+}
+
+fn unit_name(id: NotificationId) -> String {
+    format!("{}-notification-{}", app_name(), id.0)
+}
+
+/// Show a notification right away and call `delivered` once it is visible. Blocks until the notification is closed where the platform reports it, and returns the action the user clicked, [`DEFAULT_ACTION`] for the notification itself.
+///
+/// Action buttons are only shown on Linux.
+fn show(content: &NotificationContent, delivered: impl FnOnce()) -> Option<String> {
+    #[cfg(target_os = "macos")]
+    {
+        fn quote(text: &str) -> String {
+            format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+
+        let script = format!(
+            "display notification {} with title {}",
+            quote(content.body.as_deref().unwrap_or_default()),
+            quote(&content.title)
+        );
+        let shown = Command::new("osascript").arg("-e").arg(script).status();
+        if shown.map_or(false, |status| status.success()) {
+            delivered();
+        }
+        None
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        fn escape(text: &str) -> String {
+            text.replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;")
+                .replace('\'', "''")
+        }
+
+        let script = format!(
+            "[Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime] | Out-Null; \
+             $xml = [Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime]::new(); \
+             $xml.LoadXml('<toast><visual><binding template=\"ToastGeneric\"><text>{}</text><text>{}</text></binding></visual></toast>'); \
+             [Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{}').Show([Windows.UI.Notifications.ToastNotification]::new($xml))",
+            escape(&content.title),
+            escape(content.body.as_deref().unwrap_or_default()),
+            escape(&app_name()),
+        );
+        let shown = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .status();
+        if shown.map_or(false, |status| status.success()) {
+            delivered();
+        }
+        None
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        // Newer versions of notify-send can wait for the notification to be clicked
+        let actions = content
+            .actions
+            .iter()
+            .map(|action| format!("--action={}={}", action.id, action.label));
+        let child = Command::new("notify-send")
+            .arg("--wait")
+            .arg(format!("--action={DEFAULT_ACTION}=Open"))
+            .args(actions)
+            .args(notify_send_args(content))
+            .stdout(std::process::Stdio::piped())
+            .spawn();
+        if let Ok(child) = child {
+            delivered();
+            return match child.wait_with_output() {
+                Ok(output) if output.status.success() => {
+                    let action = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                    (!action.is_empty()).then_some(action)
+                }
+                // Older versions don't know the flags, so show it without click reporting
+                _ => {
+                    _ = Command::new("notify-send")
+                        .args(notify_send_args(content))
+                        .status();
+                    None
+                }
+            };
+        }
+        tracing::warn!(
+            "Failed to show notification {:?}: notify-send is not installed",
+            content.title
+        );
+        None
+    }
+}
+
+/// Shows the notifications of [`use_notification`](dioxus_html::prelude::use_notification).
+pub(crate) struct DesktopNotificationProvider {
+    desktop: DesktopContext,
+}
+
+impl DesktopNotificationProvider {
+    pub(crate) fn new(desktop: DesktopContext) -> Self {
+        Self { desktop }
+    }
+}
+
+impl NotificationProvider for DesktopNotificationProvider {
+    fn show(
+        &self,
+        content: NotificationContent,
+        on_event: Box<dyn FnMut(NotificationEvent)>,
+    ) -> NotificationId {
+        self.desktop.shared.notifications.show(content, on_event)
+    }
+}
@@ -0,0 +1,148 @@
+//! Open urls and files with the default app of the system, with [`open_external`].
+
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// An error from [`open_external`] or [`OpenExternal::open`].
+#[derive(Debug, Error)]
+pub enum OpenExternalError {
+    /// The target is empty or contains control characters.
+    #[error("The target is not a valid url or path")]
+    InvalidTarget,
+
+    /// The scheme of the url is not allowed.
+    #[error("Opening {0} urls is not allowed")]
+    SchemeNotAllowed(String),
+
+    /// The target is a path or a `file:` url, and [`OpenExternal::allow_paths`] is not set.
+    #[error("Opening paths is not allowed")]
+    PathNotAllowed,
+
+    /// The default app could not be started.
+    #[error("Failed to open the target: {0}")]
+    Failed(#[from] std::io::Error),
+}
+
+/// Open `target` with the default app of the system, like a link in the browser or a `mailto:` link in the mail app.
+///
+/// Only `http`, `https` and `mailto` urls are opened, so a link from user content can't run a `javascript:` url or open a local file. Use [`OpenExternal`] to allow other schemes or paths.
+///
+/// The app is started on a background thread, so this doesn't block the window.
+///
+/// ```rust, ignore
+/// button {
+///     onclick: move |_| async move {
+///         if let Err(err) = open_external("https://dioxuslabs.com").await {
+///             tracing::error!("{err}");
+///         }
+///     },
+///     "Open the website"
+/// }
+/// ```
+pub async fn open_external(target: impl AsRef<str>) -> Result<(), OpenExternalError> {
+    OpenExternal::new().open(target).await
+}
+
+/// Which targets [`OpenExternal::open`] opens. By default these are `http`, `https` and `mailto` urls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenExternal {
+    schemes: Vec<String>,
+    allow_paths: bool,
+}
+
+impl Default for OpenExternal {
+    fn default() -> Self {
+        Self {
+            schemes: vec!["http".into(), "https".into(), "mailto".into()],
+            allow_paths: false,
+        }
+    }
+}
+
+impl OpenExternal {
+    /// Allow `http`, `https` and `mailto` urls.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also allow urls with `scheme`, like `tel` or the scheme of another app.
+    pub fn allow_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.schemes.push(scheme.into().to_ascii_lowercase());
+        self
+    }
+
+    /// Also allow paths and `file:` urls. Only allow this for paths the app chose itself, opening a file from user content could start a program.
+    pub fn allow_paths(mut self, allow: bool) -> Self {
+        self.allow_paths = allow;
+        self
+    }
+
+    /// Open `target` with the default app of the system if it is allowed.
+    pub async fn open(&self, target: impl AsRef<str>) -> Result<(), OpenExternalError> {
+        let target = self.check(target.as_ref())?;
+
+        let (tx, rx) = futures_channel::oneshot::channel();
+        std::thread::spawn(move || {
+            _ = tx.send(open::that(target));
+        });
+        rx.await
+            .unwrap_or_else(|_| Err(std::io::ErrorKind::Interrupted.into()))?;
+        Ok(())
+    }
+
+    /// Get what to open for `target`, or why it isn't allowed.
+    fn check(&self, target: &str) -> Result<String, OpenExternalError> {
+        let target = target.trim();
+        if target.is_empty() || target.chars().any(char::is_control) {
+            return Err(OpenExternalError::InvalidTarget);
+        }
+
+        match scheme(target) {
+            Some(scheme) if scheme == "file" => match self.allow_paths {
+                true => Ok(target.to_string()),
+                false => Err(OpenExternalError::PathNotAllowed),
+            },
+            Some(scheme) => match self.schemes.contains(&scheme) {
+                true => Ok(target.to_string()),
+                false => Err(OpenExternalError::SchemeNotAllowed(scheme)),
+            },
+            None if self.allow_paths => {
+                // The default app runs in another directory, so relative paths have to be resolved here
+                let path = PathBuf::from(target);
+                let path = match std::env::current_dir() {
+                    Ok(dir) => dir.join(path),
+                    Err(_) => path,
+                };
+                Ok(path.to_string_lossy().into_owned())
+            }
+            None => Err(OpenExternalError::PathNotAllowed),
+        }
+    }
+}
+
+/// Open a link the user clicked in the page, if it is allowed by default.
+pub(crate) fn open_link(href: &str) {
+    let opener = OpenExternal::new();
+    let href = match opener.check(href) {
+        Ok(href) => href,
+        Err(err) => {
+            tracing::warn!("Not opening the link {href}: {err}");
+            return;
+        }
+    };
+    std::thread::spawn(move || {
+        if let Err(err) = open::that(&href) {
+            tracing::error!("Failed to open the link {href}: {err}");
+        }
+    });
+}
+
+/// Get the lowercase scheme of a url, or `None` if `target` is a path.
+fn scheme(target: &str) -> Option<String> {
+    let (scheme, _) = target.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next()?.is_ascii_alphabetic()
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    // A single letter is the drive of a windows path, like C:\
+    (valid && scheme.len() > 1).then(|| scheme.to_ascii_lowercase())
+}
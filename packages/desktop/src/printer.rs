@@ -0,0 +1,222 @@
+//! Print the content of a window with the native print dialog, or save it as a PDF.
+
+use crate::{element::DesktopElement, window, DesktopContext};
+use dioxus_core::{prelude::spawn_forever, ElementId, ScopeState};
+use dioxus_html::prelude::MountedData;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// An error from [`UsePrinter`].
+#[derive(Debug, Error)]
+pub enum PrintError {
+    /// The element is not mounted in this window.
+    #[error("The element is not mounted in this window")]
+    ElementNotFound,
+
+    /// The webview on this platform can't print to a PDF. Use [`UsePrinter::print`] and let the user save the PDF from the print dialog instead.
+    #[error("Printing to a PDF is not supported on this platform")]
+    Unsupported,
+
+    /// The webview failed to print.
+    #[error("Failed to print: {0}")]
+    Failed(String),
+}
+
+/// Get a handle to print the current window.
+///
+/// ```rust, ignore
+/// let printer = use_printer(cx);
+/// let invoice = use_signal(cx, || None);
+///
+/// render! {
+///     div { onmounted: move |event| invoice.set(Some(event.inner().clone())),
+///         "..."
+///     }
+///     button { onclick: move |_| printer.print(), "Print the page" }
+///     button {
+///         onclick: move |_| {
+///             if let Some(invoice) = invoice() {
+///                 cx.spawn(async move {
+///                     _ = printer.print_to_pdf(Some(&invoice), "invoice.pdf").await;
+///                 });
+///             }
+///         },
+///         "Save the invoice"
+///     }
+/// }
+/// ```
+pub fn use_printer(cx: &ScopeState) -> &UsePrinter {
+    cx.use_hook(|| UsePrinter { desktop: window() })
+}
+
+/// A handle to print the window it was created in. Created with [`use_printer`].
+#[derive(Clone)]
+pub struct UsePrinter {
+    desktop: DesktopContext,
+}
+
+impl UsePrinter {
+    /// Open the native print dialog for the whole page, like `window.print()` in a browser.
+    ///
+    /// This must be called from inside the dioxus runtime, like in an event handler.
+    pub fn print(&self) {
+        let desktop = self.desktop.clone();
+        spawn_forever(async move {
+            if set_print_target(&desktop, None).await.is_ok() {
+                desktop.print();
+            }
+        });
+    }
+
+    /// Open the native print dialog with only `element` and its children on the printed pages.
+    pub async fn print_element(&self, element: &MountedData) -> Result<(), PrintError> {
+        let id = element_id(element)?;
+        set_print_target(&self.desktop, Some(id)).await?;
+        self.desktop
+            .webview
+            .print()
+            .map_err(|err| PrintError::Failed(err.to_string()))
+    }
+
+    /// Save the page, or only `element` and its children, as a PDF at `path` without showing a dialog.
+    ///
+    /// This is supported on Windows and Linux. Other platforms return [`PrintError::Unsupported`].
+    pub async fn print_to_pdf(
+        &self,
+        element: Option<&MountedData>,
+        path: impl Into<PathBuf>,
+    ) -> Result<(), PrintError> {
+        let path = path.into();
+        let path = match std::env::current_dir() {
+            Ok(dir) => dir.join(path),
+            Err(_) => path,
+        };
+        let id = element.map(element_id).transpose()?;
+        set_print_target(&self.desktop, id).await?;
+        print_to_pdf(&self.desktop.webview, &path)
+            .await
+            .unwrap_or_else(|_| Err(PrintError::Failed("The print was cancelled".into())))
+    }
+}
+
+fn element_id(element: &MountedData) -> Result<ElementId, PrintError> {
+    element
+        .downcast::<DesktopElement>()
+        .map(DesktopElement::id)
+        .ok_or(PrintError::ElementNotFound)
+}
+
+/// Hide everything but the element with `id` on printed pages, or show the whole page again if it is `None`.
+async fn set_print_target(
+    desktop: &DesktopContext,
+    id: Option<ElementId>,
+) -> Result<(), PrintError> {
+    let id = match id {
+        Some(id) => id.0.to_string(),
+        None => "null".to_string(),
+    };
+    let script = format!("return window.interpreter.setPrintTarget({id});");
+    match desktop
+        .query
+        .new_query::<bool>(&script, desktop.clone())
+        .resolve()
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(PrintError::ElementNotFound),
+        Err(err) => Err(PrintError::Failed(err.to_string())),
+    }
+}
+
+type PrintResult = futures_channel::oneshot::Receiver<Result<(), PrintError>>;
+
+#[cfg(target_os = "windows")]
+fn print_to_pdf(webview: &wry::WebView, path: &Path) -> PrintResult {
+    use webview2_com::{
+        Microsoft::Web::WebView2::Win32::{ICoreWebView2PrintSettings, ICoreWebView2_7},
+        PrintToPdfCompletedHandler,
+    };
+    use windows::core::{ComInterface, HSTRING};
+    use wry::WebViewExtWindows;
+
+    let (tx, rx) = futures_channel::oneshot::channel();
+    let webview = match unsafe { webview.controller().CoreWebView2() }
+        .and_then(|webview| webview.cast::<ICoreWebView2_7>())
+    {
+        Ok(webview) => webview,
+        Err(_) => {
+            _ = tx.send(Err(PrintError::Unsupported));
+            return rx;
+        }
+    };
+
+    let handler = PrintToPdfCompletedHandler::create(Box::new(move |result, success| {
+        _ = tx.send(match result {
+            Ok(()) if success.as_bool() => Ok(()),
+            Ok(()) => Err(PrintError::Failed("The PDF could not be written".into())),
+            Err(err) => Err(PrintError::Failed(err.to_string())),
+        });
+        Ok(())
+    }));
+    let path = HSTRING::from(path.as_os_str());
+    if let Err(err) =
+        unsafe { webview.PrintToPdf(&path, None::<&ICoreWebView2PrintSettings>, &handler) }
+    {
+        tracing::error!("Failed to print to a PDF: {err}");
+    }
+    rx
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn print_to_pdf(webview: &wry::WebView, path: &Path) -> PrintResult {
+    use std::{cell::RefCell, rc::Rc};
+    use webkit2gtk::{PrintOperation, PrintOperationExt};
+    use wry::WebViewExtUnix;
+
+    let (tx, rx) = futures_channel::oneshot::channel();
+    let tx = Rc::new(RefCell::new(Some(tx)));
+
+    let settings = gtk::PrintSettings::new();
+    settings.set_printer(Some("Print to File"));
+    settings.set("output-file-format", Some("pdf"));
+    let uri = format!("file://{}", path.to_string_lossy());
+    settings.set("output-uri", Some(uri.as_str()));
+
+    let operation = PrintOperation::new(&webview.webview());
+    operation.set_print_settings(&settings);
+    operation.connect_failed({
+        let tx = tx.clone();
+        move |_, err| {
+            if let Some(tx) = tx.borrow_mut().take() {
+                _ = tx.send(Err(PrintError::Failed(err.to_string())));
+            }
+        }
+    });
+    operation.connect_finished(move |_| {
+        if let Some(tx) = tx.borrow_mut().take() {
+            _ = tx.send(Ok(()));
+        }
+    });
+    operation.print();
+    rx
+}
+
+#[cfg(not(any(
+    target_os = "windows",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+)))]
+fn print_to_pdf(_webview: &wry::WebView, _path: &Path) -> PrintResult {
+    let (tx, rx) = futures_channel::oneshot::channel();
+    _ = tx.send(Err(PrintError::Unsupported));
+    rx
+}
@@ -102,13 +102,9 @@ pub(super) fn desktop_handler(
 }
 
 fn serve_from_fs(path: PathBuf) -> Result<Response<Vec<u8>>> {
-    // If the path is relative, we'll try to serve it from the assets directory.
-    let mut asset = get_asset_root_or_default().join(&path);
-
+    // If the path is relative, we'll try to serve it from the assets directories.
     // If we can't find it, make it absolute and try again
-    if !asset.exists() {
-        asset = PathBuf::from("/").join(path);
-    }
+    let asset = resolve_asset(&path).unwrap_or_else(|| PathBuf::from("/").join(path));
 
     if !asset.exists() {
         return Ok(Response::builder()
@@ -167,21 +163,33 @@ fn module_loader(root_id: &str, headless: bool) -> String {
     )
 }
 
-/// Get the asset directory, following tauri/cargo-bundles directory discovery approach
-///
-/// Defaults to the current directory if no asset directory is found, which is useful for development when the app
-/// isn't bundled.
-fn get_asset_root_or_default() -> PathBuf {
-    get_asset_root().unwrap_or_else(|| Path::new(".").to_path_buf())
+/// Find `path` in the directories assets are served from. The first directory that contains it wins:
+/// - the resources of the bundled app
+/// - the asset directory of `Dioxus.toml` if the app was built by the dioxus CLI
+/// - the crate directory if the app runs with `cargo run`
+/// - the current directory
+pub(crate) fn resolve_asset(path: &Path) -> Option<PathBuf> {
+    let crate_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    let cli_asset_dir = dioxus_cli_config::CURRENT_CONFIG_JSON
+        .and_then(|_| dioxus_cli_config::CURRENT_CONFIG.as_ref().ok())
+        .map(|config| config.asset_dir());
+
+    get_asset_root()
+        .into_iter()
+        .chain(cli_asset_dir)
+        .chain(crate_dir)
+        .chain(std::iter::once(PathBuf::from(".")))
+        .map(|root| root.join(path))
+        .find(|asset| asset.exists())
 }
 
 /// Get the asset directory, following tauri/cargo-bundles directory discovery approach
 ///
 /// Currently supports:
 /// - [x] macOS
-/// - [ ] Windows
+/// - [x] Windows
 /// - [ ] Linux (rpm)
-/// - [ ] Linux (deb)
+/// - [x] Linux (deb)
 /// - [ ] iOS
 /// - [ ] Android
 #[allow(unreachable_code)]
@@ -201,6 +209,22 @@ fn get_asset_root() -> Option<PathBuf> {
         return dunce::canonicalize(absolute_resources_root).ok();
     }
 
+    // The installer puts the resources next to the executable
+    #[cfg(target_os = "windows")]
+    {
+        let exe = std::env::current_exe().ok()?;
+        return exe.parent().map(Path::to_path_buf);
+    }
+
+    // Debian packages install the executable to /usr/bin and the resources to /usr/lib/<name>
+    #[cfg(target_os = "linux")]
+    {
+        let exe = std::env::current_exe().ok()?;
+        let name = exe.file_name()?;
+        let resources = exe.parent()?.parent()?.join("lib").join(name);
+        return resources.is_dir().then_some(resources);
+    }
+
     None
 }
 
@@ -0,0 +1,89 @@
+//! Handlers that run before a window closes or the app quits.
+
+use crate::ipc::{EventData, UserWindowEvent};
+use dioxus_core::prelude::{spawn_at, Runtime, RuntimeGuard, ScopeId};
+use futures_util::future::join_all;
+use slab::Slab;
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
+use tao::{event_loop::EventLoopProxy, window::WindowId};
+
+type BeforeQuitHandler = Box<dyn FnMut() -> Pin<Box<dyn Future<Output = bool>>>>;
+
+/// The quit handlers of one window.
+#[derive(Default)]
+pub(crate) struct QuitHandlers {
+    before_quit: RefCell<Slab<(ScopeId, BeforeQuitHandler)>>,
+    will_quit: RefCell<Slab<(ScopeId, Box<dyn FnMut()>)>>,
+    // Set while the before quit handlers decide, so closing the window again doesn't ask twice
+    deciding: Rc<Cell<bool>>,
+}
+
+impl QuitHandlers {
+    pub(crate) fn add_before_quit(&self, scope: ScopeId, handler: BeforeQuitHandler) -> usize {
+        self.before_quit.borrow_mut().insert((scope, handler))
+    }
+
+    pub(crate) fn remove_before_quit(&self, id: usize) {
+        self.before_quit.borrow_mut().try_remove(id);
+    }
+
+    pub(crate) fn add_will_quit(&self, scope: ScopeId, handler: Box<dyn FnMut()>) -> usize {
+        self.will_quit.borrow_mut().insert((scope, handler))
+    }
+
+    pub(crate) fn remove_will_quit(&self, id: usize) {
+        self.will_quit.borrow_mut().try_remove(id);
+    }
+
+    /// Ask the before quit handlers if the window may close.
+    ///
+    /// Returns true if there are no handlers and the window can close right away. Otherwise the handlers run as tasks in the virtual dom, and a [`EventData::ConfirmClose`] event is sent once every handler allowed the window to close.
+    pub(crate) fn request_close(
+        &self,
+        runtime: Rc<Runtime>,
+        proxy: EventLoopProxy<UserWindowEvent>,
+        window: WindowId,
+    ) -> bool {
+        let mut handlers = self.before_quit.borrow_mut();
+        if handlers.is_empty() {
+            return true;
+        }
+        if self.deciding.replace(true) {
+            return false;
+        }
+
+        let decisions: Vec<_> = handlers
+            .iter_mut()
+            .map(|(_, (scope, handler))| RuntimeGuard::with(runtime.clone(), Some(*scope), handler))
+            .collect();
+
+        // The task lives in the root scope so it isn't canceled if the component that asked is dropped
+        let deciding = self.deciding.clone();
+        RuntimeGuard::with(runtime, None, || {
+            spawn_at(
+                async move {
+                    let allowed = join_all(decisions).await.into_iter().all(|allow| allow);
+                    deciding.set(false);
+                    if allowed {
+                        _ = proxy.send_event(UserWindowEvent(EventData::ConfirmClose, window));
+                    }
+                },
+                ScopeId::ROOT,
+            )
+        });
+
+        false
+    }
+
+    /// Call the will quit handlers right before the app exits.
+    pub(crate) fn will_quit(&self, runtime: Rc<Runtime>) {
+        for (_, (scope, handler)) in self.will_quit.borrow_mut().iter_mut() {
+            RuntimeGuard::with(runtime.clone(), Some(*scope), handler);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    ipc::{EventData, UserWindowEvent},
+    window, DesktopContext,
+};
+use dioxus_core::{
+    prelude::{consume_context, current_scope_id, ScopeId},
+    ScopeState,
+};
+use std::{
+    collections::HashSet,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+use tao::{event_loop::EventLoopProxy, window::WindowId};
+
+/// A signal that can be read and written from every window of the app, and from other threads.
+///
+/// Signals from [`dioxus_signals`] belong to the [`VirtualDom`](dioxus_core::VirtualDom) of one window, so they can't be passed to [`new_window`](crate::new_window). A shared signal can: components of any window that read it render again when it is written. Clone it to pass it around.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let count = use_shared_signal(cx, || 0);
+///
+///     render! {
+///         button {
+///             onclick: move |_| {
+///                 new_window_with_props(counter_window, count.clone(), Config::new());
+///             },
+///             "Open a second window"
+///         }
+///         "{count.read()}"
+///     }
+/// }
+///
+/// fn counter_window(cx: Scope<SharedSignal<i32>>) -> Element {
+///     let count = cx.props;
+///     render! {
+///         button { onclick: move |_| *count.write() += 1, "{count.read()}" }
+///     }
+/// }
+/// ```
+pub struct SharedSignal<T: 'static> {
+    inner: Arc<SharedSignalInner<T>>,
+}
+
+struct SharedSignalInner<T> {
+    value: RwLock<T>,
+    /// The components that read the value since it was last written
+    subscribers: Mutex<HashSet<(WindowId, ScopeId)>>,
+    proxy: Mutex<EventLoopProxy<UserWindowEvent>>,
+}
+
+impl<T: 'static> Clone for SharedSignal<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> SharedSignal<T> {
+    /// Create a new shared signal.
+    ///
+    /// ## Panics
+    ///
+    /// This function will panic if it is called outside of the context of a Dioxus App.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(SharedSignalInner {
+                value: RwLock::new(value),
+                subscribers: Default::default(),
+                proxy: Mutex::new(window().shared.proxy.clone()),
+            }),
+        }
+    }
+
+    /// Read the value, and render the current component again when it changes.
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        if let Some(scope) = current_scope_id() {
+            if let Some(desktop) = consume_context::<DesktopContext>() {
+                self.lock_subscribers().insert((desktop.window.id(), scope));
+            }
+        }
+        self.peek()
+    }
+
+    /// Read the value without subscribing to it.
+    pub fn peek(&self) -> RwLockReadGuard<'_, T> {
+        self.inner
+            .value
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Write the value. Every component that read it renders again once the guard is dropped.
+    pub fn write(&self) -> SharedSignalWrite<'_, T> {
+        SharedSignalWrite {
+            guard: self
+                .inner
+                .value
+                .write()
+                .unwrap_or_else(PoisonError::into_inner),
+            signal: self,
+        }
+    }
+
+    /// Replace the value.
+    pub fn set(&self, value: T) {
+        *self.write() = value;
+    }
+
+    /// Run a function with a mutable reference to the value.
+    pub fn with_mut<O>(&self, f: impl FnOnce(&mut T) -> O) -> O {
+        f(&mut self.write())
+    }
+
+    /// Get a clone of the value, and render the current component again when it changes.
+    pub fn value(&self) -> T
+    where
+        T: Clone,
+    {
+        self.read().clone()
+    }
+
+    fn lock_subscribers(&self) -> std::sync::MutexGuard<'_, HashSet<(WindowId, ScopeId)>> {
+        self.inner
+            .subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Tell every window to render the components that read the value. They subscribe again when they read it while rendering.
+    fn notify(&self) {
+        let subscribers = std::mem::take(&mut *self.lock_subscribers());
+        let proxy = self
+            .inner
+            .proxy
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for (window, scope) in subscribers {
+            // Windows that were closed since they read the value ignore the event
+            _ = proxy.send_event(UserWindowEvent(EventData::MarkDirty(scope), window));
+        }
+    }
+}
+
+/// A mutable reference to the value of a [`SharedSignal`]. The readers of the signal render again when it is dropped.
+pub struct SharedSignalWrite<'a, T: Send + Sync + 'static> {
+    guard: RwLockWriteGuard<'a, T>,
+    signal: &'a SharedSignal<T>,
+}
+
+impl<T: Send + Sync + 'static> Deref for SharedSignalWrite<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T: Send + Sync + 'static> DerefMut for SharedSignalWrite<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T: Send + Sync + 'static> Drop for SharedSignalWrite<'_, T> {
+    fn drop(&mut self) {
+        self.signal.notify();
+    }
+}
+
+/// Create a [`SharedSignal`] that can be passed to other windows. `init` only runs on the first render.
+pub fn use_shared_signal<T: Send + Sync + 'static>(
+    cx: &ScopeState,
+    init: impl FnOnce() -> T,
+) -> &SharedSignal<T> {
+    cx.use_hook(|| SharedSignal::new(init()))
+}
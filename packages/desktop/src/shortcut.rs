@@ -2,9 +2,9 @@ use std::{cell::RefCell, collections::HashMap, str::FromStr};
 
 use dioxus_html::input_data::keyboard_types::Modifiers;
 use slab::Slab;
-use tao::keyboard::ModifiersState;
+use tao::{keyboard::ModifiersState, window::WindowId};
 
-use crate::desktop_context::DesktopContext;
+use crate::{desktop_context::DesktopContext, webview_listener::WindowListener};
 
 #[cfg(any(
     target_os = "windows",
@@ -25,18 +25,91 @@ pub use crate::mobile_shortcut::*;
 
 pub(crate) struct ShortcutRegistry {
     manager: GlobalHotKeyManager,
-    shortcuts: RefCell<HashMap<u32, Shortcut>>,
+    shortcuts: RefCell<Shortcuts>,
+    /// The keydown listener of every window that has window shortcuts. It is removed with the last window shortcut of the window.
+    window_listeners: RefCell<HashMap<WindowId, WindowListener>>,
+}
+
+/// Only send key presses that match a window shortcut of the webview to rust. The accelerators are kept up to date by [`ShortcutRegistry::sync_window`].
+const WINDOW_SHORTCUT_FILTER: &str = r#"
+    if (event.repeat) {
+        return;
+    }
+    const target = event.target;
+    const editable = target instanceof HTMLElement &&
+        (target.isContentEditable || ["INPUT", "TEXTAREA", "SELECT"].includes(target.tagName));
+    const matches = (window.__dioxus_window_shortcuts || []).some((shortcut) =>
+        shortcut.code === event.code &&
+        shortcut.alt === event.altKey &&
+        shortcut.ctrl === event.ctrlKey &&
+        shortcut.meta === event.metaKey &&
+        shortcut.shift === event.shiftKey &&
+        (shortcut.in_editable || !editable));
+    if (!matches) {
+        return;
+    }
+    return {
+        code: event.code,
+        alt_key: event.altKey,
+        ctrl_key: event.ctrlKey,
+        meta_key: event.metaKey,
+        shift_key: event.shiftKey,
+        editable,
+    };
+"#;
+
+#[derive(serde::Deserialize)]
+struct WindowKeydown {
+    code: String,
+    alt_key: bool,
+    ctrl_key: bool,
+    meta_key: bool,
+    shift_key: bool,
+    editable: bool,
+}
+
+/// The callbacks are stored in slots that never move, so [`ShortcutId`]s stay valid when a shortcut is rebound to a different accelerator.
+#[derive(Default)]
+struct Shortcuts {
+    slots: Slab<Shortcut>,
+    /// The slot of each accelerator. Global shortcuts have no window.
+    by_accelerator: HashMap<(Option<WindowId>, u32), usize>,
+}
+
+impl Shortcuts {
+    fn get_mut(&mut self, key: (Option<WindowId>, u32)) -> Option<&mut Shortcut> {
+        let slot = *self.by_accelerator.get(&key)?;
+        self.slots.get_mut(slot)
+    }
 }
 
 struct Shortcut {
-    #[allow(unused)]
     shortcut: HotKey,
-    callbacks: Slab<Box<dyn FnMut()>>,
+    /// The window the shortcut is scoped to. Shortcuts without a window are global.
+    window: Option<WindowId>,
+    callbacks: Slab<ShortcutCallback>,
+    /// Whether the accelerator is registered with the OS. It is unregistered while every callback is disabled, so other apps can use it.
+    registered: bool,
+}
+
+struct ShortcutCallback {
+    callback: Box<dyn FnMut()>,
+    enabled: bool,
+    /// Whether a window shortcut is also called for keys typed into an input, textarea or contenteditable element.
+    in_editable: bool,
 }
 
 impl Shortcut {
+    fn key(&self) -> (Option<WindowId>, u32) {
+        (self.window, self.shortcut.id())
+    }
+
     fn insert(&mut self, callback: Box<dyn FnMut()>) -> usize {
-        self.callbacks.insert(callback)
+        self.callbacks.insert(ShortcutCallback {
+            callback,
+            enabled: true,
+            in_editable: false,
+        })
     }
 
     fn remove(&mut self, id: usize) {
@@ -46,20 +119,36 @@ impl Shortcut {
     fn is_empty(&self) -> bool {
         self.callbacks.is_empty()
     }
+
+    fn any_enabled(&self) -> bool {
+        self.callbacks.iter().any(|(_, callback)| callback.enabled)
+    }
 }
 
 impl ShortcutRegistry {
     pub fn new() -> Self {
         Self {
             manager: GlobalHotKeyManager::new().unwrap(),
-            shortcuts: RefCell::new(HashMap::new()),
+            shortcuts: RefCell::new(Shortcuts::default()),
+            window_listeners: RefCell::new(HashMap::new()),
         }
     }
 
     pub(crate) fn call_handlers(&self, id: GlobalHotKeyEvent) {
-        if let Some(Shortcut { callbacks, .. }) = self.shortcuts.borrow_mut().get_mut(&id.id) {
+        self.dispatch((None, id.id), false);
+    }
+
+    /// Call the handlers of the window shortcuts for `hotkey` after it was pressed in `window`. Key presses in editable elements only call the handlers that opted in.
+    pub(crate) fn call_window_handlers(&self, window: WindowId, hotkey: HotKey, editable: bool) {
+        self.dispatch((Some(window), hotkey.id()), editable);
+    }
+
+    fn dispatch(&self, key: (Option<WindowId>, u32), editable: bool) {
+        if let Some(Shortcut { callbacks, .. }) = self.shortcuts.borrow_mut().get_mut(key) {
             for (_, callback) in callbacks.iter_mut() {
-                (callback)();
+                if callback.enabled && (callback.in_editable || !editable) {
+                    (callback.callback)();
+                }
             }
         }
     }
@@ -69,55 +158,330 @@ impl ShortcutRegistry {
         hotkey: HotKey,
         callback: Box<dyn FnMut()>,
     ) -> Result<ShortcutId, ShortcutRegistryError> {
-        let accelerator_id = hotkey.clone().id();
+        self.add(None, hotkey, callback)
+    }
+
+    /// Add a shortcut that is only called while the window of `desktop` is focused. Window shortcuts listen to key presses in the webview instead of registering an accelerator with the OS.
+    ///
+    /// The webview only starts listening with the first window shortcut of the window.
+    pub(crate) fn add_window_shortcut(
+        &self,
+        desktop: &DesktopContext,
+        hotkey: HotKey,
+        callback: Box<dyn FnMut()>,
+    ) -> Result<ShortcutId, ShortcutRegistryError> {
+        let window = desktop.window.id();
+        let id = self.add(Some(window), hotkey, callback)?;
+
+        self.window_listeners
+            .borrow_mut()
+            .entry(window)
+            .or_insert_with(|| {
+                let shared = desktop.shared.clone();
+                WindowListener::new(
+                    desktop.clone(),
+                    "keydown",
+                    WINDOW_SHORTCUT_FILTER,
+                    move |value| {
+                        let Ok(keydown) = serde_json::from_value::<WindowKeydown>(value) else {
+                            return;
+                        };
+                        // Keys the webview does not know about can't be part of a shortcut
+                        let Ok(code) = keydown.code.parse() else {
+                            return;
+                        };
+
+                        let mut modifiers = Modifiers::empty();
+                        modifiers.set(Modifiers::ALT, keydown.alt_key);
+                        modifiers.set(Modifiers::CONTROL, keydown.ctrl_key);
+                        modifiers.set(Modifiers::META, keydown.meta_key);
+                        modifiers.set(Modifiers::SHIFT, keydown.shift_key);
+
+                        shared.shortcut_manager.call_window_handlers(
+                            window,
+                            HotKey::new(Some(modifiers), code),
+                            keydown.editable,
+                        );
+                    },
+                )
+            });
+        self.sync_window(Some(window));
+
+        Ok(id)
+    }
+
+    /// Send the accelerators of the enabled window shortcuts of `window` to its webview, or stop listening to its key presses if it has no window shortcuts left.
+    fn sync_window(&self, window: Option<WindowId>) {
+        let Some(window) = window else {
+            return;
+        };
+
+        let accelerators: Option<Vec<_>> = {
+            let shortcuts = self.shortcuts.borrow();
+            let mut window_shortcuts = shortcuts
+                .slots
+                .iter()
+                .filter(|(_, shortcut)| shortcut.window == Some(window))
+                .peekable();
+            window_shortcuts.peek().is_some().then(|| {
+                window_shortcuts
+                    .filter(|(_, shortcut)| shortcut.any_enabled())
+                    .map(|(_, shortcut)| {
+                        let mods = shortcut.shortcut.mods;
+                        serde_json::json!({
+                            "code": shortcut.shortcut.key.to_string(),
+                            "alt": mods.contains(Modifiers::ALT),
+                            "ctrl": mods.contains(Modifiers::CONTROL),
+                            "meta": mods.intersects(Modifiers::META | Modifiers::SUPER),
+                            "shift": mods.contains(Modifiers::SHIFT),
+                            "in_editable": shortcut
+                                .callbacks
+                                .iter()
+                                .any(|(_, callback)| callback.enabled && callback.in_editable),
+                        })
+                    })
+                    .collect()
+            })
+        };
+
+        let Some(accelerators) = accelerators else {
+            // Drop the listener after the borrow ends, because dropping it evaluates a script in the webview
+            let listener = self.window_listeners.borrow_mut().remove(&window);
+            drop(listener);
+            return;
+        };
+        if let Some(listener) = self.window_listeners.borrow().get(&window) {
+            _ = listener.desktop().webview.evaluate_script(&format!(
+                "window.__dioxus_window_shortcuts = {};",
+                serde_json::Value::Array(accelerators)
+            ));
+        }
+    }
+
+    fn add(
+        &self,
+        window: Option<WindowId>,
+        hotkey: HotKey,
+        callback: Box<dyn FnMut()>,
+    ) -> Result<ShortcutId, ShortcutRegistryError> {
+        let key = (window, hotkey.id());
 
         let mut shortcuts = self.shortcuts.borrow_mut();
 
-        if let Some(callbacks) = shortcuts.get_mut(&accelerator_id) {
+        if let Some(&slot) = shortcuts.by_accelerator.get(&key) {
+            let shortcut = &mut shortcuts.slots[slot];
+            self.register(shortcut)?;
             return Ok(ShortcutId {
-                id: accelerator_id,
-                number: callbacks.insert(callback),
+                slot,
+                number: shortcut.insert(callback),
             });
         };
 
-        self.manager.register(hotkey).map_err(|e| match e {
-            HotkeyError::HotKeyParseError(shortcut) => {
-                ShortcutRegistryError::InvalidShortcut(shortcut)
-            }
-            err => ShortcutRegistryError::Other(Box::new(err)),
-        })?;
-
         let mut shortcut = Shortcut {
             shortcut: hotkey,
+            window,
             callbacks: Slab::new(),
+            registered: false,
         };
+        self.register(&mut shortcut)?;
 
-        let id = shortcut.callbacks.insert(callback);
+        let number = shortcut.insert(callback);
 
-        shortcuts.insert(accelerator_id, shortcut);
+        let slot = shortcuts.slots.insert(shortcut);
+        shortcuts.by_accelerator.insert(key, slot);
 
-        Ok(ShortcutId {
-            id: accelerator_id,
-            number: id,
-        })
+        Ok(ShortcutId { slot, number })
     }
 
     pub(crate) fn remove_shortcut(&self, id: ShortcutId) {
-        let mut shortcuts = self.shortcuts.borrow_mut();
-        if let Some(callbacks) = shortcuts.get_mut(&id.id) {
-            callbacks.remove(id.number);
-            if callbacks.is_empty() {
-                if let Some(_shortcut) = shortcuts.remove(&id.id) {
-                    let _ = self.manager.unregister(_shortcut.shortcut);
-                }
+        let window = {
+            let mut shortcuts = self.shortcuts.borrow_mut();
+            let Some(shortcut) = shortcuts.slots.get_mut(id.slot) else {
+                return;
+            };
+            let window = shortcut.window;
+            shortcut.remove(id.number);
+            if shortcut.is_empty() {
+                let mut shortcut = shortcuts.slots.remove(id.slot);
+                shortcuts.by_accelerator.remove(&shortcut.key());
+                self.unregister(&mut shortcut);
+            } else if !shortcut.any_enabled() {
+                self.unregister(shortcut);
             }
+            window
+        };
+        self.sync_window(window);
+    }
+
+    /// Move every callback of a shortcut to a different accelerator. The ids of the callbacks stay the same.
+    ///
+    /// The new accelerator is registered before the old one is released, so the shortcut keeps its old accelerator if the new one can't be used.
+    pub(crate) fn rebind(
+        &self,
+        id: ShortcutId,
+        hotkey: HotKey,
+    ) -> Result<(), ShortcutRegistryError> {
+        let mut shortcuts = self.shortcuts.borrow_mut();
+        let Shortcuts {
+            slots,
+            by_accelerator,
+        } = &mut *shortcuts;
+        let shortcut = match slots.get_mut(id.slot) {
+            Some(shortcut) => shortcut,
+            None => return Ok(()),
+        };
+
+        let old_key = shortcut.key();
+        let new_key = (shortcut.window, hotkey.id());
+        if old_key == new_key {
+            return Ok(());
+        }
+        // The callbacks can't be merged into another shortcut without changing their ids
+        if by_accelerator.contains_key(&new_key) {
+            return Err(ShortcutRegistryError::AlreadyInUse(hotkey));
+        }
+
+        let mut rebound = Shortcut {
+            shortcut: hotkey,
+            window: shortcut.window,
+            callbacks: Slab::new(),
+            registered: false,
+        };
+        if shortcut.registered {
+            self.register(&mut rebound)?;
         }
+        self.unregister(shortcut);
+        shortcut.shortcut = rebound.shortcut;
+        shortcut.registered = rebound.registered;
+
+        by_accelerator.remove(&old_key);
+        by_accelerator.insert(new_key, id.slot);
+
+        let window = shortcut.window;
+        drop(shortcuts);
+        self.sync_window(window);
+        Ok(())
+    }
+
+    /// Enable or disable a single callback of a shortcut. Disabled callbacks stay registered, but are not called when the shortcut is pressed.
+    ///
+    /// The accelerator is unregistered with the OS while every callback for it is disabled, and registered again when one is enabled.
+    pub(crate) fn set_enabled(
+        &self,
+        id: ShortcutId,
+        enabled: bool,
+    ) -> Result<(), ShortcutRegistryError> {
+        let mut shortcuts = self.shortcuts.borrow_mut();
+        let shortcut = match shortcuts.slots.get_mut(id.slot) {
+            Some(shortcut) => shortcut,
+            None => return Ok(()),
+        };
+        if enabled {
+            self.register(shortcut)?;
+        }
+        if let Some(callback) = shortcut.callbacks.get_mut(id.number) {
+            callback.enabled = enabled;
+        }
+        if !shortcut.any_enabled() {
+            self.unregister(shortcut);
+        }
+
+        let window = shortcut.window;
+        drop(shortcuts);
+        self.sync_window(window);
+        Ok(())
+    }
+
+    /// Whether a window shortcut is also called for keys typed into an input, textarea or contenteditable element.
+    pub(crate) fn set_in_editable(&self, id: ShortcutId, in_editable: bool) {
+        let window = {
+            let mut shortcuts = self.shortcuts.borrow_mut();
+            let Some(shortcut) = shortcuts.slots.get_mut(id.slot) else {
+                return;
+            };
+            if let Some(callback) = shortcut.callbacks.get_mut(id.number) {
+                callback.in_editable = in_editable;
+            }
+            shortcut.window
+        };
+        self.sync_window(window);
+    }
+
+    pub(crate) fn is_enabled(&self, id: ShortcutId) -> bool {
+        self.shortcuts
+            .borrow()
+            .slots
+            .get(id.slot)
+            .and_then(|shortcut| shortcut.callbacks.get(id.number))
+            .map(|callback| callback.enabled)
+            .unwrap_or(false)
+    }
+
+    /// The accelerator a shortcut is currently bound to.
+    pub(crate) fn accelerator(&self, id: ShortcutId) -> Option<HotKey> {
+        self.shortcuts
+            .borrow()
+            .slots
+            .get(id.slot)
+            .map(|shortcut| shortcut.shortcut)
+    }
+
+    /// The accelerators of the global shortcuts that are currently registered with the OS.
+    pub(crate) fn registered_accelerators(&self) -> Vec<HotKey> {
+        self.shortcuts
+            .borrow()
+            .slots
+            .iter()
+            .filter(|(_, shortcut)| shortcut.registered && shortcut.window.is_none())
+            .map(|(_, shortcut)| shortcut.shortcut)
+            .collect()
     }
 
     pub(crate) fn remove_all(&self) {
         let mut shortcuts = self.shortcuts.borrow_mut();
-        let hotkeys: Vec<_> = shortcuts.drain().map(|(_, v)| v.shortcut).collect();
+        shortcuts.by_accelerator.clear();
+        let hotkeys: Vec<_> = shortcuts
+            .slots
+            .drain()
+            .filter(|v| v.registered && v.window.is_none())
+            .map(|v| v.shortcut)
+            .collect();
         let _ = self.manager.unregister_all(&hotkeys);
+        drop(shortcuts);
+
+        let listeners = std::mem::take(&mut *self.window_listeners.borrow_mut());
+        drop(listeners);
+    }
+
+    fn register(&self, shortcut: &mut Shortcut) -> Result<(), ShortcutRegistryError> {
+        if shortcut.registered {
+            return Ok(());
+        }
+        // Window shortcuts are matched against key presses in the webview, so only global shortcuts need the OS
+        if shortcut.window.is_none() {
+            let hotkey = shortcut.shortcut;
+            self.manager.register(hotkey).map_err(|e| match e {
+                HotkeyError::HotKeyParseError(shortcut) => {
+                    ShortcutRegistryError::InvalidShortcut(shortcut)
+                }
+                // The registry shares accelerators between handlers, so the OS only rejects accelerators other apps own
+                HotkeyError::AlreadyRegistered(_) | HotkeyError::FailedToRegister(_) => {
+                    ShortcutRegistryError::AlreadyRegisteredByOtherApp(hotkey)
+                }
+                err => ShortcutRegistryError::Other(Box::new(err)),
+            })?;
+        }
+        shortcut.registered = true;
+        Ok(())
+    }
+
+    fn unregister(&self, shortcut: &mut Shortcut) {
+        if shortcut.registered {
+            if shortcut.window.is_none() {
+                let _ = self.manager.unregister(shortcut.shortcut);
+            }
+            shortcut.registered = false;
+        }
     }
 }
 
@@ -127,14 +491,37 @@ impl ShortcutRegistry {
 pub enum ShortcutRegistryError {
     /// The shortcut is invalid.
     InvalidShortcut(String),
+    /// The OS refused to register the accelerator, usually because another app already uses it. Ask the user to pick a different one.
+    AlreadyRegisteredByOtherApp(HotKey),
+    /// Another shortcut of this app already uses the accelerator, so a shortcut can't be rebound to it.
+    AlreadyInUse(HotKey),
     /// An unknown error occurred.
     Other(Box<dyn std::error::Error>),
 }
 
+impl std::fmt::Display for ShortcutRegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShortcutRegistryError::InvalidShortcut(shortcut) => {
+                write!(f, "Invalid shortcut: {}", shortcut)
+            }
+            ShortcutRegistryError::AlreadyRegisteredByOtherApp(hotkey) => {
+                write!(f, "{:?} is already registered by another app", hotkey)
+            }
+            ShortcutRegistryError::AlreadyInUse(hotkey) => {
+                write!(f, "{:?} is already used by another shortcut", hotkey)
+            }
+            ShortcutRegistryError::Other(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ShortcutRegistryError {}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// An global id for a shortcut.
 pub struct ShortcutId {
-    id: u32,
+    slot: usize,
     number: usize,
 }
 
@@ -146,30 +533,36 @@ pub struct ShortcutHandle {
 }
 
 pub trait IntoAccelerator {
-    fn accelerator(&self) -> HotKey;
+    fn accelerator(&self) -> Result<HotKey, ShortcutRegistryError>;
 }
 
 impl IntoAccelerator for (dioxus_html::KeyCode, ModifiersState) {
-    fn accelerator(&self) -> HotKey {
-        HotKey::new(Some(self.1.into_modifiers_state()), self.0.into_key_code())
+    fn accelerator(&self) -> Result<HotKey, ShortcutRegistryError> {
+        Ok(HotKey::new(
+            Some(self.1.into_modifiers_state()),
+            self.0.into_key_code()?,
+        ))
     }
 }
 
 impl IntoAccelerator for (ModifiersState, dioxus_html::KeyCode) {
-    fn accelerator(&self) -> HotKey {
-        HotKey::new(Some(self.0.into_modifiers_state()), self.1.into_key_code())
+    fn accelerator(&self) -> Result<HotKey, ShortcutRegistryError> {
+        Ok(HotKey::new(
+            Some(self.0.into_modifiers_state()),
+            self.1.into_key_code()?,
+        ))
     }
 }
 
 impl IntoAccelerator for dioxus_html::KeyCode {
-    fn accelerator(&self) -> HotKey {
-        HotKey::new(None, self.into_key_code())
+    fn accelerator(&self) -> Result<HotKey, ShortcutRegistryError> {
+        Ok(HotKey::new(None, self.into_key_code()?))
     }
 }
 
 impl IntoAccelerator for &str {
-    fn accelerator(&self) -> HotKey {
-        HotKey::from_str(self).unwrap()
+    fn accelerator(&self) -> Result<HotKey, ShortcutRegistryError> {
+        HotKey::from_str(self).map_err(|_| ShortcutRegistryError::InvalidShortcut(self.to_string()))
     }
 }
 
@@ -178,6 +571,37 @@ impl ShortcutHandle {
     pub fn remove(&self) {
         self.desktop.remove_shortcut(self.shortcut_id);
     }
+
+    /// Enable or disable the shortcut without removing it. The handler is not called while the shortcut is disabled, which is useful to mute shortcuts while a modal is open.
+    ///
+    /// If no other handler uses the same accelerator, it is released to the OS while disabled. Enabling the shortcut fails if another app took the accelerator in the meantime.
+    pub fn set_enabled(&self, enabled: bool) -> Result<(), ShortcutRegistryError> {
+        self.desktop.set_shortcut_enabled(self.shortcut_id, enabled)
+    }
+
+    /// Check if the shortcut is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.desktop.is_shortcut_enabled(self.shortcut_id)
+    }
+
+    /// Change the accelerator that triggers the shortcut, keeping its handler and id. Every other handler that shares the accelerator moves with it.
+    ///
+    /// If the new accelerator can't be registered, the shortcut keeps its old one.
+    pub fn rebind(&self, accelerator: impl IntoAccelerator) -> Result<(), ShortcutRegistryError> {
+        self.desktop
+            .rebind_shortcut(self.shortcut_id, accelerator.accelerator()?)
+    }
+
+    /// The accelerator that currently triggers the shortcut.
+    pub fn accelerator(&self) -> Option<HotKey> {
+        self.desktop.shortcut_accelerator(self.shortcut_id)
+    }
+
+    /// Also call a window shortcut while the user is typing in an input, textarea or contenteditable element. Defaults to false, so shortcuts don't take keys away from text fields. Global shortcuts ignore this setting.
+    pub fn set_in_editable(&self, in_editable: bool) {
+        self.desktop
+            .set_shortcut_in_editable(self.shortcut_id, in_editable)
+    }
 }
 
 impl Drop for ShortcutHandle {
@@ -217,18 +641,18 @@ impl IntoModifersState for Modifiers {
 }
 
 pub trait IntoKeyCode {
-    fn into_key_code(self) -> Code;
+    fn into_key_code(self) -> Result<Code, ShortcutRegistryError>;
 }
 
 impl IntoKeyCode for Code {
-    fn into_key_code(self) -> Code {
-        self
+    fn into_key_code(self) -> Result<Code, ShortcutRegistryError> {
+        Ok(self)
     }
 }
 
 impl IntoKeyCode for dioxus_html::KeyCode {
-    fn into_key_code(self) -> Code {
-        match self {
+    fn into_key_code(self) -> Result<Code, ShortcutRegistryError> {
+        Ok(match self {
             dioxus_html::KeyCode::Backspace => Code::Backspace,
             dioxus_html::KeyCode::Tab => Code::Tab,
             dioxus_html::KeyCode::Clear => Code::NumpadClear,
@@ -236,7 +660,7 @@ impl IntoKeyCode for dioxus_html::KeyCode {
             dioxus_html::KeyCode::Shift => Code::ShiftLeft,
             dioxus_html::KeyCode::Ctrl => Code::ControlLeft,
             dioxus_html::KeyCode::Alt => Code::AltLeft,
-            dioxus_html::KeyCode::Pause => Code::Pause,
+            dioxus_html::KeyCode::Pause | dioxus_html::KeyCode::Break => Code::Pause,
             dioxus_html::KeyCode::CapsLock => Code::CapsLock,
             dioxus_html::KeyCode::Escape => Code::Escape,
             dioxus_html::KeyCode::Space => Code::Space,
@@ -250,16 +674,16 @@ impl IntoKeyCode for dioxus_html::KeyCode {
             dioxus_html::KeyCode::DownArrow => Code::ArrowDown,
             dioxus_html::KeyCode::Insert => Code::Insert,
             dioxus_html::KeyCode::Delete => Code::Delete,
-            dioxus_html::KeyCode::Num0 => Code::Numpad0,
-            dioxus_html::KeyCode::Num1 => Code::Numpad1,
-            dioxus_html::KeyCode::Num2 => Code::Numpad2,
-            dioxus_html::KeyCode::Num3 => Code::Numpad3,
-            dioxus_html::KeyCode::Num4 => Code::Numpad4,
-            dioxus_html::KeyCode::Num5 => Code::Numpad5,
-            dioxus_html::KeyCode::Num6 => Code::Numpad6,
-            dioxus_html::KeyCode::Num7 => Code::Numpad7,
-            dioxus_html::KeyCode::Num8 => Code::Numpad8,
-            dioxus_html::KeyCode::Num9 => Code::Numpad9,
+            dioxus_html::KeyCode::Num0 => Code::Digit0,
+            dioxus_html::KeyCode::Num1 => Code::Digit1,
+            dioxus_html::KeyCode::Num2 => Code::Digit2,
+            dioxus_html::KeyCode::Num3 => Code::Digit3,
+            dioxus_html::KeyCode::Num4 => Code::Digit4,
+            dioxus_html::KeyCode::Num5 => Code::Digit5,
+            dioxus_html::KeyCode::Num6 => Code::Digit6,
+            dioxus_html::KeyCode::Num7 => Code::Digit7,
+            dioxus_html::KeyCode::Num8 => Code::Digit8,
+            dioxus_html::KeyCode::Num9 => Code::Digit9,
             dioxus_html::KeyCode::A => Code::KeyA,
             dioxus_html::KeyCode::B => Code::KeyB,
             dioxus_html::KeyCode::C => Code::KeyC,
@@ -286,6 +710,9 @@ impl IntoKeyCode for dioxus_html::KeyCode {
             dioxus_html::KeyCode::X => Code::KeyX,
             dioxus_html::KeyCode::Y => Code::KeyY,
             dioxus_html::KeyCode::Z => Code::KeyZ,
+            dioxus_html::KeyCode::LeftWindow => Code::MetaLeft,
+            dioxus_html::KeyCode::RightWindow => Code::MetaRight,
+            dioxus_html::KeyCode::SelectKey => Code::ContextMenu,
             dioxus_html::KeyCode::Numpad0 => Code::Numpad0,
             dioxus_html::KeyCode::Numpad1 => Code::Numpad1,
             dioxus_html::KeyCode::Numpad2 => Code::Numpad2,
@@ -318,6 +745,7 @@ impl IntoKeyCode for dioxus_html::KeyCode {
             dioxus_html::KeyCode::Semicolon => Code::Semicolon,
             dioxus_html::KeyCode::EqualSign => Code::Equal,
             dioxus_html::KeyCode::Comma => Code::Comma,
+            dioxus_html::KeyCode::Dash => Code::Minus,
             dioxus_html::KeyCode::Period => Code::Period,
             dioxus_html::KeyCode::ForwardSlash => Code::Slash,
             dioxus_html::KeyCode::GraveAccent => Code::Backquote,
@@ -325,7 +753,12 @@ impl IntoKeyCode for dioxus_html::KeyCode {
             dioxus_html::KeyCode::BackSlash => Code::Backslash,
             dioxus_html::KeyCode::CloseBraket => Code::BracketRight,
             dioxus_html::KeyCode::SingleQuote => Code::Quote,
-            key => panic!("Failed to convert {:?} to tao::keyboard::KeyCode, try using tao::keyboard::KeyCode directly", key),
-        }
+            key => {
+                return Err(ShortcutRegistryError::InvalidShortcut(format!(
+                    "{:?} can not be used in a shortcut, try using a Code directly",
+                    key
+                )))
+            }
+        })
     }
 }
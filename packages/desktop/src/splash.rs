@@ -0,0 +1,96 @@
+//! A native window that is shown while the main window loads, set with [`Config::with_splash_screen`](crate::Config::with_splash_screen).
+
+use tao::{
+    dpi::{LogicalSize, PhysicalPosition},
+    event_loop::EventLoopWindowTarget,
+    window::{Window, WindowBuilder},
+};
+use wry::{WebView, WebViewBuilder};
+
+/// A small borderless window in the center of the screen that shows some html until the window of the app is shown.
+///
+/// ```rust, ignore
+/// let config = Config::new()
+///     .with_show_on_first_render(true)
+///     .with_splash_screen(SplashScreen::new(include_str!("splash.html")).with_size(400.0, 240.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplashScreen {
+    html: String,
+    size: LogicalSize<f64>,
+}
+
+impl SplashScreen {
+    /// Create a splash screen that shows this html document.
+    pub fn new(html: impl Into<String>) -> Self {
+        Self {
+            html: html.into(),
+            size: LogicalSize::new(480.0, 320.0),
+        }
+    }
+
+    /// Set the size of the splash screen in logical pixels. Defaults to 480x320.
+    pub fn with_size(mut self, width: f64, height: f64) -> Self {
+        self.size = LogicalSize::new(width, height);
+        self
+    }
+
+    /// Open the splash screen. It closes when the returned value is dropped.
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    pub(crate) fn show<T>(&self, target: &EventLoopWindowTarget<T>) -> Option<Splash> {
+        let window = WindowBuilder::new()
+            .with_decorations(false)
+            .with_resizable(false)
+            .with_inner_size(self.size)
+            .build(target)
+            .map_err(|err| tracing::error!("Failed to open the splash screen: {err}"))
+            .ok()?;
+
+        if let Some(monitor) = window
+            .current_monitor()
+            .or_else(|| target.primary_monitor())
+        {
+            let origin = monitor.position();
+            let area = monitor.size();
+            let size = window.outer_size();
+            window.set_outer_position(PhysicalPosition::new(
+                origin.x + (area.width as i32 - size.width as i32) / 2,
+                origin.y + (area.height as i32 - size.height as i32) / 2,
+            ));
+        }
+
+        #[cfg(any(target_os = "windows", target_os = "macos"))]
+        let builder = WebViewBuilder::new(&window);
+
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let builder = {
+            use tao::platform::unix::WindowExtUnix;
+            use wry::WebViewBuilderExtUnix;
+            WebViewBuilder::new_gtk(window.default_vbox()?)
+        };
+
+        let webview = builder
+            .with_html(self.html.clone())
+            .and_then(|builder| builder.build())
+            .map_err(|err| tracing::error!("Failed to open the splash screen: {err}"))
+            .ok()?;
+
+        Some(Splash {
+            _webview: webview,
+            _window: window,
+        })
+    }
+
+    /// Mobile apps only have one window.
+    #[cfg(any(target_os = "ios", target_os = "android"))]
+    pub(crate) fn show<T>(&self, _target: &EventLoopWindowTarget<T>) -> Option<Splash> {
+        None
+    }
+}
+
+/// An open splash screen. Dropping it closes its window.
+pub(crate) struct Splash {
+    // The webview has to be dropped before its window
+    _webview: WebView,
+    _window: Window,
+}
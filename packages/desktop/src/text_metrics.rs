@@ -0,0 +1,109 @@
+//! Measure text synchronously by shaping it with the system fonts.
+
+use std::cell::RefCell;
+
+use cosmic_text::{Attrs, Buffer, Family, FontSystem, Metrics, Shaping, Style, Weight};
+use dioxus_core::ScopeState;
+use dioxus_html::text_metrics::{Font, TextMetrics, TextMetricsCache};
+use dioxus_signals::{ReadOnlySignal, Signal};
+
+thread_local! {
+    // Loading the system fonts is slow, so it only happens the first time text is measured
+    static FONT_SYSTEM: RefCell<Option<FontSystem>> = RefCell::new(None);
+    static CACHE: TextMetricsCache = TextMetricsCache::new(1024);
+}
+
+/// Measure the size of a single line of text rendered in the given font.
+///
+/// The text is shaped with the fonts installed on the system. Fonts that are only loaded by the page, like web fonts
+/// in a stylesheet, are not available here, so the first installed family in [`Font::family`] is used instead.
+pub fn measure_text(font: &Font, text: &str) -> TextMetrics {
+    CACHE.with(|cache| {
+        cache.get_or_measure(font, text, || {
+            FONT_SYSTEM.with(|font_system| {
+                let mut font_system = font_system.borrow_mut();
+                shape(font_system.get_or_insert_with(FontSystem::new), font, text)
+            })
+        })
+    })
+}
+
+/// Get the size of a single line of text as a signal. The text is measured again when the font or text changes.
+///
+/// ```rust, ignore
+/// let metrics = use_text_metrics(cx, Font::new("sans-serif", 12.0), label.clone());
+/// let width = metrics.read().width;
+/// ```
+pub fn use_text_metrics(
+    cx: &ScopeState,
+    font: Font,
+    text: impl Into<String>,
+) -> ReadOnlySignal<TextMetrics> {
+    let text = text.into();
+    let (metrics, measured) = cx.use_hook(|| {
+        (
+            Signal::new(measure_text(&font, &text)),
+            RefCell::new((font.clone(), text.clone())),
+        )
+    });
+    let metrics = *metrics;
+
+    if *measured.borrow() != (font.clone(), text.clone()) {
+        let new = measure_text(&font, &text);
+        *measured.borrow_mut() = (font, text);
+        if *metrics.peek() != new {
+            metrics.set(new);
+        }
+    }
+
+    ReadOnlySignal::new(metrics)
+}
+
+fn shape(font_system: &mut FontSystem, font: &Font, text: &str) -> TextMetrics {
+    // Match the line height browsers use for `line-height: normal`
+    let line_height = font.size * 1.2;
+    let mut buffer = Buffer::new(font_system, Metrics::new(font.size, line_height));
+    let mut buffer = buffer.borrow_with(font_system);
+    buffer.set_size(f32::MAX, f32::MAX);
+
+    let family = font
+        .family
+        .split(',')
+        .map(|family| family.trim().trim_matches(|c| c == '"' || c == '\''))
+        .find(|family| !family.is_empty())
+        .map(|family| match family {
+            "serif" => Family::Serif,
+            "sans-serif" => Family::SansSerif,
+            "monospace" => Family::Monospace,
+            "cursive" => Family::Cursive,
+            "fantasy" => Family::Fantasy,
+            name => Family::Name(name),
+        })
+        .unwrap_or(Family::SansSerif);
+    let style = if font.italic {
+        Style::Italic
+    } else {
+        Style::Normal
+    };
+    let attrs = Attrs::new()
+        .family(family)
+        .weight(Weight(font.weight))
+        .style(style);
+    buffer.set_text(text, attrs, Shaping::Advanced);
+
+    match buffer.layout_runs().next() {
+        Some(run) => {
+            let ascent = run.line_y - run.line_top;
+            TextMetrics {
+                width: run.line_w,
+                ascent,
+                descent: line_height - ascent,
+            }
+        }
+        None => TextMetrics {
+            width: 0.0,
+            ascent: font.size,
+            descent: line_height - font.size,
+        },
+    }
+}
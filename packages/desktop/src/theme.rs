@@ -0,0 +1,52 @@
+//! Platform specific lookups for the OS color scheme and accent color.
+
+use dioxus_html::theme::{AccentColor, ColorScheme};
+use tao::window::{Theme, Window};
+
+/// Convert a theme reported by tao into a [`ColorScheme`].
+pub(crate) fn color_scheme(theme: Theme) -> ColorScheme {
+    match theme {
+        Theme::Dark => ColorScheme::Dark,
+        _ => ColorScheme::Light,
+    }
+}
+
+/// Get the color scheme the window is currently displayed with.
+pub(crate) fn current_color_scheme(window: &Window) -> ColorScheme {
+    color_scheme(window.theme())
+}
+
+/// Get the accent color the user picked in their OS settings.
+///
+/// This is only supported on macOS. Other platforms return `None`.
+pub(crate) fn current_accent_color() -> Option<AccentColor> {
+    #[cfg(target_os = "macos")]
+    {
+        use objc::runtime::Object;
+        use objc::{msg_send, sel, sel_impl};
+
+        fn channel(component: f64) -> u8 {
+            (component.clamp(0.0, 1.0) * 255.0).round() as u8
+        }
+
+        objc::rc::autoreleasepool(|| unsafe {
+            let color: *mut Object = msg_send![objc::class!(NSColor), controlAccentColor];
+            let space: *mut Object = msg_send![objc::class!(NSColorSpace), sRGBColorSpace];
+            let color: *mut Object = msg_send![color, colorUsingColorSpace: space];
+            if color.is_null() {
+                return None;
+            }
+            let red: f64 = msg_send![color, redComponent];
+            let green: f64 = msg_send![color, greenComponent];
+            let blue: f64 = msg_send![color, blueComponent];
+            Some(AccentColor {
+                red: channel(red),
+                green: channel(green),
+                blue: channel(blue),
+            })
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    None
+}
@@ -0,0 +1,265 @@
+//! An icon in the system tray with a menu defined in rust.
+//!
+//! The tray is created with [`Config::with_tray`](crate::Config::with_tray), its events are read with [`use_tray_event_handler`](crate::use_tray_event_handler), and its menu can follow signals with [`use_tray_menu`](crate::use_tray_menu).
+
+use crate::native_menu::NativeMenu;
+use slab::Slab;
+use std::cell::{Cell, RefCell};
+
+/// The configuration of the tray icon, passed to [`Config::with_tray`](crate::Config::with_tray).
+///
+/// ```rust, ignore
+/// let tray = TrayBuilder::new()
+///     .with_icon(include_bytes!("icon.rgba").to_vec(), 32, 32)
+///     .with_tooltip("My App")
+///     .with_menu(NativeMenu::new().item("show", "Show window").separator().item("quit", "Quit"));
+///
+/// dioxus_desktop::launch_cfg(app, Config::new().with_tray(tray));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TrayBuilder {
+    pub(crate) icon: Option<TrayImage>,
+    pub(crate) tooltip: Option<String>,
+    pub(crate) menu: Option<NativeMenu>,
+    pub(crate) menu_on_left_click: bool,
+}
+
+/// The image of a tray icon as RGBA pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrayImage {
+    /// The pixels of the icon, four bytes per pixel in row order.
+    pub rgba: Vec<u8>,
+    /// The width of the icon in pixels.
+    pub width: u32,
+    /// The height of the icon in pixels.
+    pub height: u32,
+}
+
+impl TrayBuilder {
+    /// Create a tray icon without an image, tooltip or menu.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the image of the icon from RGBA pixels.
+    pub fn with_icon(mut self, rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        self.icon = Some(TrayImage {
+            rgba,
+            width,
+            height,
+        });
+        self
+    }
+
+    /// Set the text that is shown when the pointer hovers over the icon.
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Set the menu that is opened when the icon is right clicked.
+    pub fn with_menu(mut self, menu: NativeMenu) -> Self {
+        self.menu = Some(menu);
+        self
+    }
+
+    /// Open the menu on left clicks too. By default left clicks are only reported as [`TrayEvent::Click`].
+    pub fn with_menu_on_left_click(mut self, menu_on_left_click: bool) -> Self {
+        self.menu_on_left_click = menu_on_left_click;
+        self
+    }
+}
+
+/// An interaction with the tray icon, passed to the handlers of [`use_tray_event_handler`](crate::use_tray_event_handler).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrayEvent {
+    /// The icon was clicked with the left mouse button.
+    Click,
+    /// The icon was clicked with the right mouse button.
+    RightClick,
+    /// The icon was double clicked.
+    DoubleClick,
+    /// The item of the tray menu with this id was clicked.
+    MenuItem(String),
+}
+
+type TrayHandler = Box<dyn FnMut(&TrayEvent)>;
+
+/// The tray icon of the app, and the handlers for its events.
+#[derive(Default)]
+pub(crate) struct Tray {
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    icon: RefCell<Option<tray_icon::TrayIcon>>,
+    #[cfg(not(any(target_os = "ios", target_os = "android")))]
+    menu: RefCell<Option<crate::native_menu::BuiltMenu>>,
+    handlers: RefCell<Slab<TrayHandler>>,
+    // Events of an app that started in the tray, from before any component of its window listened
+    missed: RefCell<Option<Vec<TrayEvent>>>,
+    // Set when the icon is used, to whether the window of the app should be shown
+    interaction: Cell<Option<bool>>,
+}
+
+impl Tray {
+    pub(crate) fn add_handler(&self, mut handler: TrayHandler) -> usize {
+        for event in self.missed.take().into_iter().flatten() {
+            handler(&event);
+        }
+        self.handlers.borrow_mut().insert(handler)
+    }
+
+    /// Keep the events until the first handler is added, because the window of the app is only created after the icon is used.
+    pub(crate) fn keep_missed_events(&self) {
+        *self.missed.borrow_mut() = Some(Vec::new());
+    }
+
+    pub(crate) fn remove_handler(&self, id: usize) {
+        self.handlers.borrow_mut().try_remove(id);
+    }
+
+    /// Take the interactions with the icon since this was last called. It is `Some(true)` if the icon was clicked, so the window should be shown, and `Some(false)` if only an item of its menu was clicked.
+    pub(crate) fn take_interaction(&self) -> Option<bool> {
+        self.interaction.take()
+    }
+
+    fn emit(&self, event: TrayEvent) {
+        match event {
+            TrayEvent::Click | TrayEvent::DoubleClick => self.interaction.set(Some(true)),
+            TrayEvent::MenuItem(_) if self.interaction.get().is_none() => {
+                self.interaction.set(Some(false))
+            }
+            _ => {}
+        }
+
+        if let Some(missed) = &mut *self.missed.borrow_mut() {
+            missed.push(event);
+            return;
+        }
+        for (_, handler) in self.handlers.borrow_mut().iter_mut() {
+            handler(&event);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+use crate::native_menu::BuiltMenu;
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+impl Tray {
+    /// Show the tray icon. The icon must be created after the event loop started. Returns false if it couldn't be created.
+    pub(crate) fn create(&self, builder: TrayBuilder) -> bool {
+        let mut tray =
+            tray_icon::TrayIconBuilder::new().with_menu_on_left_click(builder.menu_on_left_click);
+        if let Some(icon) = builder.icon.and_then(convert_icon) {
+            tray = tray.with_icon(icon);
+        }
+        if let Some(tooltip) = builder.tooltip {
+            tray = tray.with_tooltip(tooltip);
+        }
+        if let Some(menu) = &builder.menu {
+            let menu = BuiltMenu::new(menu);
+            tray = tray.with_menu(Box::new(menu.menu.clone()));
+            *self.menu.borrow_mut() = Some(menu);
+        }
+
+        match tray.build() {
+            Ok(icon) => {
+                *self.icon.borrow_mut() = Some(icon);
+                true
+            }
+            Err(err) => {
+                tracing::error!("Failed to create the tray icon: {err}");
+                false
+            }
+        }
+    }
+
+    pub(crate) fn set_menu(&self, menu: &NativeMenu) {
+        let icon = self.icon.borrow();
+        let icon = match &*icon {
+            Some(icon) => icon,
+            None => return,
+        };
+
+        let mut built = self.menu.borrow_mut();
+        match &mut *built {
+            Some(built) => built.update(menu),
+            None => {
+                let menu = BuiltMenu::new(menu);
+                icon.set_menu(Some(Box::new(menu.menu.clone())));
+                *built = Some(menu);
+            }
+        }
+    }
+
+    pub(crate) fn set_tooltip(&self, tooltip: Option<String>) {
+        if let Some(icon) = &*self.icon.borrow() {
+            if let Err(err) = icon.set_tooltip(tooltip) {
+                tracing::error!("Failed to set the tooltip of the tray icon: {err}");
+            }
+        }
+    }
+
+    pub(crate) fn set_icon(&self, image: TrayImage) {
+        if let Some(icon) = &*self.icon.borrow() {
+            if let Err(err) = icon.set_icon(convert_icon(image)) {
+                tracing::error!("Failed to set the image of the tray icon: {err}");
+            }
+        }
+    }
+
+    /// Send the clicks on the icon to the handlers.
+    pub(crate) fn poll(&self) {
+        use tray_icon::{ClickType, TrayIconEvent};
+
+        while let Ok(event) = TrayIconEvent::receiver().try_recv() {
+            self.emit(match event.click_type {
+                ClickType::Left => TrayEvent::Click,
+                ClickType::Right => TrayEvent::RightClick,
+                ClickType::Double => TrayEvent::DoubleClick,
+            });
+        }
+    }
+
+    /// Send a click on a menu item to the handlers if the item is in the tray menu. Returns false if it belongs to another menu.
+    pub(crate) fn handle_menu_event(&self, id: &str) -> bool {
+        let owned = self
+            .menu
+            .borrow()
+            .as_ref()
+            .map_or(false, |menu| menu.contains(id));
+        if owned {
+            self.emit(TrayEvent::MenuItem(id.to_string()));
+        }
+        owned
+    }
+}
+
+#[cfg(any(target_os = "ios", target_os = "android"))]
+impl Tray {
+    pub(crate) fn create(&self, _builder: TrayBuilder) -> bool {
+        false
+    }
+
+    pub(crate) fn set_menu(&self, _menu: &NativeMenu) {}
+
+    pub(crate) fn set_tooltip(&self, _tooltip: Option<String>) {}
+
+    pub(crate) fn set_icon(&self, _image: TrayImage) {}
+
+    pub(crate) fn poll(&self) {}
+
+    pub(crate) fn handle_menu_event(&self, _id: &str) -> bool {
+        false
+    }
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android")))]
+fn convert_icon(icon: TrayImage) -> Option<tray_icon::Icon> {
+    match tray_icon::Icon::from_rgba(icon.rgba, icon.width, icon.height) {
+        Ok(icon) => Some(icon),
+        Err(err) => {
+            tracing::error!("Invalid tray icon: {err}");
+            None
+        }
+    }
+}
@@ -0,0 +1,609 @@
+//! Update the app to the latest release published in a release feed. Enabled with the `updater` feature.
+//!
+//! Every release provides the new executable of the app for each platform, signed with [minisign](https://jedisct1.github.io/minisign/). The update is downloaded, its signature is checked with the public key built into the app, and it replaces the current executable. The new version runs once the app restarts.
+//!
+//! The trusted comment of the signature has to name the version of the release, so an old signed release can't be served as a newer one:
+//!
+//! ```sh
+//! minisign -S -m my-app -t "version:1.2.0"
+//! ```
+//!
+//! ```rust, ignore
+//! fn app(cx: Scope) -> Element {
+//!     let updater = use_updater(cx, || {
+//!         Updater::new(
+//!             ReleaseFeed::github("my-org", "my-app"),
+//!             "RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3",
+//!             env!("CARGO_PKG_VERSION"),
+//!         )
+//!     });
+//!     use_on_create(cx, || {
+//!         to_owned![updater];
+//!         async move { updater.check() }
+//!     });
+//!
+//!     match &*updater.state().read() {
+//!         UpdateState::Available(release) => render! {
+//!             button { onclick: move |_| updater.install(), "Update to {release.version}" }
+//!         },
+//!         UpdateState::Downloading { downloaded, total } => render! {
+//!             progress { value: *downloaded as f64, max: total.unwrap_or(0) as f64 }
+//!         },
+//!         UpdateState::Ready(_) => render! {
+//!             button { onclick: move |_| updater.restart(), "Restart to update" }
+//!         },
+//!         _ => None,
+//!     }
+//! }
+//! ```
+
+use dioxus_core::{prelude::spawn_at, ScopeId, ScopeState};
+use dioxus_signals::{ReadOnlySignal, Signal};
+use minisign_verify::{PublicKey, Signature};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+/// Where the releases of the app are published.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseFeed {
+    /// A json file that describes the latest release:
+    ///
+    /// ```json
+    /// {
+    ///   "version": "1.2.0",
+    ///   "notes": "Fixed a crash on startup",
+    ///   "platforms": {
+    ///     "windows-x86_64": { "url": "https://example.com/my-app-1.2.0.exe", "signature": "<contents of the .minisig file>" },
+    ///     "linux-x86_64": { "url": "https://example.com/my-app-1.2.0", "signature": "..." }
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// The platforms are named `{os}-{arch}` like [`std::env::consts::OS`] and [`std::env::consts::ARCH`].
+    Json(String),
+    /// The latest release of a GitHub repository. The executable for a platform is the asset whose name contains `{os}-{arch}`, and its signature is the asset with the same name followed by `.minisig`. The tag of the release is the version.
+    GitHub {
+        /// The user or organization that owns the repository.
+        owner: String,
+        /// The name of the repository.
+        repo: String,
+    },
+}
+
+impl ReleaseFeed {
+    /// A json file at `url`. See [`ReleaseFeed::Json`] for its format.
+    pub fn json(url: impl Into<String>) -> Self {
+        Self::Json(url.into())
+    }
+
+    /// The releases of a GitHub repository.
+    pub fn github(owner: impl Into<String>, repo: impl Into<String>) -> Self {
+        Self::GitHub {
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+}
+
+/// A release that is newer than the running version of the app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// The version of the release.
+    pub version: String,
+    /// The release notes, if the feed has them.
+    pub notes: Option<String>,
+    url: String,
+    signature: SignatureSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SignatureSource {
+    Inline(String),
+    Url(String),
+}
+
+/// The state of the updater returned by [`use_updater`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum UpdateState {
+    /// No update was looked for yet.
+    #[default]
+    Idle,
+    /// Looking for a new release in the feed.
+    Checking,
+    /// The running version is the latest release.
+    UpToDate,
+    /// A newer release can be installed.
+    Available(Release),
+    /// The update is being downloaded. `total` is the size of the update in bytes, if the server sent it.
+    Downloading {
+        /// The number of bytes that were downloaded.
+        downloaded: u64,
+        /// The size of the update in bytes.
+        total: Option<u64>,
+    },
+    /// The update is installed and runs once the app restarts.
+    Ready(Release),
+    /// Looking for, downloading or installing the update failed.
+    Failed(UpdateError),
+}
+
+/// Represents an error while updating the app.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UpdateError {
+    /// The feed or the update could not be downloaded.
+    #[error("failed to download the update: {0}")]
+    Network(String),
+    /// The feed is not valid.
+    #[error("the release feed is invalid: {0}")]
+    Feed(String),
+    /// The latest release has no executable for this platform.
+    #[error("the latest release has no update for {0}")]
+    NoUpdateForPlatform(String),
+    /// The signature of the update does not match the public key of the app.
+    #[error("the signature of the update is invalid: {0}")]
+    Signature(String),
+    /// The current executable could not be replaced.
+    #[error("failed to install the update: {0}")]
+    Install(String),
+}
+
+impl From<reqwest::Error> for UpdateError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Network(err.to_string())
+    }
+}
+
+/// Looks for, downloads and installs updates of the app.
+pub struct Updater {
+    feed: ReleaseFeed,
+    public_key: String,
+    current_version: String,
+    client: reqwest::Client,
+    /// The path of the executable when the app started. Installing an update moves the running executable away.
+    exe: Option<PathBuf>,
+}
+
+impl Updater {
+    /// Create an updater for the app with the version `current_version`, usually `env!("CARGO_PKG_VERSION")`.
+    ///
+    /// `public_key` is the base64 minisign public key the updates are signed with: the second line of the `.pub` file.
+    pub fn new(
+        feed: ReleaseFeed,
+        public_key: impl Into<String>,
+        current_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            feed,
+            public_key: public_key.into(),
+            current_version: current_version.into(),
+            client: reqwest::Client::new(),
+            exe: std::env::current_exe().ok(),
+        }
+    }
+
+    /// Look for a release that is newer than the running version. Returns `None` if the app is up to date.
+    pub async fn check(&self) -> Result<Option<Release>, UpdateError> {
+        let release = match &self.feed {
+            ReleaseFeed::Json(url) => self.check_json(url).await?,
+            ReleaseFeed::GitHub { owner, repo } => self.check_github(owner, repo).await?,
+        };
+
+        let current = parse_version(&self.current_version)?;
+        let latest = parse_version(&release.version)?;
+        Ok((latest > current).then_some(release))
+    }
+
+    async fn check_json(&self, url: &str) -> Result<Release, UpdateError> {
+        #[derive(Deserialize)]
+        struct Feed {
+            version: String,
+            notes: Option<String>,
+            platforms: HashMap<String, Platform>,
+        }
+
+        #[derive(Deserialize)]
+        struct Platform {
+            url: String,
+            signature: String,
+        }
+
+        let feed: Feed = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(|err| UpdateError::Feed(err.to_string()))?;
+
+        let platform = platform();
+        let Some(update) = feed.platforms.get(&platform) else {
+            return Err(UpdateError::NoUpdateForPlatform(platform));
+        };
+        Ok(Release {
+            version: feed.version,
+            notes: feed.notes,
+            url: update.url.clone(),
+            signature: SignatureSource::Inline(update.signature.clone()),
+        })
+    }
+
+    async fn check_github(&self, owner: &str, repo: &str) -> Result<Release, UpdateError> {
+        #[derive(Deserialize)]
+        struct GitHubRelease {
+            tag_name: String,
+            body: Option<String>,
+            assets: Vec<Asset>,
+        }
+
+        let release: GitHubRelease = self
+            .client
+            .get(format!(
+                "https://api.github.com/repos/{owner}/{repo}/releases/latest"
+            ))
+            // The GitHub api rejects requests without a user agent
+            .header(reqwest::header::USER_AGENT, "dioxus-desktop-updater")
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .map_err(|err| UpdateError::Feed(err.to_string()))?;
+
+        let (update, signature) = find_assets(&release.assets, &platform())?;
+        Ok(Release {
+            version: release.tag_name,
+            notes: release.body,
+            url: update.browser_download_url.clone(),
+            signature: SignatureSource::Url(signature.browser_download_url.clone()),
+        })
+    }
+
+    /// Download the update of `release`, check its signature and replace the current executable with it. `on_progress` is called with the number of bytes that were downloaded and the size of the update, if the server sent it.
+    pub async fn download_and_install(
+        &self,
+        release: &Release,
+        mut on_progress: impl FnMut(u64, Option<u64>),
+    ) -> Result<(), UpdateError> {
+        let signature = match &release.signature {
+            SignatureSource::Inline(signature) => signature.clone(),
+            SignatureSource::Url(url) => {
+                self.client
+                    .get(url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .text()
+                    .await?
+            }
+        };
+
+        let mut response = self
+            .client
+            .get(&release.url)
+            .send()
+            .await?
+            .error_for_status()?;
+        let total = response.content_length();
+        let mut update = Vec::with_capacity(total.unwrap_or(0) as usize);
+        on_progress(0, total);
+        while let Some(chunk) = response.chunk().await? {
+            update.extend_from_slice(&chunk);
+            on_progress(update.len() as u64, total);
+        }
+
+        verify(&self.public_key, &update, &signature, &release.version)?;
+        let Some(exe) = &self.exe else {
+            return Err(UpdateError::Install(
+                "the path of the executable is unknown".to_string(),
+            ));
+        };
+        install(exe, &update)
+    }
+
+    /// Start the installed version of the app with the same arguments and exit.
+    pub fn restart(&self) {
+        let Some(exe) = &self.exe else {
+            return;
+        };
+        match std::process::Command::new(exe)
+            .args(std::env::args_os().skip(1))
+            .spawn()
+        {
+            Ok(_) => std::process::exit(0),
+            Err(err) => tracing::error!("Failed to restart the app: {err}"),
+        }
+    }
+}
+
+/// The name of the platform in release feeds, like `windows-x86_64`.
+fn platform() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+fn parse_version(version: &str) -> Result<semver::Version, UpdateError> {
+    semver::Version::parse(version.trim_start_matches('v'))
+        .map_err(|err| UpdateError::Feed(format!("invalid version {version:?}: {err}")))
+}
+
+#[derive(Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Find the executable for `platform` in the assets of a GitHub release, and the asset with its signature.
+fn find_assets<'a>(
+    assets: &'a [Asset],
+    platform: &str,
+) -> Result<(&'a Asset, &'a Asset), UpdateError> {
+    let update = assets
+        .iter()
+        .find(|asset| asset.name.contains(platform) && !asset.name.ends_with(".minisig"));
+    let Some(update) = update else {
+        return Err(UpdateError::NoUpdateForPlatform(platform.to_string()));
+    };
+    let signature_name = format!("{}.minisig", update.name);
+    let Some(signature) = assets.iter().find(|asset| asset.name == signature_name) else {
+        return Err(UpdateError::Signature(format!(
+            "the release has no {signature_name} asset"
+        )));
+    };
+    Ok((update, signature))
+}
+
+/// Check that `update` is signed with `public_key`, and that the trusted comment of the signature names `version`.
+fn verify(
+    public_key: &str,
+    update: &[u8],
+    signature: &str,
+    version: &str,
+) -> Result<(), UpdateError> {
+    let signature_error = |err: minisign_verify::Error| UpdateError::Signature(err.to_string());
+    let public_key = PublicKey::from_base64(public_key.trim()).map_err(signature_error)?;
+    let signature = Signature::decode(signature).map_err(signature_error)?;
+    // Verifying also checks the signature of the trusted comment, so the version can't be changed
+    public_key
+        .verify(update, &signature, false)
+        .map_err(signature_error)?;
+
+    let signed_version = signature
+        .trusted_comment()
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("version:"));
+    let Some(signed_version) = signed_version else {
+        return Err(UpdateError::Signature(
+            "the trusted comment has no version".to_string(),
+        ));
+    };
+    if parse_version(signed_version)? != parse_version(version)? {
+        return Err(UpdateError::Signature(format!(
+            "the update is signed for version {signed_version}, not {version}"
+        )));
+    }
+    Ok(())
+}
+
+/// Replace the executable at `exe` with the update. The running executable is moved next to it, because Windows can't overwrite it.
+fn install(exe: &Path, update: &[u8]) -> Result<(), UpdateError> {
+    let new = exe.with_extension("new");
+    std::fs::write(&new, update).map_err(install_error)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&new, std::fs::Permissions::from_mode(0o755))
+            .map_err(install_error)?;
+    }
+    swap(exe, &new)
+}
+
+/// Move `new` to `exe`, and `exe` to its `.old` path. Puts `exe` back if `new` can't be moved.
+fn swap(exe: &Path, new: &Path) -> Result<(), UpdateError> {
+    let old = exe.with_extension("old");
+    // The executable of the previous update can be removed once it isn't running anymore
+    _ = std::fs::remove_file(&old);
+    std::fs::rename(exe, &old).map_err(install_error)?;
+    if let Err(err) = std::fs::rename(new, exe) {
+        _ = std::fs::rename(&old, exe);
+        return Err(install_error(err));
+    }
+    Ok(())
+}
+
+fn install_error(err: std::io::Error) -> UpdateError {
+    UpdateError::Install(err.to_string())
+}
+
+/// Update the app from a release feed, with the state of the update as a signal. `init` only runs on the first render.
+///
+/// Nothing happens until [`UseUpdater::check`] is called.
+pub fn use_updater(cx: &ScopeState, init: impl FnOnce() -> Updater) -> &UseUpdater {
+    cx.use_hook(|| UseUpdater {
+        updater: Rc::new(init()),
+        state: Signal::new(UpdateState::Idle),
+        scope: cx.scope_id(),
+    })
+}
+
+/// The updater returned by [`use_updater`].
+#[derive(Clone)]
+pub struct UseUpdater {
+    updater: Rc<Updater>,
+    state: Signal<UpdateState>,
+    /// The component that owns the tasks of the updater
+    scope: ScopeId,
+}
+
+impl UseUpdater {
+    /// The state of the update.
+    pub fn state(&self) -> ReadOnlySignal<UpdateState> {
+        ReadOnlySignal::new(self.state)
+    }
+
+    /// Look for a newer release in the feed.
+    pub fn check(&self) {
+        let updater = self.updater.clone();
+        let state = self.state;
+        state.set(UpdateState::Checking);
+        spawn_at(
+            async move {
+                state.set(match updater.check().await {
+                    Ok(Some(release)) => UpdateState::Available(release),
+                    Ok(None) => UpdateState::UpToDate,
+                    Err(err) => UpdateState::Failed(err),
+                });
+            },
+            self.scope,
+        );
+    }
+
+    /// Download and install the release that [`UseUpdater::check`] found. Does nothing if no update is available.
+    pub fn install(&self) {
+        let UpdateState::Available(release) = self.state.peek().clone() else {
+            return;
+        };
+        let updater = self.updater.clone();
+        let state = self.state;
+        spawn_at(
+            async move {
+                let result = updater
+                    .download_and_install(&release, |downloaded, total| {
+                        state.set(UpdateState::Downloading { downloaded, total });
+                    })
+                    .await;
+                state.set(match result {
+                    Ok(()) => UpdateState::Ready(release),
+                    Err(err) => UpdateState::Failed(err),
+                });
+            },
+            self.scope,
+        );
+    }
+
+    /// Start the installed update and exit.
+    pub fn restart(&self) {
+        self.updater.restart();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUBLIC_KEY: &str = "RWQBI0VniavN7xMonnKJXMf4BzanhIcG6GyzAwcIVVi9edxTZiJJnZo7";
+    const UPDATE: &[u8] = b"the new executable";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQBI0VniavN7yyoTxcHkCvRtW9vLqi96gcmYWqZl+BH/srwp03gLiawDJ03iUsGilqqQsMPLbi2ICW+6Ve8lZqGmSsIUGKY/AQ=
+trusted comment: timestamp:1700000000\tfile:my-app\tversion:1.2.0
+4QDfW/pBK8n+ZbKxo7WF2T3M6wd1iCbHWKYdyIWipI/sI31UEeVGRt6MCaLmbeRl4n9D/t8WILgDWJ4yewlHCQ==
+";
+    const UNVERSIONED_SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQBI0VniavN7yyoTxcHkCvRtW9vLqi96gcmYWqZl+BH/srwp03gLiawDJ03iUsGilqqQsMPLbi2ICW+6Ve8lZqGmSsIUGKY/AQ=
+trusted comment: timestamp:1700000000\tfile:my-app
+kUo+Qb3tkAdyb2X60yGY1r6RaY/S67NKrpZc9rt8VS9lpOE5Jhjqcgz08inue+qb9WVCDGCtdBab5TRKSCFtAQ==
+";
+
+    #[test]
+    fn parses_versions_with_a_v_prefix() {
+        assert_eq!(
+            parse_version("v1.2.0").unwrap(),
+            semver::Version::new(1, 2, 0)
+        );
+        assert!(parse_version("1.10.0").unwrap() > parse_version("1.9.0").unwrap());
+        assert!(matches!(parse_version("latest"), Err(UpdateError::Feed(_))));
+    }
+
+    #[test]
+    fn verifies_the_signature_and_version() {
+        assert_eq!(verify(PUBLIC_KEY, UPDATE, SIGNATURE, "1.2.0"), Ok(()));
+        assert_eq!(verify(PUBLIC_KEY, UPDATE, SIGNATURE, "v1.2.0"), Ok(()));
+
+        // A different update, an older release served as a newer one, or a signature without a version are rejected
+        assert!(verify(PUBLIC_KEY, b"another executable", SIGNATURE, "1.2.0").is_err());
+        assert!(matches!(
+            verify(PUBLIC_KEY, UPDATE, SIGNATURE, "1.3.0"),
+            Err(UpdateError::Signature(_))
+        ));
+        assert!(matches!(
+            verify(PUBLIC_KEY, UPDATE, UNVERSIONED_SIGNATURE, "1.2.0"),
+            Err(UpdateError::Signature(_))
+        ));
+
+        // The trusted comment is signed, so the version in it can't be edited
+        let edited = SIGNATURE.replace("version:1.2.0", "version:1.3.0");
+        assert!(verify(PUBLIC_KEY, UPDATE, &edited, "1.3.0").is_err());
+    }
+
+    #[test]
+    fn finds_the_update_and_signature_assets() {
+        let asset = |name: &str| Asset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{name}"),
+        };
+        let assets = [
+            asset("my-app-linux-x86_64.minisig"),
+            asset("my-app-linux-x86_64"),
+            asset("my-app-windows-x86_64.exe"),
+        ];
+
+        let (update, signature) = find_assets(&assets, "linux-x86_64").unwrap();
+        assert_eq!(update.name, "my-app-linux-x86_64");
+        assert_eq!(signature.name, "my-app-linux-x86_64.minisig");
+
+        assert!(matches!(
+            find_assets(&assets, "windows-x86_64"),
+            Err(UpdateError::Signature(_))
+        ));
+        assert!(matches!(
+            find_assets(&assets, "macos-aarch64"),
+            Err(UpdateError::NoUpdateForPlatform(_))
+        ));
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("dioxus-updater-{name}-{}", std::process::id()));
+        _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn install_moves_the_old_executable_away() {
+        let dir = temp_dir("install");
+        let exe = dir.join("my-app");
+        std::fs::write(&exe, "the old executable").unwrap();
+
+        install(&exe, UPDATE).unwrap();
+        assert_eq!(std::fs::read(&exe).unwrap(), UPDATE);
+        assert_eq!(
+            std::fs::read(exe.with_extension("old")).unwrap(),
+            b"the old executable"
+        );
+        assert!(!exe.with_extension("new").exists());
+
+        _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn swap_puts_the_executable_back_if_the_update_is_missing() {
+        let dir = temp_dir("rollback");
+        let exe = dir.join("my-app");
+        std::fs::write(&exe, "the old executable").unwrap();
+
+        assert!(matches!(
+            swap(&exe, &dir.join("missing")),
+            Err(UpdateError::Install(_))
+        ));
+        assert_eq!(std::fs::read(&exe).unwrap(), b"the old executable");
+        assert!(!exe.with_extension("old").exists());
+
+        _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,39 +1,117 @@
 use crate::{
+    animation::DesktopAnimationFrameProvider,
     app::SharedContext,
     assets::AssetHandlerRegistry,
+    battery::DesktopBatteryProvider,
+    clipboard::DesktopClipboardProvider,
     edits::EditQueue,
     eval::DesktopEvalProvider,
+    file_dialog::DesktopFileDialogProvider,
+    hotkeys::DesktopHotkeyProvider,
     ipc::{EventData, UserWindowEvent},
+    menubar::MenuBar,
+    network::DesktopNetworkStatusProvider,
+    notification::DesktopNotificationProvider,
     protocol::{self},
+    splash::Splash,
     waker::tao_waker,
+    window_effect,
+    window_level::{self, WindowLevel},
+    window_size::DesktopWindowSizeProvider,
+    window_state::WindowState,
     Config, DesktopContext, DesktopService,
 };
-use dioxus_core::VirtualDom;
-use dioxus_html::prelude::EvalProvider;
+use dioxus_core::{
+    prelude::{spawn_forever, RuntimeGuard},
+    ScopeId, VirtualDom,
+};
+use dioxus_html::prelude::{
+    AnimationFrameProvider, BatteryProvider, ClipboardProvider, EvalProvider, FileDialogProvider,
+    HotkeyProvider, NetworkStatusProvider, NotificationProvider, WindowSizeProvider,
+};
 use futures_util::{pin_mut, FutureExt};
-use std::{any::Any, rc::Rc, task::Waker};
-use wry::{RequestAsyncResponder, WebContext, WebViewBuilder};
+use std::{
+    path::PathBuf,
+    rc::Rc,
+    task::Waker,
+    time::{Duration, Instant},
+};
+use wry::{FileDropEvent, RequestAsyncResponder, WebContext, WebViewBuilder};
 
 pub struct WebviewInstance {
     pub dom: VirtualDom,
     pub desktop_context: DesktopContext,
     pub waker: Waker,
+    frame_budget: Option<Duration>,
 
     // Wry assumes the webcontext is alive for the lifetime of the webview.
     // We need to keep the webcontext alive, otherwise the webview will crash
     _web_context: WebContext,
 
-    // Same with the menu.
-    // Currently it's a box<dyn any> because 1) we don't touch it and 2) we support a number of platforms
-    // like ios where muda does not give us a menu type. It sucks but alas.
-    //
-    // This would be a good thing for someone looking to contribute to fix.
-    _menu: Option<Box<dyn Any>>,
+    /// Where the geometry of the window is saved when it closes
+    window_state: Option<PathBuf>,
+
+    /// The files from outside the app that are dragged over the window
+    pub(crate) dragged_files: Vec<PathBuf>,
+
+    /// The window is hidden until the webview reports that the first render was applied
+    pub(crate) show_on_render: bool,
+
+    /// The splash screen that is shown until the window is shown
+    pub(crate) splash: Option<Splash>,
 }
 
 impl WebviewInstance {
-    pub fn new(mut cfg: Config, dom: VirtualDom, shared: Rc<SharedContext>) -> WebviewInstance {
-        let window = cfg.window.clone().build(&shared.target).unwrap();
+    pub fn new(mut cfg: Config, mut dom: VirtualDom, shared: Rc<SharedContext>) -> WebviewInstance {
+        // Every window runs on the executor the app was launched with
+        if let Some(executor) = shared.executor.clone() {
+            dom = dom.with_executor(executor);
+        }
+
+        let splash = cfg
+            .splash_screen
+            .as_ref()
+            .and_then(|splash| splash.show(&shared.target));
+
+        let mut builder = cfg.window.clone();
+        if let Some(state) = cfg.window_state.as_deref().and_then(WindowState::load) {
+            builder = state.restore(builder, &shared.target);
+        }
+        let show_on_render = cfg.show_on_first_render && cfg.window.window.visible;
+        if show_on_render {
+            builder = builder.with_visible(false);
+        }
+        let transparent = cfg.window.window.transparent || cfg.window_effect.is_some();
+        let window = builder
+            .with_transparent(transparent)
+            .build(&shared.target)
+            .unwrap();
+
+        let level = match cfg.window_level {
+            Some(level) => {
+                level.apply(&window);
+                level
+            }
+            None if cfg.window.window.always_on_top => WindowLevel::AlwaysOnTop,
+            None if cfg.window.window.always_on_bottom => WindowLevel::AlwaysOnBottom,
+            None => WindowLevel::Normal,
+        };
+        if cfg.skip_taskbar {
+            window_level::set_skip_taskbar(&window, true);
+        }
+        if cfg.transparent_titlebar {
+            window_effect::set_transparent_titlebar(&window, true);
+        }
+        // Only remember the effect if it was applied, so it isn't cleared later
+        let effect = cfg
+            .window_effect
+            .filter(|effect| match effect.apply(&window) {
+                Ok(()) => true,
+                Err(err) => {
+                    tracing::warn!("Failed to apply the window effect: {err}");
+                    false
+                }
+            });
 
         // We assume that if the icon is None in cfg, then the user just didnt set it
         if cfg.window.window.window_icon.is_none() {
@@ -61,6 +139,7 @@ impl WebviewInstance {
         let asset_handlers_ = asset_handlers.clone();
         let edit_queue_ = edit_queue.clone();
         let proxy_ = shared.proxy.clone();
+        let file_drop_proxy = shared.proxy.clone();
 
         let request_handler = move |request, responder: RequestAsyncResponder| {
             // Try to serve the index file first
@@ -113,16 +192,29 @@ impl WebviewInstance {
         };
 
         webview = webview
-            .with_transparent(cfg.window.window.transparent)
+            .with_transparent(transparent)
             .with_url("dioxus://index.html/")
             .unwrap()
             .with_ipc_handler(ipc_handler)
+            .with_file_drop_handler(file_drop_handler)
             .with_asynchronous_custom_protocol(String::from("dioxus"), request_handler)
             .with_web_context(&mut web_context);
 
-        if let Some(handler) = file_handler {
-            webview = webview.with_file_drop_handler(move |evt| handler(window_id, evt))
-        }
+        let file_drop_handler = move |evt: FileDropEvent| {
+            // Tell the app which files are dragged, so the html drag events the webview fires next can carry their paths
+            let paths = match &evt {
+                FileDropEvent::Hovered { paths, .. } | FileDropEvent::Dropped { paths, .. } => {
+                    paths.clone()
+                }
+                _ => Vec::new(),
+            };
+            _ = file_drop_proxy.send_event(UserWindowEvent(EventData::FileDrop(paths), window_id));
+
+            match &file_handler {
+                Some(handler) => handler(window_id, evt),
+                None => false,
+            }
+        };
 
         if let Some(color) = cfg.background_color {
             webview = webview.with_background_color(color);
@@ -132,6 +224,20 @@ impl WebviewInstance {
             webview = webview.with_custom_protocol(name, handler);
         }
 
+        for (name, handler) in cfg.async_protocols.drain(..) {
+            let runtime = dom.runtime();
+            webview = webview.with_asynchronous_custom_protocol(
+                name,
+                move |request, responder: RequestAsyncResponder| {
+                    let response = handler(request);
+                    // Await the response as a task of the root component, so it runs in the context of the executor
+                    RuntimeGuard::with(runtime.clone(), Some(ScopeId::ROOT), || {
+                        spawn_forever(async move { responder.respond(response.await) })
+                    });
+                },
+            );
+        }
+
         const INITIALIZATION_SCRIPT: &str = r#"
         if (document.addEventListener) {
         document.addEventListener('contextmenu', function(e) {
@@ -147,19 +253,13 @@ impl WebviewInstance {
         if cfg.disable_context_menu {
             // in release mode, we don't want to show the dev tool or reload menus
             webview = webview.with_initialization_script(INITIALIZATION_SCRIPT)
-        } else {
-            // in debug, we are okay with the reload menu showing and dev tool
-            webview = webview.with_devtools(true);
         }
 
+        webview = webview.with_devtools(cfg.devtools);
+
         let webview = webview.build().unwrap();
 
-        // TODO: allow users to specify their own menubars, again :/
-        let menu = if cfg!(not(any(target_os = "android", target_os = "ios"))) {
-            crate::menubar::build_menu(&window, cfg.enable_default_menu_bar)
-        } else {
-            None
-        };
+        let menubar = MenuBar::new(&window, cfg.enable_default_menu_bar, cfg.menu.as_ref());
 
         let desktop_context = Rc::from(DesktopService::new(
             webview,
@@ -167,7 +267,10 @@ impl WebviewInstance {
             shared.clone(),
             edit_queue,
             asset_handlers,
+            menubar,
         ));
+        desktop_context.window_effect.set(effect);
+        desktop_context.window_level.set(level);
 
         // Provide the desktop context to the virtualdom
         dom.base_scope().provide_context(desktop_context.clone());
@@ -180,11 +283,48 @@ impl WebviewInstance {
 
         dom.base_scope().provide_context(provider);
 
+        // The clipboard provider has to be provided as dyn ClipboardProvider for the same reason
+        let clipboard: Rc<dyn ClipboardProvider> =
+            Rc::new(DesktopClipboardProvider::new(desktop_context.clone()));
+        dom.base_scope().provide_context(clipboard);
+
+        let window_size: Rc<dyn WindowSizeProvider> =
+            Rc::new(DesktopWindowSizeProvider::new(desktop_context.clone()));
+        dom.base_scope().provide_context(window_size);
+
+        let hotkeys: Rc<dyn HotkeyProvider> =
+            Rc::new(DesktopHotkeyProvider::new(desktop_context.clone()));
+        dom.base_scope().provide_context(hotkeys);
+
+        let animation_frame: Rc<dyn AnimationFrameProvider> =
+            Rc::new(DesktopAnimationFrameProvider::new(shared.executor.clone()));
+        dom.base_scope().provide_context(animation_frame);
+
+        let network: Rc<dyn NetworkStatusProvider> =
+            Rc::new(DesktopNetworkStatusProvider::new(desktop_context.clone()));
+        dom.base_scope().provide_context(network);
+
+        let battery: Rc<dyn BatteryProvider> =
+            Rc::new(DesktopBatteryProvider::new(desktop_context.clone()));
+        dom.base_scope().provide_context(battery);
+
+        let notifications: Rc<dyn NotificationProvider> =
+            Rc::new(DesktopNotificationProvider::new(desktop_context.clone()));
+        dom.base_scope().provide_context(notifications);
+
+        let file_dialog: Rc<dyn FileDialogProvider> =
+            Rc::new(DesktopFileDialogProvider::new(desktop_context.clone()));
+        dom.base_scope().provide_context(file_dialog);
+
         WebviewInstance {
             waker: tao_waker(shared.proxy.clone(), desktop_context.window.id()),
             desktop_context,
             dom,
-            _menu: menu,
+            frame_budget: cfg.frame_budget,
+            window_state: cfg.window_state.clone(),
+            dragged_files: Vec::new(),
+            show_on_render,
+            splash,
             _web_context: web_context,
         }
     }
@@ -206,7 +346,30 @@ impl WebviewInstance {
                 }
             }
 
-            self.desktop_context.send_edits(self.dom.render_immediate());
+            let Some(budget) = self.frame_budget else {
+                self.desktop_context.send_edits(self.dom.render_immediate());
+                continue;
+            };
+
+            let frame_start = Instant::now();
+            let edits = self
+                .dom
+                .render_with_budget(|| frame_start.elapsed() >= budget);
+            self.desktop_context.send_edits(edits);
+
+            // Let the event loop handle input before rendering the rest of the update
+            if self.dom.has_dirty_scopes() {
+                self.waker.wake_by_ref();
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for WebviewInstance {
+    fn drop(&mut self) {
+        if let Some(path) = &self.window_state {
+            WindowState::save(&self.desktop_context.window, path);
         }
     }
 }
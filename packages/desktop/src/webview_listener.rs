@@ -0,0 +1,84 @@
+//! Event listeners in the webview that send their events to rust.
+
+use crate::DesktopContext;
+use dioxus_core::{
+    prelude::{remove_future, spawn_at},
+    ScopeId, TaskId,
+};
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A listener for `event` on the webview's window. The listener is removed from the webview and stops sending events when dropped.
+pub(crate) struct WindowListener {
+    id: usize,
+    event: &'static str,
+    task: Option<TaskId>,
+    desktop_ctx: DesktopContext,
+}
+
+impl WindowListener {
+    /// Listen for `event` on the webview's window. `serialize` is the body of a javascript function of `event` that returns the value passed to `on_event`. Events it returns `undefined` for are not sent to rust.
+    pub(crate) fn new(
+        desktop_ctx: DesktopContext,
+        event: &'static str,
+        serialize: &str,
+        mut on_event: impl FnMut(Value) + 'static,
+    ) -> Self {
+        // Identifies the listener in the webview, so it can be removed when the handle is dropped
+        static NEXT_LISTENER: AtomicUsize = AtomicUsize::new(0);
+        let id = NEXT_LISTENER.fetch_add(1, Ordering::Relaxed);
+
+        let script = format!(
+            r#"
+            const serialize = (event) => {{ {serialize} }};
+            const listener = (event) => {{
+                const value = serialize(event);
+                if (value !== undefined) {{
+                    dioxus.send(value);
+                }}
+            }};
+            window.__dioxus_listeners = window.__dioxus_listeners || {{}};
+            window.__dioxus_listeners[{id}] = listener;
+            window.addEventListener("{event}", listener);
+            return true;
+            "#
+        );
+        let mut query = desktop_ctx
+            .query
+            .new_query::<bool>(&script, desktop_ctx.clone());
+
+        // The listener belongs to this handle rather than the component that is rendering, so the task lives in the root scope
+        let task = spawn_at(
+            async move {
+                while let Ok(value) = query.recv().await {
+                    on_event(value);
+                }
+            },
+            ScopeId::ROOT,
+        );
+
+        Self {
+            id,
+            event,
+            task,
+            desktop_ctx,
+        }
+    }
+
+    /// The window the listener listens to.
+    pub(crate) fn desktop(&self) -> &DesktopContext {
+        &self.desktop_ctx
+    }
+}
+
+impl Drop for WindowListener {
+    fn drop(&mut self) {
+        if let Some(task) = self.task {
+            remove_future(task);
+        }
+        _ = self.desktop_ctx.webview.evaluate_script(&format!(
+            "if (window.__dioxus_listeners) {{ window.removeEventListener(\"{1}\", window.__dioxus_listeners[{0}]); delete window.__dioxus_listeners[{0}]; }}",
+            self.id, self.event
+        ));
+    }
+}
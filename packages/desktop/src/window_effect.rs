@@ -0,0 +1,83 @@
+//! Translucent window backgrounds, set with [`Config::with_window_effect`](crate::Config::with_window_effect) or [`DesktopContext::set_window_effect`](crate::DesktopContext::set_window_effect).
+
+use tao::window::Window;
+use thiserror::Error;
+
+pub use window_vibrancy::NSVisualEffectMaterial as VibrancyMaterial;
+
+/// A background effect that shows the desktop blurred behind a window.
+///
+/// The effect is only visible through the transparent parts of the page, so give `html` and `body` a transparent or translucent background. On macOS the webview can only be transparent with the `transparent` feature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowEffect {
+    /// A blur tinted with an RGBA color. Only supported on Windows 7 and 10, it lags while the window is resized on Windows 11.
+    Blur(Option<(u8, u8, u8, u8)>),
+
+    /// The acrylic material tinted with an RGBA color. Only supported on Windows 10 and 11.
+    Acrylic(Option<(u8, u8, u8, u8)>),
+
+    /// The mica material, in dark mode, light mode, or the mode of the system if it is `None`. Only supported on Windows 11.
+    Mica(Option<bool>),
+
+    /// The vibrancy of a material of the system. Only supported on macOS.
+    Vibrancy(VibrancyMaterial),
+}
+
+/// An error from applying a [`WindowEffect`].
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum WindowEffectError {
+    /// The effect is not supported on this platform or version of the platform.
+    #[error("The window effect is not supported: {0}")]
+    Unsupported(String),
+}
+
+impl From<window_vibrancy::Error> for WindowEffectError {
+    fn from(err: window_vibrancy::Error) -> Self {
+        WindowEffectError::Unsupported(err.to_string())
+    }
+}
+
+impl WindowEffect {
+    pub(crate) fn apply(&self, window: &Window) -> Result<(), WindowEffectError> {
+        match *self {
+            WindowEffect::Blur(color) => window_vibrancy::apply_blur(window, color)?,
+            WindowEffect::Acrylic(color) => window_vibrancy::apply_acrylic(window, color)?,
+            WindowEffect::Mica(dark) => window_vibrancy::apply_mica(window, dark)?,
+            WindowEffect::Vibrancy(material) => {
+                window_vibrancy::apply_vibrancy(window, material, None, None)?
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn clear(&self, window: &Window) -> Result<(), WindowEffectError> {
+        match self {
+            WindowEffect::Blur(_) => window_vibrancy::clear_blur(window)?,
+            WindowEffect::Acrylic(_) => window_vibrancy::clear_acrylic(window)?,
+            WindowEffect::Mica(_) => window_vibrancy::clear_mica(window)?,
+            WindowEffect::Vibrancy(_) => {
+                window_vibrancy::clear_vibrancy(window)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Let the content of the window extend below a transparent title bar. Only supported on macOS.
+pub(crate) fn set_transparent_titlebar(window: &Window, transparent: bool) {
+    #[cfg(target_os = "macos")]
+    {
+        use tao::platform::macos::WindowExtMacOS;
+
+        window.set_titlebar_transparent(transparent);
+        window.set_fullsize_content_view(transparent);
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = window;
+        tracing::trace!(
+            "The title bar can't be transparent on this platform, ignoring {transparent}"
+        );
+    }
+}
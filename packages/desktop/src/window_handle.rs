@@ -0,0 +1,68 @@
+use crate::{
+    desktop_context::{DesktopContext, DesktopService},
+    ipc::{EventData, UserWindowEvent},
+    monitors::MonitorId,
+};
+use std::rc::Weak;
+use tao::{event_loop::EventLoopProxy, window::WindowId};
+
+/// A handle to a window opened with [`new_window`](crate::new_window).
+///
+/// The handle does not keep the window open. Once the window is closed, [`WindowHandle::desktop`] returns `None` and the other methods do nothing.
+#[derive(Clone)]
+pub struct WindowHandle {
+    id: WindowId,
+    desktop: Weak<DesktopService>,
+    proxy: EventLoopProxy<UserWindowEvent>,
+}
+
+impl WindowHandle {
+    pub(crate) fn new(
+        desktop: Weak<DesktopService>,
+        id: WindowId,
+        proxy: EventLoopProxy<UserWindowEvent>,
+    ) -> Self {
+        Self { id, desktop, proxy }
+    }
+
+    /// The id of the window.
+    pub fn id(&self) -> WindowId {
+        self.id
+    }
+
+    /// Get the imperative handle of the window, or `None` if it was closed.
+    pub fn desktop(&self) -> Option<DesktopContext> {
+        self.desktop.upgrade()
+    }
+
+    /// Check if the window is still open.
+    pub fn is_open(&self) -> bool {
+        self.desktop.strong_count() > 0
+    }
+
+    /// Show the window if it is hidden and bring it in front of the other windows.
+    pub fn focus(&self) {
+        if let Some(desktop) = self.desktop() {
+            desktop.window.set_visible(true);
+            desktop.window.set_minimized(false);
+            desktop.window.set_focus();
+        }
+    }
+
+    /// Center the window on a monitor from [`use_monitors`](crate::use_monitors). A borderless fullscreen window covers the new monitor instead.
+    ///
+    /// Returns `false` if the window is closed or the monitor is no longer connected.
+    pub fn move_to_monitor(&self, id: &MonitorId) -> bool {
+        match self.desktop() {
+            Some(desktop) => crate::monitors::move_to_monitor(&desktop.window, id),
+            None => false,
+        }
+    }
+
+    /// Close the window. Like [`DesktopService::close_window`], this does not ask the before quit handlers of the window.
+    pub fn close(&self) {
+        _ = self
+            .proxy
+            .send_event(UserWindowEvent(EventData::CloseWindow, self.id));
+    }
+}
@@ -0,0 +1,82 @@
+//! Where a window is stacked relative to other windows, set with [`DesktopContext::set_window_level`](crate::DesktopContext::set_window_level).
+
+use tao::window::Window;
+
+/// Where a window is stacked relative to the windows of other apps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum WindowLevel {
+    /// A normal window, that is covered by windows that are focused after it.
+    #[default]
+    Normal,
+
+    /// Stay above normal windows, like a palette or an overlay.
+    AlwaysOnTop,
+
+    /// Stay below normal windows, like a desktop widget.
+    AlwaysOnBottom,
+}
+
+impl From<bool> for WindowLevel {
+    fn from(always_on_top: bool) -> Self {
+        match always_on_top {
+            true => WindowLevel::AlwaysOnTop,
+            false => WindowLevel::Normal,
+        }
+    }
+}
+
+impl WindowLevel {
+    pub(crate) fn apply(&self, window: &Window) {
+        // Unset the other level first, a window can't be on top and on the bottom
+        match self {
+            WindowLevel::Normal => {
+                window.set_always_on_top(false);
+                window.set_always_on_bottom(false);
+            }
+            WindowLevel::AlwaysOnTop => {
+                window.set_always_on_bottom(false);
+                window.set_always_on_top(true);
+            }
+            WindowLevel::AlwaysOnBottom => {
+                window.set_always_on_top(false);
+                window.set_always_on_bottom(true);
+            }
+        }
+    }
+}
+
+/// Hide the window from the taskbar and the alt-tab switcher. Only supported on Windows and Linux.
+pub(crate) fn set_skip_taskbar(window: &Window, skip: bool) {
+    #[cfg(target_os = "windows")]
+    {
+        use tao::platform::windows::WindowExtWindows;
+        window.set_skip_taskbar(skip);
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    {
+        use tao::platform::unix::WindowExtUnix;
+        window.set_skip_taskbar(skip);
+    }
+
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )))]
+    {
+        let _ = window;
+        tracing::trace!(
+            "Windows can't be hidden from the taskbar on this platform, ignoring {skip}"
+        );
+    }
+}
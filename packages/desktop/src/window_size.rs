@@ -0,0 +1,51 @@
+use crate::{DesktopContext, WryEventHandler};
+use dioxus_html::window_size::WindowSizeProvider;
+use std::{any::Any, rc::Rc};
+use tao::{
+    event::{Event, WindowEvent},
+    window::Window,
+};
+
+/// Reads the inner size of the window, and listens for tao's resize events.
+pub(crate) struct DesktopWindowSizeProvider {
+    desktop_ctx: DesktopContext,
+}
+
+impl DesktopWindowSizeProvider {
+    pub(crate) fn new(desktop_ctx: DesktopContext) -> Self {
+        Self { desktop_ctx }
+    }
+}
+
+impl WindowSizeProvider for DesktopWindowSizeProvider {
+    fn size(&self) -> (u32, u32) {
+        logical_size(&self.desktop_ctx.window)
+    }
+
+    fn subscribe(&self, mut on_resize: Box<dyn FnMut((u32, u32))>) -> Box<dyn Any> {
+        // The handler is owned by the window, so only keep a weak reference to it
+        let desktop = Rc::downgrade(&self.desktop_ctx);
+        let id = self.desktop_ctx.create_wry_event_handler(move |event, _| {
+            if let Event::WindowEvent {
+                event: WindowEvent::Resized(_) | WindowEvent::ScaleFactorChanged { .. },
+                ..
+            } = event
+            {
+                if let Some(desktop) = desktop.upgrade() {
+                    on_resize(logical_size(&desktop.window));
+                }
+            }
+        });
+
+        Box::new(WryEventHandler {
+            handlers: self.desktop_ctx.shared.event_handlers.clone(),
+            id,
+        })
+    }
+}
+
+/// The inner size of the window in logical pixels, which match CSS pixels in the webview.
+fn logical_size(window: &Window) -> (u32, u32) {
+    let size = window.inner_size().to_logical::<f64>(window.scale_factor());
+    (size.width.round() as u32, size.height.round() as u32)
+}
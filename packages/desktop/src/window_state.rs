@@ -0,0 +1,137 @@
+//! The size and position of a window saved between launches, enabled with [`Config::with_window_state_persistence`](crate::Config::with_window_state_persistence).
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tao::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event_loop::EventLoopWindowTarget,
+    monitor::MonitorHandle,
+    window::{Window, WindowBuilder},
+};
+
+/// The geometry of a window in physical pixels, saved as json.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct WindowState {
+    width: u32,
+    height: u32,
+    x: i32,
+    y: i32,
+    maximized: bool,
+    monitor: Option<String>,
+}
+
+impl WindowState {
+    /// Read the state saved at `path`. Returns `None` if nothing was saved yet or the file is invalid.
+    pub(crate) fn load(path: &Path) -> Option<Self> {
+        let json = std::fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(state) => Some(state),
+            Err(err) => {
+                tracing::warn!(
+                    "Ignoring the saved window state in {}: {err}",
+                    path.display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Save the geometry of `window` to `path`.
+    pub(crate) fn save(window: &Window, path: &Path) {
+        let state = Self::capture(window, Self::load(path));
+        let json = match serde_json::to_string_pretty(&state) {
+            Ok(json) => json,
+            Err(err) => {
+                tracing::error!("Failed to serialize the window state: {err}");
+                return;
+            }
+        };
+        if let Some(parent) = path.parent() {
+            _ = std::fs::create_dir_all(parent);
+        }
+        if let Err(err) = std::fs::write(path, json) {
+            tracing::error!(
+                "Failed to save the window state to {}: {err}",
+                path.display()
+            );
+        }
+    }
+
+    fn capture(window: &Window, previous: Option<Self>) -> Self {
+        let monitor = window.current_monitor().and_then(|monitor| monitor.name());
+        let maximized = window.is_maximized();
+
+        // A maximized or minimized window doesn't know the geometry it is restored to, so keep the last one that was saved
+        if maximized || window.is_minimized() {
+            if let Some(previous) = previous {
+                return Self {
+                    maximized,
+                    monitor: monitor.or(previous.monitor),
+                    ..previous
+                };
+            }
+        }
+
+        let size = window.inner_size();
+        let position = window.outer_position().unwrap_or_default();
+        Self {
+            width: size.width,
+            height: size.height,
+            x: position.x,
+            y: position.y,
+            maximized,
+            monitor,
+        }
+    }
+
+    /// Open the window with this geometry. If the monitor it was on is gone, the window keeps its size but the platform picks its position.
+    pub(crate) fn restore<T>(
+        &self,
+        builder: WindowBuilder,
+        target: &EventLoopWindowTarget<T>,
+    ) -> WindowBuilder {
+        let monitor = self.monitor.as_ref().and_then(|name| {
+            target
+                .available_monitors()
+                .find(|monitor| monitor.name().as_ref() == Some(name))
+        });
+
+        let builder = builder.with_maximized(self.maximized);
+        match monitor {
+            Some(monitor) => {
+                let (width, height, x, y) = self.fit(&monitor);
+                builder
+                    .with_inner_size(PhysicalSize::new(width, height))
+                    .with_position(PhysicalPosition::new(x, y))
+            }
+            None => {
+                let (width, height) = match target.primary_monitor() {
+                    Some(monitor) => {
+                        let (width, height, _, _) = self.fit(&monitor);
+                        (width, height)
+                    }
+                    None => (self.width, self.height),
+                };
+                builder.with_inner_size(PhysicalSize::new(width, height))
+            }
+        }
+    }
+
+    /// Shrink and move the window so it is fully on `monitor`, in case its resolution changed.
+    fn fit(&self, monitor: &MonitorHandle) -> (u32, u32, i32, i32) {
+        let origin = monitor.position();
+        let size = monitor.size();
+
+        let width = self.width.min(size.width);
+        let height = self.height.min(size.height);
+        let x = self
+            .x
+            .max(origin.x)
+            .min(origin.x + (size.width - width) as i32);
+        let y = self
+            .y
+            .max(origin.y)
+            .min(origin.y + (size.height - height) as i32);
+        (width, height, x, y)
+    }
+}
@@ -21,10 +21,37 @@ pub use dioxus_rsx as rsx;
 #[cfg(feature = "macro")]
 pub use dioxus_core_macro as core_macro;
 
+#[cfg(feature = "signals")]
+pub use dioxus_signals as signals;
+
+#[cfg(feature = "web")]
+pub use dioxus_web as web;
+
+#[cfg(all(not(target_arch = "wasm32"), feature = "desktop"))]
+pub use dioxus_desktop as desktop;
+
+#[cfg(feature = "ssr")]
+pub use dioxus_ssr as ssr;
+
+#[cfg(feature = "liveview")]
+pub use dioxus_liveview as liveview;
+
 pub mod prelude {
     #[cfg(feature = "hooks")]
     pub use crate::hooks::*;
 
+    #[cfg(feature = "signals")]
+    pub use crate::signals::*;
+
+    // Both crates have a `use_effect` and a `use_selector`. The prelude keeps the hooks versions, the signal versions
+    // are still available from `dioxus::signals`
+    #[cfg(all(feature = "hooks", feature = "signals"))]
+    pub use crate::hooks::{use_effect, use_selector};
+
+    // The hooks crate still has the deprecated `use_callback`, the prelude keeps the core version
+    #[cfg(feature = "hooks")]
+    pub use dioxus_core::prelude::use_callback;
+
     pub use dioxus_core::prelude::*;
 
     #[cfg(feature = "macro")]
@@ -0,0 +1,43 @@
+//! Measures how a store shared between threads scales when every thread creates and drops boxes at the same time.
+//!
+//! `single_shard` uses one free list behind one lock, like stores created with `Store::new`. `sharded` gives every thread its own shard, like the store behind `SyncStorage::owner` does on a machine with one CPU per thread.
+//!
+//! Run with `cargo bench --bench lock`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use generational_box::{Store, SyncStorage};
+
+criterion_group!(mbenches, churn);
+criterion_main!(mbenches);
+
+const THREADS: usize = 8;
+const BOXES_PER_THREAD: usize = 1_000;
+
+fn churn(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sync_store_churn");
+
+    for (name, shards) in [("single_shard", 1), ("sharded", THREADS)] {
+        let store = Store::<SyncStorage>::with_shards(shards);
+        // Fill the free list so the benchmark measures recycling instead of allocating
+        run(&store);
+
+        group.bench_function(name, |b| b.iter(|| run(&store)));
+    }
+
+    group.finish();
+}
+
+fn run(store: &Store<SyncStorage>) {
+    std::thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for _ in 0..10 {
+                    let owner = store.owner();
+                    for i in 0..BOXES_PER_THREAD {
+                        owner.insert(i);
+                    }
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,240 @@
+use core::{
+    cell::{Cell, Ref, RefCell, RefMut},
+    ops::{Deref, DerefMut},
+};
+
+use crate::{AnyStorage, BorrowError, GenerationalBox, Storage, StorageBorrowError, UnsyncStorage};
+#[cfg(feature = "std")]
+use crate::{Owner, Store};
+
+#[cfg(feature = "std")]
+thread_local! {
+    static COPY_STORES: RefCell<std::collections::BTreeMap<core::any::TypeId, Box<dyn core::any::Any>>> =
+        RefCell::new(std::collections::BTreeMap::new());
+}
+
+/// A storage for `Copy` values that are read far more often than they are written, like counters and flags.
+///
+/// The value is kept in a [`Cell`], so [`GenerationalBox::get`] copies it out without a lock, a guard or a borrow record, even in debug builds. Boxes in this storage can still be borrowed with [`GenerationalBox::read`] and [`GenerationalBox::write`]. Those guards borrow a copy of the value, and a write guard copies its changes back when it is dropped.
+///
+/// Like [`UnsyncStorage`], the storage can only be used on one thread, and [`CopyStorage::owner`] needs the `std` feature.
+///
+/// ```rust
+/// use generational_box::CopyStorage;
+///
+/// let owner = CopyStorage::<u32>::owner();
+/// let count = owner.insert(0);
+/// count.set(count.get() + 1);
+/// assert_eq!(count.get(), 1);
+/// ```
+pub struct CopyStorage<T: Copy + 'static> {
+    value: Cell<Option<T>>,
+    // The copy that read and write guards borrow, because a cell can't hand out references
+    borrowed: RefCell<Option<T>>,
+    writing: Cell<bool>,
+}
+
+impl<T: Copy + 'static> Default for CopyStorage<T> {
+    fn default() -> Self {
+        Self {
+            value: Cell::new(None),
+            borrowed: RefCell::new(None),
+            writing: Cell::new(false),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Copy + 'static> CopyStorage<T> {
+    /// Create a new owner backed by the store for copy values of type `T` on the current thread. Every value inserted into the owner is dropped when the owner is dropped.
+    pub fn owner() -> Owner<Self> {
+        COPY_STORES.with(|stores| {
+            stores
+                .borrow_mut()
+                .entry(core::any::TypeId::of::<T>())
+                .or_insert_with(|| Box::new(Store::<Self>::new()))
+                .downcast_ref::<Store<Self>>()
+                .unwrap()
+                .owner()
+        })
+    }
+}
+
+impl<T: Copy + 'static> CopyStorage<T> {
+    fn get(&self) -> Result<T, StorageBorrowError> {
+        if self.writing.get() {
+            return Err(StorageBorrowError::Borrowed);
+        }
+        self.value.get().ok_or(StorageBorrowError::Dropped)
+    }
+
+    /// Panic like a [`RefCell`] if the value is borrowed, because changing it would not change the borrowed copy.
+    fn assert_not_borrowed(&self) {
+        drop(self.borrowed.borrow_mut());
+    }
+}
+
+impl<T: Copy + 'static> Storage<T> for CopyStorage<T> {
+    fn try_read(&'static self) -> Result<Self::Ref<T>, StorageBorrowError> {
+        // The value can't change while it is borrowed, so the copy only needs to be refreshed for the first guard
+        if let Ok(mut copy) = self.borrowed.try_borrow_mut() {
+            *copy = self.value.get();
+        }
+        let borrow = self
+            .borrowed
+            .try_borrow()
+            .map_err(|_| StorageBorrowError::Borrowed)?;
+        Ref::filter_map(borrow, Option::as_ref).map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn try_write(&'static self) -> Result<Self::Mut<T>, StorageBorrowError> {
+        let mut borrow = self
+            .borrowed
+            .try_borrow_mut()
+            .map_err(|_| StorageBorrowError::Borrowed)?;
+        *borrow = self.value.get();
+        let borrow =
+            RefMut::filter_map(borrow, Option::as_mut).map_err(|_| StorageBorrowError::Dropped)?;
+        self.writing.set(true);
+        Ok(CopyMut {
+            borrow: Some(borrow),
+            storage: self,
+        })
+    }
+
+    fn set(&self, value: T) {
+        self.assert_not_borrowed();
+        self.value.set(Some(value));
+    }
+
+    fn take_if(&self, valid: impl FnOnce() -> bool) -> Option<T> {
+        self.assert_not_borrowed();
+        if self.value.get().is_none() || !valid() {
+            return None;
+        }
+        self.value.take()
+    }
+}
+
+impl<T: Copy + 'static> AnyStorage for CopyStorage<T> {
+    type Ref<R: ?Sized + 'static> = <UnsyncStorage as AnyStorage>::Ref<R>;
+    type Mut<W: ?Sized + 'static> = CopyMut<W>;
+
+    fn map<R: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<R>,
+        f: impl FnOnce(&R) -> &U,
+    ) -> Self::Ref<U> {
+        UnsyncStorage::map(reference, f)
+    }
+
+    fn try_map<R: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<R>,
+        f: impl FnOnce(&R) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        UnsyncStorage::try_map(reference, f)
+    }
+
+    fn map_mut<W: ?Sized + 'static, U: ?Sized + 'static>(
+        mut reference: Self::Mut<W>,
+        f: impl FnOnce(&mut W) -> &mut U,
+    ) -> Self::Mut<U> {
+        let borrow = reference.borrow.take().map(|borrow| RefMut::map(borrow, f));
+        let storage = reference.storage;
+        // The new guard copies the changes back instead
+        core::mem::forget(reference);
+        CopyMut { borrow, storage }
+    }
+
+    fn try_map_mut<W: ?Sized + 'static, U: ?Sized + 'static>(
+        mut reference: Self::Mut<W>,
+        f: impl FnOnce(&mut W) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        let borrow = reference.borrow.take()?;
+        match RefMut::filter_map(borrow, f) {
+            Ok(borrow) => {
+                let storage = reference.storage;
+                core::mem::forget(reference);
+                Some(CopyMut {
+                    borrow: Some(borrow),
+                    storage,
+                })
+            }
+            Err(borrow) => {
+                // Dropping the guard copies the changes of `f` back
+                reference.borrow = Some(borrow);
+                None
+            }
+        }
+    }
+
+    fn take(&self) -> bool {
+        self.assert_not_borrowed();
+        self.value.take().is_some()
+    }
+}
+
+/// A mutable reference to the value of a [`CopyStorage`]. The changes are copied back into the storage when it is dropped.
+pub struct CopyMut<W: ?Sized + 'static> {
+    // Only `None` while the guard is mapped to another guard
+    borrow: Option<RefMut<'static, W>>,
+    storage: &'static dyn WriteBack,
+}
+
+impl<W: ?Sized + 'static> Deref for CopyMut<W> {
+    type Target = W;
+
+    fn deref(&self) -> &W {
+        self.borrow.as_deref().expect("the guard holds a borrow")
+    }
+}
+
+impl<W: ?Sized + 'static> DerefMut for CopyMut<W> {
+    fn deref_mut(&mut self) -> &mut W {
+        self.borrow
+            .as_deref_mut()
+            .expect("the guard holds a borrow")
+    }
+}
+
+impl<W: ?Sized + 'static> Drop for CopyMut<W> {
+    fn drop(&mut self) {
+        drop(self.borrow.take());
+        self.storage.write_back();
+    }
+}
+
+trait WriteBack {
+    fn write_back(&self);
+}
+
+impl<T: Copy + 'static> WriteBack for CopyStorage<T> {
+    fn write_back(&self) {
+        self.value.set(*self.borrowed.borrow());
+        self.writing.set(false);
+    }
+}
+
+impl<T: Copy + 'static> GenerationalBox<T, CopyStorage<T>> {
+    /// Try to copy the value out of the box. Returns an error if the value was dropped or is borrowed mutably.
+    ///
+    /// Unlike [`GenerationalBox::try_read`], this doesn't create a guard or record the borrow.
+    #[track_caller]
+    pub fn try_get(&self) -> Result<T, BorrowError> {
+        if !self.validate() {
+            return Err(BorrowError::Dropped(self.value_dropped()));
+        }
+        match self.raw.0.data.get() {
+            Ok(value) => Ok(value),
+            Err(StorageBorrowError::Dropped) => Err(BorrowError::Dropped(self.value_dropped())),
+            Err(StorageBorrowError::Borrowed) => Err(BorrowError::AlreadyBorrowedMut(
+                self.raw.0.borrow.already_borrowed_mut(self.value_info()),
+            )),
+        }
+    }
+
+    /// Copy the value out of the box. Panics if the value was dropped or is borrowed mutably.
+    #[track_caller]
+    pub fn get(&self) -> T {
+        self.try_get().unwrap()
+    }
+}
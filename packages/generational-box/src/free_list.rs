@@ -0,0 +1,102 @@
+//! The free list that a [`Store`](crate::Store) recycles memory locations through. It is split into shards so threads that recycle and claim locations at the same time don't wait on one lock.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::lock::Mutex;
+
+// Each shard gets its own cache line so threads working on neighbouring shards don't slow each other down
+#[repr(align(64))]
+struct Shard<T>(Mutex<Vec<T>>);
+
+pub(crate) struct FreeList<T> {
+    shards: Box<[Shard<T>]>,
+}
+
+impl<T> FreeList<T> {
+    pub(crate) fn new(shards: usize) -> Self {
+        Self {
+            shards: (0..shards.max(1))
+                .map(|_| Shard(Mutex::new(Vec::new())))
+                .collect(),
+        }
+    }
+
+    /// Push an item onto the shard of the current thread.
+    pub(crate) fn push(&self, item: T) {
+        self.shards[self.home()].0.lock().push(item);
+    }
+
+    /// Push many items onto the shard of the current thread at once.
+    pub(crate) fn extend(&self, items: impl IntoIterator<Item = T>) {
+        self.shards[self.home()].0.lock().extend(items);
+    }
+
+    /// Pop an item from the shard of the current thread. If that shard is empty, steal half of the items of the first other shard that has any.
+    pub(crate) fn pop(&self) -> Option<T> {
+        let home = self.home();
+        if let Some(item) = self.shards[home].0.lock().pop() {
+            return Some(item);
+        }
+
+        let len = self.shards.len();
+        for offset in 1..len {
+            // Skip shards that are busy. Their owner is probably about to use them
+            let mut stolen = match self.shards[(home + offset) % len].0.try_lock() {
+                Some(mut victim) if !victim.is_empty() => {
+                    let keep = victim.len() / 2;
+                    victim.split_off(keep)
+                }
+                _ => continue,
+            };
+
+            // Only one lock is held at a time, so two threads stealing from each other can't deadlock
+            let item = stolen.pop();
+            if !stolen.is_empty() {
+                self.shards[home].0.lock().append(&mut stolen);
+            }
+            return item;
+        }
+
+        None
+    }
+
+    /// Get the number of items in every shard.
+    pub(crate) fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.0.lock().len()).sum()
+    }
+
+    fn home(&self) -> usize {
+        current_thread_index() % self.shards.len()
+    }
+}
+
+/// The number of shards to use for free lists shared between threads.
+pub(crate) fn default_shards() -> usize {
+    #[cfg(feature = "std")]
+    {
+        std::thread::available_parallelism().map_or(1, |threads| threads.get())
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        1
+    }
+}
+
+#[cfg(feature = "std")]
+fn current_thread_index() -> usize {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    std::thread_local! {
+        static INDEX: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Locations can be recycled by the destructors of other thread locals after this one is gone
+    INDEX.try_with(|index| *index).unwrap_or(0)
+}
+
+#[cfg(not(feature = "std"))]
+fn current_thread_index() -> usize {
+    0
+}
@@ -0,0 +1,224 @@
+use std::{
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{AnyStorage, GenerationalBox, GenerationalBoxId, Owner, Storage, StorageBorrowError};
+
+/// [`Instrumented<S>`] in debug builds and `S` in release builds. Use this to collect statistics while developing without touching the hot path in release.
+#[cfg(debug_assertions)]
+pub type DebugInstrumented<S> = Instrumented<S>;
+
+/// [`Instrumented<S>`] in debug builds and `S` in release builds. Use this to collect statistics while developing without touching the hot path in release.
+#[cfg(not(debug_assertions))]
+pub type DebugInstrumented<S> = S;
+
+/// A storage that delegates to another storage and records how many times, and for how long, the value is borrowed.
+///
+/// The statistics are reset when the value is dropped, so they always describe the value currently in the slot.
+///
+/// ```rust
+/// use generational_box::{Instrumented, Store, UnsyncStorage};
+///
+/// let store = Store::<Instrumented<UnsyncStorage>>::new();
+/// let owner = store.owner();
+/// let key = owner.insert(1);
+/// *key.write() += 1;
+/// assert_eq!(*key.read(), 2);
+///
+/// let stats = key.stats();
+/// assert_eq!(stats.reads, 1);
+/// assert_eq!(stats.writes, 1);
+/// ```
+#[derive(Default)]
+pub struct Instrumented<S> {
+    inner: S,
+    counters: Counters,
+}
+
+#[derive(Default)]
+struct Counters {
+    reads: AtomicU64,
+    writes: AtomicU64,
+    read_nanos: AtomicU64,
+    write_nanos: AtomicU64,
+}
+
+/// The statistics collected by an [`Instrumented`] storage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageStats {
+    /// The number of times the value was borrowed immutably.
+    pub reads: u64,
+    /// The number of times the value was borrowed mutably.
+    pub writes: u64,
+    /// The total time the value was borrowed immutably. Borrows that are still alive are not included.
+    pub read_time: Duration,
+    /// The total time the value was borrowed mutably. Borrows that are still alive are not included.
+    pub write_time: Duration,
+}
+
+impl<S> Instrumented<S> {
+    /// Get the statistics collected for the value in this storage.
+    pub fn stats(&self) -> StorageStats {
+        StorageStats {
+            reads: self.counters.reads.load(Ordering::Relaxed),
+            writes: self.counters.writes.load(Ordering::Relaxed),
+            read_time: Duration::from_nanos(self.counters.read_nanos.load(Ordering::Relaxed)),
+            write_time: Duration::from_nanos(self.counters.write_nanos.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Get the storage this storage delegates to.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn reset(&self) {
+        self.counters.reads.store(0, Ordering::Relaxed);
+        self.counters.writes.store(0, Ordering::Relaxed);
+        self.counters.read_nanos.store(0, Ordering::Relaxed);
+        self.counters.write_nanos.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<T: 'static, S: Storage<T>> Storage<T> for Instrumented<S> {
+    fn try_read(&'static self) -> Result<Self::Ref<T>, StorageBorrowError> {
+        let inner = self.inner.try_read()?;
+        self.counters.reads.fetch_add(1, Ordering::Relaxed);
+        Ok(InstrumentedRef {
+            inner,
+            timer: BorrowTimer::new(&self.counters.read_nanos),
+        })
+    }
+
+    fn try_write(&'static self) -> Result<Self::Mut<T>, StorageBorrowError> {
+        let inner = self.inner.try_write()?;
+        self.counters.writes.fetch_add(1, Ordering::Relaxed);
+        Ok(InstrumentedRef {
+            inner,
+            timer: BorrowTimer::new(&self.counters.write_nanos),
+        })
+    }
+
+    fn set(&self, value: T) {
+        self.inner.set(value)
+    }
+
+    fn take_if(&self, valid: impl FnOnce() -> bool) -> Option<T> {
+        self.inner.take_if(valid)
+    }
+
+    fn set_if(&self, value: T, valid: impl FnOnce() -> bool) -> Result<(), T> {
+        self.inner.set_if(value, valid)
+    }
+}
+
+impl<S: AnyStorage> AnyStorage for Instrumented<S> {
+    type Ref<T: ?Sized + 'static> = InstrumentedRef<S::Ref<T>>;
+    type Mut<T: ?Sized + 'static> = InstrumentedRef<S::Mut<T>>;
+
+    fn map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<T>,
+        f: impl FnOnce(&T) -> &U,
+    ) -> Self::Ref<U> {
+        InstrumentedRef {
+            inner: S::map(reference.inner, f),
+            timer: reference.timer,
+        }
+    }
+
+    fn try_map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        let InstrumentedRef { inner, timer } = reference;
+        S::try_map(inner, f).map(|inner| InstrumentedRef { inner, timer })
+    }
+
+    fn map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> Self::Mut<U> {
+        InstrumentedRef {
+            inner: S::map_mut(reference.inner, f),
+            timer: reference.timer,
+        }
+    }
+
+    fn try_map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        let InstrumentedRef { inner, timer } = reference;
+        S::try_map_mut(inner, f).map(|inner| InstrumentedRef { inner, timer })
+    }
+
+    fn data_ptr(&self) -> *const () {
+        self.inner.data_ptr()
+    }
+
+    fn take(&self) -> bool {
+        self.reset();
+        self.inner.take()
+    }
+}
+
+/// A reference handed out by an [`Instrumented`] storage. The time the reference is alive is recorded when it is dropped.
+pub struct InstrumentedRef<R> {
+    inner: R,
+    timer: BorrowTimer,
+}
+
+impl<R: Deref> Deref for InstrumentedRef<R> {
+    type Target = R::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+impl<R: DerefMut> DerefMut for InstrumentedRef<R> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.deref_mut()
+    }
+}
+
+struct BorrowTimer {
+    started: Instant,
+    total_nanos: &'static AtomicU64,
+}
+
+impl BorrowTimer {
+    fn new(total_nanos: &'static AtomicU64) -> Self {
+        Self {
+            started: Instant::now(),
+            total_nanos,
+        }
+    }
+}
+
+impl Drop for BorrowTimer {
+    fn drop(&mut self) {
+        let elapsed = self.started.elapsed().as_nanos() as u64;
+        self.total_nanos.fetch_add(elapsed, Ordering::Relaxed);
+    }
+}
+
+impl<T: 'static, S: Storage<T>> GenerationalBox<T, Instrumented<S>> {
+    /// Get the statistics collected for the value in this box.
+    pub fn stats(&self) -> StorageStats {
+        self.raw.0.data.stats()
+    }
+}
+
+impl<S: AnyStorage> Owner<Instrumented<S>> {
+    /// Get the statistics collected for every value this owner is responsible for.
+    pub fn stats(&self) -> Vec<(GenerationalBoxId, StorageStats)> {
+        self.owned
+            .lock()
+            .iter()
+            .map(|location| (location.id(), location.0.data.stats()))
+            .collect()
+    }
+}
@@ -1,17 +1,55 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::{
-    any::Any,
-    cell::{Cell, Ref, RefCell, RefMut},
-    error::Error,
+#[cfg(not(any(feature = "std", feature = "critical-section")))]
+compile_error!("generational-box needs either the `std` or the `critical-section` feature");
+
+extern crate alloc;
+
+use alloc::sync::Arc;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(any(debug_assertions, feature = "check_generation"))]
+use core::sync::atomic::AtomicU64;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::{
     fmt::{Debug, Display},
     marker::PhantomData,
     ops::{Deref, DerefMut},
-    rc::Rc,
 };
+use free_list::FreeList;
+use lock::Mutex;
+
+mod copy;
+mod free_list;
+#[cfg(feature = "std")]
+mod instrumented;
+mod lock;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sync;
+mod typed;
+mod unsync;
+
+pub use copy::*;
+#[cfg(feature = "std")]
+pub use instrumented::*;
+pub use sync::*;
+pub use typed::*;
+pub use unsync::*;
+
+/// The storage that [`GenerationalBox`], [`Owner`], [`Store`] and the other types of this crate use when their storage parameter is left out.
+///
+/// This is [`UnsyncStorage`] unless the `default-sync` feature is enabled, which makes it [`SyncStorage`]. Apps that share every value between threads can enable the feature instead of naming the storage in every signature. Libraries that need values that are not `Send` should name [`UnsyncStorage`] explicitly so the feature doesn't break them.
+#[cfg(not(feature = "default-sync"))]
+pub type DefaultStorage = UnsyncStorage;
 
-use bumpalo::Bump;
+/// The storage that [`GenerationalBox`], [`Owner`], [`Store`] and the other types of this crate use when their storage parameter is left out.
+///
+/// This is [`SyncStorage`] because the `default-sync` feature is enabled. Without the feature it is [`UnsyncStorage`]. Libraries that need values that are not `Send` should name [`UnsyncStorage`] explicitly so the feature doesn't break them.
+#[cfg(feature = "default-sync")]
+pub type DefaultStorage = SyncStorage;
 
 /// # Example
 ///
@@ -32,12 +70,12 @@ fn reused() {
     let first_ptr;
     {
         let owner = store.owner();
-        first_ptr = owner.insert(1).raw.0.data.as_ptr();
+        first_ptr = owner.insert(1).raw.0.data.data_ptr();
         drop(owner);
     }
     {
         let owner = store.owner();
-        let second_ptr = owner.insert(1234).raw.0.data.as_ptr();
+        let second_ptr = owner.insert(1234).raw.0.data.data_ptr();
         assert_eq!(first_ptr, second_ptr);
         drop(owner);
     }
@@ -100,6 +138,505 @@ fn insert_while_reading() {
     assert_eq!(*value, "hello world");
 }
 
+#[test]
+fn ids() {
+    let store = Store::default();
+    let owner = store.owner();
+    let first = owner.insert(1);
+    let second = owner.insert(2);
+
+    assert_eq!(first.id(), first.id());
+    assert_ne!(first.id(), second.id());
+}
+
+#[test]
+fn dispose() {
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert(1);
+    assert_eq!(owner.owned(), vec![key.id()]);
+
+    owner.dispose();
+    assert!(owner.owned().is_empty());
+    assert!(key.try_read().is_err());
+
+    let key = owner.insert(2);
+    assert_eq!(*key.read(), 2);
+}
+
+#[test]
+fn on_dispose() {
+    use std::sync::{Arc, Mutex};
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let log = |event: &'static str| {
+        let events = events.clone();
+        move || events.lock().unwrap().push(event)
+    };
+
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert(1);
+    key.on_dispose(log("first"));
+    key.on_dispose(log("second"));
+    assert!(events.lock().unwrap().is_empty());
+
+    owner.dispose();
+    assert_eq!(*events.lock().unwrap(), ["first", "second"]);
+
+    // The hooks don't carry over to the value that reuses the location
+    let reused = owner.insert(2);
+    drop(owner);
+    assert_eq!(events.lock().unwrap().len(), 2);
+
+    // A box that was already dropped runs the hook right away
+    if cfg!(any(debug_assertions, feature = "check_generation")) {
+        reused.on_dispose(log("late"));
+        assert_eq!(events.lock().unwrap().last(), Some(&"late"));
+    }
+}
+
+#[test]
+#[cfg(any(debug_assertions, feature = "check_generation"))]
+fn generation() {
+    let store = Store::default();
+    let first = store.owner().insert(1);
+    let second = store.owner().insert(2);
+    assert_eq!(first.generation(), Some(0));
+    assert_eq!(second.generation(), Some(1));
+    assert!(!first.ptr_eq(&second));
+}
+
+#[test]
+#[cfg(any(debug_assertions, feature = "check_generation"))]
+fn try_set() {
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert(1);
+    assert!(key.try_set(2).is_ok());
+    assert_eq!(*key.read(), 2);
+
+    drop(owner);
+    assert!(key.try_set(3).is_err());
+    // Setting a dropped value is a no-op
+    key.set(3);
+
+    // Writing through a stale box does not touch the value that reuses the slot
+    let owner = store.owner();
+    let new_key = owner.insert(4);
+    assert!(key.try_set(5).is_err());
+    assert_eq!(*new_key.read(), 4);
+
+    let sync_owner = SyncStorage::owner();
+    let sync_key = sync_owner.insert(1);
+    drop(sync_owner);
+    assert!(sync_key.try_set(2).is_err());
+}
+
+#[test]
+fn take() {
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert(String::from("hello world"));
+    let copy = key;
+
+    assert_eq!(key.take().as_deref(), Some("hello world"));
+    assert!(copy.take().is_none());
+    assert!(copy.try_read().is_err());
+    assert!(owner.insert(1).take().is_some());
+
+    let owner = SyncStorage::owner();
+    let key = owner.insert(vec![1, 2, 3]);
+    assert_eq!(key.take(), Some(vec![1, 2, 3]));
+    assert!(key.try_read().is_err());
+}
+
+#[test]
+fn runtime_stats() {
+    let store = Store::default();
+    let owner = store.owner();
+    let _first = owner.insert(1);
+    let _second = owner.insert(2);
+    let stats = store.runtime_stats();
+    assert_eq!(stats.live, 2);
+    assert_eq!(stats.free, 0);
+    assert!(stats.leaked_bytes > 0);
+
+    drop(owner);
+    let stats = store.runtime_stats();
+    assert_eq!(stats.live, 0);
+    assert_eq!(stats.free, 2);
+
+    // Reusing a location does not leak more memory
+    let owner = store.owner();
+    let _third = owner.insert(3);
+    assert_eq!(store.runtime_stats().leaked_bytes, stats.leaked_bytes);
+
+    let owner = SyncStorage::owner();
+    let _key = owner.insert(1);
+    assert!(SyncStorage::runtime_stats().leaked_bytes > 0);
+}
+
+#[test]
+fn default_storage() {
+    use core::any::TypeId;
+
+    let expected = if cfg!(feature = "default-sync") {
+        TypeId::of::<SyncStorage>()
+    } else {
+        TypeId::of::<UnsyncStorage>()
+    };
+    assert_eq!(TypeId::of::<DefaultStorage>(), expected);
+
+    // Leaving out the storage parameter uses the default storage
+    let owner: Owner<DefaultStorage> = Store::default().owner();
+    let key: GenerationalBox<i32> = owner.insert(1);
+    assert_eq!(*key.read(), 1);
+}
+
+#[test]
+fn reserve() {
+    let store = Store::default();
+    store.reserve(100);
+    let reserved = store.runtime_stats();
+    assert_eq!(reserved.live, 0);
+    assert_eq!(reserved.free, 100);
+
+    // Boxes claim the reserved locations instead of leaking new ones
+    let owner = store.owner();
+    let boxes: Vec<_> = (0..100).map(|i| owner.insert(i)).collect();
+    assert_eq!(
+        store.runtime_stats(),
+        RuntimeStats {
+            live: 100,
+            free: 0,
+            leaked_bytes: reserved.leaked_bytes,
+        }
+    );
+    assert_eq!(*boxes[42].read(), 42);
+
+    UnsyncStorage::reserve(10);
+    assert!(UnsyncStorage::runtime_stats().free >= 10);
+    SyncStorage::reserve(10);
+    assert!(SyncStorage::runtime_stats().free >= 10);
+}
+
+#[test]
+fn sharded_store_reuses_locations_from_other_threads() {
+    let store = Store::<SyncStorage>::with_shards(4);
+    let owner = store.owner();
+    for i in 0..8 {
+        owner.insert(i);
+    }
+    let leaked = store.runtime_stats().leaked_bytes;
+
+    // Recycle the locations on another thread, then claim them back on this one
+    std::thread::spawn(move || drop(owner)).join().unwrap();
+    assert_eq!(store.runtime_stats().free, 8);
+
+    let owner = store.owner();
+    for i in 0..8 {
+        owner.insert(i);
+    }
+    let stats = store.runtime_stats();
+    assert_eq!(stats.free, 0);
+    assert_eq!(stats.leaked_bytes, leaked);
+}
+
+#[test]
+fn mapped() {
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert((1, String::from("hello")));
+    let second = key.map(|value| &value.1, |value| &mut value.1);
+    let text = key.map(|value| value.1.as_str(), |value| value.1.as_mut_str());
+
+    second.write().push_str(" world");
+    assert_eq!(*second.read(), "hello world");
+    assert_eq!(&*text.read(), "hello world");
+    assert_eq!(key.read().0, 1);
+
+    // The projection borrows the same slot as the original box
+    let write = key.write();
+    assert!(second.try_read().is_err());
+    drop(write);
+
+    drop(owner);
+    assert!(second.try_read().is_err());
+}
+
+#[test]
+fn borrow_errors_describe_the_value() {
+    let owner = UnsyncStorage::owner();
+    let key = owner.insert(String::from("hello")).with_label("greeting");
+
+    let write = key.write();
+    let error = match key.try_read() {
+        Err(BorrowError::AlreadyBorrowedMut(error)) => error,
+        _ => panic!("expected the value to be borrowed mutably"),
+    };
+    assert_eq!(error.id(), key.id());
+    assert_eq!(error.type_name(), core::any::type_name::<String>());
+    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+    assert_eq!(error.label(), Some("greeting"));
+    drop(write);
+
+    drop(owner);
+    let message = match key.try_write() {
+        Err(error) => error.to_string(),
+        Ok(_) => panic!("expected the value to be dropped"),
+    };
+    assert!(message.contains(core::any::type_name::<String>()));
+    assert!(message.contains(&format!("{:?}", key.id())));
+    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+    assert!(message.contains("`greeting`"));
+}
+
+#[cfg(feature = "backtrace")]
+#[test]
+fn backtraces() {
+    let owner = UnsyncStorage::owner();
+    let key = owner.insert(1);
+
+    let read = key.read();
+    match key.try_write() {
+        Err(BorrowMutError::AlreadyBorrowed(error)) => {
+            assert_eq!(error.backtraces().len(), 1);
+            assert!(error.to_string().contains("The value was borrowed here"));
+        }
+        _ => panic!("expected the value to be borrowed"),
+    }
+    drop(read);
+
+    let read = key.read();
+    match key.try_write() {
+        Err(BorrowMutError::AlreadyBorrowed(error)) => assert_eq!(error.backtraces().len(), 1),
+        _ => panic!("expected the value to be borrowed"),
+    }
+    drop(read);
+}
+
+#[test]
+fn leak() {
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert(1);
+    let leaked = key.leak().unwrap();
+    drop(owner);
+
+    // The value outlives its owner and the location is never reused
+    assert_eq!(*leaked.read(), 1);
+    assert_eq!(*key.read(), 1);
+    *leaked.write() = 2;
+    assert_eq!(*key.read(), 2);
+    let owner = store.owner();
+    assert!(!owner.insert(3).ptr_eq(&key));
+    assert_eq!(store.runtime_stats().free, 0);
+
+    let dropped = owner.insert(4);
+    drop(owner);
+    assert!(dropped.leak().is_err());
+}
+
+#[test]
+fn leak_stale_box() {
+    let store: Store = Store::with_shards(1);
+    let owner = store.owner();
+    let stale = owner.insert(1);
+    drop(owner);
+
+    // The location is reused and leaked by its new owner
+    let owner = store.owner();
+    let current = owner.insert(2);
+    assert_eq!(current.id().data_ptr, stale.id().data_ptr);
+    let leaked = current.leak().unwrap();
+
+    // A stale box can neither leak the location nor undo the leak of the current box
+    assert!(stale.leak().is_err());
+    drop(owner);
+    assert_eq!(*leaked.read(), 2);
+    assert_eq!(store.runtime_stats().free, 0);
+    let owner = store.owner();
+    assert!(!owner.insert(3).ptr_eq(&current));
+    assert_eq!(*leaked.read(), 2);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let owner = SyncStorage::owner();
+    let key = owner.insert(vec![1, 2, 3]);
+    let json = serde_json::to_string(&key).unwrap();
+    assert_eq!(json, "[1,2,3]");
+
+    // Deserializing creates a fresh box
+    let copy = owner
+        .deserialize::<Vec<i32>, _>(&mut serde_json::Deserializer::from_str(&json))
+        .unwrap();
+    assert!(!copy.ptr_eq(&key));
+    assert_eq!(*copy.read(), vec![1, 2, 3]);
+
+    let leaked = owner
+        .deserialize::<Vec<i32>, _>(&mut serde_json::Deserializer::from_str(&json))
+        .unwrap()
+        .leak()
+        .unwrap();
+    drop(owner);
+    assert_eq!(*leaked.read(), vec![1, 2, 3]);
+    assert!(serde_json::to_string(&copy).is_err());
+}
+
+#[test]
+fn weak() {
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert(1);
+    let weak = key.downgrade();
+    assert_eq!(*weak.upgrade().unwrap().read(), 1);
+
+    drop(owner);
+    assert!(weak.upgrade().is_none());
+
+    // The slot is reused, but the weak handle still points to the old value
+    let owner = store.owner();
+    let _new_key = owner.insert(2);
+    #[cfg(any(debug_assertions, feature = "check_generation"))]
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn owner_without_store() {
+    let key;
+    {
+        let owner = UnsyncStorage::owner();
+        key = owner.insert(1);
+        assert_eq!(*key.read(), 1);
+    }
+    assert!(key.try_read().is_err());
+
+    let owner = SyncStorage::owner();
+    let key = owner.insert(2);
+    assert_eq!(*key.read(), 2);
+}
+
+#[test]
+fn borrow_tracking_is_free_in_release() {
+    #[cfg(not(any(debug_assertions, feature = "debug_borrows")))]
+    {
+        assert_eq!(std::mem::size_of::<MemoryLocationBorrowInfo>(), 0);
+        assert_eq!(std::mem::size_of::<GenerationalRefBorrowInfo>(), 0);
+        assert_eq!(std::mem::size_of::<GenerationalRefMutBorrowInfo>(), 0);
+    }
+
+    // Conflicting borrows are still reported
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert(1);
+    let read = key.read();
+    assert!(matches!(
+        key.try_write(),
+        Err(BorrowMutError::AlreadyBorrowed(_))
+    ));
+    drop(read);
+    let write = key.write();
+    assert!(matches!(
+        key.try_read(),
+        Err(BorrowError::AlreadyBorrowedMut(_))
+    ));
+    drop(write);
+}
+
+#[test]
+fn sync() {
+    let store = Store::<SyncStorage>::new();
+    let owner = store.owner();
+    let key = owner.insert(String::from("hello world"));
+
+    std::thread::spawn(move || {
+        assert_eq!(*key.read(), "hello world");
+        key.write().push('!');
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*key.read(), "hello world!");
+}
+
+#[test]
+fn instrumented() {
+    let store = Store::<Instrumented<UnsyncStorage>>::new();
+    let owner = store.owner();
+    let key = owner.insert(1);
+    {
+        let _first = key.read();
+        let _second = GenerationalRef::map(key.read(), |value| value);
+    }
+    *key.write() += 1;
+
+    let stats = key.stats();
+    assert_eq!(stats.reads, 2);
+    assert_eq!(stats.writes, 1);
+    assert_eq!(owner.stats(), vec![(key.id(), stats)]);
+
+    // The statistics are reset when the slot is reused
+    drop(owner);
+    let owner = store.owner();
+    let key = owner.insert(1);
+    assert_eq!(key.stats(), StorageStats::default());
+}
+
+#[test]
+fn typed() {
+    let store = Store::<TypedUnsyncStorage<String>>::new();
+    let first_ptr;
+    {
+        let owner = store.owner();
+        let key = owner.insert(String::from("hello"));
+        key.write().push_str(" world");
+        assert_eq!(*key.read(), "hello world");
+        first_ptr = key.raw.0.data.data_ptr();
+    }
+    // Slots are recycled within the arena for the type
+    let owner = store.owner();
+    let key = owner.insert(String::from("reused"));
+    assert_eq!(key.raw.0.data.data_ptr(), first_ptr);
+
+    let owner = TypedSyncStorage::<u32>::owner();
+    let key = owner.insert(1);
+    std::thread::spawn(move || *key.write() += 1)
+        .join()
+        .unwrap();
+    assert_eq!(*key.read(), 2);
+}
+
+#[test]
+fn copy() {
+    let store = Store::<CopyStorage<u32>>::new();
+    let owner = store.owner();
+    let key = owner.insert(1);
+    key.set(key.get() + 1);
+    assert_eq!(key.get(), 2);
+
+    // Copying the value out fails while it is borrowed mutably, but not while it is read
+    let read = key.read();
+    assert_eq!(key.get(), 2);
+    drop(read);
+    let mut write = key.write();
+    assert!(matches!(
+        key.try_get(),
+        Err(BorrowError::AlreadyBorrowedMut(_))
+    ));
+    *write += 1;
+    drop(write);
+
+    // Changes through a write guard are copied back when it is dropped
+    assert_eq!(key.get(), 3);
+    assert_eq!(*key.read(), 3);
+
+    drop(owner);
+    assert!(matches!(key.try_get(), Err(BorrowError::Dropped(_))));
+}
+
 #[test]
 #[should_panic]
 fn panics() {
@@ -148,231 +685,856 @@ fn fuzz() {
         }
     }
 
-    for _ in 0..10 {
-        let store = Store::default();
-        maybe_owner_scope(&store, &mut Vec::new(), &mut Vec::new(), &mut Vec::new());
+    for _ in 0..10 {
+        let store = Store::default();
+        maybe_owner_scope(&store, &mut Vec::new(), &mut Vec::new(), &mut Vec::new());
+    }
+}
+
+/// The type erased id of a generational box.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GenerationalBoxId {
+    // The address is only used for comparisons, it is never dereferenced
+    data_ptr: usize,
+    #[cfg(any(debug_assertions, feature = "check_generation"))]
+    generation: u64,
+}
+
+impl Debug for GenerationalBoxId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(any(debug_assertions, feature = "check_generation"))]
+        f.write_fmt(format_args!("{:#x}@{:?}", self.data_ptr, self.generation))?;
+        #[cfg(not(any(debug_assertions, feature = "check_generation")))]
+        f.write_fmt(format_args!("{:#x}", self.data_ptr))?;
+        Ok(())
+    }
+}
+
+/// The core Copy state type. The generational box will be dropped when the [Owner] is dropped.
+pub struct GenerationalBox<T, S: 'static = DefaultStorage> {
+    raw: MemoryLocation<S>,
+    #[cfg(any(debug_assertions, feature = "check_generation"))]
+    generation: u64,
+    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+    created_at: &'static core::panic::Location<'static>,
+    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+    label: Option<&'static str>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: 'static, S: AnyStorage> Debug for GenerationalBox<T, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        #[cfg(any(debug_assertions, feature = "check_generation"))]
+        f.write_fmt(format_args!(
+            "{:?}@{:?}",
+            self.raw.0.data.data_ptr(),
+            self.generation
+        ))?;
+        #[cfg(not(any(debug_assertions, feature = "check_generation")))]
+        f.write_fmt(format_args!("{:?}", self.raw.0.data.data_ptr()))?;
+        Ok(())
+    }
+}
+
+impl<T: 'static, S: Storage<T>> GenerationalBox<T, S> {
+    #[inline(always)]
+    fn validate(&self) -> bool {
+        #[cfg(any(debug_assertions, feature = "check_generation"))]
+        {
+            self.raw.0.generation.load(Ordering::Relaxed) == self.generation
+        }
+        #[cfg(not(any(debug_assertions, feature = "check_generation")))]
+        {
+            true
+        }
+    }
+
+    /// Get the id of the generational box.
+    pub fn id(&self) -> GenerationalBoxId {
+        GenerationalBoxId {
+            data_ptr: self.raw.0.data.data_ptr() as usize,
+            #[cfg(any(debug_assertions, feature = "check_generation"))]
+            generation: self.generation,
+        }
+    }
+
+    /// Get the generation of the slot this box points to when the box was created. Every time a slot is reused, its generation is incremented. This is only available in debug builds or with the `check_generation` feature.
+    pub fn generation(&self) -> Option<u64> {
+        #[cfg(any(debug_assertions, feature = "check_generation"))]
+        {
+            Some(self.generation)
+        }
+        #[cfg(not(any(debug_assertions, feature = "check_generation")))]
+        {
+            None
+        }
+    }
+
+    /// Get the location the generational box was created at. This is only available in debug builds or with the `debug_ownership` feature.
+    pub fn created_at(&self) -> Option<&'static core::panic::Location<'static>> {
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        {
+            Some(self.created_at)
+        }
+        #[cfg(not(any(debug_assertions, feature = "debug_ownership")))]
+        {
+            None
+        }
+    }
+
+    /// Give the value a name that borrow errors include, so it is easier to tell which value failed to borrow in a big app. This is only stored in debug builds or with the `debug_ownership` feature.
+    ///
+    /// The label is part of the handle, so set it before the handle is copied.
+    ///
+    /// ```rust
+    /// use generational_box::UnsyncStorage;
+    ///
+    /// let owner = UnsyncStorage::owner();
+    /// let count = owner.insert(0).with_label("count");
+    /// drop(owner);
+    /// if let Err(error) = count.try_read() {
+    ///     // Failed to borrow `count` (i32 ...) because the value was dropped.
+    ///     println!("{error}");
+    /// }
+    /// ```
+    #[allow(unused_mut)]
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        {
+            self.label = Some(label);
+        }
+        #[cfg(not(any(debug_assertions, feature = "debug_ownership")))]
+        let _ = label;
+        self
+    }
+
+    /// Get the label set with [`GenerationalBox::with_label`]. This is only available in debug builds or with the `debug_ownership` feature.
+    pub fn label(&self) -> Option<&'static str> {
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        {
+            self.label
+        }
+        #[cfg(not(any(debug_assertions, feature = "debug_ownership")))]
+        {
+            None
+        }
+    }
+
+    fn value_info(&self) -> ValueInfo {
+        ValueInfo {
+            id: self.id(),
+            type_name: core::any::type_name::<T>(),
+            label: self.label(),
+        }
+    }
+
+    fn value_dropped(&self) -> ValueDroppedError {
+        ValueDroppedError {
+            value: self.value_info(),
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at: self.created_at,
+        }
+    }
+
+    /// Try to read the value. Returns None if the value is no longer valid.
+    #[track_caller]
+    pub fn try_read(&self) -> Result<GenerationalRef<T, S>, BorrowError> {
+        if !self.validate() {
+            return Err(BorrowError::Dropped(self.value_dropped()));
+        }
+        self.try_read_unchecked()
+    }
+
+    /// Try to read the value without checking the generation.
+    #[track_caller]
+    fn try_read_unchecked(&self) -> Result<GenerationalRef<T, S>, BorrowError> {
+        match self.raw.0.data.try_read() {
+            Ok(reference) => Ok(GenerationalRef {
+                inner: reference,
+                borrow: self.raw.0.borrow.borrow(),
+            }),
+            Err(StorageBorrowError::Dropped) => Err(BorrowError::Dropped(self.value_dropped())),
+            Err(StorageBorrowError::Borrowed) => Err(BorrowError::AlreadyBorrowedMut(
+                self.raw.0.borrow.already_borrowed_mut(self.value_info()),
+            )),
+        }
+    }
+
+    /// Read the value. Panics if the value is no longer valid.
+    #[track_caller]
+    pub fn read(&self) -> GenerationalRef<T, S> {
+        self.try_read().unwrap()
+    }
+
+    /// Try to write the value. Returns None if the value is no longer valid.
+    #[track_caller]
+    pub fn try_write(&self) -> Result<GenerationalRefMut<T, S>, BorrowMutError> {
+        if !self.validate() {
+            return Err(BorrowMutError::Dropped(self.value_dropped()));
+        }
+        self.try_write_unchecked()
+    }
+
+    /// Try to write the value without checking the generation.
+    #[track_caller]
+    fn try_write_unchecked(&self) -> Result<GenerationalRefMut<T, S>, BorrowMutError> {
+        match self.raw.0.data.try_write() {
+            Ok(reference) => Ok(GenerationalRefMut {
+                inner: reference,
+                borrow: self.raw.0.borrow.borrow_mut(),
+            }),
+            Err(StorageBorrowError::Dropped) => Err(BorrowMutError::Dropped(self.value_dropped())),
+            Err(StorageBorrowError::Borrowed) => Err(BorrowMutError::AlreadyBorrowed(
+                self.raw.0.borrow.already_borrowed(self.value_info()),
+            )),
+        }
+    }
+
+    /// Write the value. Panics if the value is no longer valid.
+    #[track_caller]
+    pub fn write(&self) -> GenerationalRefMut<T, S> {
+        self.try_write().unwrap()
+    }
+
+    /// Try to set the value. Returns an error if the value has been dropped, so a background task can tell it is writing to a box that no longer exists.
+    ///
+    /// The generation is checked while the storage is locked, so the value can't be written into a slot that is being dropped on another thread.
+    pub fn try_set(&self, value: T) -> Result<(), ValueDroppedError> {
+        self.raw
+            .0
+            .data
+            .set_if(value, || self.validate())
+            .map_err(|_| self.value_dropped())
+    }
+
+    /// Set the value. Does nothing if the value has been dropped. Use [`GenerationalBox::try_set`] to find out if the value was set.
+    pub fn set(&self, value: T) {
+        _ = self.try_set(value);
+    }
+
+    /// Take the value out of the box. Every copy of the box becomes invalid, as if the owner had dropped the value. Returns None if the value has already been dropped.
+    ///
+    /// This is the only way to get an owned value out of a box without cloning it.
+    pub fn take(&self) -> Option<T> {
+        self.raw.0.data.take_if(|| {
+            if !self.validate() {
+                return false;
+            }
+            #[cfg(any(debug_assertions, feature = "check_generation"))]
+            self.raw.0.generation.fetch_add(1, Ordering::Relaxed);
+            true
+        })
+    }
+
+    /// Run `hook` when the owner drops the value and the memory location is recycled. Use this to tear down resources that are wrapped in the box, like closing a file or aborting a task, exactly when the value goes away.
+    ///
+    /// Hooks run in the order they were registered, after the value itself is dropped. If the value has already been dropped, `hook` runs immediately. Hooks of a leaked box never run.
+    ///
+    /// ```rust
+    /// use generational_box::Store;
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let closed = Arc::new(AtomicBool::new(false));
+    /// let store = Store::default();
+    /// let owner = store.owner();
+    /// let file = owner.insert("log.txt");
+    /// file.on_dispose({
+    ///     let closed = closed.clone();
+    ///     move || closed.store(true, Ordering::SeqCst)
+    /// });
+    ///
+    /// drop(owner);
+    /// assert!(closed.load(Ordering::SeqCst));
+    /// ```
+    pub fn on_dispose(&self, hook: impl FnOnce() + Send + 'static) {
+        let mut hooks = self.raw.0.dispose_hooks.lock();
+        // The generation is bumped before the hooks are taken, so checking it under the lock can't miss a recycle
+        if self.validate() {
+            hooks.push(Box::new(hook));
+        } else {
+            drop(hooks);
+            hook();
+        }
+    }
+
+    /// Create a weak handle to the value. The weak handle can only be upgraded while the value is still alive.
+    pub fn downgrade(&self) -> GenerationalWeak<T, S> {
+        GenerationalWeak { inner: *self }
+    }
+
+    /// Keep the value alive forever, even after its owner is dropped. Returns an error if the value has already been dropped.
+    ///
+    /// The returned handle reads and writes the value without checking the generation, so it is a good fit for global values like config that live as long as the app. Other copies of the box stay valid too. The memory of the value is never freed or reused.
+    ///
+    /// ```rust
+    /// use generational_box::UnsyncStorage;
+    ///
+    /// let owner = UnsyncStorage::owner();
+    /// let config = owner.insert(String::from("dark")).leak().unwrap();
+    /// drop(owner);
+    /// assert_eq!(*config.read(), "dark");
+    /// ```
+    pub fn leak(self) -> Result<LeakedBox<T, S>, ValueDroppedError> {
+        // Recycling checks the flag and bumps the generation under the same lock, so the location is either marked while this box is still valid or the leak fails
+        let _recycling = self.raw.0.dispose_hooks.lock();
+        let dropped = matches!(self.raw.0.data.try_read(), Err(StorageBorrowError::Dropped));
+        if !self.validate() || dropped {
+            return Err(self.value_dropped());
+        }
+        // Another copy of this box may have leaked the value already
+        _ = self
+            .raw
+            .0
+            .leaked
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst);
+        Ok(LeakedBox { inner: self })
+    }
+
+    /// Create a handle to a part of the value, like a field of a struct. `map` and `map_mut` project a borrow of the whole value onto the part for reads and writes.
+    ///
+    /// ```rust
+    /// use generational_box::Store;
+    ///
+    /// struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let store = Store::default();
+    /// let owner = store.owner();
+    /// let user = owner.insert(User { name: "Alice".to_string(), age: 30 });
+    ///
+    /// let name = user.map(|user| &user.name, |user| &mut user.name);
+    /// name.write().push_str(" Smith");
+    /// assert_eq!(*name.read(), "Alice Smith");
+    /// assert_eq!(user.read().name, "Alice Smith");
+    /// ```
+    pub fn map<U: ?Sized + 'static>(
+        self,
+        map: fn(&T) -> &U,
+        map_mut: fn(&mut T) -> &mut U,
+    ) -> MappedGenerationalBox<T, U, S> {
+        MappedGenerationalBox {
+            source: self,
+            map,
+            map_mut,
+        }
+    }
+
+    /// Returns true if the pointer is equal to the other pointer.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        #[cfg(any(debug_assertions, feature = "check_generation"))]
+        {
+            self.raw.0.data.data_ptr() == other.raw.0.data.data_ptr()
+                && self.generation == other.generation
+        }
+        #[cfg(not(any(debug_assertions, feature = "check_generation")))]
+        {
+            self.raw.0.data.data_ptr() == other.raw.0.data.data_ptr()
+        }
+    }
+}
+
+impl<T, S: 'static> Copy for GenerationalBox<T, S> {}
+
+impl<T, S: 'static> Clone for GenerationalBox<T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// A weak handle to a value in a generational box. Weak handles are meant for caches and registries that need to refer to a value without being able to read it after it has been dropped.
+///
+/// Upgrading a weak handle checks that the value is still alive. Without the `check_generation` feature (or debug assertions), a slot that was recycled for a new value of the same type cannot be told apart from the original value.
+pub struct GenerationalWeak<T, S: 'static = DefaultStorage> {
+    inner: GenerationalBox<T, S>,
+}
+
+impl<T: 'static, S: Storage<T>> GenerationalWeak<T, S> {
+    /// Try to get a strong handle to the value. Returns None if the value has been dropped.
+    pub fn upgrade(&self) -> Option<GenerationalBox<T, S>> {
+        if !self.inner.validate() {
+            return None;
+        }
+        match self.inner.raw.0.data.try_read() {
+            Ok(_) => Some(self.inner),
+            // The value is borrowed mutably, so it must still be alive
+            Err(StorageBorrowError::Borrowed) => Some(self.inner),
+            Err(StorageBorrowError::Dropped) => None,
+        }
+    }
+
+    /// Get the id of the generational box this handle points to.
+    pub fn id(&self) -> GenerationalBoxId {
+        self.inner.id()
+    }
+}
+
+impl<T: 'static, S: AnyStorage> Debug for GenerationalWeak<T, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("Weak({:?})", self.inner))
+    }
+}
+
+impl<T, S: 'static> Copy for GenerationalWeak<T, S> {}
+
+impl<T, S: 'static> Clone for GenerationalWeak<T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// A handle to a part of the value in a generational box. Created with [`GenerationalBox::map`].
+///
+/// Reads and writes borrow the whole value in the same slot as the original box, and then project the borrow onto the part. The handle is `Copy` and becomes invalid when the original value is dropped.
+pub struct MappedGenerationalBox<T, U: ?Sized, S: 'static = DefaultStorage> {
+    source: GenerationalBox<T, S>,
+    map: fn(&T) -> &U,
+    map_mut: fn(&mut T) -> &mut U,
+}
+
+impl<T: 'static, U: ?Sized + 'static, S: Storage<T>> MappedGenerationalBox<T, U, S> {
+    /// Get the box this handle projects from.
+    pub fn source(&self) -> GenerationalBox<T, S> {
+        self.source
+    }
+
+    /// Get the id of the generational box this handle projects from.
+    pub fn id(&self) -> GenerationalBoxId {
+        self.source.id()
+    }
+
+    /// Try to read the part of the value. Returns an error if the value has been dropped or is borrowed mutably.
+    #[track_caller]
+    pub fn try_read(&self) -> Result<GenerationalRef<U, S>, BorrowError> {
+        self.source
+            .try_read()
+            .map(|value| GenerationalRef::map(value, self.map))
+    }
+
+    /// Read the part of the value. Panics if the value is no longer valid.
+    #[track_caller]
+    pub fn read(&self) -> GenerationalRef<U, S> {
+        self.try_read().unwrap()
+    }
+
+    /// Try to write the part of the value. Returns an error if the value has been dropped or is already borrowed.
+    #[track_caller]
+    pub fn try_write(&self) -> Result<GenerationalRefMut<U, S>, BorrowMutError> {
+        self.source
+            .try_write()
+            .map(|value| GenerationalRefMut::map(value, self.map_mut))
+    }
+
+    /// Write the part of the value. Panics if the value is no longer valid.
+    #[track_caller]
+    pub fn write(&self) -> GenerationalRefMut<U, S> {
+        self.try_write().unwrap()
+    }
+}
+
+impl<T: 'static, U: ?Sized, S: AnyStorage> Debug for MappedGenerationalBox<T, U, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("Mapped({:?})", self.source))
+    }
+}
+
+impl<T, U: ?Sized, S: 'static> Copy for MappedGenerationalBox<T, U, S> {}
+
+impl<T, U: ?Sized, S: 'static> Clone for MappedGenerationalBox<T, U, S> {
+    fn clone(&self) -> Self {
+        *self
     }
 }
 
-/// The core Copy state type. The generational box will be dropped when the [Owner] is dropped.
-pub struct GenerationalBox<T> {
-    raw: MemoryLocation,
-    #[cfg(any(debug_assertions, feature = "check_generation"))]
-    generation: u32,
-    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-    created_at: &'static std::panic::Location<'static>,
-    _marker: PhantomData<T>,
+/// A handle to a value that is never dropped. Created with [`GenerationalBox::leak`].
+///
+/// The value can't be dropped by its owner, so reads and writes skip the generation check and only fail if the value is already borrowed in a conflicting way. Taking the value out of a copy of the original box with [`GenerationalBox::take`] still drops it.
+pub struct LeakedBox<T, S: 'static = DefaultStorage> {
+    inner: GenerationalBox<T, S>,
 }
 
-impl<T: 'static> Debug for GenerationalBox<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        #[cfg(any(debug_assertions, feature = "check_generation"))]
-        f.write_fmt(format_args!(
-            "{:?}@{:?}",
-            self.raw.0.data.as_ptr(),
-            self.generation
-        ))?;
-        #[cfg(not(any(debug_assertions, feature = "check_generation")))]
-        f.write_fmt(format_args!("{:?}", self.raw.data.as_ptr()))?;
-        Ok(())
+impl<T: 'static, S: Storage<T>> LeakedBox<T, S> {
+    /// Get a generational box pointing to the value.
+    pub fn as_box(&self) -> GenerationalBox<T, S> {
+        self.inner
     }
-}
 
-impl<T: 'static> GenerationalBox<T> {
-    #[inline(always)]
-    fn validate(&self) -> bool {
-        #[cfg(any(debug_assertions, feature = "check_generation"))]
-        {
-            self.raw.0.generation.get() == self.generation
-        }
-        #[cfg(not(any(debug_assertions, feature = "check_generation")))]
-        {
-            true
-        }
+    /// Get the id of the generational box this handle points to.
+    pub fn id(&self) -> GenerationalBoxId {
+        self.inner.id()
     }
 
-    /// Try to read the value. Returns None if the value is no longer valid.
+    /// Try to read the value. Returns an error if the value is borrowed mutably.
     #[track_caller]
-    pub fn try_read(&self) -> Result<GenerationalRef<T>, BorrowError> {
-        if !self.validate() {
-            return Err(BorrowError::Dropped(ValueDroppedError {
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                created_at: self.created_at,
-            }));
-        }
-        self.raw.try_borrow(
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            self.created_at,
-        )
+    pub fn try_read(&self) -> Result<GenerationalRef<T, S>, BorrowError> {
+        self.inner.try_read_unchecked()
     }
 
-    /// Read the value. Panics if the value is no longer valid.
+    /// Read the value. Panics if the value is borrowed mutably.
     #[track_caller]
-    pub fn read(&self) -> GenerationalRef<T> {
+    pub fn read(&self) -> GenerationalRef<T, S> {
         self.try_read().unwrap()
     }
 
-    /// Try to write the value. Returns None if the value is no longer valid.
+    /// Try to write the value. Returns an error if the value is already borrowed.
     #[track_caller]
-    pub fn try_write(&self) -> Result<GenerationalRefMut<T>, BorrowMutError> {
-        if !self.validate() {
-            return Err(BorrowMutError::Dropped(ValueDroppedError {
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                created_at: self.created_at,
-            }));
-        }
-        self.raw.try_borrow_mut(
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            self.created_at,
-        )
+    pub fn try_write(&self) -> Result<GenerationalRefMut<T, S>, BorrowMutError> {
+        self.inner.try_write_unchecked()
     }
 
-    /// Write the value. Panics if the value is no longer valid.
+    /// Write the value. Panics if the value is already borrowed.
     #[track_caller]
-    pub fn write(&self) -> GenerationalRefMut<T> {
+    pub fn write(&self) -> GenerationalRefMut<T, S> {
         self.try_write().unwrap()
     }
 
-    /// Set the value. Panics if the value is no longer valid.
+    /// Set the value.
     pub fn set(&self, value: T) {
-        self.validate().then(|| {
-            *self.raw.0.data.borrow_mut() = Some(Box::new(value));
-        });
+        self.inner.raw.0.data.set(value);
     }
+}
 
-    /// Returns true if the pointer is equal to the other pointer.
-    pub fn ptr_eq(&self, other: &Self) -> bool {
-        #[cfg(any(debug_assertions, feature = "check_generation"))]
-        {
-            self.raw.0.data.as_ptr() == other.raw.0.data.as_ptr()
-                && self.generation == other.generation
-        }
-        #[cfg(not(any(debug_assertions, feature = "check_generation")))]
-        {
-            self.raw.data.as_ptr() == other.raw.data.as_ptr()
-        }
+impl<T: 'static, S: AnyStorage> Debug for LeakedBox<T, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!("Leaked({:?})", self.inner))
     }
 }
 
-impl<T> Copy for GenerationalBox<T> {}
+impl<T, S: 'static> Copy for LeakedBox<T, S> {}
 
-impl<T> Clone for GenerationalBox<T> {
+impl<T, S: 'static> Clone for LeakedBox<T, S> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-#[derive(Clone, Copy)]
-struct MemoryLocation(&'static MemoryLocationInner);
-
-struct MemoryLocationInner {
-    data: RefCell<Option<Box<dyn std::any::Any>>>,
-    #[cfg(any(debug_assertions, feature = "check_generation"))]
-    generation: Cell<u32>,
-    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-    borrowed_at: RefCell<Vec<&'static std::panic::Location<'static>>>,
-    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-    borrowed_mut_at: Cell<Option<&'static std::panic::Location<'static>>>,
+/// The reason a [`Storage`] could not borrow its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageBorrowError {
+    /// The storage is empty or holds a value of a different type.
+    Dropped,
+    /// The value is already borrowed in a way that conflicts with the requested borrow.
+    Borrowed,
 }
 
-impl MemoryLocation {
-    #[allow(unused)]
-    fn drop(&self) {
-        let old = self.0.data.borrow_mut().take();
-        #[cfg(any(debug_assertions, feature = "check_generation"))]
-        if old.is_some() {
-            drop(old);
-            let new_generation = self.0.generation.get() + 1;
-            self.0.generation.set(new_generation);
+/// A cell that holds the type erased value of a generational box. [`UnsyncStorage`] and [`SyncStorage`] are provided by this crate, and storages can be wrapped to add behavior like [`Instrumented`].
+///
+/// # Implementing a custom storage
+///
+/// Storages outside of this crate work the same way as the built-in ones. A [`Store`] drives every storage through the same lifecycle:
+///
+/// - When the store runs out of recycled memory locations, it creates a new storage with [`Default`] and leaks it. Storages are never dropped, which is why borrows can be `'static`.
+/// - When a value is inserted into an [`Owner`], the store claims a location and calls [`Storage::set`].
+/// - Boxes read and write the value with [`Storage::try_read`] and [`Storage::try_write`], and replace or move it with [`Storage::set_if`] and [`Storage::take_if`]. The generation of the box is checked in the `valid` callbacks, so a storage that can be shared between threads must call them while it is locked.
+/// - When the owner is dropped or disposed, the store calls [`AnyStorage::take`] and puts the location back into the free list. The next value claimed from that location may have a different type.
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use generational_box::{AnyStorage, Storage, StorageBorrowError, Store, UnsyncStorage};
+///
+/// static LIVE: AtomicUsize = AtomicUsize::new(0);
+///
+/// // Counts how many values are alive, and keeps the values in an `UnsyncStorage`
+/// #[derive(Default)]
+/// struct Counted(UnsyncStorage);
+///
+/// impl<T: 'static> Storage<T> for Counted {
+///     fn try_read(&'static self) -> Result<Self::Ref<T>, StorageBorrowError> {
+///         self.0.try_read()
+///     }
+///
+///     fn try_write(&'static self) -> Result<Self::Mut<T>, StorageBorrowError> {
+///         self.0.try_write()
+///     }
+///
+///     fn set(&self, value: T) {
+///         if !self.0.take() {
+///             LIVE.fetch_add(1, Ordering::Relaxed);
+///         }
+///         self.0.set(value);
+///     }
+///
+///     fn take_if(&self, valid: impl FnOnce() -> bool) -> Option<T> {
+///         let value = self.0.take_if(valid)?;
+///         LIVE.fetch_sub(1, Ordering::Relaxed);
+///         Some(value)
+///     }
+/// }
+///
+/// impl AnyStorage for Counted {
+///     type Ref<T: ?Sized + 'static> = <UnsyncStorage as AnyStorage>::Ref<T>;
+///     type Mut<T: ?Sized + 'static> = <UnsyncStorage as AnyStorage>::Mut<T>;
+///
+///     fn map<T: ?Sized + 'static, U: ?Sized + 'static>(
+///         reference: Self::Ref<T>,
+///         f: impl FnOnce(&T) -> &U,
+///     ) -> Self::Ref<U> {
+///         UnsyncStorage::map(reference, f)
+///     }
+///
+///     fn try_map<T: ?Sized + 'static, U: ?Sized + 'static>(
+///         reference: Self::Ref<T>,
+///         f: impl FnOnce(&T) -> Option<&U>,
+///     ) -> Option<Self::Ref<U>> {
+///         UnsyncStorage::try_map(reference, f)
+///     }
+///
+///     fn map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+///         reference: Self::Mut<T>,
+///         f: impl FnOnce(&mut T) -> &mut U,
+///     ) -> Self::Mut<U> {
+///         UnsyncStorage::map_mut(reference, f)
+///     }
+///
+///     fn try_map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+///         reference: Self::Mut<T>,
+///         f: impl FnOnce(&mut T) -> Option<&mut U>,
+///     ) -> Option<Self::Mut<U>> {
+///         UnsyncStorage::try_map_mut(reference, f)
+///     }
+///
+///     fn take(&self) -> bool {
+///         let dropped = self.0.take();
+///         if dropped {
+///             LIVE.fetch_sub(1, Ordering::Relaxed);
+///         }
+///         dropped
+///     }
+/// }
+///
+/// let store = Store::<Counted>::new();
+/// let owner = store.owner();
+/// let key = owner.insert("hello world".to_string());
+/// assert_eq!(*key.read(), "hello world");
+/// assert_eq!(LIVE.load(Ordering::Relaxed), 1);
+/// drop(owner);
+/// assert_eq!(LIVE.load(Ordering::Relaxed), 0);
+/// ```
+pub trait Storage<Data = ()>: AnyStorage {
+    /// Try to borrow the value immutably.
+    fn try_read(&'static self) -> Result<Self::Ref<Data>, StorageBorrowError>;
+
+    /// Try to borrow the value mutably.
+    fn try_write(&'static self) -> Result<Self::Mut<Data>, StorageBorrowError>;
+
+    /// Replace the value in the storage.
+    fn set(&self, value: Data);
+
+    /// Take the value out of the storage if `valid` returns true and the storage holds a value of this type.
+    ///
+    /// Storages that can be shared between threads should call `valid` while the storage is locked.
+    fn take_if(&self, valid: impl FnOnce() -> bool) -> Option<Data>;
+
+    /// Replace the value in the storage if `valid` returns true, or give the value back if it returns false.
+    ///
+    /// Storages that can be shared between threads should call `valid` while the storage is locked, so the check can't race with the value being dropped.
+    fn set_if(&self, value: Data, valid: impl FnOnce() -> bool) -> Result<(), Data> {
+        if !valid() {
+            return Err(value);
         }
+        self.set(value);
+        Ok(())
     }
+}
 
-    fn replace_with_caller<T: 'static>(
-        &mut self,
-        value: T,
-        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-        caller: &'static std::panic::Location<'static>,
-    ) -> GenerationalBox<T> {
-        let mut inner_mut = self.0.data.borrow_mut();
+/// The part of a [`Storage`] that does not depend on the type of the value it holds.
+pub trait AnyStorage: Default + 'static {
+    /// The immutable reference type the storage hands out.
+    type Ref<T: ?Sized + 'static>: Deref<Target = T> + 'static;
+    /// The mutable reference type the storage hands out.
+    type Mut<T: ?Sized + 'static>: DerefMut<Target = T> + 'static;
 
-        let raw = Box::new(value);
-        let old = inner_mut.replace(raw);
-        assert!(old.is_none());
-        GenerationalBox {
-            raw: *self,
+    /// Map one ref type to another.
+    fn map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<T>,
+        f: impl FnOnce(&T) -> &U,
+    ) -> Self::Ref<U>;
+
+    /// Filter one ref type to another.
+    fn try_map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>>;
+
+    /// Map one mutable ref type to another.
+    fn map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> Self::Mut<U>;
+
+    /// Filter one mutable ref type to another.
+    fn try_map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>>;
+
+    /// Get a pointer that identifies this storage.
+    fn data_ptr(&self) -> *const () {
+        self as *const Self as *const ()
+    }
+
+    /// Drop the value in the storage. Returns true if there was a value to drop.
+    ///
+    /// This is called when the owner of the value is dropped, right before the storage is put back into the free list of its [`Store`].
+    fn take(&self) -> bool;
+}
+
+struct MemoryLocation<S: 'static = DefaultStorage>(&'static MemoryLocationInner<S>);
+
+impl<S: 'static> Copy for MemoryLocation<S> {}
+
+impl<S: 'static> Clone for MemoryLocation<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+struct MemoryLocationInner<S = DefaultStorage> {
+    data: S,
+    #[cfg(any(debug_assertions, feature = "check_generation"))]
+    generation: AtomicU64,
+    // Set by `GenerationalBox::leak`. Leaked locations are never dropped or recycled
+    leaked: AtomicBool,
+    borrow: MemoryLocationBorrowInfo,
+    // Registered with `GenerationalBox::on_dispose` and run when the location is recycled. The lock is also held while the location is leaked or invalidated, so the two can't interleave
+    dispose_hooks: Mutex<Vec<DisposeHook>>,
+}
+
+type DisposeHook = Box<dyn FnOnce() + Send>;
+
+impl<S: AnyStorage> MemoryLocationInner<S> {
+    fn new() -> Self {
+        Self {
+            data: S::default(),
             #[cfg(any(debug_assertions, feature = "check_generation"))]
-            generation: self.0.generation.get(),
-            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-            created_at: caller,
-            _marker: PhantomData,
+            generation: AtomicU64::new(0),
+            leaked: AtomicBool::new(false),
+            borrow: Default::default(),
+            dispose_hooks: Mutex::new(Vec::new()),
         }
     }
+}
+
+/// Tracks where the value in a memory location is borrowed. Without debug assertions or the `debug_borrows` feature, this is a zero sized stub and tracking borrows is free.
+#[derive(Default)]
+struct MemoryLocationBorrowInfo {
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_at: lock::RwLock<Vec<&'static core::panic::Location<'static>>>,
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_mut_at: lock::RwLock<Option<&'static core::panic::Location<'static>>>,
+    #[cfg(feature = "backtrace")]
+    borrowed_backtraces: lock::RwLock<Vec<Arc<std::backtrace::Backtrace>>>,
+}
 
+impl MemoryLocationBorrowInfo {
     #[track_caller]
-    fn try_borrow<T: Any>(
-        &self,
-        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-        created_at: &'static std::panic::Location<'static>,
-    ) -> Result<GenerationalRef<T>, BorrowError> {
+    fn borrow(&'static self) -> GenerationalRefBorrowInfo {
         #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-        self.0
-            .borrowed_at
-            .borrow_mut()
-            .push(std::panic::Location::caller());
-        match self.0.data.try_borrow() {
-            Ok(borrow) => match Ref::filter_map(borrow, |any| any.as_ref()?.downcast_ref::<T>()) {
-                Ok(reference) => Ok(GenerationalRef {
-                    inner: reference,
-                    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                    borrow: GenerationalRefBorrowInfo {
-                        borrowed_at: std::panic::Location::caller(),
-                        borrowed_from: self.0,
-                    },
-                }),
-                Err(_) => Err(BorrowError::Dropped(ValueDroppedError {
-                    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-                    created_at,
-                })),
-            },
-            Err(_) => Err(BorrowError::AlreadyBorrowedMut(AlreadyBorrowedMutError {
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrowed_mut_at: self.0.borrowed_mut_at.get().unwrap(),
-            })),
+        {
+            let borrowed_at = core::panic::Location::caller();
+            self.borrowed_at.write().push(borrowed_at);
+            #[cfg(feature = "backtrace")]
+            let backtrace = Arc::new(std::backtrace::Backtrace::force_capture());
+            #[cfg(feature = "backtrace")]
+            self.borrowed_backtraces.write().push(backtrace.clone());
+            GenerationalRefBorrowInfo {
+                borrowed_at,
+                borrowed_from: self,
+                #[cfg(feature = "backtrace")]
+                backtrace,
+            }
+        }
+        #[cfg(not(any(debug_assertions, feature = "debug_borrows")))]
+        {
+            GenerationalRefBorrowInfo {}
         }
     }
 
     #[track_caller]
-    fn try_borrow_mut<T: Any>(
-        &self,
-        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-        created_at: &'static std::panic::Location<'static>,
-    ) -> Result<GenerationalRefMut<T>, BorrowMutError> {
+    fn borrow_mut(&'static self) -> GenerationalRefMutBorrowInfo {
         #[cfg(any(debug_assertions, feature = "debug_borrows"))]
         {
-            self.0
-                .borrowed_mut_at
-                .set(Some(std::panic::Location::caller()));
-        }
-        match self.0.data.try_borrow_mut() {
-            Ok(borrow_mut) => {
-                match RefMut::filter_map(borrow_mut, |any| any.as_mut()?.downcast_mut::<T>()) {
-                    Ok(reference) => Ok(GenerationalRefMut {
-                        inner: reference,
-                        #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                        borrow: GenerationalRefMutBorrowInfo {
-                            borrowed_from: self.0,
-                        },
-                    }),
-                    Err(_) => Err(BorrowMutError::Dropped(ValueDroppedError {
-                        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-                        created_at,
-                    })),
-                }
+            *self.borrowed_mut_at.write() = Some(core::panic::Location::caller());
+            GenerationalRefMutBorrowInfo {
+                borrowed_from: self,
+            }
+        }
+        #[cfg(not(any(debug_assertions, feature = "debug_borrows")))]
+        {
+            GenerationalRefMutBorrowInfo {}
+        }
+    }
+
+    fn already_borrowed_mut(&self, value: ValueInfo) -> AlreadyBorrowedMutError {
+        AlreadyBorrowedMutError {
+            value,
+            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            borrowed_mut_at: self.borrowed_mut_at.read().unwrap(),
+        }
+    }
+
+    fn already_borrowed(&self, value: ValueInfo) -> AlreadyBorrowedError {
+        AlreadyBorrowedError {
+            value,
+            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            borrowed_at: self.borrowed_at.read().clone(),
+            #[cfg(feature = "backtrace")]
+            backtraces: self.borrowed_backtraces.read().clone(),
+        }
+    }
+}
+
+impl<S: AnyStorage> MemoryLocation<S> {
+    fn id(&self) -> GenerationalBoxId {
+        GenerationalBoxId {
+            data_ptr: self.0.data.data_ptr() as usize,
+            #[cfg(any(debug_assertions, feature = "check_generation"))]
+            generation: self.0.generation.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Drop the value and run its dispose hooks. Returns false without doing anything if the location was leaked.
+    fn drop(&self) -> bool {
+        let hooks = {
+            let mut hooks = self.0.dispose_hooks.lock();
+            if self.0.leaked.load(Ordering::SeqCst) {
+                return false;
             }
-            Err(_) => Err(BorrowMutError::AlreadyBorrowed(AlreadyBorrowedError {
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrowed_at: self.0.borrowed_at.borrow().clone(),
-            })),
+            // The generation is bumped before the value is taken so a `set_if` that checks the generation while the storage is locked either sees the new generation or has its value dropped here
+            #[cfg(any(debug_assertions, feature = "check_generation"))]
+            self.0.generation.fetch_add(1, Ordering::Relaxed);
+            core::mem::take(&mut *hooks)
+        };
+        self.0.data.take();
+
+        // Hooks run after the value is dropped and outside of the lock, so they can use other boxes or register hooks of their own
+        for hook in hooks {
+            hook();
+        }
+        true
+    }
+
+    fn replace_with_caller<T: 'static>(
+        &mut self,
+        value: T,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        caller: &'static core::panic::Location<'static>,
+    ) -> GenerationalBox<T, S>
+    where
+        S: Storage<T>,
+    {
+        self.0.data.set(value);
+        GenerationalBox {
+            raw: *self,
+            #[cfg(any(debug_assertions, feature = "check_generation"))]
+            generation: self.0.generation.load(Ordering::Relaxed),
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at: caller,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            label: None,
+            _marker: PhantomData,
         }
     }
 }
@@ -387,7 +1549,7 @@ pub enum BorrowError {
 }
 
 impl Display for BorrowError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             BorrowError::Dropped(error) => Display::fmt(error, f),
             BorrowError::AlreadyBorrowedMut(error) => Display::fmt(error, f),
@@ -395,7 +1557,8 @@ impl Display for BorrowError {
     }
 }
 
-impl Error for BorrowError {}
+#[cfg(feature = "std")]
+impl std::error::Error for BorrowError {}
 
 #[derive(Debug, Clone)]
 /// An error that can occur when trying to borrow a value mutably.
@@ -409,7 +1572,7 @@ pub enum BorrowMutError {
 }
 
 impl Display for BorrowMutError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             BorrowMutError::Dropped(error) => Display::fmt(error, f),
             BorrowMutError::AlreadyBorrowedMut(error) => Display::fmt(error, f),
@@ -418,111 +1581,199 @@ impl Display for BorrowMutError {
     }
 }
 
-impl Error for BorrowMutError {}
+#[cfg(feature = "std")]
+impl std::error::Error for BorrowMutError {}
+
+/// The value a borrow error is about.
+#[derive(Debug, Copy, Clone)]
+struct ValueInfo {
+    id: GenerationalBoxId,
+    type_name: &'static str,
+    label: Option<&'static str>,
+}
+
+impl Display for ValueInfo {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(label) = self.label {
+            f.write_fmt(format_args!("`{}` ", label))?;
+        }
+        f.write_fmt(format_args!("({} {:?})", self.type_name, self.id))
+    }
+}
 
 /// An error that can occur when trying to use a value that has been dropped.
 #[derive(Debug, Copy, Clone)]
 pub struct ValueDroppedError {
+    value: ValueInfo,
     #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-    created_at: &'static std::panic::Location<'static>,
+    created_at: &'static core::panic::Location<'static>,
+}
+
+impl ValueDroppedError {
+    /// Get the id of the generational box that was borrowed.
+    pub fn id(&self) -> GenerationalBoxId {
+        self.value.id
+    }
+
+    /// Get the name of the type of the value that was borrowed.
+    pub fn type_name(&self) -> &'static str {
+        self.value.type_name
+    }
+
+    /// Get the label set with [`GenerationalBox::with_label`].
+    pub fn label(&self) -> Option<&'static str> {
+        self.value.label
+    }
 }
 
 impl Display for ValueDroppedError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("Failed to borrow because the value was dropped.")?;
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "Failed to borrow {} because the value was dropped.",
+            self.value
+        ))?;
         #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-        f.write_fmt(format_args!("created_at: {}", self.created_at))?;
+        f.write_fmt(format_args!(" created_at: {}", self.created_at))?;
         Ok(())
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for ValueDroppedError {}
 
 /// An error that can occur when trying to borrow a value that has already been borrowed mutably.
 #[derive(Debug, Copy, Clone)]
 pub struct AlreadyBorrowedMutError {
+    value: ValueInfo,
     #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-    borrowed_mut_at: &'static std::panic::Location<'static>,
+    borrowed_mut_at: &'static core::panic::Location<'static>,
+}
+
+impl AlreadyBorrowedMutError {
+    /// Get the id of the generational box that was borrowed.
+    pub fn id(&self) -> GenerationalBoxId {
+        self.value.id
+    }
+
+    /// Get the name of the type of the value that was borrowed.
+    pub fn type_name(&self) -> &'static str {
+        self.value.type_name
+    }
+
+    /// Get the label set with [`GenerationalBox::with_label`].
+    pub fn label(&self) -> Option<&'static str> {
+        self.value.label
+    }
 }
 
 impl Display for AlreadyBorrowedMutError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("Failed to borrow because the value was already borrowed mutably.")?;
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "Failed to borrow {} because the value was already borrowed mutably.",
+            self.value
+        ))?;
         #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-        f.write_fmt(format_args!("borrowed_mut_at: {}", self.borrowed_mut_at))?;
+        f.write_fmt(format_args!(" borrowed_mut_at: {}", self.borrowed_mut_at))?;
         Ok(())
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for AlreadyBorrowedMutError {}
 
 /// An error that can occur when trying to borrow a value mutably that has already been borrowed immutably.
 #[derive(Debug, Clone)]
 pub struct AlreadyBorrowedError {
+    value: ValueInfo,
     #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-    borrowed_at: Vec<&'static std::panic::Location<'static>>,
+    borrowed_at: Vec<&'static core::panic::Location<'static>>,
+    #[cfg(feature = "backtrace")]
+    backtraces: Vec<Arc<std::backtrace::Backtrace>>,
+}
+
+impl AlreadyBorrowedError {
+    /// Get the backtraces captured when each of the borrows that are still alive was created. This is only available with the `backtrace` feature.
+    ///
+    /// The locations in the error point at the code that created the borrow, which is often a hook. The backtraces also show the task or callback that is holding the borrow.
+    #[cfg(feature = "backtrace")]
+    pub fn backtraces(&self) -> &[Arc<std::backtrace::Backtrace>] {
+        &self.backtraces
+    }
+
+    /// Get the id of the generational box that was borrowed.
+    pub fn id(&self) -> GenerationalBoxId {
+        self.value.id
+    }
+
+    /// Get the name of the type of the value that was borrowed.
+    pub fn type_name(&self) -> &'static str {
+        self.value.type_name
+    }
+
+    /// Get the label set with [`GenerationalBox::with_label`].
+    pub fn label(&self) -> Option<&'static str> {
+        self.value.label
+    }
 }
 
 impl Display for AlreadyBorrowedError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("Failed to borrow mutably because the value was already borrowed immutably.")?;
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_fmt(format_args!(
+            "Failed to borrow {} mutably because the value was already borrowed immutably.",
+            self.value
+        ))?;
         #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-        f.write_str("borrowed_at:")?;
+        f.write_str(" borrowed_at:")?;
         #[cfg(any(debug_assertions, feature = "debug_borrows"))]
         for location in self.borrowed_at.iter() {
             f.write_fmt(format_args!("\t{}", location))?;
         }
+        #[cfg(feature = "backtrace")]
+        for backtrace in self.backtraces.iter() {
+            f.write_fmt(format_args!(
+                "\n\nThe value was borrowed here:\n{}",
+                backtrace
+            ))?;
+        }
         Ok(())
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for AlreadyBorrowedError {}
 
 /// A reference to a value in a generational box.
-pub struct GenerationalRef<T: 'static> {
-    inner: Ref<'static, T>,
-    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+pub struct GenerationalRef<T: ?Sized + 'static, S: AnyStorage = DefaultStorage> {
+    inner: S::Ref<T>,
     borrow: GenerationalRefBorrowInfo,
 }
 
-impl<T: 'static> GenerationalRef<T> {
+impl<T: ?Sized + 'static, S: AnyStorage> GenerationalRef<T, S> {
     /// Map one ref type to another.
-    pub fn map<U, F>(orig: GenerationalRef<T>, f: F) -> GenerationalRef<U>
+    pub fn map<U: ?Sized + 'static, F>(orig: GenerationalRef<T, S>, f: F) -> GenerationalRef<U, S>
     where
         F: FnOnce(&T) -> &U,
     {
         GenerationalRef {
-            inner: Ref::map(orig.inner, f),
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            borrow: GenerationalRefBorrowInfo {
-                borrowed_at: orig.borrow.borrowed_at,
-                borrowed_from: orig.borrow.borrowed_from,
-            },
+            inner: S::map(orig.inner, f),
+            borrow: orig.borrow,
         }
     }
 
     /// Filter one ref type to another.
-    pub fn filter_map<U, F>(orig: GenerationalRef<T>, f: F) -> Option<GenerationalRef<U>>
+    pub fn filter_map<U: ?Sized + 'static, F>(
+        orig: GenerationalRef<T, S>,
+        f: F,
+    ) -> Option<GenerationalRef<U, S>>
     where
         F: FnOnce(&T) -> Option<&U>,
     {
-        let Self {
-            inner,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            borrow,
-        } = orig;
-        Ref::filter_map(inner, f).ok().map(|inner| GenerationalRef {
-            inner,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            borrow: GenerationalRefBorrowInfo {
-                borrowed_at: borrow.borrowed_at,
-                borrowed_from: borrow.borrowed_from,
-            },
-        })
+        let Self { inner, borrow } = orig;
+        S::try_map(inner, f).map(|inner| GenerationalRef { inner, borrow })
     }
 }
 
-impl<T: 'static> Deref for GenerationalRef<T> {
+impl<T: ?Sized + 'static, S: AnyStorage> Deref for GenerationalRef<T, S> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -530,10 +1781,13 @@ impl<T: 'static> Deref for GenerationalRef<T> {
     }
 }
 
-#[cfg(any(debug_assertions, feature = "debug_borrows"))]
 struct GenerationalRefBorrowInfo {
-    borrowed_at: &'static std::panic::Location<'static>,
-    borrowed_from: &'static MemoryLocationInner,
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_at: &'static core::panic::Location<'static>,
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_from: &'static MemoryLocationBorrowInfo,
+    #[cfg(feature = "backtrace")]
+    backtrace: Arc<std::backtrace::Backtrace>,
 }
 
 #[cfg(any(debug_assertions, feature = "debug_borrows"))]
@@ -541,52 +1795,51 @@ impl Drop for GenerationalRefBorrowInfo {
     fn drop(&mut self) {
         self.borrowed_from
             .borrowed_at
-            .borrow_mut()
-            .retain(|location| std::ptr::eq(*location, self.borrowed_at as *const _));
+            .write()
+            .retain(|location| !core::ptr::eq(*location, self.borrowed_at as *const _));
+        #[cfg(feature = "backtrace")]
+        self.borrowed_from
+            .borrowed_backtraces
+            .write()
+            .retain(|backtrace| !Arc::ptr_eq(backtrace, &self.backtrace));
     }
 }
 
 /// A mutable reference to a value in a generational box.
-pub struct GenerationalRefMut<T: 'static> {
-    inner: RefMut<'static, T>,
-    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+pub struct GenerationalRefMut<T: ?Sized + 'static, S: AnyStorage = DefaultStorage> {
+    inner: S::Mut<T>,
     borrow: GenerationalRefMutBorrowInfo,
 }
 
-impl<T: 'static> GenerationalRefMut<T> {
+impl<T: ?Sized + 'static, S: AnyStorage> GenerationalRefMut<T, S> {
     /// Map one ref type to another.
-    pub fn map<U, F>(orig: GenerationalRefMut<T>, f: F) -> GenerationalRefMut<U>
+    pub fn map<U: ?Sized + 'static, F>(
+        orig: GenerationalRefMut<T, S>,
+        f: F,
+    ) -> GenerationalRefMut<U, S>
     where
         F: FnOnce(&mut T) -> &mut U,
     {
         GenerationalRefMut {
-            inner: RefMut::map(orig.inner, f),
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            inner: S::map_mut(orig.inner, f),
             borrow: orig.borrow,
         }
     }
 
     /// Filter one ref type to another.
-    pub fn filter_map<U, F>(orig: GenerationalRefMut<T>, f: F) -> Option<GenerationalRefMut<U>>
+    pub fn filter_map<U: ?Sized + 'static, F>(
+        orig: GenerationalRefMut<T, S>,
+        f: F,
+    ) -> Option<GenerationalRefMut<U, S>>
     where
         F: FnOnce(&mut T) -> Option<&mut U>,
     {
-        let Self {
-            inner,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            borrow,
-        } = orig;
-        RefMut::filter_map(inner, f)
-            .ok()
-            .map(|inner| GenerationalRefMut {
-                inner,
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrow,
-            })
+        let Self { inner, borrow } = orig;
+        S::try_map_mut(inner, f).map(|inner| GenerationalRefMut { inner, borrow })
     }
 }
 
-impl<T: 'static> Deref for GenerationalRefMut<T> {
+impl<T: ?Sized + 'static, S: AnyStorage> Deref for GenerationalRefMut<T, S> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -594,65 +1847,125 @@ impl<T: 'static> Deref for GenerationalRefMut<T> {
     }
 }
 
-impl<T: 'static> DerefMut for GenerationalRefMut<T> {
+impl<T: ?Sized + 'static, S: AnyStorage> DerefMut for GenerationalRefMut<T, S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.inner.deref_mut()
     }
 }
 
-#[cfg(any(debug_assertions, feature = "debug_borrows"))]
 struct GenerationalRefMutBorrowInfo {
-    borrowed_from: &'static MemoryLocationInner,
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_from: &'static MemoryLocationBorrowInfo,
 }
 
 #[cfg(any(debug_assertions, feature = "debug_borrows"))]
 impl Drop for GenerationalRefMutBorrowInfo {
     fn drop(&mut self) {
-        self.borrowed_from.borrowed_mut_at.take();
+        self.borrowed_from.borrowed_mut_at.write().take();
     }
 }
 
 /// Handles recycling generational boxes that have been dropped. Your application should have one store or one store per thread.
-#[derive(Clone)]
-pub struct Store {
-    bump: &'static Bump,
-    recycled: Rc<RefCell<Vec<MemoryLocation>>>,
+///
+/// Stores that are shared between threads can split their free list into shards with [`Store::with_shards`].
+pub struct Store<S: 'static = DefaultStorage> {
+    recycled: Arc<FreeList<MemoryLocation<S>>>,
+    allocated: Arc<AtomicUsize>,
+}
+
+impl<S: 'static> Clone for Store<S> {
+    fn clone(&self) -> Self {
+        Self {
+            recycled: self.recycled.clone(),
+            allocated: self.allocated.clone(),
+        }
+    }
+}
+
+/// Memory statistics for a [`Store`].
+///
+/// Memory locations are leaked when they are created and are never freed. Dropped locations are kept in a free list and reused, so the memory a store uses only grows when more values are alive at once than ever before.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RuntimeStats {
+    /// The number of memory locations that are currently claimed by an owner.
+    pub live: usize,
+    /// The number of memory locations that are waiting in the free list to be reused.
+    pub free: usize,
+    /// The number of bytes allocated for memory locations. This memory is never reclaimed. It does not include the heap memory of the values themselves.
+    pub leaked_bytes: usize,
 }
 
+// Default is only implemented for the default storage so `Store::default()` can infer the storage type
 impl Default for Store {
     fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: AnyStorage> Store<S> {
+    /// Create a new store with a custom storage type. Use [`Store::default`] for the [`DefaultStorage`].
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self::with_shards(1)
+    }
+
+    /// Create a new store whose free list is split into `shards` parts.
+    ///
+    /// Every thread recycles locations into its own shard and claims them from it. When its shard is empty, it takes half of the locations from another shard before allocating new ones. Use this when many threads create and drop boxes in the same store at once, so they don't all wait on one lock. [`SyncStorage::owner`] uses one shard per available CPU.
+    pub fn with_shards(shards: usize) -> Self {
         Self {
-            bump: Box::leak(Box::new(Bump::new())),
-            recycled: Default::default(),
+            recycled: Arc::new(FreeList::new(shards)),
+            allocated: Default::default(),
         }
     }
-}
 
-impl Store {
-    fn recycle(&self, location: MemoryLocation) {
-        location.drop();
-        self.recycled.borrow_mut().push(location);
+    /// Allocate `additional` memory locations up front and add them to the free list.
+    ///
+    /// The locations are leaked as one block instead of one allocation for every box that is created. Call this before creating a lot of boxes at once, like when thousands of components mount on the first render.
+    pub fn reserve(&self, additional: usize) {
+        if additional == 0 {
+            return;
+        }
+        self.allocated.fetch_add(additional, Ordering::Relaxed);
+        let block: &'static [MemoryLocationInner<S>] = Box::leak(
+            (0..additional)
+                .map(|_| MemoryLocationInner::new())
+                .collect::<Box<[_]>>(),
+        );
+        self.recycled.extend(block.iter().map(MemoryLocation));
+    }
+
+    /// Get memory statistics for the store.
+    pub fn runtime_stats(&self) -> RuntimeStats {
+        let allocated = self.allocated.load(Ordering::Relaxed);
+        let free = self.recycled.len();
+        RuntimeStats {
+            // Locations may be allocated and recycled on other threads between the two reads
+            live: allocated.saturating_sub(free),
+            free,
+            leaked_bytes: allocated * core::mem::size_of::<MemoryLocationInner<S>>(),
+        }
+    }
+
+    fn recycle(&self, location: MemoryLocation<S>) {
+        if location.drop() {
+            self.recycled.push(location);
+        }
     }
 
-    fn claim(&self) -> MemoryLocation {
-        if let Some(location) = self.recycled.borrow_mut().pop() {
+    fn claim(&self) -> MemoryLocation<S> {
+        if let Some(location) = self.recycled.pop() {
             location
         } else {
-            let data: &'static MemoryLocationInner = self.bump.alloc(MemoryLocationInner {
-                data: RefCell::new(None),
-                #[cfg(any(debug_assertions, feature = "check_generation"))]
-                generation: Cell::new(0),
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrowed_at: Default::default(),
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrowed_mut_at: Default::default(),
-            });
+            self.allocated.fetch_add(1, Ordering::Relaxed);
+            let data: &'static MemoryLocationInner<S> =
+                Box::leak(Box::new(MemoryLocationInner::new()));
             MemoryLocation(data)
         }
     }
 
     /// Create a new owner. The owner will be responsible for dropping all of the generational boxes that it creates.
-    pub fn owner(&self) -> Owner {
+    pub fn owner(&self) -> Owner<S> {
         Owner {
             store: self.clone(),
             owned: Default::default(),
@@ -661,23 +1974,23 @@ impl Store {
 }
 
 /// Owner: Handles dropping generational boxes. The owner acts like a runtime lifetime guard. Any states that you create with an owner will be dropped when that owner is dropped.
-pub struct Owner {
-    store: Store,
-    owned: Rc<RefCell<Vec<MemoryLocation>>>,
+pub struct Owner<S: AnyStorage = DefaultStorage> {
+    store: Store<S>,
+    owned: Arc<Mutex<Vec<MemoryLocation<S>>>>,
 }
 
-impl Owner {
+impl<S: AnyStorage> Owner<S> {
     /// Insert a value into the store. The value will be dropped when the owner is dropped.
     #[track_caller]
-    pub fn insert<T: 'static>(&self, value: T) -> GenerationalBox<T> {
-        let mut location = self.store.claim();
-        let key = location.replace_with_caller(
+    pub fn insert<T: 'static>(&self, value: T) -> GenerationalBox<T, S>
+    where
+        S: Storage<T>,
+    {
+        self.insert_with_caller(
             value,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            std::panic::Location::caller(),
-        );
-        self.owned.borrow_mut().push(location);
-        key
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            core::panic::Location::caller(),
+        )
     }
 
     /// Insert a value into the store with a specific location blamed for creating the value. The value will be dropped when the owner is dropped.
@@ -685,38 +1998,61 @@ impl Owner {
         &self,
         value: T,
         #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-        caller: &'static std::panic::Location<'static>,
-    ) -> GenerationalBox<T> {
+        caller: &'static core::panic::Location<'static>,
+    ) -> GenerationalBox<T, S>
+    where
+        S: Storage<T>,
+    {
         let mut location = self.store.claim();
         let key = location.replace_with_caller(
             value,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
             caller,
         );
-        self.owned.borrow_mut().push(location);
+        self.owned.lock().push(location);
         key
     }
 
     /// Creates an invalid handle. This is useful for creating a handle that will be filled in later. If you use this before the value is filled in, you will get may get a panic or an out of date value.
-    pub fn invalid<T: 'static>(&self) -> GenerationalBox<T> {
+    pub fn invalid<T: 'static>(&self) -> GenerationalBox<T, S> {
         let location = self.store.claim();
         let key = GenerationalBox {
             raw: location,
             #[cfg(any(debug_assertions, feature = "check_generation"))]
-            generation: location.0.generation.get(),
+            generation: location.0.generation.load(Ordering::Relaxed),
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at: core::panic::Location::caller(),
             #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-            created_at: std::panic::Location::caller(),
+            label: None,
             _marker: PhantomData,
         };
-        self.owned.borrow_mut().push(location);
+        self.owned.lock().push(location);
         key
     }
+
+    /// Get the ids of every generational box this owner is responsible for.
+    pub fn owned(&self) -> Vec<GenerationalBoxId> {
+        self.owned
+            .lock()
+            .iter()
+            .map(|location| location.id())
+            .collect()
+    }
+
+    /// Drop every value this owner is responsible for now instead of waiting for the owner to be dropped. Any boxes pointing to those values will become invalid.
+    ///
+    /// The owner can still be used to insert new values afterward.
+    pub fn dispose(&self) {
+        // Take the locations first so values that insert into this owner while they are dropped don't cause a deadlock
+        let owned = core::mem::take(&mut *self.owned.lock());
+        for location in owned {
+            self.store.recycle(location)
+        }
+    }
 }
 
-impl Drop for Owner {
+impl<S: AnyStorage> Drop for Owner<S> {
     fn drop(&mut self) {
-        for location in self.owned.borrow().iter() {
-            self.store.recycle(*location)
-        }
+        self.dispose();
     }
 }
@@ -0,0 +1,118 @@
+//! The locks the crate uses. With the `std` feature they are the `parking_lot` locks. Without it they are spin locks whose state is only changed inside of a critical section, so they work without an operating system. The rest of the crate still needs atomic pointers for `Arc`, and 64 bit atomics for the generation checks.
+
+#[cfg(feature = "std")]
+use parking_lot::{RawMutex, RawRwLock};
+
+#[cfg(not(feature = "std"))]
+pub use critical::{RawSpinMutex as RawMutex, RawSpinRwLock as RawRwLock};
+
+pub(crate) type Mutex<T> = lock_api::Mutex<RawMutex, T>;
+pub(crate) type RwLock<T> = lock_api::RwLock<RawRwLock, T>;
+pub(crate) type RwLockReadGuard<'a, T> = lock_api::RwLockReadGuard<'a, RawRwLock, T>;
+pub(crate) type RwLockWriteGuard<'a, T> = lock_api::RwLockWriteGuard<'a, RawRwLock, T>;
+pub(crate) type MappedRwLockReadGuard<'a, T> = lock_api::MappedRwLockReadGuard<'a, RawRwLock, T>;
+pub(crate) type MappedRwLockWriteGuard<'a, T> = lock_api::MappedRwLockWriteGuard<'a, RawRwLock, T>;
+
+/// Create a mutex in a const context, for statics.
+pub(crate) const fn const_mutex<T>(value: T) -> Mutex<T> {
+    Mutex::const_new(<RawMutex as lock_api::RawMutex>::INIT, value)
+}
+
+#[cfg(not(feature = "std"))]
+mod critical {
+    use core::{cell::Cell, hint::spin_loop};
+
+    use critical_section::Mutex;
+    use lock_api::GuardSend;
+
+    /// A mutex that spins until it can take the lock. The lock state is only changed inside of a critical section.
+    pub struct RawSpinMutex {
+        locked: Mutex<Cell<bool>>,
+    }
+
+    unsafe impl lock_api::RawMutex for RawSpinMutex {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const INIT: Self = Self {
+            locked: Mutex::new(Cell::new(false)),
+        };
+
+        type GuardMarker = GuardSend;
+
+        fn lock(&self) {
+            while !self.try_lock() {
+                spin_loop();
+            }
+        }
+
+        fn try_lock(&self) -> bool {
+            critical_section::with(|cs| !self.locked.borrow(cs).replace(true))
+        }
+
+        unsafe fn unlock(&self) {
+            critical_section::with(|cs| self.locked.borrow(cs).set(false))
+        }
+    }
+
+    // The reader count while the lock is shared, or WRITER while it is exclusive
+    const WRITER: usize = usize::MAX;
+
+    /// A reader-writer lock that spins until it can take the lock. The lock state is only changed inside of a critical section.
+    pub struct RawSpinRwLock {
+        state: Mutex<Cell<usize>>,
+    }
+
+    unsafe impl lock_api::RawRwLock for RawSpinRwLock {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const INIT: Self = Self {
+            state: Mutex::new(Cell::new(0)),
+        };
+
+        type GuardMarker = GuardSend;
+
+        fn lock_shared(&self) {
+            while !self.try_lock_shared() {
+                spin_loop();
+            }
+        }
+
+        fn try_lock_shared(&self) -> bool {
+            critical_section::with(|cs| {
+                let state = self.state.borrow(cs);
+                let readers = state.get();
+                if readers >= WRITER - 1 {
+                    return false;
+                }
+                state.set(readers + 1);
+                true
+            })
+        }
+
+        unsafe fn unlock_shared(&self) {
+            critical_section::with(|cs| {
+                let state = self.state.borrow(cs);
+                state.set(state.get() - 1);
+            })
+        }
+
+        fn lock_exclusive(&self) {
+            while !self.try_lock_exclusive() {
+                spin_loop();
+            }
+        }
+
+        fn try_lock_exclusive(&self) -> bool {
+            critical_section::with(|cs| {
+                let state = self.state.borrow(cs);
+                if state.get() != 0 {
+                    return false;
+                }
+                state.set(WRITER);
+                true
+            })
+        }
+
+        unsafe fn unlock_exclusive(&self) {
+            critical_section::with(|cs| self.state.borrow(cs).set(0))
+        }
+    }
+}
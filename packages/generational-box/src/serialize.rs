@@ -0,0 +1,47 @@
+//! Serialize boxes as their value, and deserialize values into fresh boxes.
+
+use serde::{ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{AnyStorage, GenerationalBox, LeakedBox, Owner, Storage};
+
+/// Serializes the value in the box. Fails if the value was dropped or is borrowed mutably.
+impl<T: Serialize + 'static, S: Storage<T>> Serialize for GenerationalBox<T, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let value = self.try_read().map_err(Ser::Error::custom)?;
+        (*value).serialize(serializer)
+    }
+}
+
+/// Serializes the value in the box. Fails if the value is borrowed mutably.
+impl<T: Serialize + 'static, S: Storage<T>> Serialize for LeakedBox<T, S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let value = self.try_read().map_err(Ser::Error::custom)?;
+        (*value).serialize(serializer)
+    }
+}
+
+impl<S: AnyStorage> Owner<S> {
+    /// Deserialize a value into a fresh box. The value will be dropped when the owner is dropped.
+    ///
+    /// A [`GenerationalBox`] needs an owner, so it can't implement [`Deserialize`] by itself. Use this in a `deserialize_with` function to deserialize fields into boxes of an owner. For values that live as long as the app, deserialize into an owner and [`leak`](GenerationalBox::leak) the box.
+    ///
+    /// ```rust
+    /// use generational_box::SyncStorage;
+    ///
+    /// let owner = SyncStorage::owner();
+    /// let mut deserializer = serde_json::Deserializer::from_str(r#""hello world""#);
+    /// let key = owner.deserialize::<String, _>(&mut deserializer).unwrap();
+    /// assert_eq!(*key.read(), "hello world");
+    /// assert_eq!(serde_json::to_string(&key).unwrap(), r#""hello world""#);
+    /// ```
+    #[track_caller]
+    pub fn deserialize<'de, T, D>(&self, deserializer: D) -> Result<GenerationalBox<T, S>, D::Error>
+    where
+        T: Deserialize<'de> + 'static,
+        S: Storage<T>,
+        D: Deserializer<'de>,
+    {
+        let value = T::deserialize(deserializer)?;
+        Ok(self.insert(value))
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+use core::any::Any;
+
+use crate::free_list::default_shards;
+use crate::lock::{
+    const_mutex, MappedRwLockReadGuard, MappedRwLockWriteGuard, Mutex, RwLock, RwLockReadGuard,
+    RwLockWriteGuard,
+};
+use crate::{AnyStorage, Owner, RuntimeStats, Storage, StorageBorrowError, Store};
+
+static SYNC_STORE: Mutex<Option<Store<SyncStorage>>> = const_mutex(None);
+
+fn sync_store() -> Store<SyncStorage> {
+    SYNC_STORE
+        .lock()
+        .get_or_insert_with(|| Store::with_shards(default_shards()))
+        .clone()
+}
+
+/// A storage for values that can be shared between threads. Boxes backed by this storage are `Send` and `Sync` when their value is.
+#[derive(Default)]
+pub struct SyncStorage(RwLock<Option<Box<dyn Any + Send + Sync>>>);
+
+impl SyncStorage {
+    /// Create a new owner backed by a store shared between every thread. Every value inserted into the owner is dropped when the owner is dropped.
+    pub fn owner() -> Owner<Self> {
+        sync_store().owner()
+    }
+
+    /// Allocate `additional` memory locations up front in the store shared between every thread. See [`Store::reserve`].
+    pub fn reserve(additional: usize) {
+        sync_store().reserve(additional)
+    }
+
+    /// Get memory statistics for the store shared between every thread that backs [`SyncStorage::owner`].
+    pub fn runtime_stats() -> RuntimeStats {
+        sync_store().runtime_stats()
+    }
+}
+
+impl<T: Send + Sync + 'static> Storage<T> for SyncStorage {
+    fn try_read(&'static self) -> Result<Self::Ref<T>, StorageBorrowError> {
+        let read = self.0.try_read().ok_or(StorageBorrowError::Borrowed)?;
+        RwLockReadGuard::try_map(read, |any| any.as_ref()?.downcast_ref())
+            .map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn try_write(&'static self) -> Result<Self::Mut<T>, StorageBorrowError> {
+        let write = self.0.try_write().ok_or(StorageBorrowError::Borrowed)?;
+        RwLockWriteGuard::try_map(write, |any| any.as_mut()?.downcast_mut())
+            .map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn set(&self, value: T) {
+        *self.0.write() = Some(Box::new(value));
+    }
+
+    fn take_if(&self, valid: impl FnOnce() -> bool) -> Option<T> {
+        let mut write = self.0.write();
+        if !write.as_ref()?.is::<T>() || !valid() {
+            return None;
+        }
+        write.take()?.downcast().ok().map(|value| *value)
+    }
+
+    fn set_if(&self, value: T, valid: impl FnOnce() -> bool) -> Result<(), T> {
+        let mut write = self.0.write();
+        if !valid() {
+            return Err(value);
+        }
+        *write = Some(Box::new(value));
+        Ok(())
+    }
+}
+
+impl AnyStorage for SyncStorage {
+    type Ref<T: ?Sized + 'static> = MappedRwLockReadGuard<'static, T>;
+    type Mut<T: ?Sized + 'static> = MappedRwLockWriteGuard<'static, T>;
+
+    fn map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<T>,
+        f: impl FnOnce(&T) -> &U,
+    ) -> Self::Ref<U> {
+        MappedRwLockReadGuard::map(reference, f)
+    }
+
+    fn try_map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        MappedRwLockReadGuard::try_map(reference, f).ok()
+    }
+
+    fn map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> Self::Mut<U> {
+        MappedRwLockWriteGuard::map(reference, f)
+    }
+
+    fn try_map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        MappedRwLockWriteGuard::try_map(reference, f).ok()
+    }
+
+    fn take(&self) -> bool {
+        let old = self.0.write().take();
+        old.is_some()
+    }
+}
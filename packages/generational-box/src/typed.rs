@@ -0,0 +1,211 @@
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::{
+    any::{Any, TypeId},
+    cell::{Ref, RefCell, RefMut},
+};
+
+use crate::lock::{const_mutex, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use crate::{AnyStorage, Owner, Storage, StorageBorrowError, Store, SyncStorage, UnsyncStorage};
+
+#[cfg(feature = "std")]
+thread_local! {
+    static TYPED_UNSYNC_STORES: RefCell<BTreeMap<TypeId, Box<dyn Any>>> = RefCell::new(BTreeMap::new());
+}
+
+static TYPED_SYNC_STORES: Mutex<BTreeMap<TypeId, Box<dyn Any + Send + Sync>>> =
+    const_mutex(BTreeMap::new());
+
+/// A storage for values of a single type that are only used on one thread.
+///
+/// Unlike [`UnsyncStorage`], the value is stored inline instead of in a `Box<dyn Any>`, so reading and writing is a plain `RefCell` borrow without an allocation or a downcast. A store of this storage acts as an arena for values of type `T`.
+///
+/// Like [`UnsyncStorage`], [`TypedUnsyncStorage::owner`] needs the `std` feature.
+pub struct TypedUnsyncStorage<T: 'static>(RefCell<Option<T>>);
+
+impl<T: 'static> Default for TypedUnsyncStorage<T> {
+    fn default() -> Self {
+        Self(RefCell::new(None))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: 'static> TypedUnsyncStorage<T> {
+    /// Create a new owner backed by the store for values of type `T` on the current thread. Every value inserted into the owner is dropped when the owner is dropped.
+    pub fn owner() -> Owner<Self> {
+        TYPED_UNSYNC_STORES.with(|stores| {
+            stores
+                .borrow_mut()
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| Box::new(Store::<Self>::new()))
+                .downcast_ref::<Store<Self>>()
+                .unwrap()
+                .owner()
+        })
+    }
+}
+
+impl<T: 'static> Storage<T> for TypedUnsyncStorage<T> {
+    fn try_read(&'static self) -> Result<Self::Ref<T>, StorageBorrowError> {
+        let borrow = self
+            .0
+            .try_borrow()
+            .map_err(|_| StorageBorrowError::Borrowed)?;
+        Ref::filter_map(borrow, Option::as_ref).map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn try_write(&'static self) -> Result<Self::Mut<T>, StorageBorrowError> {
+        let borrow = self
+            .0
+            .try_borrow_mut()
+            .map_err(|_| StorageBorrowError::Borrowed)?;
+        RefMut::filter_map(borrow, Option::as_mut).map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn set(&self, value: T) {
+        *self.0.borrow_mut() = Some(value);
+    }
+
+    fn take_if(&self, valid: impl FnOnce() -> bool) -> Option<T> {
+        let mut borrow = self.0.borrow_mut();
+        if borrow.is_none() || !valid() {
+            return None;
+        }
+        borrow.take()
+    }
+}
+
+impl<T: 'static> AnyStorage for TypedUnsyncStorage<T> {
+    type Ref<R: ?Sized + 'static> = <UnsyncStorage as AnyStorage>::Ref<R>;
+    type Mut<W: ?Sized + 'static> = <UnsyncStorage as AnyStorage>::Mut<W>;
+
+    fn map<R: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<R>,
+        f: impl FnOnce(&R) -> &U,
+    ) -> Self::Ref<U> {
+        UnsyncStorage::map(reference, f)
+    }
+
+    fn try_map<R: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<R>,
+        f: impl FnOnce(&R) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        UnsyncStorage::try_map(reference, f)
+    }
+
+    fn map_mut<W: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<W>,
+        f: impl FnOnce(&mut W) -> &mut U,
+    ) -> Self::Mut<U> {
+        UnsyncStorage::map_mut(reference, f)
+    }
+
+    fn try_map_mut<W: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<W>,
+        f: impl FnOnce(&mut W) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        UnsyncStorage::try_map_mut(reference, f)
+    }
+
+    fn take(&self) -> bool {
+        // Drop the old value after the borrow is released in case its destructor touches other boxes
+        let old = self.0.borrow_mut().take();
+        old.is_some()
+    }
+}
+
+/// A storage for values of a single type that can be shared between threads.
+///
+/// Unlike [`SyncStorage`], the value is stored inline instead of in a `Box<dyn Any>`, so reading and writing is a plain `RwLock` borrow without an allocation or a downcast. A store of this storage acts as an arena for values of type `T`.
+pub struct TypedSyncStorage<T: 'static>(RwLock<Option<T>>);
+
+impl<T: 'static> Default for TypedSyncStorage<T> {
+    fn default() -> Self {
+        Self(RwLock::new(None))
+    }
+}
+
+impl<T: Send + Sync + 'static> TypedSyncStorage<T> {
+    /// Create a new owner backed by the store for values of type `T` shared between every thread. Every value inserted into the owner is dropped when the owner is dropped.
+    pub fn owner() -> Owner<Self> {
+        TYPED_SYNC_STORES
+            .lock()
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Store::<Self>::new()))
+            .downcast_ref::<Store<Self>>()
+            .unwrap()
+            .owner()
+    }
+}
+
+impl<T: Send + Sync + 'static> Storage<T> for TypedSyncStorage<T> {
+    fn try_read(&'static self) -> Result<Self::Ref<T>, StorageBorrowError> {
+        let read = self.0.try_read().ok_or(StorageBorrowError::Borrowed)?;
+        RwLockReadGuard::try_map(read, Option::as_ref).map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn try_write(&'static self) -> Result<Self::Mut<T>, StorageBorrowError> {
+        let write = self.0.try_write().ok_or(StorageBorrowError::Borrowed)?;
+        RwLockWriteGuard::try_map(write, Option::as_mut).map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn set(&self, value: T) {
+        *self.0.write() = Some(value);
+    }
+
+    fn take_if(&self, valid: impl FnOnce() -> bool) -> Option<T> {
+        let mut write = self.0.write();
+        if write.is_none() || !valid() {
+            return None;
+        }
+        write.take()
+    }
+
+    fn set_if(&self, value: T, valid: impl FnOnce() -> bool) -> Result<(), T> {
+        let mut write = self.0.write();
+        if !valid() {
+            return Err(value);
+        }
+        *write = Some(value);
+        Ok(())
+    }
+}
+
+impl<T: 'static> AnyStorage for TypedSyncStorage<T> {
+    type Ref<R: ?Sized + 'static> = <SyncStorage as AnyStorage>::Ref<R>;
+    type Mut<W: ?Sized + 'static> = <SyncStorage as AnyStorage>::Mut<W>;
+
+    fn map<R: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<R>,
+        f: impl FnOnce(&R) -> &U,
+    ) -> Self::Ref<U> {
+        SyncStorage::map(reference, f)
+    }
+
+    fn try_map<R: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<R>,
+        f: impl FnOnce(&R) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        SyncStorage::try_map(reference, f)
+    }
+
+    fn map_mut<W: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<W>,
+        f: impl FnOnce(&mut W) -> &mut U,
+    ) -> Self::Mut<U> {
+        SyncStorage::map_mut(reference, f)
+    }
+
+    fn try_map_mut<W: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<W>,
+        f: impl FnOnce(&mut W) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        SyncStorage::try_map_mut(reference, f)
+    }
+
+    fn take(&self) -> bool {
+        let old = self.0.write().take();
+        old.is_some()
+    }
+}
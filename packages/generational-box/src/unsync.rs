@@ -0,0 +1,110 @@
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+use core::{
+    any::Any,
+    cell::{Ref, RefCell, RefMut},
+};
+
+use crate::{AnyStorage, Storage, StorageBorrowError};
+#[cfg(feature = "std")]
+use crate::{Owner, RuntimeStats, Store};
+
+#[cfg(feature = "std")]
+thread_local! {
+    static UNSYNC_STORE: Store<UnsyncStorage> = Store::new();
+}
+
+/// A storage for values that are only used on one thread. This is the default storage.
+///
+/// Without the `std` feature there are no thread locals, so [`UnsyncStorage::owner`] is not available. Create a `Store::<UnsyncStorage>::new()` and keep it around instead.
+#[derive(Default)]
+pub struct UnsyncStorage(RefCell<Option<Box<dyn Any>>>);
+
+#[cfg(feature = "std")]
+impl UnsyncStorage {
+    /// Create a new owner backed by the store of the current thread. Every value inserted into the owner is dropped when the owner is dropped.
+    pub fn owner() -> Owner<Self> {
+        UNSYNC_STORE.with(|store| store.owner())
+    }
+
+    /// Allocate `additional` memory locations up front in the store of the current thread. See [`Store::reserve`].
+    pub fn reserve(additional: usize) {
+        UNSYNC_STORE.with(|store| store.reserve(additional))
+    }
+
+    /// Get memory statistics for the store of the current thread that backs [`UnsyncStorage::owner`].
+    pub fn runtime_stats() -> RuntimeStats {
+        UNSYNC_STORE.with(|store| store.runtime_stats())
+    }
+}
+
+impl<T: 'static> Storage<T> for UnsyncStorage {
+    fn try_read(&'static self) -> Result<Self::Ref<T>, StorageBorrowError> {
+        let borrow = self
+            .0
+            .try_borrow()
+            .map_err(|_| StorageBorrowError::Borrowed)?;
+        Ref::filter_map(borrow, |any| any.as_ref()?.downcast_ref())
+            .map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn try_write(&'static self) -> Result<Self::Mut<T>, StorageBorrowError> {
+        let borrow = self
+            .0
+            .try_borrow_mut()
+            .map_err(|_| StorageBorrowError::Borrowed)?;
+        RefMut::filter_map(borrow, |any| any.as_mut()?.downcast_mut())
+            .map_err(|_| StorageBorrowError::Dropped)
+    }
+
+    fn set(&self, value: T) {
+        *self.0.borrow_mut() = Some(Box::new(value));
+    }
+
+    fn take_if(&self, valid: impl FnOnce() -> bool) -> Option<T> {
+        let mut borrow = self.0.borrow_mut();
+        if !borrow.as_ref()?.is::<T>() || !valid() {
+            return None;
+        }
+        borrow.take()?.downcast().ok().map(|value| *value)
+    }
+}
+
+impl AnyStorage for UnsyncStorage {
+    type Ref<T: ?Sized + 'static> = Ref<'static, T>;
+    type Mut<T: ?Sized + 'static> = RefMut<'static, T>;
+
+    fn map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<T>,
+        f: impl FnOnce(&T) -> &U,
+    ) -> Self::Ref<U> {
+        Ref::map(reference, f)
+    }
+
+    fn try_map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        Ref::filter_map(reference, f).ok()
+    }
+
+    fn map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> Self::Mut<U> {
+        RefMut::map(reference, f)
+    }
+
+    fn try_map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        reference: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        RefMut::filter_map(reference, f).ok()
+    }
+
+    fn take(&self) -> bool {
+        // Drop the old value after the borrow is released in case its destructor touches other boxes
+        let old = self.0.borrow_mut().take();
+        old.is_some()
+    }
+}
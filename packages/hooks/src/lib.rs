@@ -81,10 +81,20 @@ pub use use_coroutine::*;
 mod use_future;
 pub use use_future::*;
 
+mod use_retry_future;
+pub use use_retry_future::*;
+
+mod use_online;
+pub use use_online::*;
+
+mod use_offline_queue;
+pub use use_offline_queue::*;
+
 mod use_effect;
 pub use use_effect::*;
 
 mod use_callback;
+#[allow(deprecated)]
 pub use use_callback::*;
 
 mod use_memo;
@@ -94,3 +104,23 @@ mod use_on_create;
 pub use use_on_create::*;
 mod use_root_context;
 pub use use_root_context::*;
+
+mod timer;
+
+mod use_debounced_callback;
+pub use use_debounced_callback::*;
+
+mod use_throttled_callback;
+pub use use_throttled_callback::*;
+
+mod use_interval;
+pub use use_interval::*;
+
+mod use_timeout;
+pub use use_timeout::*;
+
+mod use_query;
+pub use use_query::*;
+
+mod use_transition;
+pub use use_transition::*;
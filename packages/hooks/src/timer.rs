@@ -0,0 +1,16 @@
+use dioxus_core::prelude::current_executor;
+use std::time::Duration;
+
+/// Wait for `duration` with the timer of the [`Executor`](dioxus_core::Executor) of the virtual dom, or the timer of the current platform if it has none. This is shared by the hooks that delay work.
+pub(crate) async fn sleep(duration: Duration) {
+    if let Some(executor) = current_executor() {
+        executor.sleep(duration).await;
+        return;
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+}
@@ -1,30 +1,39 @@
 use dioxus_core::ScopeState;
 use std::future::Future;
 
+#[deprecated(note = "Use `dioxus_core::prelude::use_async_callback` instead")]
 #[macro_export]
 macro_rules! use_callback {
-    // ($cx:ident, || || $($rest:tt)*) => { use_callback( $cx, (), |_| $($rest)* ) };
-    // ($cx:ident, || || $($rest:tt)*) => { use_callback( $cx, (), |_| $($rest)* ) };
-    ($cx:ident, || $($rest:tt)*) => {
-        use_callback(
+    ($cx:ident, || $($rest:tt)*) => {{
+        #[allow(deprecated)]
+        let callback = $crate::use_callback(
             $cx,
             move || $($rest)*
-        )
-    };
-    ($cx:ident, |$($args:tt),* | $($rest:tt)*) => {
-        use_callback(
+        );
+        callback
+    }};
+    ($cx:ident, |$($args:tt),* | $($rest:tt)*) => {{
+        #[allow(deprecated)]
+        let callback = $crate::use_callback(
             $cx,
             move || $($rest)*
-        )
-    };
-    ($cx:ident, $($rest:tt)*) => {
-        use_callback(
+        );
+        callback
+    }};
+    ($cx:ident, $($rest:tt)*) => {{
+        #[allow(deprecated)]
+        let callback = $crate::use_callback(
             $cx,
             move || $($rest)*
-        )
-    };
+        );
+        callback
+    }};
 }
 
+/// Spawn the future `make` returns for every call of the returned closure.
+#[deprecated(
+    note = "Use `dioxus_core::prelude::use_async_callback`, which returns a `Copy` callback that can also be awaited"
+)]
 pub fn use_callback<T, R, F>(cx: &ScopeState, make: impl FnOnce() -> R) -> impl FnMut(T) + '_
 where
     R: FnMut(T) -> F + 'static,
@@ -34,15 +43,3 @@ where
 
     move |evt| cx.spawn(hook(evt))
 }
-
-fn _it_works(cx: &ScopeState) {
-    let _p = use_callback(cx, || {
-        |()| async {
-            //
-        }
-    });
-
-    // let p = use_callback!(cx, || |evt| async {
-    //     //
-    // });
-}
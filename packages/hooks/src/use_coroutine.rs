@@ -1,5 +1,6 @@
 use dioxus_core::{ScopeState, TaskId};
 pub use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender};
+use futures_channel::oneshot;
 use std::future::Future;
 
 /// Maintain a handle over a future that can be paused, resumed, and canceled.
@@ -28,10 +29,10 @@ use std::future::Future;
 /// global data in a coroutine, and then access display-level values from the rest
 /// of our app through atoms.
 ///
-/// ## UseCallback instead
+/// ## UseAsyncCallback instead
 ///
 /// However, you must plan out your own concurrency and synchronization. If you
-/// don't care about actions in your app being synchronized, you can use [`use_callback`]
+/// don't care about actions in your app being synchronized, you can use the [`dioxus_core::prelude::use_async_callback`]
 /// hook to spawn multiple tasks and run them concurrently.
 ///
 /// ### Notice
@@ -72,7 +73,65 @@ where
     cx.use_hook(|| {
         let (tx, rx) = futures_channel::mpsc::unbounded();
         let task = cx.push_future(init(rx));
-        cx.provide_context(Coroutine { tx, task })
+        cx.provide_context(Coroutine {
+            tx: CoroutineSender::Messages(tx),
+            task,
+        })
+    })
+}
+
+/// Maintain a handle over a coroutine that replies to the messages it receives.
+///
+/// This works like [`use_coroutine`], but every message comes with a [`CoroutineResponder`].
+/// Components send a message with [`Coroutine::request`] and await the reply, which makes
+/// request/response patterns with a persistent worker task easy.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// enum Query {
+///     Count,
+///     Add(usize),
+/// }
+///
+/// let counter = use_coroutine_with_response(cx, |mut rx: UnboundedReceiver<(Query, CoroutineResponder<usize>)>| async move {
+///     let mut count = 0;
+///     while let Some((query, responder)) = rx.next().await {
+///         if let Query::Add(amount) = query {
+///             count += amount;
+///         }
+///         responder.respond(count);
+///     }
+/// });
+///
+/// cx.render(rsx!{
+///     button {
+///         onclick: move |_| {
+///             to_owned![counter];
+///             cx.spawn(async move {
+///                 if let Ok(count) = counter.request(Query::Add(1)).await {
+///                     println!("the count is now {count}");
+///                 }
+///             });
+///         },
+///         "Add one"
+///     }
+/// })
+/// ```
+pub fn use_coroutine_with_response<In, Out, G, F>(cx: &ScopeState, init: G) -> &Coroutine<In, Out>
+where
+    In: 'static,
+    Out: 'static,
+    G: FnOnce(UnboundedReceiver<(In, CoroutineResponder<Out>)>) -> F,
+    F: Future<Output = ()> + 'static,
+{
+    cx.use_hook(|| {
+        let (tx, rx) = futures_channel::mpsc::unbounded();
+        let task = cx.push_future(init(rx));
+        cx.provide_context(Coroutine {
+            tx: CoroutineSender::Requests(tx),
+            task,
+        })
     })
 }
 
@@ -85,40 +144,108 @@ pub fn use_coroutine_handle<M: 'static>(cx: &ScopeState) -> Option<&Coroutine<M>
         .as_ref()
 }
 
-pub struct Coroutine<T> {
-    tx: UnboundedSender<T>,
+/// Get a handle to a coroutine created with [`use_coroutine_with_response`] higher in the tree
+#[must_use]
+pub fn use_coroutine_with_response_handle<In: 'static, Out: 'static>(
+    cx: &ScopeState,
+) -> Option<&Coroutine<In, Out>> {
+    cx.use_hook(|| cx.consume_context::<Coroutine<In, Out>>())
+        .as_ref()
+}
+
+/// A handle to a coroutine, created with [`use_coroutine`] or [`use_coroutine_with_response`].
+///
+/// `Out` is the type of the replies from the coroutine. Coroutines created with [`use_coroutine`] don't reply.
+pub struct Coroutine<In, Out = ()> {
+    tx: CoroutineSender<In, Out>,
     task: TaskId,
 }
 
+enum CoroutineSender<In, Out> {
+    Messages(UnboundedSender<In>),
+    Requests(UnboundedSender<(In, CoroutineResponder<Out>)>),
+}
+
 // for use in futures
-impl<T> Clone for Coroutine<T> {
+impl<In, Out> Clone for Coroutine<In, Out> {
     fn clone(&self) -> Self {
+        let tx = match &self.tx {
+            CoroutineSender::Messages(tx) => CoroutineSender::Messages(tx.clone()),
+            CoroutineSender::Requests(tx) => CoroutineSender::Requests(tx.clone()),
+        };
         Self {
-            tx: self.tx.clone(),
+            tx,
             task: self.task,
         }
     }
 }
 
-impl<T> Coroutine<T> {
+impl<In, Out> Coroutine<In, Out> {
     /// Get the ID of this coroutine
     #[must_use]
     pub fn task_id(&self) -> TaskId {
         self.task
     }
 
-    /// Send a message to the coroutine
-    pub fn send(&self, msg: T) {
-        let _ = self.tx.unbounded_send(msg);
+    /// Send a message to the coroutine without waiting for a reply
+    pub fn send(&self, msg: In) {
+        match &self.tx {
+            CoroutineSender::Messages(tx) => {
+                let _ = tx.unbounded_send(msg);
+            }
+            CoroutineSender::Requests(tx) => {
+                let _ = tx.unbounded_send((msg, CoroutineResponder { tx: None }));
+            }
+        }
+    }
+
+    /// Send a message to the coroutine and wait for its reply
+    ///
+    /// The reply is an error if the coroutine stopped, or dropped the [`CoroutineResponder`] without responding.
+    /// Coroutines created with [`use_coroutine`] never reply.
+    pub fn request(&self, msg: In) -> oneshot::Receiver<Out> {
+        let (reply, response) = oneshot::channel();
+        match &self.tx {
+            CoroutineSender::Messages(tx) => {
+                let _ = tx.unbounded_send(msg);
+            }
+            CoroutineSender::Requests(tx) => {
+                let responder = CoroutineResponder { tx: Some(reply) };
+                let _ = tx.unbounded_send((msg, responder));
+            }
+        }
+        response
     }
 }
 
-impl<T> PartialEq for Coroutine<T> {
+impl<In, Out> PartialEq for Coroutine<In, Out> {
     fn eq(&self, other: &Self) -> bool {
         self.task == other.task
     }
 }
 
+/// Replies to a message sent to a coroutine created with [`use_coroutine_with_response`].
+pub struct CoroutineResponder<Out> {
+    tx: Option<oneshot::Sender<Out>>,
+}
+
+impl<Out> CoroutineResponder<Out> {
+    /// Reply to the message. The reply is ignored if the message was sent with [`Coroutine::send`] or the sender stopped waiting.
+    pub fn respond(self, response: Out) {
+        if let Some(tx) = self.tx {
+            let _ = tx.send(response);
+        }
+    }
+
+    /// Returns true if nobody is waiting for the reply, so the work to compute it can be skipped.
+    pub fn is_canceled(&self) -> bool {
+        match &self.tx {
+            Some(tx) => tx.is_canceled(),
+            None => true,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(unused)]
@@ -139,6 +266,15 @@ mod tests {
 
         let task3 = use_coroutine(cx, |rx| complex_task(rx, 10));
 
+        let task4 = use_coroutine_with_response(
+            cx,
+            |mut rx: UnboundedReceiver<(i32, CoroutineResponder<i32>)>| async move {
+                while let Some((msg, responder)) = rx.next().await {
+                    responder.respond(msg * 2);
+                }
+            },
+        );
+
         todo!()
     }
 
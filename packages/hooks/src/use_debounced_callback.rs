@@ -0,0 +1,180 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use dioxus_core::{
+    prelude::{remove_future, spawn_at, use_callback, Callback},
+    ScopeId, ScopeState, TaskId,
+};
+
+/// Controls when a [`DebouncedCallback`] calls the wrapped function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebounceOptions {
+    /// Call the function immediately when the first call in a burst happens.
+    pub leading: bool,
+    /// Call the function with the latest arguments once calls stop for the delay.
+    pub trailing: bool,
+}
+
+impl Default for DebounceOptions {
+    fn default() -> Self {
+        Self {
+            leading: false,
+            trailing: true,
+        }
+    }
+}
+
+/// Wrap a function so it is only called once calls to it stop for `delay`. This is useful for event handlers that fire quickly, like `oninput` in a search box.
+///
+/// The function always sees the latest props and state. Pending calls are cancelled when the component is dropped.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn app(cx: Scope) -> Element {
+///     let results = use_state(cx, Vec::<String>::new);
+///     let set_results = results.setter();
+///     let search = use_debounced_callback(cx, Duration::from_millis(300), move |query: String| {
+///         set_results(vec![format!("results for {query}")]);
+///     });
+///
+///     render! {
+///         input { oninput: move |evt| search.call(evt.value.clone()) }
+///         for result in results.iter() {
+///             p { "{result}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_debounced_callback<Args: 'static>(
+    cx: &ScopeState,
+    delay: Duration,
+    f: impl FnMut(Args) + 'static,
+) -> &DebouncedCallback<Args> {
+    use_debounced_callback_with_options(cx, delay, DebounceOptions::default(), f)
+}
+
+/// Wrap a function so it is only called once calls to it stop for `delay`, with control over whether the first and last call in a burst go through. See [`use_debounced_callback`] for more information.
+pub fn use_debounced_callback_with_options<Args: 'static>(
+    cx: &ScopeState,
+    delay: Duration,
+    options: DebounceOptions,
+    f: impl FnMut(Args) + 'static,
+) -> &DebouncedCallback<Args> {
+    let callback = use_callback(cx, f);
+    let debounced = cx.use_hook(|| DebouncedCallback {
+        callback,
+        scope: cx.scope_id(),
+        state: Default::default(),
+    });
+    let mut state = debounced.state.borrow_mut();
+    state.delay = delay;
+    state.options = options;
+    drop(state);
+    debounced
+}
+
+/// A function that is only called once calls to it stop for a delay. Created with [`use_debounced_callback`].
+pub struct DebouncedCallback<Args: 'static = ()> {
+    callback: Callback<Args>,
+    scope: ScopeId,
+    state: Rc<RefCell<DebounceState<Args>>>,
+}
+
+struct DebounceState<Args> {
+    delay: Duration,
+    options: DebounceOptions,
+    task: Option<TaskId>,
+    pending: Option<Args>,
+}
+
+impl<Args> Default for DebounceState<Args> {
+    fn default() -> Self {
+        Self {
+            delay: Duration::ZERO,
+            options: DebounceOptions::default(),
+            task: None,
+            pending: None,
+        }
+    }
+}
+
+impl<Args: 'static> DebouncedCallback<Args> {
+    /// Call the function once calls stop for the delay. Every call restarts the delay.
+    pub fn call(&self, args: Args) {
+        let mut state = self.state.borrow_mut();
+        let previous = state.task.take();
+        let fire_now = previous.is_none() && state.options.leading;
+        if let Some(task) = previous {
+            remove_future(task);
+        }
+        let args = if fire_now {
+            Some(args)
+        } else {
+            if state.options.trailing {
+                state.pending = Some(args);
+            }
+            None
+        };
+        let delay = state.delay;
+        drop(state);
+
+        let state = self.state.clone();
+        let callback = self.callback;
+        let task = spawn_at(
+            async move {
+                crate::timer::sleep(delay).await;
+                let pending = {
+                    let mut state = state.borrow_mut();
+                    state.task = None;
+                    state.pending.take()
+                };
+                if let Some(args) = pending {
+                    callback.call(args);
+                }
+            },
+            self.scope,
+        );
+        self.state.borrow_mut().task = task;
+
+        if let Some(args) = args {
+            self.callback.call(args);
+        }
+    }
+
+    /// Call the function now with the arguments of the pending call, if there is one.
+    pub fn flush(&self) {
+        let pending = self.cancel_pending();
+        if let Some(args) = pending {
+            self.callback.call(args);
+        }
+    }
+
+    /// Drop the pending call, if there is one.
+    pub fn cancel(&self) {
+        self.cancel_pending();
+    }
+
+    /// Returns true if a call is waiting for the delay to pass.
+    pub fn is_pending(&self) -> bool {
+        self.state.borrow().pending.is_some()
+    }
+
+    fn cancel_pending(&self) -> Option<Args> {
+        let mut state = self.state.borrow_mut();
+        if let Some(task) = state.task.take() {
+            remove_future(task);
+        }
+        state.pending.take()
+    }
+}
+
+impl<Args: 'static> Clone for DebouncedCallback<Args> {
+    fn clone(&self) -> Self {
+        Self {
+            callback: self.callback,
+            scope: self.scope,
+            state: self.state.clone(),
+        }
+    }
+}
@@ -1,6 +1,15 @@
 #![allow(missing_docs)]
 use dioxus_core::{ScopeState, TaskId};
-use std::{any::Any, cell::Cell, future::Future, rc::Rc, sync::Arc};
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
 
 use crate::{use_state, UseState};
 
@@ -16,6 +25,10 @@ use crate::{use_state, UseState};
 /// If a future is pending when the dependencies change, the previous future
 /// will be canceled before the new one is started.
 ///
+/// The returned handle can [pause](UseFuture::pause), [resume](UseFuture::resume),
+/// [cancel](UseFuture::cancel) and [restart](UseFuture::restart) the future
+/// from event handlers, so long running loops don't need to poll a flag to stop.
+///
 /// - dependencies: a tuple of references to values that are PartialEq + Clone
 pub fn use_future<T, F, D>(
     cx: &ScopeState,
@@ -34,6 +47,10 @@ where
         needs_regen: Rc::new(Cell::new(true)),
         state: val.clone(),
         task: Default::default(),
+        control: Rc::new(FutureControl {
+            status: Signal::new(FutureStatus::Running),
+            waker: RefCell::new(None),
+        }),
     });
 
     let state_dependencies = cx.use_hook(Vec::new);
@@ -48,9 +65,19 @@ where
         let fut = future(dependencies.out());
         let val = val.clone();
         let task = state.task.clone();
+        let control = state.control.clone();
+        if *control.status.peek() != FutureStatus::Running {
+            control.status.set(FutureStatus::Running);
+        }
 
         state.task.set(Some(cx.push_future(async move {
-            val.set(Some(fut.await));
+            let controlled = Controlled {
+                future: Box::pin(fut),
+                control: control.clone(),
+            };
+            let value = controlled.await;
+            control.status.set(FutureStatus::Finished);
+            val.set(Some(value));
             task.take();
         })));
 
@@ -70,12 +97,59 @@ pub enum FutureState<'a, T> {
     Regenerating(&'a T), // the old value
 }
 
+/// Whether the future of a [`UseFuture`] is running. See [`UseFuture::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FutureStatus {
+    /// The future is running.
+    Running,
+    /// The future was paused with [`UseFuture::pause`], and continues after [`UseFuture::resume`].
+    Paused,
+    /// The future was cancelled with [`UseFuture::cancel`] before it finished.
+    Cancelled,
+    /// The future finished.
+    Finished,
+}
+
 #[derive(Clone)]
 pub struct UseFuture<T: 'static> {
     update: Arc<dyn Fn()>,
     needs_regen: Rc<Cell<bool>>,
     task: Rc<Cell<Option<TaskId>>>,
     state: UseState<Option<T>>,
+    control: Rc<FutureControl>,
+}
+
+/// Shared between a [`UseFuture`] and the future it runs, so the handle can stop polling the future.
+struct FutureControl {
+    status: Signal<FutureStatus>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl FutureControl {
+    fn wake(&self) {
+        let waker = self.waker.borrow_mut().take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Polls the future only while it is not paused.
+struct Controlled<F> {
+    future: Pin<Box<F>>,
+    control: Rc<FutureControl>,
+}
+
+impl<F: Future> Future for Controlled<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if *self.control.status.peek() == FutureStatus::Paused {
+            *self.control.waker.borrow_mut() = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        self.future.as_mut().poll(cx)
+    }
 }
 
 pub enum UseFutureState<'a, T> {
@@ -95,12 +169,37 @@ impl<T> UseFuture<T> {
     }
 
     /// Forcefully cancel a future
+    ///
+    /// The future is dropped right away. Call [`Self::restart`] to start it over.
     pub fn cancel(&self, cx: &ScopeState) {
         if let Some(task) = self.task.take() {
             cx.remove_future(task);
+            self.control.status.set(FutureStatus::Cancelled);
+        }
+    }
+
+    /// Stop polling the future until [`Self::resume`] is called, without dropping it
+    pub fn pause(&self) {
+        if *self.control.status.peek() == FutureStatus::Running && self.task.get().is_some() {
+            self.control.status.set(FutureStatus::Paused);
+        }
+    }
+
+    /// Continue polling a future that was paused with [`Self::pause`]
+    pub fn resume(&self) {
+        if *self.control.status.peek() == FutureStatus::Paused {
+            self.control.status.set(FutureStatus::Running);
+            self.control.wake();
         }
     }
 
+    /// Get whether the future is running, paused, cancelled or finished.
+    ///
+    /// Components that read the signal are rendered again whenever this changes.
+    pub fn status(&self) -> ReadOnlySignal<FutureStatus> {
+        ReadOnlySignal::new(self.control.status)
+    }
+
     // Manually set the value in the future slot without starting the future over
     pub fn set(&self, new_value: T) {
         self.state.set(Some(new_value));
@@ -284,4 +383,78 @@ mod tests {
             todo!()
         }
     }
+
+    #[tokio::test]
+    async fn pause_resume_cancel_and_restart() {
+        use dioxus_core::prelude::*;
+        use futures_channel::mpsc::{unbounded, UnboundedReceiver};
+        use futures_util::StreamExt;
+        use std::time::Duration;
+
+        #[derive(Clone, Default)]
+        struct Harness {
+            receiver: Rc<RefCell<Option<UnboundedReceiver<()>>>>,
+            received: Rc<Cell<usize>>,
+            handle: Rc<RefCell<Option<UseFuture<()>>>>,
+        }
+
+        fn app(cx: Scope<Harness>) -> Element {
+            let harness = cx.props.clone();
+            let future = use_future(cx, (), move |_| async move {
+                let mut receiver = harness.receiver.borrow_mut().take().unwrap();
+                while receiver.next().await.is_some() {
+                    harness.received.set(harness.received.get() + 1);
+                }
+            });
+            *cx.props.handle.borrow_mut() = Some(future.clone());
+            None
+        }
+
+        async fn run(dom: &mut VirtualDom) {
+            _ = tokio::time::timeout(Duration::from_millis(10), dom.wait_for_work()).await;
+            _ = dom.render_immediate();
+        }
+
+        let harness = Harness::default();
+        let (sender, receiver) = unbounded();
+        *harness.receiver.borrow_mut() = Some(receiver);
+        let mut dom = VirtualDom::new_with_props(app, harness.clone());
+        _ = dom.rebuild();
+        let future = harness.handle.borrow().clone().unwrap();
+        let status = || *future.status().peek();
+
+        sender.unbounded_send(()).unwrap();
+        run(&mut dom).await;
+        assert_eq!(harness.received.get(), 1);
+        assert_eq!(status(), FutureStatus::Running);
+
+        // A paused future keeps its state, but isn't polled
+        future.pause();
+        assert_eq!(status(), FutureStatus::Paused);
+        sender.unbounded_send(()).unwrap();
+        run(&mut dom).await;
+        assert_eq!(harness.received.get(), 1);
+
+        future.resume();
+        assert_eq!(status(), FutureStatus::Running);
+        run(&mut dom).await;
+        assert_eq!(harness.received.get(), 2);
+
+        // Cancelling drops the future
+        future.cancel(dom.base_scope());
+        assert_eq!(status(), FutureStatus::Cancelled);
+        assert!(future.task().is_none());
+        run(&mut dom).await;
+        assert!(sender.is_closed());
+
+        // Restarting creates the future again
+        let (sender, receiver) = unbounded();
+        *harness.receiver.borrow_mut() = Some(receiver);
+        future.restart();
+        run(&mut dom).await;
+        assert_eq!(status(), FutureStatus::Running);
+        sender.unbounded_send(()).unwrap();
+        run(&mut dom).await;
+        assert_eq!(harness.received.get(), 3);
+    }
 }
@@ -0,0 +1,129 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use dioxus_core::{
+    prelude::{remove_future, spawn_at, use_callback, Callback},
+    ScopeId, ScopeState, TaskId,
+};
+
+/// Call a function every `period` while the component is mounted. This is useful for polling a server or updating a clock.
+///
+/// The function always sees the latest props and state. The interval is stopped when the component is dropped, and restarted when the `period` passed to the hook changes.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn app(cx: Scope) -> Element {
+///     let seconds = use_state(cx, || 0);
+///     let tick = seconds.setter();
+///     let count = *seconds.get();
+///     let timer = use_interval(cx, Duration::from_secs(1), move || tick(count + 1));
+///
+///     render! {
+///         p { "{seconds} seconds" }
+///         button { onclick: move |_| timer.pause(), "Pause" }
+///         button { onclick: move |_| timer.resume(), "Resume" }
+///     }
+/// }
+/// ```
+pub fn use_interval(
+    cx: &ScopeState,
+    period: Duration,
+    mut f: impl FnMut() + 'static,
+) -> &IntervalHandle {
+    let callback = use_callback(cx, move |()| f());
+    let handle = cx.use_hook(|| {
+        let handle = IntervalHandle {
+            callback,
+            scope: cx.scope_id(),
+            state: Rc::new(RefCell::new(IntervalState {
+                requested_period: period,
+                period,
+                task: None,
+            })),
+        };
+        handle.resume();
+        handle
+    });
+
+    let requested_period = handle.state.borrow().requested_period;
+    if requested_period != period {
+        handle.state.borrow_mut().requested_period = period;
+        handle.set_period(period);
+    }
+
+    handle
+}
+
+/// A handle to control an interval created with [`use_interval`].
+pub struct IntervalHandle {
+    callback: Callback<()>,
+    scope: ScopeId,
+    state: Rc<RefCell<IntervalState>>,
+}
+
+struct IntervalState {
+    /// The period last passed to the hook, to tell when it changes.
+    requested_period: Duration,
+    period: Duration,
+    task: Option<TaskId>,
+}
+
+impl IntervalHandle {
+    /// Stop calling the function until [`IntervalHandle::resume`] is called.
+    pub fn pause(&self) {
+        if let Some(task) = self.state.borrow_mut().task.take() {
+            remove_future(task);
+        }
+    }
+
+    /// Start calling the function again after the interval was paused. The first call happens one period after resuming.
+    pub fn resume(&self) {
+        if self.state.borrow().task.is_some() {
+            return;
+        }
+
+        let state = self.state.clone();
+        let callback = self.callback;
+        let task = spawn_at(
+            async move {
+                loop {
+                    let period = state.borrow().period;
+                    crate::timer::sleep(period).await;
+                    callback.call(());
+                }
+            },
+            self.scope,
+        );
+        self.state.borrow_mut().task = task;
+    }
+
+    /// Change how often the function is called. If the interval is running, it restarts with the new period.
+    pub fn set_period(&self, period: Duration) {
+        self.state.borrow_mut().period = period;
+        if !self.is_paused() {
+            self.pause();
+            self.resume();
+        }
+    }
+
+    /// The time between calls to the function.
+    pub fn period(&self) -> Duration {
+        self.state.borrow().period
+    }
+
+    /// Returns true if the interval is paused.
+    pub fn is_paused(&self) -> bool {
+        self.state.borrow().task.is_none()
+    }
+}
+
+impl Clone for IntervalHandle {
+    fn clone(&self) -> Self {
+        Self {
+            callback: self.callback,
+            scope: self.scope,
+            state: self.state.clone(),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+};
+
+use dioxus_core::{prelude::spawn_at, ScopeId, ScopeState};
+
+use crate::use_online;
+
+/// Where an [`OfflineQueue`] keeps mutations that have not been sent yet, so they survive a reload.
+///
+/// The queue calls [`MutationStorage::save`] with every pending mutation, in order, whenever the queue changes. `()` is an in-memory storage that forgets the queue when the app closes.
+pub trait MutationStorage<M> {
+    /// Load the mutations that were still pending when the queue was last saved.
+    fn load(&self) -> Vec<M>;
+
+    /// Replace the saved queue with `pending`.
+    fn save(&self, pending: &[M]);
+}
+
+impl<M> MutationStorage<M> for () {
+    fn load(&self) -> Vec<M> {
+        Vec::new()
+    }
+
+    fn save(&self, _: &[M]) {}
+}
+
+/// The reason a mutation could not be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SendError<E> {
+    /// The network could not be reached. The mutation stays at the front of the queue and is sent again on reconnect.
+    Offline,
+    /// The server rejected the mutation because it conflicts with the current state. The conflict callback decides what happens next.
+    Conflict(E),
+}
+
+/// What to do with a mutation that the server rejected with a [`SendError::Conflict`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConflictResolution<M> {
+    /// Drop the mutation and continue with the rest of the queue. This is the default.
+    Discard,
+    /// Send this mutation instead.
+    Replace(M),
+    /// Keep the mutation at the front of the queue and stop replaying until the next reconnect or [`OfflineQueue::flush`].
+    Stop,
+}
+
+type SendFn<M, E> = Box<dyn Fn(M) -> Pin<Box<dyn Future<Output = Result<(), SendError<E>>>>>>;
+type ConflictFn<M, E> = Box<dyn Fn(&M, &E) -> ConflictResolution<M>>;
+
+/// Queue mutations while the app is offline and replay them in order once it reconnects.
+///
+/// Mutations are always sent through the queue, one at a time, so they reach the server in the order they were made. While [`use_online`] reports the app as offline, they are kept in `storage`. When the app comes back online, the queue is replayed from the front. A mutation that fails with [`SendError::Offline`] stops the replay until the next reconnect, and one that fails with [`SendError::Conflict`] is passed to the [`OfflineQueue::on_conflict`] callback.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// #[derive(Clone)]
+/// struct Rename(String);
+///
+/// async fn save_name(mutation: Rename) -> Result<(), SendError<String>> {
+///     Ok(())
+/// }
+///
+/// fn app(cx: Scope) -> Element {
+///     let queue = use_offline_queue(cx, (), save_name);
+///
+///     render! {
+///         button { onclick: move |_| queue.mutate(Rename("Dioxus".into())), "Rename" }
+///         "{queue.len()} changes waiting to be saved"
+///     }
+/// }
+/// ```
+pub fn use_offline_queue<M, E, F>(
+    cx: &ScopeState,
+    storage: impl MutationStorage<M> + 'static,
+    send: impl Fn(M) -> F + 'static,
+) -> &OfflineQueue<M, E>
+where
+    M: Clone + 'static,
+    E: 'static,
+    F: Future<Output = Result<(), SendError<E>>> + 'static,
+{
+    let online = use_online(cx);
+
+    let queue = cx.use_hook(|| {
+        let pending = storage.load().into();
+        OfflineQueue {
+            scope: cx.scope_id(),
+            update: cx.schedule_update(),
+            state: Rc::new(RefCell::new(QueueState {
+                pending,
+                replaying: false,
+            })),
+            storage: Rc::new(storage),
+            send: Rc::new(RefCell::new(Box::new(|_| {
+                Box::pin(async { Err(SendError::Offline) })
+            }))),
+            on_conflict: Rc::new(RefCell::new(Box::new(|_, _| ConflictResolution::Discard))),
+            online: Rc::new(Cell::new(None)),
+        }
+    });
+
+    // Always send with the latest closure so it can capture new values
+    *queue.send.borrow_mut() = Box::new(move |mutation| Box::pin(send(mutation)));
+
+    let was_online = queue.online.replace(Some(online));
+    if online && was_online != Some(true) {
+        queue.flush();
+    }
+
+    queue
+}
+
+struct QueueState<M> {
+    pending: VecDeque<M>,
+    replaying: bool,
+}
+
+/// A queue of mutations that are replayed in order when the app is online. Created with [`use_offline_queue`].
+pub struct OfflineQueue<M: 'static, E: 'static> {
+    scope: ScopeId,
+    update: Arc<dyn Fn()>,
+    state: Rc<RefCell<QueueState<M>>>,
+    storage: Rc<dyn MutationStorage<M>>,
+    send: Rc<RefCell<SendFn<M, E>>>,
+    on_conflict: Rc<RefCell<ConflictFn<M, E>>>,
+    online: Rc<Cell<Option<bool>>>,
+}
+
+impl<M, E> Clone for OfflineQueue<M, E> {
+    fn clone(&self) -> Self {
+        Self {
+            scope: self.scope,
+            update: self.update.clone(),
+            state: self.state.clone(),
+            storage: self.storage.clone(),
+            send: self.send.clone(),
+            on_conflict: self.on_conflict.clone(),
+            online: self.online.clone(),
+        }
+    }
+}
+
+impl<M: Clone, E> OfflineQueue<M, E> {
+    /// Add a mutation to the end of the queue. It is sent right away if the app is online and nothing is queued in front of it.
+    pub fn mutate(&self, mutation: M) {
+        self.state.borrow_mut().pending.push_back(mutation);
+        self.save();
+        (self.update)();
+        if self.online.get() == Some(true) {
+            self.flush();
+        }
+    }
+
+    /// Decide what happens to mutations that the server rejects with a [`SendError::Conflict`]. By default they are discarded.
+    pub fn on_conflict(&self, on_conflict: impl Fn(&M, &E) -> ConflictResolution<M> + 'static) {
+        *self.on_conflict.borrow_mut() = Box::new(on_conflict);
+    }
+
+    /// Start replaying the queue from the front, unless a replay is already running.
+    ///
+    /// This is called automatically when the app comes back online, but can be used to retry after a [`ConflictResolution::Stop`].
+    pub fn flush(&self) {
+        {
+            let mut state = self.state.borrow_mut();
+            if state.replaying || state.pending.is_empty() {
+                return;
+            }
+            state.replaying = true;
+        }
+
+        let queue = self.clone();
+        spawn_at(
+            async move {
+                queue.replay().await;
+                queue.state.borrow_mut().replaying = false;
+                (queue.update)();
+            },
+            self.scope,
+        );
+    }
+
+    async fn replay(&self) {
+        loop {
+            let front = self.state.borrow().pending.front().cloned();
+            let mutation = match front {
+                Some(mutation) => mutation,
+                None => return,
+            };
+
+            let sent = (self.send.borrow())(mutation.clone());
+            match sent.await {
+                Ok(()) => {
+                    self.state.borrow_mut().pending.pop_front();
+                }
+                Err(SendError::Offline) => return,
+                Err(SendError::Conflict(error)) => {
+                    let resolution = (self.on_conflict.borrow())(&mutation, &error);
+                    match resolution {
+                        ConflictResolution::Discard => {
+                            self.state.borrow_mut().pending.pop_front();
+                        }
+                        ConflictResolution::Replace(replacement) => {
+                            if let Some(front) = self.state.borrow_mut().pending.front_mut() {
+                                *front = replacement;
+                            }
+                        }
+                        ConflictResolution::Stop => return,
+                    }
+                }
+            }
+
+            self.save();
+            (self.update)();
+        }
+    }
+
+    fn save(&self) {
+        let mut state = self.state.borrow_mut();
+        self.storage.save(state.pending.make_contiguous());
+    }
+
+    /// Get a copy of the mutations that have not been sent yet, in the order they will be sent.
+    pub fn pending(&self) -> Vec<M> {
+        self.state.borrow().pending.iter().cloned().collect()
+    }
+
+    /// Get the number of mutations that have not been sent yet.
+    pub fn len(&self) -> usize {
+        self.state.borrow().pending.len()
+    }
+
+    /// Returns true if every mutation has been sent.
+    pub fn is_empty(&self) -> bool {
+        self.state.borrow().pending.is_empty()
+    }
+
+    /// Returns true while the queue is being replayed.
+    pub fn is_replaying(&self) -> bool {
+        self.state.borrow().replaying
+    }
+}
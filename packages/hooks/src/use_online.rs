@@ -0,0 +1,39 @@
+use dioxus_core::ScopeState;
+
+use crate::use_shared_state;
+
+/// Whether the app can currently reach the network.
+///
+/// Provide this as shared state with [`use_shared_state_provider`](crate::use_shared_state_provider) near the root of your app and write to it when the connection changes. Every component that calls [`use_online`] rerenders when it changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnlineStatus(pub bool);
+
+impl Default for OnlineStatus {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Returns true if the app is online, as reported by the closest [`OnlineStatus`] shared state.
+///
+/// If no [`OnlineStatus`] was provided, the app is assumed to be online.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn app(cx: Scope) -> Element {
+///     use_shared_state_provider(cx, OnlineStatus::default);
+///     render! { Banner {} }
+/// }
+///
+/// fn Banner(cx: Scope) -> Element {
+///     if use_online(cx) {
+///         None
+///     } else {
+///         render! { "You are offline" }
+///     }
+/// }
+/// ```
+pub fn use_online(cx: &ScopeState) -> bool {
+    use_shared_state::<OnlineStatus>(cx).map_or(true, |status| status.read().0)
+}
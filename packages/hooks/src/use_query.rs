@@ -0,0 +1,825 @@
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    hash::Hash,
+    pin::Pin,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use dioxus_core::{
+    prelude::{remove_future, spawn_at},
+    ScopeId, ScopeState, TaskId,
+};
+
+use crate::{timer::sleep, use_on_destroy, use_root_context, RetryPolicy};
+
+/// Where [`use_persisted_query`] keeps the last value fetched for a key, so it can be shown right away the next time the app starts.
+pub trait QueryStorage<K, T> {
+    /// Load the value that was saved for `key`, if there is one.
+    fn load(&self, key: &K) -> Option<T>;
+
+    /// Save the value that was just fetched for `key`.
+    fn save(&self, key: &K, value: &T);
+}
+
+type Fetcher<K, T, E> = Rc<dyn Fn(K) -> Pin<Box<dyn Future<Output = Result<T, E>>>>>;
+
+/// The cache shared by every [`use_query`] in the app.
+///
+/// A client with the default options is created the first time a query is used. Provide your own client with [`use_context_provider`](crate::use_context_provider) in the root component to change the options.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn app(cx: Scope) -> Element {
+///     use_context_provider(cx, || QueryClient::new().stale_time(Duration::from_secs(30)));
+///     render! { "..." }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct QueryClient {
+    inner: Rc<ClientInner>,
+    stale_time: Duration,
+    cache_time: Duration,
+    pub(crate) retry_policy: RetryPolicy,
+}
+
+#[derive(Default)]
+struct ClientInner {
+    caches: RefCell<HashMap<TypeId, Rc<dyn ErasedCache>>>,
+}
+
+impl Default for QueryClient {
+    fn default() -> Self {
+        Self {
+            inner: Default::default(),
+            stale_time: Duration::ZERO,
+            cache_time: Duration::from_secs(5 * 60),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
+
+impl QueryClient {
+    /// Create a client where cached values become stale as soon as they are fetched, so they are shown right away but fetched again every time a component starts using them. Values no component uses are kept for five minutes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long fetched values stay fresh. Fresh values are not fetched again when a component starts using them.
+    ///
+    /// Like the other options, this only changes the client it is called on and the clients cloned from it later.
+    pub fn stale_time(mut self, stale_time: Duration) -> Self {
+        self.stale_time = stale_time;
+        self
+    }
+
+    /// Set how long values stay cached once no component uses them. They are fetched again if a component starts using them after that.
+    pub fn cache_time(mut self, cache_time: Duration) -> Self {
+        self.cache_time = cache_time;
+        self
+    }
+
+    /// Set how failed fetches are retried. This is also the default policy of [`use_retry_future`](crate::use_retry_future) when there is no [`RetryPolicy`] context. Defaults to [`RetryPolicy::default`].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Mark every cached value with this key as stale. Values that are currently used by a component are fetched again.
+    ///
+    /// The key is compared with the keys of every query that uses the same key type.
+    pub fn invalidate<K: 'static>(&self, key: &K) {
+        for cache in self.caches() {
+            cache.invalidate(Some(key as &dyn Any), self);
+        }
+    }
+
+    /// Mark every cached value as stale. Values that are currently used by a component are fetched again.
+    pub fn invalidate_all(&self) {
+        for cache in self.caches() {
+            cache.invalidate(None, self);
+        }
+    }
+
+    fn caches(&self) -> Vec<Rc<dyn ErasedCache>> {
+        self.inner.caches.borrow().values().cloned().collect()
+    }
+
+    fn cache<K, T, E>(&self) -> Rc<dyn ErasedCache>
+    where
+        K: Hash + Eq + Clone + 'static,
+        T: 'static,
+        E: 'static,
+    {
+        self.inner
+            .caches
+            .borrow_mut()
+            .entry(TypeId::of::<QueryCache<K, T, E>>())
+            .or_insert_with(|| Rc::new(QueryCache::<K, T, E>::default()))
+            .clone()
+    }
+
+    fn entry<K, T, E>(&self, key: &K) -> SharedEntry<K, T, E>
+    where
+        K: Hash + Eq + Clone + 'static,
+        T: 'static,
+        E: 'static,
+    {
+        let cache = self.cache::<K, T, E>();
+        let cache = downcast_cache::<K, T, E>(&*cache);
+        let mut entries = cache.entries.borrow_mut();
+        entries
+            .entry(key.clone())
+            .or_insert_with(|| Rc::new(RefCell::new(QueryEntry::default())))
+            .clone()
+    }
+
+    /// Remove the entry of `key` from the cache once it has been unused for the cache time.
+    fn collect_later<K, T, E>(&self, key: K, entry: SharedEntry<K, T, E>)
+    where
+        K: Hash + Eq + Clone + 'static,
+        T: 'static,
+        E: 'static,
+    {
+        let cache = self.cache::<K, T, E>();
+        let cache_time = self.cache_time;
+        let collect = move |cache: Rc<dyn ErasedCache>, entry: SharedEntry<K, T, E>| {
+            let mut state = entry.borrow_mut();
+            state.gc_timer = None;
+            if !state.subscribers.is_empty() {
+                return;
+            }
+            if let Some(task) = state.stale_timer.take() {
+                remove_future(task);
+            }
+            drop(state);
+            let mut entries = downcast_cache::<K, T, E>(&*cache).entries.borrow_mut();
+            // The entry may have been replaced after it was removed before
+            if entries
+                .get(&key)
+                .map_or(false, |current| Rc::ptr_eq(current, &entry))
+            {
+                entries.remove(&key);
+            }
+        };
+
+        if cache_time.is_zero() {
+            collect(cache, entry);
+            return;
+        }
+        let timer_entry = entry.clone();
+        let timer = spawn_at(
+            async move {
+                sleep(cache_time).await;
+                collect(cache, timer_entry);
+            },
+            ScopeId::ROOT,
+        );
+        let old = std::mem::replace(&mut entry.borrow_mut().gc_timer, timer);
+        if let Some(task) = old {
+            remove_future(task);
+        }
+    }
+}
+
+fn downcast_cache<K, T, E>(cache: &dyn ErasedCache) -> &QueryCache<K, T, E>
+where
+    K: 'static,
+    T: 'static,
+    E: 'static,
+{
+    cache
+        .as_any()
+        .downcast_ref::<QueryCache<K, T, E>>()
+        .expect("query caches are stored by their type")
+}
+
+/// Lets the client invalidate caches without knowing their value and error types.
+trait ErasedCache {
+    fn as_any(&self) -> &dyn Any;
+
+    fn invalidate(&self, key: Option<&dyn Any>, client: &QueryClient);
+}
+
+struct QueryCache<K, T, E> {
+    entries: RefCell<HashMap<K, SharedEntry<K, T, E>>>,
+}
+
+impl<K, T, E> Default for QueryCache<K, T, E> {
+    fn default() -> Self {
+        Self {
+            entries: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, T, E> ErasedCache for QueryCache<K, T, E>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn invalidate(&self, key: Option<&dyn Any>, client: &QueryClient) {
+        let key = match key {
+            Some(key) => match key.downcast_ref::<K>() {
+                Some(key) => Some(key),
+                // This cache uses a different key type
+                None => return,
+            },
+            None => None,
+        };
+
+        let entries: Vec<_> = self
+            .entries
+            .borrow()
+            .iter()
+            .filter(|(entry_key, _)| match key {
+                Some(key) => key == *entry_key,
+                None => true,
+            })
+            .map(|(key, entry)| (key.clone(), entry.clone()))
+            .collect();
+
+        for (key, entry) in entries {
+            let used = {
+                let mut entry = entry.borrow_mut();
+                entry.stale = true;
+                !entry.subscribers.is_empty()
+            };
+            if used {
+                fetch(key, entry, client);
+            } else {
+                notify(&entry);
+            }
+        }
+    }
+}
+
+type SharedEntry<K, T, E> = Rc<RefCell<QueryEntry<K, T, E>>>;
+
+struct QueryEntry<K, T, E> {
+    data: Option<T>,
+    error: Option<E>,
+    fetching: bool,
+    stale: bool,
+    /// The fetcher of the component that rendered last, used to fetch the value again when it is invalidated.
+    fetcher: Option<Fetcher<K, T, E>>,
+    storage: Option<Rc<dyn QueryStorage<K, T>>>,
+    /// The update function of every query that uses the entry, by the id of the query's subscription.
+    subscribers: Vec<(usize, Arc<dyn Fn()>)>,
+    stale_timer: Option<TaskId>,
+    /// Removes the entry from the cache once no component has used it for the cache time.
+    gc_timer: Option<TaskId>,
+}
+
+impl<K, T, E> Default for QueryEntry<K, T, E> {
+    fn default() -> Self {
+        Self {
+            data: None,
+            error: None,
+            fetching: false,
+            stale: true,
+            fetcher: None,
+            storage: None,
+            subscribers: Vec::new(),
+            stale_timer: None,
+            gc_timer: None,
+        }
+    }
+}
+
+/// Rerender every component that uses the entry.
+fn notify<K, T, E>(entry: &SharedEntry<K, T, E>) {
+    let subscribers: Vec<_> = entry
+        .borrow()
+        .subscribers
+        .iter()
+        .map(|(_, update)| update.clone())
+        .collect();
+    for update in subscribers {
+        update();
+    }
+}
+
+/// Fetch the value of an entry, unless it is already being fetched.
+///
+/// The fetch runs in the root scope so it finishes even if the component that started it is dropped. Failed fetches are retried with the retry policy of the client.
+fn fetch<K, T, E>(key: K, entry: SharedEntry<K, T, E>, client: &QueryClient)
+where
+    K: Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    let fetcher = {
+        let mut state = entry.borrow_mut();
+        if state.fetching {
+            return;
+        }
+        let fetcher = match state.fetcher.clone() {
+            Some(fetcher) => fetcher,
+            None => return,
+        };
+        state.fetching = true;
+        if let Some(task) = state.stale_timer.take() {
+            remove_future(task);
+        }
+        fetcher
+    };
+    notify(&entry);
+
+    let stale_time = client.stale_time;
+    let retry_policy = client.retry_policy.clone();
+    spawn_at(
+        async move {
+            let mut attempt = 0;
+            let result = loop {
+                attempt += 1;
+                match fetcher(key.clone()).await {
+                    Err(error) if retry_policy.should_retry(attempt, &error) => {
+                        sleep(retry_policy.delay(attempt)).await;
+                    }
+                    result => break result,
+                }
+            };
+            let fresh = {
+                let mut state = entry.borrow_mut();
+                state.fetching = false;
+                match result {
+                    Ok(value) => {
+                        if let Some(storage) = &state.storage {
+                            storage.save(&key, &value);
+                        }
+                        state.data = Some(value);
+                        state.error = None;
+                        state.stale = stale_time.is_zero();
+                    }
+                    Err(error) => {
+                        state.error = Some(error);
+                        state.stale = true;
+                    }
+                }
+                !state.stale
+            };
+
+            if fresh {
+                let timer_entry = entry.clone();
+                let timer = spawn_at(
+                    async move {
+                        sleep(stale_time).await;
+                        let mut state = timer_entry.borrow_mut();
+                        state.stale = true;
+                        state.stale_timer = None;
+                    },
+                    ScopeId::ROOT,
+                );
+                entry.borrow_mut().stale_timer = timer;
+            }
+
+            notify(&entry);
+        },
+        ScopeId::ROOT,
+    );
+}
+
+/// Get the [`QueryClient`] that caches the values of [`use_query`].
+pub fn use_query_client(cx: &ScopeState) -> &QueryClient {
+    use_root_context(cx, QueryClient::new)
+}
+
+/// Fetch a value and cache it under `key`, like react-query's `useQuery`.
+///
+/// Every component that uses the same key shares one cached value, and only one fetch runs for a key at a time. Cached values are shown right away while they are fetched again in the background once they are stale (stale-while-revalidate). When `key` changes, the value for the new key is shown and fetched if needed.
+///
+/// Values can be fetched again with [`UseQuery::refetch`], or from anywhere with [`QueryClient::invalidate`].
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// async fn fetch_user(id: u32) -> Result<String, String> {
+///     Ok(format!("User {id}"))
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope, id: u32) -> Element {
+///     let user = use_query(cx, *id, fetch_user);
+///
+///     match (user.data(), user.error()) {
+///         (Some(name), _) => render! { "{name}" },
+///         (None, Some(error)) => render! { "Failed to load the user: {error}" },
+///         (None, None) => render! { "Loading..." },
+///     }
+/// }
+/// ```
+pub fn use_query<K, T, E, F>(
+    cx: &ScopeState,
+    key: K,
+    fetcher: impl Fn(K) -> F + 'static,
+) -> &UseQuery<K, T, E>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    use_query_inner(cx, key, None, fetcher)
+}
+
+/// Like [`use_query`], but values are also saved to `storage` when they are fetched. When nothing is cached for a key yet, the saved value is shown while the value is fetched again.
+pub fn use_persisted_query<K, T, E, F>(
+    cx: &ScopeState,
+    key: K,
+    storage: impl QueryStorage<K, T> + 'static,
+    fetcher: impl Fn(K) -> F + 'static,
+) -> &UseQuery<K, T, E>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    let storage = cx.use_hook(|| Rc::new(storage) as Rc<dyn QueryStorage<K, T>>);
+    use_query_inner(cx, key, Some(storage.clone()), fetcher)
+}
+
+fn use_query_inner<K, T, E, F>(
+    cx: &ScopeState,
+    key: K,
+    storage: Option<Rc<dyn QueryStorage<K, T>>>,
+    fetcher: impl Fn(K) -> F + 'static,
+) -> &UseQuery<K, T, E>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    let client = use_query_client(cx);
+    // Identifies this query in the subscribers of its entry, because a component can use the same key more than once
+    static NEXT_SUBSCRIPTION: AtomicUsize = AtomicUsize::new(0);
+    let query = cx.use_hook(|| UseQuery {
+        client: client.clone(),
+        subscription: NEXT_SUBSCRIPTION.fetch_add(1, Ordering::Relaxed),
+        update: cx.schedule_update(),
+        current: Rc::new(RefCell::new(None)),
+    });
+
+    let changed = match &*query.current.borrow() {
+        Some((current, _)) => *current != key,
+        None => true,
+    };
+    if changed {
+        query.unsubscribe();
+        let entry = query.client.entry::<K, T, E>(&key);
+        {
+            let mut state = entry.borrow_mut();
+            if let Some(storage) = storage {
+                if state.data.is_none() {
+                    state.data = storage.load(&key);
+                }
+                state.storage = Some(storage);
+            }
+            if let Some(task) = state.gc_timer.take() {
+                remove_future(task);
+            }
+            state
+                .subscribers
+                .push((query.subscription, query.update.clone()));
+        }
+        *query.current.borrow_mut() = Some((key.clone(), entry));
+    }
+
+    // Always fetch with the latest closure so it can capture new values
+    let entry = query.entry();
+    let fetcher: Fetcher<K, T, E> =
+        Rc::new(move |key| -> Pin<Box<dyn Future<Output = Result<T, E>>>> {
+            Box::pin(fetcher(key))
+        });
+    entry.borrow_mut().fetcher = Some(fetcher);
+
+    if changed {
+        let needs_fetch = {
+            let state = entry.borrow();
+            state.stale || (state.data.is_none() && state.error.is_none())
+        };
+        if needs_fetch {
+            fetch(key, entry, &query.client);
+        }
+    }
+
+    let cleanup = query.clone();
+    use_on_destroy(cx, move || cleanup.unsubscribe());
+
+    query
+}
+
+/// A cached value fetched with [`use_query`].
+#[allow(clippy::type_complexity)]
+pub struct UseQuery<K, T, E> {
+    client: QueryClient,
+    subscription: usize,
+    update: Arc<dyn Fn()>,
+    /// The key the component uses, and its entry in the cache.
+    current: Rc<RefCell<Option<(K, SharedEntry<K, T, E>)>>>,
+}
+
+impl<K, T, E> Clone for UseQuery<K, T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            subscription: self.subscription,
+            update: self.update.clone(),
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<K, T, E> UseQuery<K, T, E>
+where
+    K: Hash + Eq + Clone + 'static,
+    T: 'static,
+    E: 'static,
+{
+    fn entry(&self) -> SharedEntry<K, T, E> {
+        self.current
+            .borrow()
+            .as_ref()
+            .map(|(_, entry)| entry.clone())
+            .expect("the query is subscribed to a key when it is rendered")
+    }
+
+    fn unsubscribe(&self) {
+        let current = self.current.borrow_mut().take();
+        if let Some((key, entry)) = current {
+            let unused = {
+                let mut state = entry.borrow_mut();
+                state
+                    .subscribers
+                    .retain(|(subscription, _)| *subscription != self.subscription);
+                state.subscribers.is_empty()
+            };
+            if unused {
+                self.client.collect_later(key, entry);
+            }
+        }
+    }
+
+    /// Get the last value that was fetched for the key. The value stays available while it is fetched again.
+    pub fn data(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.entry().borrow().data.clone()
+    }
+
+    /// Get the error of the last fetch, if it failed. The error is cleared when a fetch succeeds.
+    pub fn error(&self) -> Option<E>
+    where
+        E: Clone,
+    {
+        self.entry().borrow().error.clone()
+    }
+
+    /// Returns true while the value is being fetched, even if an older value is available.
+    pub fn is_fetching(&self) -> bool {
+        self.entry().borrow().fetching
+    }
+
+    /// Returns true while the value is being fetched for the first time, so there is nothing to show yet.
+    pub fn is_loading(&self) -> bool {
+        let state = self.entry();
+        let state = state.borrow();
+        state.fetching && state.data.is_none()
+    }
+
+    /// Returns true if the value will be fetched again the next time a component starts using it.
+    pub fn is_stale(&self) -> bool {
+        self.entry().borrow().stale
+    }
+
+    /// Fetch the value again, unless it is already being fetched.
+    pub fn refetch(&self) {
+        let key = match &*self.current.borrow() {
+            Some((key, _)) => key.clone(),
+            None => return,
+        };
+        fetch(key, self.entry(), &self.client);
+    }
+
+    /// Get the client that caches the value.
+    pub fn client(&self) -> &QueryClient {
+        &self.client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::use_context_provider;
+    use dioxus_core::prelude::*;
+    use std::cell::Cell;
+
+    #[derive(Clone, Default)]
+    struct Harness {
+        key: Rc<Cell<u32>>,
+        fetches: Rc<Cell<usize>>,
+        query: Rc<RefCell<Option<UseQuery<u32, String, ()>>>>,
+    }
+
+    fn app(cx: Scope<Harness>) -> Element {
+        use_context_provider(cx, || {
+            QueryClient::new().cache_time(Duration::from_millis(50))
+        });
+        let key = cx.props.key.get();
+        let fetcher = |fetches: Rc<Cell<usize>>| {
+            move |key: u32| {
+                fetches.set(fetches.get() + 1);
+                async move { Ok::<_, ()>(format!("value {key}")) }
+            }
+        };
+        let query = use_query(cx, key, fetcher(cx.props.fetches.clone()));
+        // A second query for the same key shares the fetch
+        use_query(cx, key, fetcher(cx.props.fetches.clone()));
+        *cx.props.query.borrow_mut() = Some(query.clone());
+        None
+    }
+
+    async fn run(dom: &mut VirtualDom, wait: Duration) {
+        _ = tokio::time::timeout(wait, dom.wait_for_work()).await;
+        _ = dom.render_immediate();
+    }
+
+    fn cached_keys(client: &QueryClient) -> Vec<u32> {
+        let cache = client.cache::<u32, String, ()>();
+        let mut keys: Vec<_> = downcast_cache::<u32, String, ()>(&*cache)
+            .entries
+            .borrow()
+            .keys()
+            .copied()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    #[tokio::test]
+    async fn queries_are_cached_and_fetched_again() {
+        let harness = Harness::default();
+        harness.key.set(1);
+        let mut dom = VirtualDom::new_with_props(app, harness.clone());
+        _ = dom.rebuild();
+        let query = harness.query.borrow().clone().unwrap();
+        let client = query.client().clone();
+
+        // Both queries for the key share one fetch
+        assert!(query.is_loading());
+        run(&mut dom, Duration::from_millis(10)).await;
+        assert_eq!(harness.fetches.get(), 1);
+        assert_eq!(query.data().as_deref(), Some("value 1"));
+
+        // The stale value is shown while it is fetched again
+        let runtime = dom.runtime();
+        let in_root = |f: &dyn Fn()| RuntimeGuard::with(runtime.clone(), Some(ScopeId::ROOT), f);
+        in_root(&|| query.refetch());
+        assert!(query.is_fetching());
+        assert_eq!(query.data().as_deref(), Some("value 1"));
+        run(&mut dom, Duration::from_millis(10)).await;
+        assert_eq!(harness.fetches.get(), 2);
+        assert!(!query.is_fetching());
+
+        // Invalidating only fetches the used values with that key again
+        in_root(&|| client.invalidate(&2u32));
+        assert!(!query.is_fetching());
+        in_root(&|| client.invalidate(&1u32));
+        assert!(query.is_fetching());
+        run(&mut dom, Duration::from_millis(10)).await;
+        assert_eq!(harness.fetches.get(), 3);
+
+        // A new key is fetched, while the value of the old key stays cached for the cache time
+        harness.key.set(2);
+        dom.mark_dirty(ScopeId::ROOT);
+        _ = dom.render_immediate();
+        let query = harness.query.borrow().clone().unwrap();
+        assert!(query.is_loading());
+        run(&mut dom, Duration::from_millis(10)).await;
+        assert_eq!(query.data().as_deref(), Some("value 2"));
+        assert_eq!(harness.fetches.get(), 4);
+        assert_eq!(cached_keys(&client), vec![1, 2]);
+
+        run(&mut dom, Duration::from_millis(100)).await;
+        assert_eq!(cached_keys(&client), vec![2]);
+
+        // The old key was removed, so it is loaded again
+        harness.key.set(1);
+        dom.mark_dirty(ScopeId::ROOT);
+        _ = dom.render_immediate();
+        let query = harness.query.borrow().clone().unwrap();
+        assert!(query.is_loading());
+        run(&mut dom, Duration::from_millis(10)).await;
+        assert_eq!(query.data().as_deref(), Some("value 1"));
+        assert_eq!(harness.fetches.get(), 5);
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedKeyHarness {
+        second_key: Rc<Cell<u32>>,
+        fetches: Rc<Cell<usize>>,
+        queries: Rc<RefCell<Vec<UseQuery<u32, String, ()>>>>,
+    }
+
+    fn shared_key_app(cx: Scope<SharedKeyHarness>) -> Element {
+        use_context_provider(cx, || {
+            QueryClient::new().cache_time(Duration::from_millis(50))
+        });
+        let fetches = cx.props.fetches.clone();
+        let fetcher = move |key: u32| {
+            fetches.set(fetches.get() + 1);
+            async move { Ok::<_, ()>(format!("value {key}")) }
+        };
+        let first = use_query(cx, 1, fetcher.clone());
+        let second = use_query(cx, cx.props.second_key.get(), fetcher);
+        *cx.props.queries.borrow_mut() = vec![first.clone(), second.clone()];
+        None
+    }
+
+    #[tokio::test]
+    async fn queries_with_the_same_key_in_one_component_unsubscribe_separately() {
+        let harness = SharedKeyHarness::default();
+        harness.second_key.set(1);
+        let mut dom = VirtualDom::new_with_props(shared_key_app, harness.clone());
+        _ = dom.rebuild();
+        run(&mut dom, Duration::from_millis(10)).await;
+        assert_eq!(harness.fetches.get(), 1);
+
+        // The second query stops using the key the first query still uses
+        harness.second_key.set(2);
+        dom.mark_dirty(ScopeId::ROOT);
+        _ = dom.render_immediate();
+        let first = harness.queries.borrow()[0].clone();
+        assert_eq!(first.entry().borrow().subscribers.len(), 1);
+
+        // The entry is not collected, and the first query is still told about invalidations
+        run(&mut dom, Duration::from_millis(100)).await;
+        let client = first.client().clone();
+        assert_eq!(cached_keys(&client), vec![1, 2]);
+        let fetches = harness.fetches.get();
+        RuntimeGuard::with(dom.runtime(), Some(ScopeId::ROOT), || {
+            client.invalidate(&1u32)
+        });
+        assert!(first.is_fetching());
+        run(&mut dom, Duration::from_millis(10)).await;
+        assert_eq!(harness.fetches.get(), fetches + 1);
+        assert_eq!(first.data().as_deref(), Some("value 1"));
+    }
+
+    #[tokio::test]
+    async fn failed_fetches_are_retried_with_the_client_policy() {
+        fn app(cx: Scope<Rc<Cell<usize>>>) -> Element {
+            use_context_provider(cx, || {
+                QueryClient::new().retry_policy(RetryPolicy::default().backoff(Duration::ZERO, 1.0))
+            });
+            let fetches = cx.props.clone();
+            use_query(cx, 1, move |_: u32| {
+                fetches.set(fetches.get() + 1);
+                let fetches = fetches.get();
+                async move {
+                    if fetches < 3 {
+                        Err(())
+                    } else {
+                        Ok("value".to_string())
+                    }
+                }
+            });
+            None
+        }
+
+        let fetches = Rc::new(Cell::new(0));
+        let mut dom = VirtualDom::new_with_props(app, fetches.clone());
+        _ = dom.rebuild();
+        for _ in 0..3 {
+            run(&mut dom, Duration::from_millis(10)).await;
+        }
+        assert_eq!(fetches.get(), 3);
+    }
+
+    #[test]
+    fn options_only_change_the_client_they_are_set_on() {
+        let client = QueryClient::new();
+        let configured = client.clone().stale_time(Duration::from_secs(30));
+        assert_eq!(client.stale_time, Duration::ZERO);
+        assert_eq!(configured.stale_time, Duration::from_secs(30));
+        // The clients still share their cache
+        assert!(Rc::ptr_eq(&client.inner, &configured.inner));
+    }
+}
@@ -0,0 +1,339 @@
+use std::{
+    any::Any,
+    cell::{Cell, Ref, RefCell},
+    future::Future,
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
+
+use dioxus_core::{ScopeState, TaskId};
+
+use crate::{timer::sleep, UseFutureDep};
+
+/// Controls how often and how quickly [`use_retry_future`] retries a future that failed.
+///
+/// The [`QueryClient`](crate::QueryClient) holds the default policy for the whole app, see [`QueryClient::retry_policy`](crate::QueryClient::retry_policy). Provide a policy as a context with `use_context_provider` to change the default for every [`use_retry_future`] below it.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_delay: Duration,
+    multiplier: f64,
+    max_delay: Duration,
+    retry_on: Option<Rc<dyn Fn(&dyn Any) -> bool>>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(30),
+            retry_on: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn never() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Set how many times the future runs in total before the error is kept. Defaults to 3.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Wait `initial_delay` before the first retry, and multiply the delay by `multiplier` after every retry after that. Defaults to 500ms doubling every retry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `multiplier` is less than 1 or not finite.
+    pub fn backoff(mut self, initial_delay: Duration, multiplier: f64) -> Self {
+        assert!(
+            multiplier.is_finite() && multiplier >= 1.0,
+            "the backoff multiplier must be a finite number of at least 1, got {multiplier}"
+        );
+        self.initial_delay = initial_delay;
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Never wait longer than `max_delay` between retries. Defaults to 30 seconds.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Only retry errors of type `E` that `retry_on` returns true for. Errors of other types are always retried.
+    pub fn retry_on<E: 'static>(mut self, retry_on: impl Fn(&E) -> bool + 'static) -> Self {
+        self.retry_on = Some(Rc::new(move |error: &dyn Any| {
+            error.downcast_ref::<E>().map_or(true, &retry_on)
+        }));
+        self
+    }
+
+    /// Get how long to wait before retrying after attempt number `attempt` failed. The first attempt is 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        if self.initial_delay.is_zero() {
+            return Duration::ZERO;
+        }
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        // The delay is capped before it is turned back into a duration, because it can grow past what a duration can hold
+        let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(exponent);
+        if delay >= self.max_delay.as_secs_f64() {
+            return self.max_delay;
+        }
+        Duration::from_secs_f64(delay)
+    }
+
+    /// Returns true if attempt number `attempt` failed with `error` and should be retried.
+    pub fn should_retry<E: 'static>(&self, attempt: u32, error: &E) -> bool {
+        attempt < self.max_attempts
+            && self
+                .retry_on
+                .as_ref()
+                .map_or(true, |retry_on| retry_on(error))
+    }
+}
+
+/// A future that is retried with a [`RetryPolicy`] when it fails.
+///
+/// This works like [`use_future`](crate::use_future), but the future returns a [`Result`]. When it returns an error, it is run again after a delay until it succeeds or the policy gives up. Once it gives up, the error is kept and [`UseRetryFuture::retry`] can be called from the error UI to start over.
+///
+/// The policy is read from the closest [`RetryPolicy`] context, then from the [`QueryClient`](crate::QueryClient) context, or [`RetryPolicy::default`] if there is neither.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// async fn fetch_user(id: u32) -> Result<String, String> {
+///     Ok(format!("user {id}"))
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope, id: u32) -> Element {
+///     let user = use_retry_future(cx, (id,), |(id,)| fetch_user(id));
+///
+///     match (user.value(), user.error()) {
+///         (Some(user), _) => render! { "{user}" },
+///         (None, Some(error)) => render! {
+///             "Failed to load the user: {error}"
+///             button { onclick: move |_| user.retry(), "Try again" }
+///         },
+///         (None, None) => render! { "Loading... (attempt {user.attempts()})" },
+///     }
+/// }
+/// ```
+pub fn use_retry_future<T, E, F, D>(
+    cx: &ScopeState,
+    dependencies: D,
+    future: impl Fn(D::Out) -> F + 'static,
+) -> &UseRetryFuture<T, E>
+where
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+    D: UseFutureDep,
+    D::Out: Clone + 'static,
+{
+    let policy = cx.use_hook(|| {
+        cx.consume_context::<RetryPolicy>()
+            .or_else(|| {
+                cx.consume_context::<crate::QueryClient>()
+                    .map(|client| client.retry_policy)
+            })
+            .unwrap_or_default()
+    });
+    use_retry_future_with_policy(cx, dependencies, policy.clone(), future)
+}
+
+/// A future that is retried with a specific [`RetryPolicy`] when it fails. See [`use_retry_future`] for more information.
+pub fn use_retry_future_with_policy<T, E, F, D>(
+    cx: &ScopeState,
+    dependencies: D,
+    policy: RetryPolicy,
+    future: impl Fn(D::Out) -> F + 'static,
+) -> &UseRetryFuture<T, E>
+where
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+    D: UseFutureDep,
+    D::Out: Clone + 'static,
+{
+    let state = cx.use_hook(|| UseRetryFuture {
+        update: cx.schedule_update(),
+        needs_regen: Rc::new(Cell::new(true)),
+        task: Default::default(),
+        state: Rc::new(RefCell::new(RetryState {
+            value: None,
+            error: None,
+            attempts: 0,
+        })),
+    });
+
+    let state_dependencies = cx.use_hook(Vec::new);
+
+    if dependencies.clone().apply(state_dependencies) || state.needs_regen.get() {
+        if let Some(task) = state.task.take() {
+            cx.remove_future(task);
+        }
+
+        {
+            let mut current = state.state.borrow_mut();
+            current.error = None;
+            current.attempts = 0;
+        }
+
+        let dependencies = dependencies.out();
+        let inner = state.state.clone();
+        let task = state.task.clone();
+        let update = state.update.clone();
+        state.task.set(Some(cx.push_future(async move {
+            let mut attempt = 0;
+            loop {
+                attempt += 1;
+                inner.borrow_mut().attempts = attempt;
+                update();
+                match future(dependencies.clone()).await {
+                    Ok(value) => {
+                        inner.borrow_mut().value = Some(value);
+                        break;
+                    }
+                    Err(error) if policy.should_retry(attempt, &error) => {
+                        sleep(policy.delay(attempt)).await;
+                    }
+                    Err(error) => {
+                        inner.borrow_mut().error = Some(error);
+                        break;
+                    }
+                }
+            }
+            task.take();
+            update();
+        })));
+
+        state.needs_regen.set(false);
+    }
+
+    state
+}
+
+struct RetryState<T, E> {
+    value: Option<T>,
+    error: Option<E>,
+    attempts: u32,
+}
+
+/// A future that is retried when it fails. Created with [`use_retry_future`].
+pub struct UseRetryFuture<T: 'static, E: 'static> {
+    update: Arc<dyn Fn()>,
+    needs_regen: Rc<Cell<bool>>,
+    task: Rc<Cell<Option<TaskId>>>,
+    state: Rc<RefCell<RetryState<T, E>>>,
+}
+
+impl<T, E> Clone for UseRetryFuture<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            update: self.update.clone(),
+            needs_regen: self.needs_regen.clone(),
+            task: self.task.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T, E> UseRetryFuture<T, E> {
+    /// Start over with a fresh set of attempts. Call this from the error UI once the retries ran out.
+    pub fn retry(&self) {
+        self.needs_regen.set(true);
+        (self.update)();
+    }
+
+    /// Stop the future and any retries that are waiting.
+    pub fn cancel(&self, cx: &ScopeState) {
+        if let Some(task) = self.task.take() {
+            cx.remove_future(task);
+        }
+    }
+
+    /// Get the latest value the future resolved to. This is kept while the future is retried.
+    pub fn value(&self) -> Option<Ref<'_, T>> {
+        Ref::filter_map(self.state.borrow(), |state| state.value.as_ref()).ok()
+    }
+
+    /// Get the error of the last attempt once the policy gave up.
+    pub fn error(&self) -> Option<Ref<'_, E>> {
+        Ref::filter_map(self.state.borrow(), |state| state.error.as_ref()).ok()
+    }
+
+    /// Get the number of the attempt that is running, or that ran last.
+    pub fn attempts(&self) -> u32 {
+        self.state.borrow().attempts
+    }
+
+    /// Returns true while an attempt is running or a retry is waiting.
+    pub fn is_loading(&self) -> bool {
+        self.task.get().is_some()
+    }
+
+    /// Get the ID of the future in Dioxus' internal scheduler
+    pub fn task(&self) -> Option<TaskId> {
+        self.task.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_until_the_max_delay() {
+        let policy = RetryPolicy::default()
+            .backoff(Duration::from_millis(100), 2.0)
+            .max_delay(Duration::from_millis(300));
+        assert_eq!(policy.delay(1), Duration::from_millis(100));
+        assert_eq!(policy.delay(2), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn backoff_is_capped_for_any_attempt() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(70), Duration::from_secs(30));
+        assert_eq!(policy.delay(5000), Duration::from_secs(30));
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(30));
+
+        let policy = RetryPolicy::default().max_delay(Duration::MAX);
+        assert_eq!(policy.delay(u32::MAX), Duration::MAX);
+
+        let policy = RetryPolicy::default().backoff(Duration::ZERO, 2.0);
+        assert_eq!(policy.delay(u32::MAX), Duration::ZERO);
+    }
+
+    #[test]
+    #[should_panic]
+    fn backoff_rejects_shrinking_multipliers() {
+        RetryPolicy::default().backoff(Duration::from_millis(100), 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn backoff_rejects_nan_multipliers() {
+        RetryPolicy::default().backoff(Duration::from_millis(100), f64::NAN);
+    }
+
+    #[test]
+    fn retry_on_filters_errors() {
+        let policy = RetryPolicy::default().retry_on(|error: &u16| *error >= 500);
+        assert!(policy.should_retry(1, &503u16));
+        assert!(!policy.should_retry(1, &404u16));
+        assert!(!policy.should_retry(3, &503u16));
+        // Errors of other types are always retried
+        assert!(policy.should_retry(1, &"timeout"));
+    }
+}
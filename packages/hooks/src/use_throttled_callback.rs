@@ -0,0 +1,176 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use dioxus_core::{
+    prelude::{remove_future, spawn_at, use_callback, Callback},
+    ScopeId, ScopeState, TaskId,
+};
+
+/// Controls when a [`ThrottledCallback`] calls the wrapped function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottleOptions {
+    /// Call the function immediately when it is called outside of an interval.
+    pub leading: bool,
+    /// Call the function with the latest arguments at the end of an interval if it was called during the interval.
+    pub trailing: bool,
+}
+
+impl Default for ThrottleOptions {
+    fn default() -> Self {
+        Self {
+            leading: true,
+            trailing: true,
+        }
+    }
+}
+
+/// Wrap a function so it is called at most once every `interval`. This is useful for event handlers that fire continuously, like `onscroll` or `onmousemove`.
+///
+/// The function always sees the latest props and state. Pending calls are cancelled when the component is dropped.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn app(cx: Scope) -> Element {
+///     let position = use_state(cx, || (0.0, 0.0));
+///     let set_position = position.setter();
+///     let track = use_throttled_callback(cx, Duration::from_millis(100), move |point: (f64, f64)| {
+///         set_position(point);
+///     });
+///
+///     render! {
+///         div {
+///             onmousemove: move |evt| {
+///                 let point = evt.client_coordinates();
+///                 track.call((point.x, point.y));
+///             },
+///             "{position:?}"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_throttled_callback<Args: 'static>(
+    cx: &ScopeState,
+    interval: Duration,
+    f: impl FnMut(Args) + 'static,
+) -> &ThrottledCallback<Args> {
+    use_throttled_callback_with_options(cx, interval, ThrottleOptions::default(), f)
+}
+
+/// Wrap a function so it is called at most once every `interval`, with control over whether the first and last call in an interval go through. See [`use_throttled_callback`] for more information.
+pub fn use_throttled_callback_with_options<Args: 'static>(
+    cx: &ScopeState,
+    interval: Duration,
+    options: ThrottleOptions,
+    f: impl FnMut(Args) + 'static,
+) -> &ThrottledCallback<Args> {
+    let callback = use_callback(cx, f);
+    let throttled = cx.use_hook(|| ThrottledCallback {
+        callback,
+        scope: cx.scope_id(),
+        state: Default::default(),
+    });
+    let mut state = throttled.state.borrow_mut();
+    state.interval = interval;
+    state.options = options;
+    drop(state);
+    throttled
+}
+
+/// A function that is called at most once per interval. Created with [`use_throttled_callback`].
+pub struct ThrottledCallback<Args: 'static = ()> {
+    callback: Callback<Args>,
+    scope: ScopeId,
+    state: Rc<RefCell<ThrottleState<Args>>>,
+}
+
+struct ThrottleState<Args> {
+    interval: Duration,
+    options: ThrottleOptions,
+    task: Option<TaskId>,
+    pending: Option<Args>,
+}
+
+impl<Args> Default for ThrottleState<Args> {
+    fn default() -> Self {
+        Self {
+            interval: Duration::ZERO,
+            options: ThrottleOptions::default(),
+            task: None,
+            pending: None,
+        }
+    }
+}
+
+impl<Args: 'static> ThrottledCallback<Args> {
+    /// Call the function, or delay the call until the current interval ends.
+    pub fn call(&self, args: Args) {
+        let mut state = self.state.borrow_mut();
+        if state.task.is_some() {
+            if state.options.trailing {
+                state.pending = Some(args);
+            }
+            return;
+        }
+        let fire_now = state.options.leading;
+        let args = if fire_now {
+            Some(args)
+        } else {
+            if state.options.trailing {
+                state.pending = Some(args);
+            }
+            None
+        };
+        let interval = state.interval;
+        drop(state);
+
+        let state = self.state.clone();
+        let callback = self.callback;
+        let task = spawn_at(
+            async move {
+                loop {
+                    crate::timer::sleep(interval).await;
+                    let pending = state.borrow_mut().pending.take();
+                    match pending {
+                        // Calling the function starts another interval
+                        Some(args) => callback.call(args),
+                        None => {
+                            state.borrow_mut().task = None;
+                            break;
+                        }
+                    }
+                }
+            },
+            self.scope,
+        );
+        self.state.borrow_mut().task = task;
+
+        if let Some(args) = args {
+            self.callback.call(args);
+        }
+    }
+
+    /// Drop the pending call, if there is one, and end the current interval.
+    pub fn cancel(&self) {
+        let mut state = self.state.borrow_mut();
+        if let Some(task) = state.task.take() {
+            remove_future(task);
+        }
+        state.pending = None;
+    }
+
+    /// Returns true if a call is waiting for the current interval to end.
+    pub fn is_pending(&self) -> bool {
+        self.state.borrow().pending.is_some()
+    }
+}
+
+impl<Args: 'static> Clone for ThrottledCallback<Args> {
+    fn clone(&self) -> Self {
+        Self {
+            callback: self.callback,
+            scope: self.scope,
+            state: self.state.clone(),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use dioxus_core::{
+    prelude::{remove_future, spawn_at, use_callback, Callback},
+    ScopeId, ScopeState, TaskId,
+};
+
+use crate::use_on_destroy;
+
+/// Call a function once `delay` after the component is mounted. This is useful for hiding toasts or warning about inactivity.
+///
+/// The function always sees the latest props and state. The returned handle can restart the delay with [`TimeoutHandle::reset`] or stop it with [`TimeoutHandle::cancel`]. A pending call is cancelled when the component is dropped.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use std::time::Duration;
+///
+/// fn app(cx: Scope) -> Element {
+///     let idle = use_state(cx, || false);
+///     let set_idle = idle.setter();
+///     let timeout = use_timeout(cx, Duration::from_secs(60), move || set_idle(true));
+///     let message = if **idle { "Are you still there?" } else { "Welcome back" };
+///
+///     render! {
+///         div {
+///             onmousemove: move |_| {
+///                 idle.set(false);
+///                 timeout.reset();
+///             },
+///             "{message}"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_timeout(
+    cx: &ScopeState,
+    delay: Duration,
+    mut f: impl FnMut() + 'static,
+) -> &TimeoutHandle {
+    let callback = use_callback(cx, move |()| f());
+    let timeout = cx.use_hook(|| {
+        let timeout = TimeoutHandle {
+            callback,
+            scope: cx.scope_id(),
+            state: Rc::new(RefCell::new(TimeoutState { delay, task: None })),
+        };
+        timeout.reset();
+        timeout
+    });
+    timeout.state.borrow_mut().delay = delay;
+
+    let cleanup = timeout.clone();
+    use_on_destroy(cx, move || cleanup.cancel());
+
+    timeout
+}
+
+/// A handle to control a timeout created with [`use_timeout`].
+pub struct TimeoutHandle {
+    callback: Callback<()>,
+    scope: ScopeId,
+    state: Rc<RefCell<TimeoutState>>,
+}
+
+struct TimeoutState {
+    delay: Duration,
+    task: Option<TaskId>,
+}
+
+impl TimeoutHandle {
+    /// Restart the delay. The function is called once the full delay passes from now, even if it already ran or was cancelled.
+    pub fn reset(&self) {
+        self.cancel();
+
+        let state = self.state.clone();
+        let callback = self.callback;
+        let delay = self.state.borrow().delay;
+        let task = spawn_at(
+            async move {
+                crate::timer::sleep(delay).await;
+                state.borrow_mut().task = None;
+                callback.call(());
+            },
+            self.scope,
+        );
+        self.state.borrow_mut().task = task;
+    }
+
+    /// Stop the pending call, if there is one.
+    pub fn cancel(&self) {
+        if let Some(task) = self.state.borrow_mut().task.take() {
+            remove_future(task);
+        }
+    }
+
+    /// Returns true if the function will be called once the delay passes.
+    pub fn is_pending(&self) -> bool {
+        self.state.borrow().task.is_some()
+    }
+}
+
+impl Clone for TimeoutHandle {
+    fn clone(&self) -> Self {
+        Self {
+            callback: self.callback,
+            scope: self.scope,
+            state: self.state.clone(),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::{cell::Cell, rc::Rc, sync::Arc};
+
+use dioxus_core::{prelude::Runtime, ScopeState};
+
+/// Mark state updates as low priority, so the expensive re-renders they cause don't delay the response to input.
+///
+/// Returns whether a transition is still rendering, and a [`StartTransition`] handle. Components that are marked dirty inside [`StartTransition::start`] are rendered after every other dirty component. Children that get new props are still rendered with their parent, so keep the state of the slow part of the page in a component of its own. Give the renderer a frame budget, like `Config::with_frame_budget` on web and desktop, to let it handle input between the chunks of a large transition.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+///
+/// fn app(cx: Scope) -> Element {
+///     use_shared_state_provider(cx, String::new);
+///     let filter = use_shared_state::<String>(cx).unwrap();
+///     let query = use_state(cx, String::new);
+///     let (is_pending, start_transition) = use_transition(cx);
+///
+///     render! {
+///         input {
+///             value: "{query}",
+///             oninput: move |evt| {
+///                 // The input updates right away, and the list follows once it is rendered
+///                 query.set(evt.value.clone());
+///                 start_transition.start(|| *filter.write() = evt.value.clone());
+///             }
+///         }
+///         if is_pending {
+///             rsx! { "Updating..." }
+///         }
+///         SlowList {}
+///     }
+/// }
+///
+/// fn SlowList(cx: Scope) -> Element {
+///     let filter = use_shared_state::<String>(cx).unwrap().read().clone();
+///
+///     render! {
+///         for i in (0..10_000).filter(|i| i.to_string().contains(filter.as_str())) {
+///             p { "{i}" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_transition(cx: &ScopeState) -> (bool, &StartTransition) {
+    let start_transition = cx.use_hook(|| StartTransition {
+        runtime: Runtime::current().expect("hooks are called inside a virtual dom"),
+        pending: Rc::new(Cell::new(false)),
+        update: cx.schedule_update(),
+    });
+
+    (start_transition.pending.get(), start_transition)
+}
+
+/// A handle to start transitions, created by [`use_transition`]. See its documentation for more details.
+#[derive(Clone)]
+pub struct StartTransition {
+    runtime: Rc<Runtime>,
+    pending: Rc<Cell<bool>>,
+    update: Arc<dyn Fn()>,
+}
+
+impl StartTransition {
+    /// Run `update`, and render the components it marks dirty after every other dirty component.
+    ///
+    /// The component that called [`use_transition`] is rendered right away with `is_pending` set to true, and again once every update of the transition is rendered.
+    pub fn start(&self, update: impl FnOnce()) {
+        if !self.pending.replace(true) {
+            (self.update)();
+        }
+
+        let pending = self.pending.clone();
+        let rerender = self.update.clone();
+        self.runtime.start_transition(update, move || {
+            if pending.replace(false) {
+                rerender();
+            }
+        });
+    }
+
+    /// Whether a transition started with this handle is still rendering.
+    pub fn is_pending(&self) -> bool {
+        self.pending.get()
+    }
+}
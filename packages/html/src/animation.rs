@@ -0,0 +1,272 @@
+//! Run code every frame and animate values with springs.
+//!
+//! Renderers provide an `Rc<dyn AnimationFrameProvider>` through the root scope's context, and components use it through [`use_animation_frame`] and [`use_spring`].
+
+use dioxus_core::{
+    prelude::{Runtime, RuntimeGuard},
+    ScopeId, ScopeState,
+};
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// A struct that implements AnimationFrameProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_animation_frame`] and [`use_spring`] can run once per frame on any platform.
+///
+/// It must be provided as `Rc<dyn AnimationFrameProvider>`, because the concrete type has a different TypeId.
+pub trait AnimationFrameProvider {
+    /// Call `callback` with the current time in milliseconds before the next frame is drawn. Like `requestAnimationFrame`, the callback is only called once.
+    fn request_frame(&self, callback: Box<dyn FnOnce(f64)>);
+}
+
+/// The timing of a frame passed to the callback of [`use_animation_frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationFrame {
+    /// The time since the first frame.
+    pub elapsed: Duration,
+    /// The time since the previous frame. This is zero for the first frame.
+    pub delta: Duration,
+}
+
+/// Run `callback` once every frame while the component is rendered.
+///
+/// ```rust, ignore
+/// let angle = use_signal(cx, || 0.0);
+/// use_animation_frame(cx, move |frame| angle.set(frame.elapsed.as_secs_f64() * 90.0));
+///
+/// render! {
+///     div { transform: "rotate({angle}deg)", "Spinning" }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide an [`AnimationFrameProvider`].
+pub fn use_animation_frame(cx: &ScopeState, mut callback: impl FnMut(AnimationFrame) + 'static) {
+    let ticker = cx.use_hook(|| {
+        let ticker = Ticker::new(cx, Box::new(|_| true));
+        ticker.start();
+        ticker
+    });
+
+    // Always use the latest closure so it can capture new values
+    *ticker.on_frame.borrow_mut() = Box::new(move |frame| {
+        callback(frame);
+        true
+    });
+}
+
+/// The physical properties of a spring animated with [`use_spring_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringConfig {
+    /// How strongly the spring pulls the value towards the target.
+    pub stiffness: f64,
+    /// How strongly the spring resists motion. Low damping makes the value overshoot and wobble.
+    pub damping: f64,
+    /// The mass of the value. Heavier values accelerate and stop more slowly.
+    pub mass: f64,
+    /// The animation stops once the value is this close to the target and moving slower than this.
+    pub precision: f64,
+}
+
+impl SpringConfig {
+    /// A spring that settles quickly without overshooting much.
+    pub const DEFAULT: Self = Self {
+        stiffness: 170.0,
+        damping: 26.0,
+        mass: 1.0,
+        precision: 0.01,
+    };
+
+    /// A soft, slow spring.
+    pub const GENTLE: Self = Self {
+        stiffness: 120.0,
+        damping: 14.0,
+        ..Self::DEFAULT
+    };
+
+    /// A spring that overshoots and wobbles around the target.
+    pub const WOBBLY: Self = Self {
+        stiffness: 180.0,
+        damping: 12.0,
+        ..Self::DEFAULT
+    };
+
+    /// A fast spring.
+    pub const STIFF: Self = Self {
+        stiffness: 210.0,
+        damping: 20.0,
+        ..Self::DEFAULT
+    };
+}
+
+impl Default for SpringConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Get a signal that follows `target` smoothly, like it is pulled by a spring. The signal starts at the first target, and animates towards every new target it is rendered with.
+///
+/// ```rust, ignore
+/// let open = use_state(cx, || false);
+/// let height = use_spring(cx, if *open.get() { 200.0 } else { 0.0 });
+///
+/// render! {
+///     button { onclick: move |_| open.set(!open.get()), "Toggle" }
+///     div { height: "{height}px", overflow: "hidden", "Content" }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide an [`AnimationFrameProvider`].
+pub fn use_spring(cx: &ScopeState, target: f64) -> ReadOnlySignal<f64> {
+    use_spring_with(cx, target, SpringConfig::default())
+}
+
+/// Get a signal that follows `target` smoothly, with a [`SpringConfig`] to change how the spring moves.
+///
+/// ```rust, ignore
+/// let scale = use_spring_with(cx, if *hovered.get() { 1.2 } else { 1.0 }, SpringConfig::WOBBLY);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide an [`AnimationFrameProvider`].
+pub fn use_spring_with(cx: &ScopeState, target: f64, config: SpringConfig) -> ReadOnlySignal<f64> {
+    let (value, spring, ticker) = cx.use_hook(|| {
+        let value = Signal::new(target);
+        let spring = Rc::new(Spring {
+            target: Cell::new(target),
+            velocity: Cell::new(0.0),
+            config: Cell::new(config),
+        });
+        let ticker = Ticker::new(cx, {
+            let spring = spring.clone();
+            Box::new(move |frame| spring.step(value, frame.delta))
+        });
+        (value, spring, ticker)
+    });
+
+    spring.config.set(config);
+    if spring.target.get() != target {
+        spring.target.set(target);
+        ticker.start();
+    }
+
+    ReadOnlySignal::new(*value)
+}
+
+struct Spring {
+    target: Cell<f64>,
+    velocity: Cell<f64>,
+    config: Cell<SpringConfig>,
+}
+
+impl Spring {
+    /// The longest time step of the simulation. Stiff springs become unstable with larger steps.
+    const STEP: f64 = 0.004;
+    /// Slow frames, like after the tab was in the background, are only simulated up to this long so the value does not jump.
+    const MAX_DELTA: f64 = 0.1;
+
+    /// Move the value for a frame, and return whether it is still moving.
+    fn step(&self, value: Signal<f64>, delta: Duration) -> bool {
+        let config = self.config.get();
+        let target = self.target.get();
+        let mut position = *value.peek();
+        let mut velocity = self.velocity.get();
+
+        let mut remaining = delta.as_secs_f64().min(Self::MAX_DELTA);
+        while remaining > 0.0 {
+            let dt = remaining.min(Self::STEP);
+            let force = -config.stiffness * (position - target) - config.damping * velocity;
+            velocity += force / config.mass * dt;
+            position += velocity * dt;
+            remaining -= dt;
+        }
+
+        let at_rest =
+            velocity.abs() < config.precision && (position - target).abs() < config.precision;
+        if at_rest {
+            position = target;
+            velocity = 0.0;
+        }
+        self.velocity.set(velocity);
+        if *value.peek() != position {
+            value.set(position);
+        }
+
+        !at_rest
+    }
+}
+
+/// Calls `on_frame` every frame while it returns true.
+struct Ticker {
+    provider: Rc<dyn AnimationFrameProvider>,
+    /// Frames are requested from outside of the virtual dom, so the runtime has to be set to run the callback.
+    runtime: Rc<Runtime>,
+    scope: ScopeId,
+    on_frame: RefCell<Box<dyn FnMut(AnimationFrame) -> bool>>,
+    running: Cell<bool>,
+    start: Cell<Option<f64>>,
+    last: Cell<Option<f64>>,
+}
+
+impl Ticker {
+    fn new(cx: &ScopeState, on_frame: Box<dyn FnMut(AnimationFrame) -> bool>) -> Rc<Self> {
+        let provider = cx
+            .consume_context::<Rc<dyn AnimationFrameProvider>>()
+            .expect("animation frame provider not provided");
+        Rc::new(Self {
+            provider,
+            runtime: Runtime::current().expect("hooks are called inside a virtual dom"),
+            scope: cx.scope_id(),
+            on_frame: RefCell::new(on_frame),
+            running: Cell::new(false),
+            start: Cell::new(None),
+            last: Cell::new(None),
+        })
+    }
+
+    /// Start calling `on_frame` if it is not running already.
+    fn start(self: &Rc<Self>) {
+        if self.running.replace(true) {
+            return;
+        }
+        self.start.set(None);
+        self.last.set(None);
+        self.request();
+    }
+
+    fn request(self: &Rc<Self>) {
+        // The frame only keeps a weak reference, so frames stop once the component is dropped
+        let ticker = Rc::downgrade(self);
+        self.provider.request_frame(Box::new(move |time| {
+            if let Some(ticker) = ticker.upgrade() {
+                ticker.tick(time);
+            }
+        }));
+    }
+
+    fn tick(self: &Rc<Self>, time: f64) {
+        let start = self.start.get().unwrap_or(time);
+        self.start.set(Some(start));
+        let last = self.last.replace(Some(time)).unwrap_or(time);
+        let millis = |ms: f64| Duration::from_secs_f64(ms.max(0.0) / 1000.0);
+        let frame = AnimationFrame {
+            elapsed: millis(time - start),
+            delta: millis(time - last),
+        };
+
+        let keep_running = RuntimeGuard::with(self.runtime.clone(), Some(self.scope), || {
+            (self.on_frame.borrow_mut())(frame)
+        });
+        if keep_running {
+            self.request();
+        } else {
+            self.running.set(false);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Track the battery level of the device.
+//!
+//! Renderers provide an `Rc<dyn BatteryProvider>` through the root scope's context, and components read it with [`use_battery`].
+
+use dioxus_core::ScopeState;
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::any::Any;
+use std::rc::Rc;
+
+/// A struct that implements BatteryProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_battery`] can track the battery on any platform.
+///
+/// It must be provided as `Rc<dyn BatteryProvider>`, because the concrete type has a different TypeId.
+pub trait BatteryProvider {
+    /// Call `on_change` with the status of the battery as soon as it is known, and again whenever the level or charging state changes. `None` means the platform does not report a battery. The callback is removed when the returned value is dropped.
+    fn subscribe(&self, on_change: Box<dyn FnMut(Option<BatteryStatus>)>) -> Box<dyn Any>;
+}
+
+/// The charge of the device's battery.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatteryStatus {
+    /// How full the battery is, from `0.0` to `1.0`.
+    pub level: f64,
+    /// Whether the battery is charging.
+    pub charging: bool,
+}
+
+/// Get the status of the device's battery. The signal is `None` until the battery is read, and stays `None` if the platform does not report a battery.
+///
+/// ```rust, ignore
+/// let battery = use_battery(cx);
+/// // Poll less often when the battery is running low
+/// let low_power = matches!(*battery.read(), Some(battery) if !battery.charging && battery.level < 0.2);
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide a [`BatteryProvider`].
+pub fn use_battery(cx: &ScopeState) -> ReadOnlySignal<Option<BatteryStatus>> {
+    let (status, _subscription) = cx.use_hook(|| {
+        let provider = cx
+            .consume_context::<Rc<dyn BatteryProvider>>()
+            .expect("battery provider not provided");
+        let status = Signal::new(None);
+        let subscription = provider.subscribe(Box::new(move |new| {
+            if *status.peek() != new {
+                status.set(new);
+            }
+        }));
+        (status, subscription)
+    });
+
+    ReadOnlySignal::new(*status)
+}
@@ -0,0 +1,162 @@
+//! A platform agnostic interface to the system clipboard.
+//!
+//! Renderers provide an `Rc<dyn ClipboardProvider>` through the root scope's context, and components access it with [`use_clipboard`].
+
+use async_trait::async_trait;
+use dioxus_core::ScopeState;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// A struct that implements ClipboardProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_clipboard`] can provide a platform agnostic interface for the system clipboard.
+///
+/// It must be provided as `Rc<dyn ClipboardProvider>`, because the concrete type has a different TypeId.
+#[async_trait(?Send)]
+pub trait ClipboardProvider {
+    /// Read the text on the clipboard.
+    async fn read_text(&self) -> Result<String, ClipboardError>;
+
+    /// Replace the contents of the clipboard with text.
+    async fn write_text(&self, text: String) -> Result<(), ClipboardError>;
+
+    /// Read the image on the clipboard. Platforms that can't read images return [`ClipboardError::Unsupported`].
+    async fn read_image(&self) -> Result<ClipboardImage, ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+
+    /// Replace the contents of the clipboard with an image. Platforms that can't write images return [`ClipboardError::Unsupported`].
+    async fn write_image(&self, _image: ClipboardImage) -> Result<(), ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+
+    /// Read the html on the clipboard. Platforms that can't read html return [`ClipboardError::Unsupported`].
+    async fn read_html(&self) -> Result<String, ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+
+    /// Replace the contents of the clipboard with html, and `alt_text` for apps that only paste text. Platforms that can't write html return [`ClipboardError::Unsupported`].
+    async fn write_html(
+        &self,
+        _html: String,
+        _alt_text: Option<String>,
+    ) -> Result<(), ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+}
+
+/// An image on the clipboard, stored as RGBA pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    /// The width of the image in pixels.
+    pub width: usize,
+    /// The height of the image in pixels.
+    pub height: usize,
+    /// The pixels of the image, row by row, with four bytes (red, green, blue, alpha) per pixel.
+    pub rgba: Vec<u8>,
+}
+
+/// Represents an error when reading or writing the clipboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// The platform does not support this operation, like reading images on the web.
+    Unsupported,
+    /// The clipboard does not contain the requested kind of data.
+    Empty,
+    /// The user or platform refused access to the clipboard. On the web this happens when the page is not focused or the user denied the permission.
+    Denied(String),
+    /// Any other error reported by the platform.
+    Other(String),
+}
+
+impl Display for ClipboardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClipboardError::Unsupported => write!(f, "this clipboard operation is not supported"),
+            ClipboardError::Empty => write!(f, "the clipboard does not contain this kind of data"),
+            ClipboardError::Denied(reason) => write!(f, "clipboard access was denied: {reason}"),
+            ClipboardError::Other(reason) => write!(f, "clipboard error: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
+
+/// Get a handle to the system clipboard.
+///
+/// ```rust, ignore
+/// let clipboard = use_clipboard(cx);
+///
+/// render! {
+///     button {
+///         onclick: move |_| {
+///             to_owned![clipboard];
+///             async move {
+///                 if let Err(err) = clipboard.write_text("Hello!").await {
+///                     log::error!("Failed to copy: {err}");
+///                 }
+///             }
+///         },
+///         "Copy"
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide a [`ClipboardProvider`].
+#[must_use]
+pub fn use_clipboard(cx: &ScopeState) -> &UseClipboard {
+    cx.use_hook(|| {
+        let provider = cx
+            .consume_context::<Rc<dyn ClipboardProvider>>()
+            .expect("clipboard provider not provided");
+        UseClipboard { provider }
+    })
+}
+
+/// A handle to the system clipboard returned by [`use_clipboard`].
+#[derive(Clone)]
+pub struct UseClipboard {
+    provider: Rc<dyn ClipboardProvider>,
+}
+
+impl UseClipboard {
+    /// Create a handle to the clipboard of a provider. Renderers use this to hand out the clipboard outside of components.
+    pub fn new(provider: Rc<dyn ClipboardProvider>) -> Self {
+        Self { provider }
+    }
+
+    /// Read the text on the clipboard.
+    pub async fn read_text(&self) -> Result<String, ClipboardError> {
+        self.provider.read_text().await
+    }
+
+    /// Replace the contents of the clipboard with text.
+    pub async fn write_text(&self, text: impl Into<String>) -> Result<(), ClipboardError> {
+        self.provider.write_text(text.into()).await
+    }
+
+    /// Read the image on the clipboard. This is only supported on desktop.
+    pub async fn read_image(&self) -> Result<ClipboardImage, ClipboardError> {
+        self.provider.read_image().await
+    }
+
+    /// Replace the contents of the clipboard with an image. This is only supported on desktop.
+    pub async fn write_image(&self, image: ClipboardImage) -> Result<(), ClipboardError> {
+        self.provider.write_image(image).await
+    }
+
+    /// Read the html on the clipboard. This is only supported on desktop.
+    pub async fn read_html(&self) -> Result<String, ClipboardError> {
+        self.provider.read_html().await
+    }
+
+    /// Replace the contents of the clipboard with html, and `alt_text` for apps that only paste text. This is only supported on desktop.
+    pub async fn write_html(
+        &self,
+        html: impl Into<String>,
+        alt_text: Option<String>,
+    ) -> Result<(), ClipboardError> {
+        self.provider.write_html(html.into(), alt_text).await
+    }
+}
@@ -0,0 +1,152 @@
+//! Track the position and size of a mounted element.
+
+use crate::geometry::euclid::Rect;
+use crate::window_size::WindowSizeProvider;
+use crate::MountedData;
+use dioxus_core::{
+    prelude::{spawn_at, Runtime, RuntimeGuard},
+    ScopeId, ScopeState,
+};
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// The element that is measured, and the values that stop observing it when dropped.
+type Observed = Option<(Rc<MountedData>, Vec<Box<dyn Any>>)>;
+
+/// Get the bounding rectangle of a mounted element relative to the viewport as a signal, like [`MountedData::get_client_rect`]. The element is measured again whenever it is resized, the window is resized, or the page or one of the element's ancestors scrolls.
+///
+/// Pass the element you got from its `onmounted` event. The signal is `None` until the element is mounted and first measured.
+///
+/// ```rust, ignore
+/// let anchor = use_state(cx, || None);
+/// let bounds = use_element_bounds(cx, anchor.get().clone());
+///
+/// render! {
+///     button { onmounted: move |event| anchor.set(Some(event.inner().clone())), "Hover me" }
+///     if let Some(rect) = *bounds.read() {
+///         rsx! {
+///             div {
+///                 position: "fixed",
+///                 left: "{rect.min_x()}px",
+///                 top: "{rect.max_y()}px",
+///                 "Tooltip"
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_element_bounds(
+    cx: &ScopeState,
+    element: Option<Rc<MountedData>>,
+) -> ReadOnlySignal<Option<Rect<f64, f64>>> {
+    let (bounds, observed, _window) = cx.use_hook(|| {
+        let bounds = Bounds {
+            rect: Signal::new(None),
+            state: Rc::new(BoundsState {
+                scope: cx.scope_id(),
+                runtime: Runtime::current().expect("hooks are called inside a virtual dom"),
+                element: RefCell::new(None),
+                measuring: Cell::new(false),
+                dirty: Cell::new(false),
+            }),
+        };
+
+        // Resizing the window can move the element without resizing it
+        let window = cx
+            .consume_context::<Rc<dyn WindowSizeProvider>>()
+            .map(|provider| {
+                let bounds = bounds.clone();
+                provider.subscribe(Box::new(move |_| bounds.measure()))
+            });
+
+        let observed: RefCell<Observed> = RefCell::new(None);
+        (bounds, observed, window)
+    });
+
+    let changed = match (&*observed.borrow(), &element) {
+        (Some((old, _)), Some(new)) => !Rc::ptr_eq(old, new),
+        (None, None) => false,
+        _ => true,
+    };
+    if changed {
+        // Stop observing the old element before observing the new one
+        observed.borrow_mut().take();
+        *bounds.state.element.borrow_mut() = element.clone();
+        if bounds.rect.peek().is_some() {
+            bounds.rect.set(None);
+        }
+        if let Some(element) = element {
+            let mut observers = Vec::new();
+            let on_resize = bounds.clone();
+            if let Ok(observer) = element.observe_resize(move |_| on_resize.measure()) {
+                observers.push(observer);
+            }
+            let on_scroll = bounds.clone();
+            if let Ok(observer) = element.observe_scroll(move || on_scroll.measure()) {
+                observers.push(observer);
+            }
+            *observed.borrow_mut() = Some((element, observers));
+            bounds.measure();
+        }
+    }
+
+    ReadOnlySignal::new(bounds.rect)
+}
+
+struct BoundsState {
+    scope: ScopeId,
+    /// The observers call back from outside of the virtual dom, so the runtime has to be set to spawn the measurement.
+    runtime: Rc<Runtime>,
+    element: RefCell<Option<Rc<MountedData>>>,
+    measuring: Cell<bool>,
+    /// Set when the element should be measured again after the current measurement, so bursts of scroll events only measure it once at a time.
+    dirty: Cell<bool>,
+}
+
+#[derive(Clone)]
+struct Bounds {
+    rect: Signal<Option<Rect<f64, f64>>>,
+    state: Rc<BoundsState>,
+}
+
+impl Bounds {
+    fn measure(&self) {
+        if self.state.measuring.replace(true) {
+            self.state.dirty.set(true);
+            return;
+        }
+        let element = match &*self.state.element.borrow() {
+            Some(element) => element.clone(),
+            None => {
+                self.state.measuring.set(false);
+                return;
+            }
+        };
+
+        let bounds = self.clone();
+        let measure = async move {
+            let result = element.get_client_rect().await;
+            bounds.state.measuring.set(false);
+
+            // Ignore the result if the element was replaced while it was measured
+            let current = match &*bounds.state.element.borrow() {
+                Some(current) => Rc::ptr_eq(current, &element),
+                None => false,
+            };
+            if let (true, Ok(rect)) = (current, result) {
+                if *bounds.rect.peek() != Some(rect) {
+                    bounds.rect.set(Some(rect));
+                }
+            }
+
+            if bounds.state.dirty.replace(false) {
+                bounds.measure();
+            }
+        };
+        RuntimeGuard::with(self.state.runtime.clone(), None, || {
+            spawn_at(measure, self.state.scope)
+        });
+    }
+}
@@ -1,7 +1,13 @@
+use crate::file_data::{FileEngine, HasFileData};
 use dioxus_core::Event;
 
 pub type ClipboardEvent = Event<ClipboardData>;
 
+/// The contents of the clipboard during a copy, cut or paste event.
+///
+/// Paste events carry the data that is being pasted: plain text, HTML, and any files or images. Small text payloads
+/// are available right away, while the bytes of files are read lazily through the [`FileEngine`] returned by
+/// [`HasFileData::files`].
 pub struct ClipboardData {
     inner: Box<dyn HasClipboardData>,
 }
@@ -14,13 +20,17 @@ impl<E: HasClipboardData> From<E> for ClipboardData {
 
 impl std::fmt::Debug for ClipboardData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ClipboardData").finish()
+        f.debug_struct("ClipboardData")
+            .field("types", &self.types())
+            .field("text", &self.text())
+            .field("html", &self.html())
+            .finish()
     }
 }
 
 impl PartialEq for ClipboardData {
-    fn eq(&self, _other: &Self) -> bool {
-        true
+    fn eq(&self, other: &Self) -> bool {
+        self.types() == other.types() && self.text() == other.text() && self.html() == other.html()
     }
 }
 
@@ -36,27 +46,121 @@ impl ClipboardData {
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
         self.inner.as_ref().as_any().downcast_ref::<T>()
     }
+
+    /// The MIME types of the data on the clipboard, like `text/plain`, `text/html` or `Files`
+    pub fn types(&self) -> Vec<String> {
+        self.inner.types()
+    }
+
+    /// The plain text on the clipboard, if there is any
+    pub fn text(&self) -> Option<String> {
+        self.inner.text()
+    }
+
+    /// The HTML on the clipboard, if there is any
+    pub fn html(&self) -> Option<String> {
+        self.inner.html()
+    }
+
+    /// Read the first image on the clipboard, like a pasted screenshot.
+    ///
+    /// Returns the name of the image file and its bytes.
+    pub async fn image(&self) -> Option<(String, Vec<u8>)> {
+        let files = self.files()?;
+        let name = files.files().into_iter().find(|name| is_image(name))?;
+        let bytes = files.read_file(&name).await?;
+        Some((name, bytes))
+    }
+}
+
+impl HasFileData for ClipboardData {
+    fn files(&self) -> Option<std::sync::Arc<dyn FileEngine>> {
+        self.inner.files()
+    }
+}
+
+fn is_image(name: &str) -> bool {
+    const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "avif"];
+
+    name.rsplit_once('.').map_or(false, |(_, extension)| {
+        IMAGE_EXTENSIONS
+            .iter()
+            .any(|image| extension.eq_ignore_ascii_case(image))
+    })
 }
 
 #[cfg(feature = "serialize")]
 /// A serialized version of ClipboardData
-#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone)]
-pub struct SerializedClipboardData {}
+#[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct SerializedClipboardData {
+    #[serde(default)]
+    types: Vec<String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    html: Option<String>,
+    #[serde(default)]
+    files: Option<crate::file_data::SerializedFileEngine>,
+}
+
+#[cfg(feature = "serialize")]
+impl SerializedClipboardData {
+    /// Create a new SerializedClipboardData
+    pub fn new(
+        types: Vec<String>,
+        text: Option<String>,
+        html: Option<String>,
+        files: Option<crate::file_data::SerializedFileEngine>,
+    ) -> Self {
+        Self {
+            types,
+            text,
+            html,
+            files,
+        }
+    }
+}
 
 #[cfg(feature = "serialize")]
 impl From<&ClipboardData> for SerializedClipboardData {
-    fn from(_: &ClipboardData) -> Self {
-        Self {}
+    fn from(data: &ClipboardData) -> Self {
+        Self {
+            types: data.types(),
+            text: data.text(),
+            html: data.html(),
+            files: None,
+        }
     }
 }
 
 #[cfg(feature = "serialize")]
 impl HasClipboardData for SerializedClipboardData {
+    fn types(&self) -> Vec<String> {
+        self.types.clone()
+    }
+
+    fn text(&self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn html(&self) -> Option<String> {
+        self.html.clone()
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
 }
 
+#[cfg(feature = "serialize")]
+impl HasFileData for SerializedClipboardData {
+    fn files(&self) -> Option<std::sync::Arc<dyn FileEngine>> {
+        self.files
+            .as_ref()
+            .map(|files| std::sync::Arc::new(files.clone()) as _)
+    }
+}
+
 #[cfg(feature = "serialize")]
 impl serde::Serialize for ClipboardData {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -74,7 +178,22 @@ impl<'de> serde::Deserialize<'de> for ClipboardData {
     }
 }
 
-pub trait HasClipboardData: std::any::Any {
+pub trait HasClipboardData: HasFileData + std::any::Any {
+    /// The MIME types of the data on the clipboard
+    fn types(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// The plain text on the clipboard
+    fn text(&self) -> Option<String> {
+        None
+    }
+
+    /// The HTML on the clipboard
+    fn html(&self) -> Option<String> {
+        None
+    }
+
     /// return self as Any
     fn as_any(&self) -> &dyn std::any::Any;
 }
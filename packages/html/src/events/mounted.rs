@@ -34,6 +34,44 @@ pub trait RenderedElementBacking: std::any::Any {
     fn set_focus(&self, _focus: bool) -> Pin<Box<dyn Future<Output = MountedResult<()>>>> {
         Box::pin(async { Err(MountedError::NotSupported) })
     }
+
+    /// Call `on_resize` with the content rectangle of the element whenever its size changes. The observer is stopped when the returned value is dropped.
+    #[allow(clippy::type_complexity)]
+    fn observe_resize(
+        &self,
+        _on_resize: Box<dyn FnMut(Rect<f64, f64>)>,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        Err(MountedError::NotSupported)
+    }
+
+    /// Call `on_change` with `true` when the element starts being visible in the viewport and with `false` when it stops. The observer is stopped when the returned value is dropped.
+    fn observe_intersection(
+        &self,
+        _on_change: Box<dyn FnMut(bool)>,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        Err(MountedError::NotSupported)
+    }
+
+    /// Call `on_change` with whether the element or one of its descendants has focus, right away and whenever that changes. The observer is stopped when the returned value is dropped.
+    fn observe_focus(
+        &self,
+        _on_change: Box<dyn FnMut(bool)>,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        Err(MountedError::NotSupported)
+    }
+
+    /// Keep keyboard focus inside the element, so tabbing past its last focusable descendant wraps around to the first one. The trap is released and focus returns to the element that had it before when the returned value is dropped.
+    fn trap_focus(&self) -> MountedResult<Box<dyn std::any::Any>> {
+        Err(MountedError::NotSupported)
+    }
+
+    /// Call `on_scroll` whenever the page or one of the element's ancestors scrolls, which moves the element. The observer is stopped when the returned value is dropped.
+    fn observe_scroll(
+        &self,
+        _on_scroll: Box<dyn FnMut()>,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        Err(MountedError::NotSupported)
+    }
 }
 
 impl RenderedElementBacking for () {
@@ -92,6 +130,51 @@ impl MountedData {
         self.inner.set_focus(focus)
     }
 
+    /// Call `on_resize` with the content rectangle of the element whenever its size changes. The observer is stopped when the returned value is dropped.
+    ///
+    /// Use [`use_resize_observer`](crate::resize_observer::use_resize_observer) to get the size as a signal instead.
+    pub fn observe_resize(
+        &self,
+        on_resize: impl FnMut(Rect<f64, f64>) + 'static,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        self.inner.observe_resize(Box::new(on_resize))
+    }
+
+    /// Call `on_change` with `true` when the element starts being visible in the viewport and with `false` when it stops. The observer is stopped when the returned value is dropped.
+    pub fn observe_intersection(
+        &self,
+        on_change: impl FnMut(bool) + 'static,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        self.inner.observe_intersection(Box::new(on_change))
+    }
+
+    /// Call `on_change` with whether the element or one of its descendants has focus, right away and whenever that changes. The observer is stopped when the returned value is dropped.
+    ///
+    /// Use [`use_focus`](crate::focus::use_focus) to get the focus as a signal instead.
+    pub fn observe_focus(
+        &self,
+        on_change: impl FnMut(bool) + 'static,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        self.inner.observe_focus(Box::new(on_change))
+    }
+
+    /// Keep keyboard focus inside the element, so tabbing past its last focusable descendant wraps around to the first one. The trap is released and focus returns to the element that had it before when the returned value is dropped.
+    ///
+    /// Use [`use_focus_trap`](crate::focus::use_focus_trap) to trap focus while a component is rendered instead.
+    pub fn trap_focus(&self) -> MountedResult<Box<dyn std::any::Any>> {
+        self.inner.trap_focus()
+    }
+
+    /// Call `on_scroll` whenever the page or one of the element's ancestors scrolls, which moves the element. The observer is stopped when the returned value is dropped.
+    ///
+    /// Use [`use_element_bounds`](crate::element_bounds::use_element_bounds) to get the position of the element as a signal instead.
+    pub fn observe_scroll(
+        &self,
+        on_scroll: impl FnMut() + 'static,
+    ) -> MountedResult<Box<dyn std::any::Any>> {
+        self.inner.observe_scroll(Box::new(on_scroll))
+    }
+
     /// Downcast this event to a concrete event type
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
         self.inner.as_any().downcast_ref::<T>()
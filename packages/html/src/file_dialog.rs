@@ -0,0 +1,183 @@
+//! A platform agnostic interface to the file open and save dialogs.
+//!
+//! Renderers provide an `Rc<dyn FileDialogProvider>` through the root scope's context, and components access it with [`use_file_dialog`].
+
+use crate::FileEngine;
+use async_trait::async_trait;
+use dioxus_core::ScopeState;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A struct that implements FileDialogProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_file_dialog`] can provide a platform agnostic interface for file dialogs.
+///
+/// It must be provided as `Rc<dyn FileDialogProvider>`, because the concrete type has a different TypeId.
+#[async_trait(?Send)]
+pub trait FileDialogProvider {
+    /// Let the user pick one file, or several if `multiple` is true. Returns `None` if the dialog was cancelled.
+    async fn pick_files(
+        &self,
+        options: FileDialogOptions,
+        multiple: bool,
+    ) -> Option<Arc<dyn FileEngine>>;
+
+    /// Let the user pick a folder. Returns the files in it, or `None` if the dialog was cancelled.
+    async fn pick_folder(&self, options: FileDialogOptions) -> Option<Arc<dyn FileEngine>>;
+
+    /// Let the user pick where to save `contents` and write them there. Returns the name of the saved file, or `None` if the dialog was cancelled.
+    async fn save_file(
+        &self,
+        options: FileDialogOptions,
+        contents: Vec<u8>,
+    ) -> Result<Option<String>, FileDialogError>;
+}
+
+/// The options of a file dialog. Options a platform doesn't support are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileDialogOptions {
+    /// The title of the dialog. Only used on desktop.
+    pub title: Option<String>,
+    /// The kinds of files the user can pick. If empty, any file can be picked.
+    pub filters: Vec<FileFilter>,
+    /// The folder the dialog opens in. Only used on desktop.
+    pub directory: Option<PathBuf>,
+    /// The name the save dialog suggests.
+    pub file_name: Option<String>,
+}
+
+impl FileDialogOptions {
+    /// Create options that let the user pick any file.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the title of the dialog.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Only show files with one of these extensions, like `["png", "jpg"]`. The name describes them in the dialog.
+    pub fn filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileFilter {
+            name: name.into(),
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+        });
+        self
+    }
+
+    /// Set the folder the dialog opens in.
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Set the name the save dialog suggests.
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+}
+
+/// A kind of file the user can pick in a file dialog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The description of the files, like "Images".
+    pub name: String,
+    /// The extensions of the files without a leading dot, like `png`.
+    pub extensions: Vec<String>,
+}
+
+/// Represents an error when saving a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDialogError {
+    /// The platform does not have file dialogs.
+    Unsupported,
+    /// The file could not be written.
+    Write(String),
+}
+
+impl Display for FileDialogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileDialogError::Unsupported => write!(f, "file dialogs are not supported"),
+            FileDialogError::Write(reason) => write!(f, "failed to save the file: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for FileDialogError {}
+
+/// Get a handle to the file open and save dialogs.
+///
+/// The picked files are returned as a [`FileEngine`]. On desktop its file names are full paths.
+///
+/// ```rust, ignore
+/// let dialog = use_file_dialog(cx);
+///
+/// render! {
+///     button {
+///         onclick: move |_| {
+///             to_owned![dialog];
+///             async move {
+///                 let options = FileDialogOptions::new().filter("Text", &["txt", "md"]);
+///                 if let Some(files) = dialog.pick_file(options).await {
+///                     for name in files.files() {
+///                         let text = files.read_file_to_string(&name).await;
+///                     }
+///                 }
+///             }
+///         },
+///         "Open"
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide a [`FileDialogProvider`].
+#[must_use]
+pub fn use_file_dialog(cx: &ScopeState) -> &UseFileDialog {
+    cx.use_hook(|| {
+        let provider = cx
+            .consume_context::<Rc<dyn FileDialogProvider>>()
+            .expect("file dialog provider not provided");
+        UseFileDialog { provider }
+    })
+}
+
+/// A handle to the file dialogs returned by [`use_file_dialog`].
+#[derive(Clone)]
+pub struct UseFileDialog {
+    provider: Rc<dyn FileDialogProvider>,
+}
+
+impl UseFileDialog {
+    /// Let the user pick a file. Returns `None` if the dialog was cancelled.
+    pub async fn pick_file(&self, options: FileDialogOptions) -> Option<Arc<dyn FileEngine>> {
+        self.provider.pick_files(options, false).await
+    }
+
+    /// Let the user pick any number of files. Returns `None` if the dialog was cancelled.
+    pub async fn pick_files(&self, options: FileDialogOptions) -> Option<Arc<dyn FileEngine>> {
+        self.provider.pick_files(options, true).await
+    }
+
+    /// Let the user pick a folder. Returns the files in it, including the files in its subfolders, or `None` if the dialog was cancelled.
+    pub async fn pick_folder(&self, options: FileDialogOptions) -> Option<Arc<dyn FileEngine>> {
+        self.provider.pick_folder(options).await
+    }
+
+    /// Let the user pick where to save `contents` and write them there. Returns the name of the saved file, or `None` if the dialog was cancelled.
+    ///
+    /// Browsers without the File System Access API download the file instead of showing a dialog.
+    pub async fn save_file(
+        &self,
+        options: FileDialogOptions,
+        contents: impl Into<Vec<u8>>,
+    ) -> Result<Option<String>, FileDialogError> {
+        self.provider.save_file(options, contents.into()).await
+    }
+}
@@ -0,0 +1,178 @@
+//! Move and track keyboard focus without reaching for eval.
+
+use crate::{MountedData, MountedEvent};
+use dioxus_core::{
+    prelude::{spawn_at, Runtime, RuntimeGuard},
+    ScopeId, ScopeState,
+};
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// The element that is trapping focus, and the value that releases the trap when dropped.
+type Trapped = Option<(Rc<MountedData>, Option<Box<dyn Any>>)>;
+
+/// Get a handle to move focus to an element and track whether it has focus.
+///
+/// Attach the handle to an element by calling [`UseFocus::onmounted`] from the element's `onmounted` event. Calling [`UseFocus::focus`] before the element is mounted focuses it as soon as it is.
+///
+/// ```rust, ignore
+/// let search = use_focus(cx);
+/// use_hotkeys(cx, "/", move |_| search.focus());
+///
+/// render! {
+///     input {
+///         class: if *search.is_focused().read() { "active" } else { "" },
+///         onmounted: move |event| search.onmounted(event),
+///     }
+/// }
+/// ```
+pub fn use_focus(cx: &ScopeState) -> &UseFocus {
+    cx.use_hook(|| UseFocus {
+        focused: Signal::new(false),
+        state: Rc::new(FocusState {
+            scope: cx.scope_id(),
+            runtime: Runtime::current().expect("hooks are called inside a virtual dom"),
+            element: RefCell::new(None),
+            observer: RefCell::new(None),
+            pending: Cell::new(None),
+        }),
+    })
+}
+
+struct FocusState {
+    scope: ScopeId,
+    /// Focus may be moved from outside of the virtual dom, like in a hotkey handler, so the runtime has to be set to spawn the request.
+    runtime: Rc<Runtime>,
+    element: RefCell<Option<Rc<MountedData>>>,
+    /// Stops observing the focus of the element when dropped. `None` if the renderer can't observe focus.
+    observer: RefCell<Option<Box<dyn Any>>>,
+    /// Whether to focus or blur the element once it is mounted.
+    pending: Cell<Option<bool>>,
+}
+
+/// A handle to move focus to an element, returned by [`use_focus`].
+#[derive(Clone)]
+pub struct UseFocus {
+    focused: Signal<bool>,
+    state: Rc<FocusState>,
+}
+
+impl UseFocus {
+    /// Attach the handle to the element of a mounted event.
+    pub fn onmounted(&self, event: MountedEvent) {
+        self.set_element(event.inner().clone());
+    }
+
+    /// Attach the handle to an element, and start tracking whether it has focus.
+    pub fn set_element(&self, element: Rc<MountedData>) {
+        if let Some(old) = &*self.state.element.borrow() {
+            if Rc::ptr_eq(old, &element) {
+                return;
+            }
+        }
+
+        // Stop observing the old element before observing the new one
+        self.state.observer.borrow_mut().take();
+        let focused = self.focused;
+        let observer = element
+            .observe_focus(move |new| {
+                if *focused.peek() != new {
+                    focused.set(new);
+                }
+            })
+            .ok();
+        *self.state.observer.borrow_mut() = observer;
+        *self.state.element.borrow_mut() = Some(element);
+
+        if let Some(focus) = self.state.pending.take() {
+            self.set_focus(focus);
+        }
+    }
+
+    /// The element the handle is attached to.
+    pub fn element(&self) -> Option<Rc<MountedData>> {
+        self.state.element.borrow().clone()
+    }
+
+    /// Whether the element or one of its descendants has focus.
+    ///
+    /// If the renderer can't observe focus, this only follows calls to [`UseFocus::focus`] and [`UseFocus::blur`].
+    pub fn is_focused(&self) -> ReadOnlySignal<bool> {
+        ReadOnlySignal::new(self.focused)
+    }
+
+    /// Move focus to the element.
+    pub fn focus(&self) {
+        self.set_focus(true);
+    }
+
+    /// Remove focus from the element.
+    pub fn blur(&self) {
+        self.set_focus(false);
+    }
+
+    fn set_focus(&self, focus: bool) {
+        let element = match &*self.state.element.borrow() {
+            Some(element) => element.clone(),
+            None => {
+                self.state.pending.set(Some(focus));
+                return;
+            }
+        };
+
+        let observed = self.state.observer.borrow().is_some();
+        let focused = self.focused;
+        let request = async move {
+            let result = element.set_focus(focus).await;
+            // Without an observer, trust that the request worked
+            if !observed && result.is_ok() && *focused.peek() != focus {
+                focused.set(focus);
+            }
+        };
+        RuntimeGuard::with(self.state.runtime.clone(), None, || {
+            spawn_at(request, self.state.scope)
+        });
+    }
+}
+
+/// Keep keyboard focus inside `container` while `active` is true, like in a modal dialog. Tabbing past the last focusable element in the container wraps around to the first one.
+///
+/// When the trap becomes active, focus moves to the first focusable element in the container. When it is released, focus returns to the element that had it before.
+///
+/// ```rust, ignore
+/// let dialog = use_state(cx, || None);
+/// use_focus_trap(cx, dialog.get().clone(), *open.get());
+///
+/// render! {
+///     if *open.get() {
+///         rsx! {
+///             div {
+///                 role: "dialog",
+///                 onmounted: move |event| dialog.set(Some(event.inner().clone())),
+///                 input {}
+///                 button { onclick: move |_| open.set(false), "Close" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_focus_trap(cx: &ScopeState, container: Option<Rc<MountedData>>, active: bool) {
+    let trapped: &RefCell<Trapped> = cx.use_hook(|| RefCell::new(None));
+    let container = if active { container } else { None };
+
+    let changed = match (&*trapped.borrow(), &container) {
+        (Some((old, _)), Some(new)) => !Rc::ptr_eq(old, new),
+        (None, None) => false,
+        _ => true,
+    };
+    if changed {
+        // Release the old trap first, so the new one remembers the right element to return focus to
+        trapped.borrow_mut().take();
+        if let Some(container) = container {
+            let trap = container.trap_focus().ok();
+            *trapped.borrow_mut() = Some((container, trap));
+        }
+    }
+}
@@ -137,6 +137,14 @@ trait_methods! {
     /// <https://developer.mozilla.org/en-US/docs/Web/API/Event/preventDefault>
     prevent_default: "dioxus-prevent-default";
 
+    /// Move focus back to this element if it loses focus because it was moved or re-created while the DOM was
+    /// updated, for example when a keyed list is reordered. The caret position and selection of text inputs are
+    /// restored as well.
+    ///
+    /// The value identifies the element across re-creations, so it must be unique on the page. The key of the element
+    /// is usually a good choice.
+    restore_focus: "dioxus-restore-focus";
+
 
     /// <https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/accesskey>
     accesskey: "accesskey";
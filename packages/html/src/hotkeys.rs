@@ -0,0 +1,503 @@
+//! Keyboard shortcuts that are active while the app's window is focused.
+//!
+//! Renderers provide an `Rc<dyn HotkeyProvider>` through the root scope's context, and components register shortcuts with [`use_hotkeys`].
+
+use crate::input_data::keyboard_types::Modifiers;
+use crate::point_interaction::ModifiersInteraction;
+use crate::KeyboardData;
+use dioxus_core::{
+    prelude::{Runtime, RuntimeGuard},
+    ScopeId, ScopeState,
+};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+use std::rc::{Rc, Weak};
+use std::str::FromStr;
+
+/// A struct that implements HotkeyProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_hotkeys`] can listen to key presses on any platform.
+///
+/// It must be provided as `Rc<dyn HotkeyProvider>`, because the concrete type has a different TypeId.
+pub trait HotkeyProvider {
+    /// Call `on_keydown` for every keydown event in the window, along with whether the event target is an editable element like an input, textarea or contenteditable element.
+    ///
+    /// `on_keydown` returns true if a hotkey handled the event. Renderers that can should prevent the default action of handled events. The callback is removed when the returned value is dropped.
+    fn subscribe(&self, on_keydown: Box<dyn FnMut(KeyboardData, bool) -> bool>) -> Box<dyn Any>;
+}
+
+/// One or more key combinations, parsed from strings like `"ctrl+k"` or `"ctrl+k, meta+k"`.
+///
+/// Each combination is a list of modifiers and a key separated by `+`. The modifiers are `ctrl` (or `control`), `shift`, `alt` (or `option`) and `meta` (or `cmd`, `command`, `super`). `cmdorctrl` is `meta` on macOS and `ctrl` everywhere else. The key is either a character like `k` or `/`, or the name of a key like `escape`, `enter`, `arrowup`, `space` or `f1`. Names are case insensitive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hotkeys {
+    combinations: Vec<KeyCombination>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct KeyCombination {
+    modifiers: Modifiers,
+    /// The lowercase value of [`crate::input_data::keyboard_types::Key`] this combination matches.
+    key: String,
+}
+
+impl Hotkeys {
+    /// Check if a key press matches any of the combinations.
+    pub fn matches(&self, event: &KeyboardData) -> bool {
+        let key = event.key().to_string().to_lowercase();
+        let code = event.code().to_string();
+        let modifiers = event.modifiers()
+            & (Modifiers::CONTROL | Modifiers::SHIFT | Modifiers::ALT | Modifiers::META);
+        self.combinations
+            .iter()
+            .any(|combination| combination.matches(&key, &code, modifiers))
+    }
+}
+
+impl KeyCombination {
+    fn matches(&self, key: &str, code: &str, modifiers: Modifiers) -> bool {
+        if key == self.key {
+            // Symbols like `?` need shift on most layouts, so only letters and named keys check it
+            let is_symbol = self.key.chars().count() == 1
+                && !self.key.chars().all(|c| c.is_alphanumeric() || c == ' ');
+            if is_symbol {
+                return (modifiers - Modifiers::SHIFT) == (self.modifiers - Modifiers::SHIFT);
+            }
+            return modifiers == self.modifiers;
+        }
+
+        // Alt and shift change the character a key types, so fall back to the physical key for letters and digits
+        let mut chars = self.key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii_lowercase() => {
+                modifiers == self.modifiers && code == format!("Key{}", c.to_ascii_uppercase())
+            }
+            (Some(c), None) if c.is_ascii_digit() => {
+                modifiers == self.modifiers && code == format!("Digit{c}")
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Hotkeys {
+    type Err = HotkeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let combinations = s
+            .split(',')
+            .map(|combination| combination.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { combinations })
+    }
+}
+
+impl FromStr for KeyCombination {
+    type Err = HotkeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // A trailing `+` is the plus key, like in `ctrl++`
+        let (modifiers, key) = if s == "+" {
+            ("", "+")
+        } else if let Some(modifiers) = s.strip_suffix("++") {
+            (modifiers, "+")
+        } else {
+            s.rsplit_once('+').unwrap_or(("", s))
+        };
+
+        let mut parsed = Modifiers::empty();
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            parsed |= match modifier.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => Modifiers::CONTROL,
+                "shift" => Modifiers::SHIFT,
+                "alt" | "option" => Modifiers::ALT,
+                "meta" | "cmd" | "command" | "super" => Modifiers::META,
+                "cmdorctrl" | "commandorcontrol" if cfg!(target_os = "macos") => Modifiers::META,
+                "cmdorctrl" | "commandorcontrol" => Modifiers::CONTROL,
+                _ => return Err(HotkeyParseError::UnknownModifier(modifier.trim().into())),
+            };
+        }
+
+        let key = key.trim().to_lowercase();
+        let key = match key.as_str() {
+            "" => return Err(HotkeyParseError::MissingKey(s.into())),
+            "space" => " ".into(),
+            "esc" => "escape".into(),
+            "up" | "down" | "left" | "right" => format!("arrow{key}"),
+            "del" => "delete".into(),
+            "return" => "enter".into(),
+            _ => key,
+        };
+
+        Ok(Self {
+            modifiers: parsed,
+            key,
+        })
+    }
+}
+
+/// An error parsing [`Hotkeys`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HotkeyParseError {
+    /// A combination has modifiers but no key, like `ctrl+`.
+    MissingKey(String),
+    /// A modifier is not one of `ctrl`, `shift`, `alt`, `meta` or `cmdorctrl`.
+    UnknownModifier(String),
+}
+
+impl Display for HotkeyParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HotkeyParseError::MissingKey(combination) => {
+                write!(f, "the hotkey `{combination}` has no key")
+            }
+            HotkeyParseError::UnknownModifier(modifier) => {
+                write!(f, "unknown modifier `{modifier}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HotkeyParseError {}
+
+/// A part of the page that hotkeys can be scoped to, created with [`use_hotkey_scope`].
+///
+/// Call [`HotkeyScope::focus`] and [`HotkeyScope::blur`] from the `onfocusin` and `onfocusout` events of the element that contains the part of the page. Hotkeys scoped to it only run while an element inside it has focus.
+#[derive(Clone, Default)]
+pub struct HotkeyScope {
+    focused: Rc<Cell<bool>>,
+}
+
+impl HotkeyScope {
+    /// Mark the scope as focused.
+    pub fn focus(&self) {
+        self.focused.set(true);
+    }
+
+    /// Mark the scope as not focused.
+    pub fn blur(&self) {
+        self.focused.set(false);
+    }
+
+    /// Whether an element inside the scope has focus.
+    pub fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
+}
+
+/// Create a [`HotkeyScope`] to limit hotkeys to a focused part of the page.
+///
+/// ```rust, ignore
+/// let editor = use_hotkey_scope(cx);
+/// use_hotkeys_with(cx, "ctrl+s", HotkeyOptions::new().scope(editor), |_| save());
+///
+/// render! {
+///     div {
+///         onfocusin: move |_| editor.focus(),
+///         onfocusout: move |_| editor.blur(),
+///         textarea {}
+///     }
+/// }
+/// ```
+pub fn use_hotkey_scope(cx: &ScopeState) -> &HotkeyScope {
+    cx.use_hook(HotkeyScope::default)
+}
+
+/// Options for a hotkey registered with [`use_hotkeys_with`].
+#[derive(Clone, Default)]
+pub struct HotkeyOptions {
+    priority: i32,
+    scope: Option<HotkeyScope>,
+    in_inputs: bool,
+}
+
+impl HotkeyOptions {
+    /// Create the default options: priority 0, not scoped, and ignored while typing in inputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// When several hotkeys match a key press, only the one with the highest priority runs. Defaults to 0.
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Only run the hotkey while an element inside `scope` has focus.
+    pub fn scope(mut self, scope: &HotkeyScope) -> Self {
+        self.scope = Some(scope.clone());
+        self
+    }
+
+    /// Also run the hotkey while typing in an input, textarea or contenteditable element. Defaults to false.
+    pub fn in_inputs(mut self, in_inputs: bool) -> Self {
+        self.in_inputs = in_inputs;
+        self
+    }
+}
+
+/// Run `handler` when one of the key combinations in `hotkeys` is pressed while the window is focused. See [`Hotkeys`] for the syntax.
+///
+/// Unlike global shortcuts, hotkeys only work while the app has focus, and they are ignored while typing in inputs. Use [`use_hotkeys_with`] to change that, scope the hotkey to part of the page, or give it a priority.
+///
+/// When several hotkeys match a key press, only one of them runs: the one with the highest priority, then a scoped hotkey over an unscoped one, then the one registered last.
+///
+/// ```rust, ignore
+/// let palette_open = use_state(cx, || false);
+/// use_hotkeys(cx, "ctrl+k, meta+k", move |_| palette_open.set(true));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `hotkeys` can't be parsed, or if the renderer does not provide a [`HotkeyProvider`].
+pub fn use_hotkeys(cx: &ScopeState, hotkeys: &str, handler: impl FnMut(KeyboardData) + 'static) {
+    use_hotkeys_with(cx, hotkeys, HotkeyOptions::default(), handler)
+}
+
+/// Run `handler` when one of the key combinations in `hotkeys` is pressed, with [`HotkeyOptions`] to scope the hotkey or change its priority.
+///
+/// ```rust, ignore
+/// // Close the dialog before anything else handles escape, even while typing
+/// use_hotkeys_with(cx, "escape", HotkeyOptions::new().priority(10).in_inputs(true), |_| close());
+/// ```
+///
+/// # Panics
+///
+/// Panics if `hotkeys` can't be parsed, or if the renderer does not provide a [`HotkeyProvider`].
+pub fn use_hotkeys_with(
+    cx: &ScopeState,
+    hotkeys: &str,
+    options: HotkeyOptions,
+    handler: impl FnMut(KeyboardData) + 'static,
+) {
+    let registration = cx.use_hook(|| {
+        let registry = match cx.consume_context::<Rc<HotkeyRegistry>>() {
+            Some(registry) => registry,
+            None => cx.provide_root_context(HotkeyRegistry::new(cx)),
+        };
+        let registration = Rc::new(Registration {
+            source: RefCell::new(String::new()),
+            hotkeys: RefCell::new(Hotkeys {
+                combinations: Vec::new(),
+            }),
+            options: RefCell::new(HotkeyOptions::default()),
+            handler: RefCell::new(Box::new(|_| {})),
+            scope: cx.scope_id(),
+            order: registry.next_order.get(),
+        });
+        registry.next_order.set(registration.order + 1);
+        registry
+            .registrations
+            .borrow_mut()
+            .push(Rc::downgrade(&registration));
+        registration
+    });
+
+    // Always use the latest values so the handler can capture new state
+    if *registration.source.borrow() != hotkeys {
+        let parsed = hotkeys
+            .parse()
+            .unwrap_or_else(|err| panic!("invalid hotkey `{}`: {}", hotkeys, err));
+        *registration.hotkeys.borrow_mut() = parsed;
+        *registration.source.borrow_mut() = hotkeys.to_string();
+    }
+    *registration.options.borrow_mut() = options;
+    *registration.handler.borrow_mut() = Box::new(handler);
+}
+
+struct Registration {
+    source: RefCell<String>,
+    hotkeys: RefCell<Hotkeys>,
+    options: RefCell<HotkeyOptions>,
+    handler: RefCell<Box<dyn FnMut(KeyboardData)>>,
+    scope: ScopeId,
+    /// Registrations made later win ties.
+    order: usize,
+}
+
+impl Registration {
+    /// Whether the registration should handle the key press, and how it ranks against other registrations if it should.
+    fn rank(&self, event: &KeyboardData, in_editable: bool) -> Option<(i32, bool, usize)> {
+        let options = self.options.borrow();
+        let scoped = match &options.scope {
+            Some(scope) if !scope.is_focused() => return None,
+            Some(_) => true,
+            None => false,
+        };
+        if in_editable && !options.in_inputs {
+            return None;
+        }
+        if !self.hotkeys.borrow().matches(event) {
+            return None;
+        }
+        Some((options.priority, scoped, self.order))
+    }
+}
+
+/// Dispatches key presses to the registered hotkeys. It is shared through the root context, so there is only one listener for the whole app.
+struct HotkeyRegistry {
+    registrations: RefCell<Vec<Weak<Registration>>>,
+    next_order: Cell<usize>,
+    subscription: RefCell<Option<Box<dyn Any>>>,
+}
+
+impl HotkeyRegistry {
+    fn new(cx: &ScopeState) -> Rc<Self> {
+        let provider = cx
+            .consume_context::<Rc<dyn HotkeyProvider>>()
+            .expect("hotkey provider not provided");
+        // The provider calls back from outside of the virtual dom, so the runtime has to be set to run the handlers
+        let runtime = Runtime::current().expect("hooks are called inside a virtual dom");
+
+        let registry = Rc::new(Self {
+            registrations: RefCell::new(Vec::new()),
+            next_order: Cell::new(0),
+            subscription: RefCell::new(None),
+        });
+
+        // The registry owns the subscription, so the callback only keeps a weak reference to it
+        let weak = Rc::downgrade(&registry);
+        let subscription = provider.subscribe(Box::new(move |event, in_editable| {
+            let registry = match weak.upgrade() {
+                Some(registry) => registry,
+                None => return false,
+            };
+            match registry.find(&event, in_editable) {
+                Some(registration) => {
+                    RuntimeGuard::with(runtime.clone(), Some(registration.scope), || {
+                        (registration.handler.borrow_mut())(event)
+                    });
+                    true
+                }
+                None => false,
+            }
+        }));
+        *registry.subscription.borrow_mut() = Some(subscription);
+
+        registry
+    }
+
+    /// Find the registration with the highest rank for a key press, and forget registrations of components that were dropped.
+    fn find(&self, event: &KeyboardData, in_editable: bool) -> Option<Rc<Registration>> {
+        let mut registrations = self.registrations.borrow_mut();
+        registrations.retain(|registration| registration.strong_count() > 0);
+        registrations
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter_map(|registration| {
+                let rank = registration.rank(event, in_editable)?;
+                Some((rank, registration))
+            })
+            .max_by_key(|(rank, _)| *rank)
+            .map(|(_, registration)| registration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combination(modifiers: Modifiers, key: &str) -> KeyCombination {
+        KeyCombination {
+            modifiers,
+            key: key.into(),
+        }
+    }
+
+    fn assert_parses(cases: &[(&str, Modifiers, &str)]) {
+        for &(input, modifiers, key) in cases {
+            assert_eq!(
+                input.parse::<KeyCombination>(),
+                Ok(combination(modifiers, key)),
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_modifiers_in_any_case() {
+        assert_parses(&[
+            ("k", Modifiers::empty(), "k"),
+            ("K", Modifiers::empty(), "k"),
+            ("ctrl+k", Modifiers::CONTROL, "k"),
+            ("Control+K", Modifiers::CONTROL, "k"),
+            ("SHIFT+k", Modifiers::SHIFT, "k"),
+            ("alt+k", Modifiers::ALT, "k"),
+            ("Option+k", Modifiers::ALT, "k"),
+            ("meta+k", Modifiers::META, "k"),
+            (
+                "ctrl+Shift+ALT+meta+k",
+                Modifiers::CONTROL | Modifiers::SHIFT | Modifiers::ALT | Modifiers::META,
+                "k",
+            ),
+            (" ctrl + k ", Modifiers::CONTROL, "k"),
+            ("ctrl++", Modifiers::CONTROL, "+"),
+            ("+", Modifiers::empty(), "+"),
+        ]);
+    }
+
+    #[test]
+    fn parses_modifier_aliases() {
+        let cmd_or_ctrl = if cfg!(target_os = "macos") {
+            Modifiers::META
+        } else {
+            Modifiers::CONTROL
+        };
+        assert_parses(&[
+            ("Cmd+s", Modifiers::META, "s"),
+            ("command+s", Modifiers::META, "s"),
+            ("super+s", Modifiers::META, "s"),
+            ("Ctrl+s", Modifiers::CONTROL, "s"),
+            ("CmdOrCtrl+s", cmd_or_ctrl, "s"),
+            ("CommandOrControl+s", cmd_or_ctrl, "s"),
+            ("CmdOrCtrl+Shift+s", cmd_or_ctrl | Modifiers::SHIFT, "s"),
+        ]);
+    }
+
+    #[test]
+    fn parses_digit_function_and_named_keys() {
+        assert_parses(&[
+            ("1", Modifiers::empty(), "1"),
+            ("ctrl+0", Modifiers::CONTROL, "0"),
+            ("F1", Modifiers::empty(), "f1"),
+            ("shift+f12", Modifiers::SHIFT, "f12"),
+            ("space", Modifiers::empty(), " "),
+            ("Esc", Modifiers::empty(), "escape"),
+            ("alt+up", Modifiers::ALT, "arrowup"),
+            ("del", Modifiers::empty(), "delete"),
+            ("return", Modifiers::empty(), "enter"),
+            ("ctrl+/", Modifiers::CONTROL, "/"),
+        ]);
+    }
+
+    #[test]
+    fn parses_every_combination_of_a_list() {
+        let hotkeys: Hotkeys = "ctrl+k, meta+k".parse().unwrap();
+        assert_eq!(
+            hotkeys.combinations,
+            [
+                combination(Modifiers::CONTROL, "k"),
+                combination(Modifiers::META, "k")
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        let cases = [
+            ("", HotkeyParseError::MissingKey("".into())),
+            ("ctrl+", HotkeyParseError::MissingKey("ctrl+".into())),
+            (
+                "ctrl+shift+ ",
+                HotkeyParseError::MissingKey("ctrl+shift+".into()),
+            ),
+            ("hyper+k", HotkeyParseError::UnknownModifier("hyper".into())),
+            ("ctrl+a+b", HotkeyParseError::UnknownModifier("a".into())),
+            ("ctrl+k,", HotkeyParseError::MissingKey("".into())),
+        ];
+        for (input, error) in cases {
+            assert_eq!(input.parse::<Hotkeys>(), Err(error), "{input:?}");
+        }
+    }
+}
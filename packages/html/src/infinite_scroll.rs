@@ -0,0 +1,189 @@
+//! Load more items when the end of a list scrolls into view.
+
+use crate::MountedData;
+use dioxus_core::{
+    prelude::{spawn_at, Runtime, RuntimeGuard},
+    ScopeId, ScopeState,
+};
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+type Loader<T> = Box<dyn FnMut(usize) -> Pin<Box<dyn Future<Output = Vec<T>>>>>;
+
+/// The sentinel element that is observed, and the value that stops the observer when dropped.
+type Observed = Option<(Rc<MountedData>, Option<Box<dyn Any>>)>;
+
+/// Load pages of items into a list whenever a sentinel element at the end of the list scrolls into view.
+///
+/// `loader` is called with the index of the page to load, starting at 0, and returns the items of that page. The first page is loaded right away. An empty page means there is nothing more to load. Only one page is loaded at a time, so the loader is never called twice for the same page, even if the sentinel is seen again while a page is loading.
+///
+/// Pass the sentinel element you got from its `onmounted` event. Pages are only loaded automatically if the renderer can observe when elements become visible. Call [`InfiniteScroll::load_more`] to load a page manually.
+///
+/// ```rust, ignore
+/// let sentinel = use_state(cx, || None);
+/// let posts = use_infinite_scroll(cx, sentinel.get().clone(), |page| fetch_posts(page));
+///
+/// render! {
+///     for post in posts.items().read().iter() {
+///         p { "{post}" }
+///     }
+///     if *posts.is_loading().read() {
+///         rsx! { "Loading..." }
+///     }
+///     div { onmounted: move |event| sentinel.set(Some(event.inner().clone())) }
+/// }
+/// ```
+pub fn use_infinite_scroll<T, F>(
+    cx: &ScopeState,
+    sentinel: Option<Rc<MountedData>>,
+    mut loader: impl FnMut(usize) -> F + 'static,
+) -> &InfiniteScroll<T>
+where
+    T: 'static,
+    F: Future<Output = Vec<T>> + 'static,
+{
+    let (scroll, observed, started) = cx.use_hook(|| {
+        let scroll = InfiniteScroll {
+            items: Signal::new(Vec::new()),
+            has_more: Signal::new(true),
+            is_loading: Signal::new(false),
+            state: Rc::new(ScrollState {
+                scope: cx.scope_id(),
+                runtime: Runtime::current().expect("hooks are called inside a virtual dom"),
+                loader: RefCell::new(Box::new(|_| Box::pin(async { Vec::new() }))),
+                next_page: Cell::new(0),
+                generation: Cell::new(0),
+                visible: Cell::new(false),
+            }),
+        };
+        let observed: RefCell<Observed> = RefCell::new(None);
+        (scroll, observed, Cell::new(false))
+    });
+
+    // Always load with the latest closure so it can capture new values
+    let new_loader: Loader<T> = Box::new(move |page| Box::pin(loader(page)));
+    *scroll.state.loader.borrow_mut() = new_loader;
+    if !started.replace(true) {
+        scroll.load_more();
+    }
+
+    let changed = match (&*observed.borrow(), &sentinel) {
+        (Some((old, _)), Some(new)) => !Rc::ptr_eq(old, new),
+        (None, None) => false,
+        _ => true,
+    };
+    if changed {
+        // Stop observing the old sentinel before observing the new one
+        observed.borrow_mut().take();
+        scroll.state.visible.set(false);
+        if let Some(sentinel) = sentinel {
+            let on_change = scroll.clone();
+            let observer = sentinel
+                .observe_intersection(move |visible| {
+                    on_change.state.visible.set(visible);
+                    if visible {
+                        on_change.load_more();
+                    }
+                })
+                .ok();
+            *observed.borrow_mut() = Some((sentinel, observer));
+        }
+    }
+
+    scroll
+}
+
+struct ScrollState<T> {
+    scope: ScopeId,
+    /// The observer calls back from outside of the virtual dom, so the runtime has to be set to spawn the loader.
+    runtime: Rc<Runtime>,
+    loader: RefCell<Loader<T>>,
+    next_page: Cell<usize>,
+    /// Increased when the list is reset, so pages that were loading before are ignored.
+    generation: Cell<usize>,
+    visible: Cell<bool>,
+}
+
+/// A list that is loaded page by page, created with [`use_infinite_scroll`].
+pub struct InfiniteScroll<T: 'static> {
+    items: Signal<Vec<T>>,
+    has_more: Signal<bool>,
+    is_loading: Signal<bool>,
+    state: Rc<ScrollState<T>>,
+}
+
+impl<T> Clone for InfiniteScroll<T> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items,
+            has_more: self.has_more,
+            is_loading: self.is_loading,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> InfiniteScroll<T> {
+    /// The items of every page loaded so far. New pages are appended to the end.
+    pub fn items(&self) -> Signal<Vec<T>> {
+        self.items
+    }
+
+    /// Whether there may be more pages to load. This becomes false once the loader returns an empty page.
+    pub fn has_more(&self) -> ReadOnlySignal<bool> {
+        ReadOnlySignal::new(self.has_more)
+    }
+
+    /// Whether a page is being loaded.
+    pub fn is_loading(&self) -> ReadOnlySignal<bool> {
+        ReadOnlySignal::new(self.is_loading)
+    }
+
+    /// Load the next page, unless a page is already loading or there are no more pages.
+    pub fn load_more(&self) {
+        if *self.is_loading.peek() || !*self.has_more.peek() {
+            return;
+        }
+        self.is_loading.set(true);
+
+        let page = self.state.next_page.get();
+        let generation = self.state.generation.get();
+        let load = (self.state.loader.borrow_mut())(page);
+        let scroll = self.clone();
+        let load = async move {
+            let new_items = load.await;
+            if scroll.state.generation.get() != generation {
+                return;
+            }
+            scroll.state.next_page.set(page + 1);
+            if new_items.is_empty() {
+                scroll.has_more.set(false);
+            } else {
+                scroll.items.write().extend(new_items);
+            }
+            scroll.is_loading.set(false);
+
+            // If the sentinel is still visible, the page did not fill the screen
+            if scroll.state.visible.get() {
+                scroll.load_more();
+            }
+        };
+        RuntimeGuard::with(self.state.runtime.clone(), None, || {
+            spawn_at(load, self.state.scope)
+        });
+    }
+
+    /// Clear the list and load it again from the first page. Pages that are still loading are ignored.
+    pub fn reset(&self) {
+        self.state.generation.set(self.state.generation.get() + 1);
+        self.state.next_page.set(0);
+        self.items.write().clear();
+        self.has_more.set(true);
+        self.is_loading.set(false);
+        self.load_more();
+    }
+}
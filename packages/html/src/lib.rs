@@ -16,6 +16,10 @@
 //!
 //! Currently, we don't validate for structures, but do validate attributes.
 
+pub mod animation;
+pub mod battery;
+pub mod clipboard;
+pub mod element_bounds;
 mod elements;
 #[cfg(feature = "hot-reload-context")]
 pub use elements::HtmlCtx;
@@ -23,16 +27,26 @@ pub use elements::HtmlCtx;
 pub use elements::{map_html_attribute_to_rsx, map_html_element_to_rsx};
 pub mod events;
 pub(crate) mod file_data;
+pub mod file_dialog;
 pub use file_data::*;
+pub mod focus;
 pub mod geometry;
 mod global_attributes;
+pub mod hotkeys;
+pub mod infinite_scroll;
 pub mod input_data;
 #[cfg(feature = "native-bind")]
 pub mod native_bind;
+pub mod network;
+pub mod notification;
 pub mod point_interaction;
 mod render_template;
+pub mod resize_observer;
+pub mod text_metrics;
+pub mod theme;
 #[cfg(feature = "wasm-bind")]
 mod web_sys_bind;
+pub mod window_size;
 
 #[cfg(feature = "serialize")]
 mod transit;
@@ -54,11 +68,41 @@ pub mod extensions {
 }
 
 pub mod prelude {
+    pub use crate::animation::{
+        use_animation_frame, use_spring, use_spring_with, AnimationFrame, AnimationFrameProvider,
+        SpringConfig,
+    };
+    pub use crate::battery::{use_battery, BatteryProvider, BatteryStatus};
+    pub use crate::clipboard::{
+        use_clipboard, ClipboardError, ClipboardImage, ClipboardProvider, UseClipboard,
+    };
+    pub use crate::element_bounds::use_element_bounds;
     pub use crate::elements::extensions::*;
     #[cfg(feature = "eval")]
     pub use crate::eval::*;
     pub use crate::events::*;
+    pub use crate::file_dialog::{
+        use_file_dialog, FileDialogError, FileDialogOptions, FileDialogProvider, FileFilter,
+        UseFileDialog,
+    };
+    pub use crate::focus::{use_focus, use_focus_trap, UseFocus};
     pub use crate::global_attributes::{GlobalAttributesExtension, SvgAttributesExtension};
+    pub use crate::hotkeys::{
+        use_hotkey_scope, use_hotkeys, use_hotkeys_with, HotkeyOptions, HotkeyProvider,
+        HotkeyScope, Hotkeys,
+    };
+    pub use crate::infinite_scroll::{use_infinite_scroll, InfiniteScroll};
+    pub use crate::network::{
+        use_network_status, ConnectionType, NetworkStatus, NetworkStatusProvider,
+    };
+    pub use crate::notification::{
+        use_notification, NotificationAction, NotificationContent, NotificationEvent,
+        NotificationEventKind, NotificationId, NotificationProvider, ScheduleAt, UseNotification,
+    };
     pub use crate::point_interaction::*;
+    pub use crate::resize_observer::use_resize_observer;
+    pub use crate::text_metrics::{Font, TextMetrics};
+    pub use crate::theme::{AccentColor, ColorScheme};
+    pub use crate::window_size::{use_window_size, WindowSizeProvider};
     pub use keyboard_types::{self, Code, Key, Location, Modifiers};
 }
@@ -0,0 +1,112 @@
+//! Track whether the app is online and how it is connected.
+//!
+//! Renderers provide an `Rc<dyn NetworkStatusProvider>` through the root scope's context, and components read it with [`use_network_status`].
+
+use dioxus_core::ScopeState;
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::any::Any;
+use std::rc::Rc;
+
+/// A struct that implements NetworkStatusProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_network_status`] can track the connection on any platform.
+///
+/// It must be provided as `Rc<dyn NetworkStatusProvider>`, because the concrete type has a different TypeId.
+pub trait NetworkStatusProvider {
+    /// The current status of the connection.
+    fn status(&self) -> NetworkStatus;
+
+    /// Call `on_change` with the new status whenever the app goes online or offline or the connection type changes. The callback is removed when the returned value is dropped.
+    fn subscribe(&self, on_change: Box<dyn FnMut(NetworkStatus)>) -> Box<dyn Any>;
+}
+
+/// Whether the app is online, and how it is connected.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NetworkStatus {
+    /// Whether the app has a network connection. A connection does not guarantee that a server can be reached.
+    pub online: bool,
+    /// The kind of connection.
+    pub connection: ConnectionType,
+}
+
+impl Default for NetworkStatus {
+    fn default() -> Self {
+        Self {
+            online: true,
+            connection: ConnectionType::Unknown,
+        }
+    }
+}
+
+/// The kind of network connection, as reported by the `NetworkInformation` web API.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionType {
+    /// A wired connection.
+    Ethernet,
+    /// A wireless LAN connection.
+    Wifi,
+    /// A mobile data connection. These are often slow or metered, so it is a good idea to poll less.
+    Cellular,
+    /// A bluetooth connection.
+    Bluetooth,
+    /// Any other kind of connection.
+    Other,
+    /// There is no connection.
+    None,
+    /// The platform does not report the kind of connection.
+    Unknown,
+}
+
+impl Default for ConnectionType {
+    fn default() -> Self {
+        ConnectionType::Unknown
+    }
+}
+
+impl ConnectionType {
+    /// Parse the `type` of a `NetworkInformation` object. Unknown values become [`ConnectionType::Unknown`].
+    pub fn from_web(value: &str) -> Self {
+        match value {
+            "ethernet" => ConnectionType::Ethernet,
+            "wifi" | "wimax" => ConnectionType::Wifi,
+            "cellular" => ConnectionType::Cellular,
+            "bluetooth" => ConnectionType::Bluetooth,
+            "other" | "mixed" => ConnectionType::Other,
+            "none" => ConnectionType::None,
+            _ => ConnectionType::Unknown,
+        }
+    }
+}
+
+/// Get the status of the network connection. The signal is updated when the app goes online or offline, or the connection type changes.
+///
+/// ```rust, ignore
+/// let network = use_network_status(cx);
+///
+/// render! {
+///     if !network.read().online {
+///         rsx! { "You are offline. Changes will be saved when you reconnect." }
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide a [`NetworkStatusProvider`].
+pub fn use_network_status(cx: &ScopeState) -> ReadOnlySignal<NetworkStatus> {
+    let (status, _subscription) = cx.use_hook(|| {
+        let provider = cx
+            .consume_context::<Rc<dyn NetworkStatusProvider>>()
+            .expect("network status provider not provided");
+        let status = Signal::new(provider.status());
+        let subscription = provider.subscribe(Box::new(move |new| {
+            if *status.peek() != new {
+                status.set(new);
+            }
+        }));
+        (status, subscription)
+    });
+
+    ReadOnlySignal::new(*status)
+}
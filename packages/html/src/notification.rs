@@ -0,0 +1,229 @@
+//! Types describing local notifications, shown right away with [`use_notification`] or scheduled to be shown at a later time.
+//!
+//! Renderers schedule them with `schedule_notification` and report what happened to them with the `use_notification_events` hook. Notifications that are shown right away go through an `Rc<dyn NotificationProvider>` in the root scope's context.
+
+use dioxus_core::ScopeState;
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::rc::Rc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The content of a local notification.
+///
+/// ```rust
+/// use dioxus_html::notification::NotificationContent;
+///
+/// let content = NotificationContent::new("Standup")
+///     .body("The daily standup starts in 5 minutes")
+///     .data("meeting-42");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotificationContent {
+    /// The title of the notification.
+    pub title: String,
+    /// The text shown below the title.
+    pub body: Option<String>,
+    /// A path or URL to an icon shown next to the notification.
+    pub icon: Option<String>,
+    /// Any data you want to get back in the [`NotificationEvent`]s of the notification, like the ID of the item it is about.
+    pub data: Option<String>,
+    /// Buttons shown on the notification. Not every platform can show them.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub actions: Vec<NotificationAction>,
+}
+
+/// A button on a notification. Clicking it sends a [`NotificationEventKind::Action`] event with the id of the action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotificationAction {
+    /// The id reported when the button is clicked.
+    pub id: String,
+    /// The text of the button.
+    pub label: String,
+}
+
+impl NotificationContent {
+    /// Create a notification with a title.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the text shown below the title.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// Set a path or URL to an icon shown next to the notification.
+    pub fn icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set data you want to get back in the [`NotificationEvent`]s of the notification.
+    pub fn data(mut self, data: impl Into<String>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Add a button that reports `id` when it is clicked.
+    pub fn action(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.actions.push(NotificationAction {
+            id: id.into(),
+            label: label.into(),
+        });
+        self
+    }
+}
+
+/// When a scheduled notification should be shown.
+///
+/// Use a [`Duration`] to show it after a delay, or a [`SystemTime`] to show it at a point in time. `SystemTime::now` is not available on the web, so use a delay there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleAt {
+    /// Show the notification after this much time has passed.
+    After(Duration),
+    /// Show the notification at this many milliseconds since the unix epoch.
+    UnixMillis(u64),
+}
+
+impl ScheduleAt {
+    /// Get the time to show the notification at in milliseconds since the unix epoch, given the current time in milliseconds since the unix epoch.
+    pub fn unix_millis(self, now: u64) -> u64 {
+        match self {
+            ScheduleAt::After(delay) => now.saturating_add(delay.as_millis() as u64),
+            ScheduleAt::UnixMillis(at) => at,
+        }
+    }
+}
+
+impl From<Duration> for ScheduleAt {
+    fn from(delay: Duration) -> Self {
+        ScheduleAt::After(delay)
+    }
+}
+
+impl From<SystemTime> for ScheduleAt {
+    fn from(at: SystemTime) -> Self {
+        let millis = at
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+        ScheduleAt::UnixMillis(millis)
+    }
+}
+
+/// Identifies a scheduled notification. Returned when the notification is scheduled and included in its [`NotificationEvent`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotificationId(pub u64);
+
+/// Something that happened to a scheduled notification.
+///
+/// Events that happened while the app was closed are reported the next time the app is launched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct NotificationEvent {
+    /// The notification the event is about.
+    pub id: NotificationId,
+    /// What happened.
+    pub kind: NotificationEventKind,
+    /// The [`NotificationContent::data`] of the notification.
+    pub data: Option<String>,
+    /// The id of the [`NotificationAction`] that was clicked, for [`NotificationEventKind::Action`] events.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub action: Option<String>,
+}
+
+/// What happened to a scheduled notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum NotificationEventKind {
+    /// The notification was shown.
+    Delivered,
+    /// The user clicked the notification.
+    Clicked,
+    /// The user clicked one of the [`NotificationContent::actions`] of the notification.
+    Action,
+}
+
+/// A struct that implements NotificationProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_notification`] can show notifications on any platform.
+///
+/// It must be provided as `Rc<dyn NotificationProvider>`, because the concrete type has a different TypeId.
+pub trait NotificationProvider {
+    /// Show a notification right away, and call `on_event` with everything that happens to it.
+    fn show(
+        &self,
+        content: NotificationContent,
+        on_event: Box<dyn FnMut(NotificationEvent)>,
+    ) -> NotificationId;
+}
+
+/// Show notifications right away and get what happens to them as a signal.
+///
+/// ```rust, ignore
+/// let notification = use_notification(cx);
+/// if let Some(event) = &*notification.event().read() {
+///     if event.action.as_deref() == Some("reply") {
+///         // ...
+///     }
+/// }
+///
+/// render! {
+///     button {
+///         onclick: move |_| {
+///             notification.show(
+///                 NotificationContent::new("New message")
+///                     .body("Are you coming tonight?")
+///                     .action("reply", "Reply"),
+///             );
+///         },
+///         "Notify"
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide a [`NotificationProvider`].
+pub fn use_notification(cx: &ScopeState) -> &UseNotification {
+    cx.use_hook(|| UseNotification {
+        provider: cx
+            .consume_context::<Rc<dyn NotificationProvider>>()
+            .expect("notification provider not provided"),
+        event: Signal::new(None),
+        alive: Rc::new(()),
+    })
+}
+
+/// Shows notifications for a component. Created with [`use_notification`].
+pub struct UseNotification {
+    provider: Rc<dyn NotificationProvider>,
+    event: Signal<Option<NotificationEvent>>,
+    // Events can arrive after the component is dropped, when the signal is gone
+    alive: Rc<()>,
+}
+
+impl UseNotification {
+    /// Show a notification right away. Everything that happens to it is written to [`UseNotification::event`].
+    pub fn show(&self, content: NotificationContent) -> NotificationId {
+        let event = self.event;
+        let alive = Rc::downgrade(&self.alive);
+        self.provider.show(
+            content,
+            Box::new(move |new| {
+                if alive.upgrade().is_some() {
+                    event.set(Some(new));
+                }
+            }),
+        )
+    }
+
+    /// The last event of a notification shown with [`UseNotification::show`], or `None` if nothing happened yet.
+    pub fn event(&self) -> ReadOnlySignal<Option<NotificationEvent>> {
+        ReadOnlySignal::new(self.event)
+    }
+}
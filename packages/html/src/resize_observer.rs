@@ -0,0 +1,63 @@
+//! Track the size of a mounted element.
+
+use crate::geometry::euclid::Rect;
+use crate::MountedData;
+use dioxus_core::ScopeState;
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The element that is observed, and the value that stops the observer when dropped.
+type Observed = Option<(Rc<MountedData>, Option<Box<dyn Any>>)>;
+
+/// Get the content rectangle of a mounted element as a signal. The signal is updated whenever the size of the element changes, even if the window is not resized.
+///
+/// Pass the element you got from its `onmounted` event. The signal is `None` until the element is mounted and its size is first reported. It also stays `None` if the renderer can't observe the size of elements.
+///
+/// ```rust, ignore
+/// let element = use_state(cx, || None);
+/// let rect = use_resize_observer(cx, element.get().clone());
+///
+/// render! {
+///     canvas {
+///         onmounted: move |event| element.set(Some(event.inner().clone())),
+///         width: "{rect.read().map(|rect| rect.width()).unwrap_or_default()}",
+///     }
+/// }
+/// ```
+pub fn use_resize_observer(
+    cx: &ScopeState,
+    element: Option<Rc<MountedData>>,
+) -> ReadOnlySignal<Option<Rect<f64, f64>>> {
+    let (rect, observed) = cx.use_hook(|| {
+        let observed: RefCell<Observed> = RefCell::new(None);
+        (Signal::new(None), observed)
+    });
+    let rect = *rect;
+
+    let changed = match (&*observed.borrow(), &element) {
+        (Some((old, _)), Some(new)) => !Rc::ptr_eq(old, new),
+        (None, None) => false,
+        _ => true,
+    };
+    if changed {
+        // Stop observing the old element before observing the new one
+        observed.borrow_mut().take();
+        if rect.peek().is_some() {
+            rect.set(None);
+        }
+        if let Some(element) = element {
+            let observer = element
+                .observe_resize(move |new| {
+                    if *rect.peek() != Some(new) {
+                        rect.set(Some(new));
+                    }
+                })
+                .ok();
+            *observed.borrow_mut() = Some((element, observer));
+        }
+    }
+
+    ReadOnlySignal::new(rect)
+}
@@ -0,0 +1,153 @@
+//! Types for measuring how much space a string of text takes up when it is rendered.
+//!
+//! Renderers expose a `measure_text` function and a cached `use_text_metrics` hook built on these types.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+/// The font to measure text with.
+///
+/// This formats as a CSS `font` shorthand, so it can be used directly in a style attribute:
+///
+/// ```rust
+/// use dioxus_html::text_metrics::Font;
+///
+/// let font = Font::new("Inter, sans-serif", 14.0).weight(700).italic();
+/// assert_eq!(font.to_string(), "italic 700 14px Inter, sans-serif");
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Font {
+    /// The font family, or a comma separated list of families to fall back through.
+    pub family: String,
+    /// The font size in pixels.
+    pub size: f32,
+    /// The font weight, from 100 to 900. Regular text is 400 and bold text is 700.
+    pub weight: u16,
+    /// Whether the font is italic.
+    pub italic: bool,
+}
+
+impl Font {
+    /// Create a regular font with the given family and size in pixels.
+    pub fn new(family: impl Into<String>, size: f32) -> Self {
+        Self {
+            family: family.into(),
+            size,
+            weight: 400,
+            italic: false,
+        }
+    }
+
+    /// Set the weight of the font, from 100 to 900.
+    pub fn weight(mut self, weight: u16) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Make the font italic.
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+}
+
+impl PartialEq for Font {
+    fn eq(&self, other: &Self) -> bool {
+        self.family == other.family
+            && self.size.to_bits() == other.size.to_bits()
+            && self.weight == other.weight
+            && self.italic == other.italic
+    }
+}
+
+impl Eq for Font {}
+
+impl Hash for Font {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.family.hash(state);
+        self.size.to_bits().hash(state);
+        self.weight.hash(state);
+        self.italic.hash(state);
+    }
+}
+
+impl Display for Font {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.italic {
+            write!(f, "italic ")?;
+        }
+        write!(f, "{} {}px {}", self.weight, self.size, self.family)
+    }
+}
+
+/// The size of a string of text rendered in a [`Font`], in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextMetrics {
+    /// The advance width of the text.
+    pub width: f32,
+    /// The distance from the baseline to the top of the line.
+    pub ascent: f32,
+    /// The distance from the baseline to the bottom of the line.
+    pub descent: f32,
+}
+
+impl TextMetrics {
+    /// The height of a line of the text.
+    pub fn height(&self) -> f32 {
+        self.ascent + self.descent
+    }
+}
+
+/// A cache of measured text, used by renderers so measuring the same label every render is cheap.
+///
+/// The cache is cleared once it holds more than `capacity` entries.
+pub struct TextMetricsCache {
+    capacity: usize,
+    entries: RefCell<HashMap<(Font, String), TextMetrics>>,
+}
+
+impl TextMetricsCache {
+    /// Create an empty cache that holds up to `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: RefCell::default(),
+        }
+    }
+
+    /// Get the cached metrics of the text.
+    pub fn get(&self, font: &Font, text: &str) -> Option<TextMetrics> {
+        self.entries
+            .borrow()
+            .get(&(font.clone(), text.to_string()))
+            .copied()
+    }
+
+    /// Cache the metrics of the text.
+    pub fn insert(&self, font: &Font, text: &str, metrics: TextMetrics) {
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() >= self.capacity {
+            entries.clear();
+        }
+        entries.insert((font.clone(), text.to_string()), metrics);
+    }
+
+    /// Get the cached metrics of the text, or measure and cache them.
+    pub fn get_or_measure(
+        &self,
+        font: &Font,
+        text: &str,
+        measure: impl FnOnce() -> TextMetrics,
+    ) -> TextMetrics {
+        if let Some(metrics) = self.get(font, text) {
+            return metrics;
+        }
+        let metrics = measure();
+        self.insert(font, text, metrics);
+        metrics
+    }
+}
@@ -0,0 +1,50 @@
+//! Types describing the color scheme and accent color the user picked in their OS or browser settings.
+//!
+//! Renderers expose these as signals with the `use_color_scheme` and `use_accent_color` hooks.
+
+use std::fmt::{Display, Formatter};
+
+/// Whether the user prefers a light or a dark interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum ColorScheme {
+    /// Dark text on a light background.
+    #[default]
+    Light,
+    /// Light text on a dark background.
+    Dark,
+}
+
+impl ColorScheme {
+    /// Returns true if the color scheme is dark.
+    pub fn is_dark(&self) -> bool {
+        matches!(self, ColorScheme::Dark)
+    }
+}
+
+/// The accent (highlight) color the user picked, as an sRGB color.
+///
+/// This formats as a CSS hex color, so it can be used directly in a style attribute:
+///
+/// ```rust
+/// use dioxus_html::theme::AccentColor;
+///
+/// let accent = AccentColor { red: 0, green: 122, blue: 255 };
+/// assert_eq!(accent.to_string(), "#007aff");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccentColor {
+    /// The red channel.
+    pub red: u8,
+    /// The green channel.
+    pub green: u8,
+    /// The blue channel.
+    pub blue: u8,
+}
+
+impl Display for AccentColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
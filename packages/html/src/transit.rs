@@ -261,6 +261,50 @@ fn test_back_and_forth() {
     assert_eq!(data, p);
 }
 
+#[test]
+fn test_paste_payload() {
+    let o = r#"
+{
+  "element": 0,
+  "name": "paste",
+  "bubbles": true,
+  "data": {
+    "types": ["text/plain", "text/html", "Files"],
+    "text": "hello",
+    "html": "<b>hello</b>",
+    "files": {
+      "files": {
+        "image.png": [137, 80, 78, 71]
+      }
+    }
+  }
+}
+    "#;
+
+    let p: HtmlEvent = serde_json::from_str(o).unwrap();
+
+    let files = crate::file_data::SerializedFileEngine {
+        files: std::iter::once(("image.png".to_string(), vec![137, 80, 78, 71])).collect(),
+    };
+    let data = HtmlEvent {
+        element: ElementId(0),
+        data: EventData::Clipboard(SerializedClipboardData::new(
+            vec![
+                "text/plain".to_string(),
+                "text/html".to_string(),
+                "Files".to_string(),
+            ],
+            Some("hello".to_string()),
+            Some("<b>hello</b>".to_string()),
+            Some(files),
+        )),
+        name: "paste".to_string(),
+        bubbles: true,
+    };
+
+    assert_eq!(data, p);
+}
+
 /// A trait for converting from a serialized event to a concrete event type.
 pub struct SerializedHtmlEventConverter;
 
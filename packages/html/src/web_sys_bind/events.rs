@@ -469,8 +469,302 @@ impl crate::RenderedElementBacking for web_sys::Element {
             });
         Box::pin(async { result })
     }
+
+    fn observe_resize(
+        &self,
+        mut on_resize: Box<dyn FnMut(euclid::Rect<f64, f64>)>,
+    ) -> crate::MountedResult<Box<dyn std::any::Any>> {
+        let callback = wasm_bindgen::closure::Closure::<dyn FnMut(web_sys::js_sys::Array)>::new(
+            move |entries: web_sys::js_sys::Array| {
+                for entry in entries.iter() {
+                    let rect = entry
+                        .unchecked_into::<web_sys::ResizeObserverEntry>()
+                        .content_rect();
+                    on_resize(euclid::Rect::new(
+                        euclid::Point2D::new(rect.x(), rect.y()),
+                        euclid::Size2D::new(rect.width(), rect.height()),
+                    ));
+                }
+            },
+        );
+        let observer = web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref())
+            .map_err(|err| crate::MountedError::OperationFailed(Box::new(ObserverError(err))))?;
+        observer.observe(self);
+        Ok(Box::new(WebResizeObserver {
+            observer,
+            _callback: callback,
+        }))
+    }
+
+    fn observe_intersection(
+        &self,
+        mut on_change: Box<dyn FnMut(bool)>,
+    ) -> crate::MountedResult<Box<dyn std::any::Any>> {
+        let callback = wasm_bindgen::closure::Closure::<dyn FnMut(web_sys::js_sys::Array)>::new(
+            move |entries: web_sys::js_sys::Array| {
+                for entry in entries.iter() {
+                    on_change(
+                        entry
+                            .unchecked_into::<web_sys::IntersectionObserverEntry>()
+                            .is_intersecting(),
+                    );
+                }
+            },
+        );
+        let observer = web_sys::IntersectionObserver::new(callback.as_ref().unchecked_ref())
+            .map_err(|err| crate::MountedError::OperationFailed(Box::new(ObserverError(err))))?;
+        observer.observe(self);
+        Ok(Box::new(WebIntersectionObserver {
+            observer,
+            _callback: callback,
+        }))
+    }
+
+    fn observe_focus(
+        &self,
+        on_change: Box<dyn FnMut(bool)>,
+    ) -> crate::MountedResult<Box<dyn std::any::Any>> {
+        let on_change = std::rc::Rc::new(std::cell::RefCell::new(on_change));
+        let contains = |element: &web_sys::Element, target: Option<web_sys::EventTarget>| {
+            let node = target.and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+            node.is_some() && element.contains(node.as_ref())
+        };
+
+        let focusin = wasm_bindgen::closure::Closure::<dyn FnMut(Event)>::new({
+            let on_change = on_change.clone();
+            move |_: Event| (on_change.borrow_mut())(true)
+        });
+        let focusout = wasm_bindgen::closure::Closure::<dyn FnMut(Event)>::new({
+            let on_change = on_change.clone();
+            let element = self.clone();
+            move |event: Event| {
+                // Focus moving between descendants of the element does not change anything
+                let event: web_sys::FocusEvent = event.unchecked_into();
+                if !contains(&element, event.related_target()) {
+                    (on_change.borrow_mut())(false)
+                }
+            }
+        });
+        let listeners = WebEventListeners::new(
+            self.clone().into(),
+            vec![("focusin", focusin), ("focusout", focusout)],
+        );
+
+        let active = self
+            .owner_document()
+            .and_then(|document| document.active_element());
+        (on_change.borrow_mut())(contains(self, active.map(Into::into)));
+
+        Ok(Box::new(listeners))
+    }
+
+    fn trap_focus(&self) -> crate::MountedResult<Box<dyn std::any::Any>> {
+        let document = self
+            .owner_document()
+            .ok_or(crate::MountedError::NotSupported)?;
+        let previous = document.active_element();
+
+        let container = self.clone();
+        let keydown = wasm_bindgen::closure::Closure::<dyn FnMut(Event)>::new({
+            let document = document.clone();
+            move |event: Event| {
+                let event: KeyboardEvent = event.unchecked_into();
+                if event.key() != "Tab" {
+                    return;
+                }
+                let focusable = focusable_descendants(&container);
+                let (first, last) = match (focusable.first(), focusable.last()) {
+                    (Some(first), Some(last)) => (first, last),
+                    _ => {
+                        event.prevent_default();
+                        return;
+                    }
+                };
+                let active: Option<web_sys::Node> = document.active_element().map(Into::into);
+                let outside = !container.contains(active.as_ref());
+                let (edge, wrap_to) = if event.shift_key() {
+                    (first, last)
+                } else {
+                    (last, first)
+                };
+                if outside || edge.is_same_node(active.as_ref()) {
+                    event.prevent_default();
+                    _ = wrap_to.focus();
+                }
+            }
+        });
+
+        // Move focus into the element if it is somewhere else
+        let active: Option<web_sys::Node> = previous.clone().map(Into::into);
+        if !self.contains(active.as_ref()) {
+            if let Some(first) = focusable_descendants(self).first() {
+                _ = first.focus();
+            }
+        }
+
+        Ok(Box::new(WebFocusTrap {
+            _listeners: WebEventListeners::new(document.into(), vec![("keydown", keydown)]),
+            previous,
+        }))
+    }
+
+    fn observe_scroll(
+        &self,
+        mut on_scroll: Box<dyn FnMut()>,
+    ) -> crate::MountedResult<Box<dyn std::any::Any>> {
+        let document = self
+            .owner_document()
+            .ok_or(crate::MountedError::NotSupported)?;
+        let element = self.clone();
+        let scroll =
+            wasm_bindgen::closure::Closure::<dyn FnMut(Event)>::new(move |event: Event| {
+                // Only scrolling the page or an ancestor of the element moves it
+                let target = event
+                    .target()
+                    .and_then(|target| target.dyn_into::<web_sys::Node>().ok());
+                if let Some(target) = target {
+                    if target.contains(Some(&element)) {
+                        on_scroll();
+                    }
+                }
+            });
+        Ok(Box::new(WebEventListeners::with_capture(
+            document.into(),
+            vec![("scroll", scroll)],
+            true,
+        )))
+    }
+}
+
+/// The descendants of an element that can be focused with the tab key, in document order.
+#[cfg(feature = "mounted")]
+fn focusable_descendants(element: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+    const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), input:not([disabled]):not([type=\"hidden\"]), \
+        select:not([disabled]), textarea:not([disabled]), iframe, [contenteditable], [tabindex]:not([tabindex=\"-1\"])";
+    let nodes = match element.query_selector_all(FOCUSABLE) {
+        Ok(nodes) => nodes,
+        Err(_) => return Vec::new(),
+    };
+    (0..nodes.length())
+        .filter_map(|i| nodes.item(i))
+        .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        .filter(|element| element.tab_index() >= 0)
+        .collect()
+}
+
+#[cfg(feature = "mounted")]
+type WebListener = (
+    &'static str,
+    wasm_bindgen::closure::Closure<dyn FnMut(Event)>,
+);
+
+/// Event listeners that are removed when dropped.
+#[cfg(feature = "mounted")]
+struct WebEventListeners {
+    target: web_sys::EventTarget,
+    listeners: Vec<WebListener>,
+    capture: bool,
+}
+
+#[cfg(feature = "mounted")]
+impl WebEventListeners {
+    fn new(target: web_sys::EventTarget, listeners: Vec<WebListener>) -> Self {
+        Self::with_capture(target, listeners, false)
+    }
+
+    /// Listen in the capture phase, to also see events that do not bubble, like `scroll` on elements.
+    fn with_capture(
+        target: web_sys::EventTarget,
+        listeners: Vec<WebListener>,
+        capture: bool,
+    ) -> Self {
+        for (event, callback) in &listeners {
+            _ = target.add_event_listener_with_callback_and_bool(
+                event,
+                callback.as_ref().unchecked_ref(),
+                capture,
+            );
+        }
+        Self {
+            target,
+            listeners,
+            capture,
+        }
+    }
+}
+
+#[cfg(feature = "mounted")]
+impl Drop for WebEventListeners {
+    fn drop(&mut self) {
+        for (event, callback) in &self.listeners {
+            _ = self.target.remove_event_listener_with_callback_and_bool(
+                event,
+                callback.as_ref().unchecked_ref(),
+                self.capture,
+            );
+        }
+    }
+}
+
+/// Releases the focus trap and returns focus to the element that had it before when dropped.
+#[cfg(feature = "mounted")]
+struct WebFocusTrap {
+    _listeners: WebEventListeners,
+    previous: Option<web_sys::Element>,
+}
+
+#[cfg(feature = "mounted")]
+impl Drop for WebFocusTrap {
+    fn drop(&mut self) {
+        if let Some(previous) = self
+            .previous
+            .as_ref()
+            .and_then(|previous| previous.dyn_ref::<web_sys::HtmlElement>())
+        {
+            _ = previous.focus();
+        }
+    }
+}
+
+/// Disconnects the resize observer when dropped.
+#[cfg(feature = "mounted")]
+struct WebResizeObserver {
+    observer: web_sys::ResizeObserver,
+    _callback: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::js_sys::Array)>,
+}
+
+#[cfg(feature = "mounted")]
+impl Drop for WebResizeObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+/// Disconnects the intersection observer when dropped.
+#[cfg(feature = "mounted")]
+struct WebIntersectionObserver {
+    observer: web_sys::IntersectionObserver,
+    _callback: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::js_sys::Array)>,
+}
+
+#[cfg(feature = "mounted")]
+impl Drop for WebIntersectionObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[derive(Debug)]
+struct ObserverError(JsValue);
+
+impl std::fmt::Display for ObserverError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "failed to create an observer {:?}", self.0)
+    }
 }
 
+impl std::error::Error for ObserverError {}
+
 #[derive(Debug)]
 struct FocusError(JsValue);
 
@@ -0,0 +1,47 @@
+//! A platform agnostic way to track the size of the window.
+//!
+//! Renderers provide an `Rc<dyn WindowSizeProvider>` through the root scope's context, and components read it with [`use_window_size`].
+
+use dioxus_core::ScopeState;
+use dioxus_signals::{ReadOnlySignal, Signal};
+use std::any::Any;
+use std::rc::Rc;
+
+/// A struct that implements WindowSizeProvider is sent through [`ScopeState`]'s provide_context function
+/// so that [`use_window_size`] can track the size of the window on any platform.
+///
+/// It must be provided as `Rc<dyn WindowSizeProvider>`, because the concrete type has a different TypeId.
+pub trait WindowSizeProvider {
+    /// The current size of the window's content area as `(width, height)` in CSS pixels.
+    fn size(&self) -> (u32, u32);
+
+    /// Call `on_resize` with the new size whenever the window is resized. The callback is removed when the returned value is dropped.
+    fn subscribe(&self, on_resize: Box<dyn FnMut((u32, u32))>) -> Box<dyn Any>;
+}
+
+/// Get the size of the window's content area as `(width, height)` in CSS pixels. The signal is updated when the window is resized.
+///
+/// ```rust, ignore
+/// let size = use_window_size(cx);
+/// let columns = if size.read().0 < 600 { 1 } else { 3 };
+/// ```
+///
+/// # Panics
+///
+/// Panics if the renderer does not provide a [`WindowSizeProvider`].
+pub fn use_window_size(cx: &ScopeState) -> ReadOnlySignal<(u32, u32)> {
+    let (size, _subscription) = cx.use_hook(|| {
+        let provider = cx
+            .consume_context::<Rc<dyn WindowSizeProvider>>()
+            .expect("window size provider not provided");
+        let size = Signal::new(provider.size());
+        let subscription = provider.subscribe(Box::new(move |new| {
+            if *size.peek() != new {
+                size.set(new);
+            }
+        }));
+        (size, subscription)
+    });
+
+    ReadOnlySignal::new(*size)
+}
@@ -31,7 +31,7 @@ export function setAttributeInner(node, field, value, ns) {
         node.defaultSelected = truthy(value);
         break;
       case "dangerous_inner_html":
-        node.innerHTML = value;
+        node.innerHTML = trustedHTML(value);
         break;
       default:
         // https://github.com/facebook/react/blob/8b88ac2592c5f555f315f9440cbb665dd1e7457a/packages/react-dom/src/shared/DOMProperty.js#L352-L364
@@ -77,3 +77,113 @@ const bool_attrs = {
 function truthy(val) {
   return val === "true" || val === true;
 }
+
+// Pages that enforce Trusted Types only accept HTML created by a policy. The renderer hands its policy to every copy
+// of this file with `setTrustedTypesPolicy`
+let trustedTypesPolicy = null;
+
+export function setTrustedTypesPolicy(policy) {
+  trustedTypesPolicy = policy;
+}
+
+function trustedHTML(html) {
+  return trustedTypesPolicy ? trustedTypesPolicy.createHTML(html) : html;
+}
+
+// Moves nodes that are already mounted in front of the reference node, or to the end of the parent if the reference
+// is null. Browsers that support `moveBefore` keep the state of moved nodes, like running CSS animations and the
+// document of iframes. Other browsers (and moves `moveBefore` rejects) fall back to re-inserting the node.
+function moveNodes(parent, nodes, reference) {
+  for (const node of nodes) {
+    if (parent.moveBefore) {
+      try {
+        parent.moveBefore(node, reference);
+        continue;
+      } catch (e) {}
+    }
+    parent.insertBefore(node, reference);
+  }
+}
+
+export function moveBefore(reference, nodes) {
+  moveNodes(reference.parentNode, nodes, reference);
+}
+
+export function moveAfter(reference, nodes) {
+  moveNodes(reference.parentNode, nodes, reference.nextSibling);
+}
+
+// Plugins that run before and after each batch of mutations is applied to the DOM
+const mutationPlugins = [];
+
+export function registerMutationPlugin(plugin) {
+  mutationPlugins.push(plugin);
+}
+
+export function beforeMutations() {
+  for (const plugin of mutationPlugins) {
+    if (plugin.before) {
+      plugin.before();
+    }
+  }
+}
+
+export function afterMutations() {
+  for (const plugin of mutationPlugins) {
+    if (plugin.after) {
+      plugin.after();
+    }
+  }
+}
+
+// Moves focus back to an element with the dioxus-restore-focus attribute if it lost focus because it was moved or
+// re-created while the mutations were applied. The attribute value identifies the element across re-creations.
+class FocusRestorer {
+  constructor() {
+    this.saved = null;
+  }
+
+  before() {
+    this.saved = null;
+    const active = document.activeElement;
+    if (!active || !active.hasAttribute || !active.hasAttribute("dioxus-restore-focus")) {
+      return;
+    }
+    this.saved = {
+      key: active.getAttribute("dioxus-restore-focus"),
+      selection: null,
+    };
+    try {
+      // Only text like inputs have a selection, everything else returns null or throws
+      if (active.selectionStart !== null && active.selectionStart !== undefined) {
+        this.saved.selection = [
+          active.selectionStart,
+          active.selectionEnd,
+          active.selectionDirection,
+        ];
+      }
+    } catch (e) { }
+  }
+
+  after() {
+    const saved = this.saved;
+    this.saved = null;
+    if (!saved) {
+      return;
+    }
+    const element = document.querySelector(
+      `[dioxus-restore-focus="${CSS.escape(saved.key)}"]`
+    );
+    if (!element || document.activeElement === element) {
+      return;
+    }
+    element.focus({ preventScroll: true });
+    if (saved.selection && element.setSelectionRange) {
+      try {
+        element.setSelectionRange(...saved.selection);
+      } catch (e) { }
+    }
+  }
+}
+
+registerMutationPlugin(new FocusRestorer());
@@ -152,11 +152,18 @@ async function handler(event, name, bubbles, config) {
   }
 }
 
-function find_real_id(target) {
-  let realId = null;
+// elements with a listener have a data-dioxus-id attribute. Bubbling events that are delegated to
+// the root don't mark their elements, so fall back to the id the element was created with and let
+// rust bubble the event up to the listener
+function element_id(target) {
   if (target instanceof Element) {
-    realId = target.getAttribute(`data-dioxus-id`);
+    return target.getAttribute(`data-dioxus-id`) ?? target.dioxusId;
   }
+  return null;
+}
+
+function find_real_id(target) {
+  let realId = element_id(target);
   // walk the tree to find the real element
   while (realId == null) {
     // we've reached the root we don't want to send an event
@@ -165,9 +172,7 @@ function find_real_id(target) {
     }
 
     target = target.parentElement;
-    if (target instanceof Element) {
-      realId = target.getAttribute(`data-dioxus-id`);
-    }
+    realId = element_id(target);
   }
   return realId;
 }
@@ -179,14 +184,19 @@ class ListenerMap {
     // non bubbling events listen at the element the listener was created at
     this.local = {};
     this.root = null;
+    // every listener shares these two callbacks instead of allocating a closure per listener, and
+    // removing a listener detaches the same callback that was attached. This doesn't change how
+    // events are serialized or sent to rust, each event the page handles is still one message
+    this.bubblingHandler = (event) => handler(event, event.type, true, config);
+    this.nonBubblingHandler = (event) => handler(event, event.type, false, config);
   }
 
-  create(event_name, element, bubbles, handler) {
+  create(event_name, element, bubbles) {
     if (bubbles) {
       if (this.global[event_name] === undefined) {
         this.global[event_name] = {};
         this.global[event_name].active = 1;
-        this.root.addEventListener(event_name, handler);
+        this.root.addEventListener(event_name, this.bubblingHandler);
       } else {
         this.global[event_name].active++;
       }
@@ -196,25 +206,31 @@ class ListenerMap {
       if (!this.local[id]) {
         this.local[id] = {};
       }
-      element.addEventListener(event_name, handler);
+      this.local[id][event_name] = true;
+      element.addEventListener(event_name, this.nonBubblingHandler);
     }
   }
 
   remove(element, event_name, bubbles) {
     if (bubbles) {
+      if (this.global[event_name] === undefined) {
+        return;
+      }
       this.global[event_name].active--;
       if (this.global[event_name].active === 0) {
-        this.root.removeEventListener(event_name, this.global[event_name].callback);
+        this.root.removeEventListener(event_name, this.bubblingHandler);
         delete this.global[event_name];
       }
     }
     else {
       const id = element.getAttribute("data-dioxus-id");
-      delete this.local[id][event_name];
-      if (this.local[id].length === 0) {
-        delete this.local[id];
+      if (this.local[id]) {
+        delete this.local[id][event_name];
+        if (Object.keys(this.local[id]).length === 0) {
+          delete this.local[id];
+        }
       }
-      element.removeEventListener(event_name, this.global[event_name].callback);
+      element.removeEventListener(event_name, this.nonBubblingHandler);
     }
   }
 
@@ -258,6 +274,261 @@ window.interpreter.initialize = function (root) {
   listeners.root = root;
 }
 
+/// Register a plugin with `before` and `after` methods that run around each batch of mutations
+window.interpreter.registerMutationPlugin = registerMutationPlugin;
+
+window.interpreter.observers = {};
+
+window.interpreter.observeResize = function (id, key, callback) {
+  const node = nodes[id];
+  if (!node || typeof ResizeObserver === "undefined") {
+    return false;
+  }
+  const observer = new ResizeObserver((entries) => {
+    for (const entry of entries) {
+      const rect = entry.contentRect;
+      callback({
+        origin: [rect.x, rect.y],
+        size: [rect.width, rect.height],
+      });
+    }
+  });
+  observer.observe(node);
+  window.interpreter.observers[key] = observer;
+  return true;
+}
+
+window.interpreter.observeIntersection = function (id, key, callback) {
+  const node = nodes[id];
+  if (!node || typeof IntersectionObserver === "undefined") {
+    return false;
+  }
+  const observer = new IntersectionObserver((entries) => {
+    for (const entry of entries) {
+      callback(entry.isIntersecting);
+    }
+  });
+  observer.observe(node);
+  window.interpreter.observers[key] = observer;
+  return true;
+}
+
+window.interpreter.observeFocus = function (id, key, callback) {
+  const node = nodes[id];
+  if (!node) {
+    return false;
+  }
+  const focusin = () => callback(true);
+  const focusout = (event) => {
+    // Focus moving between descendants of the node does not change anything
+    if (!node.contains(event.relatedTarget)) {
+      callback(false);
+    }
+  };
+  node.addEventListener("focusin", focusin);
+  node.addEventListener("focusout", focusout);
+  callback(node.contains(document.activeElement));
+  window.interpreter.observers[key] = {
+    disconnect() {
+      node.removeEventListener("focusin", focusin);
+      node.removeEventListener("focusout", focusout);
+    },
+  };
+  return true;
+}
+
+const FOCUSABLE =
+  'a[href], area[href], button:not([disabled]), input:not([disabled]):not([type="hidden"]), ' +
+  'select:not([disabled]), textarea:not([disabled]), iframe, [contenteditable], [tabindex]:not([tabindex="-1"])';
+
+function focusableDescendants(node) {
+  return Array.from(node.querySelectorAll(FOCUSABLE)).filter(
+    (element) => element.tabIndex >= 0
+  );
+}
+
+window.interpreter.trapFocus = function (id, key) {
+  const node = nodes[id];
+  if (!node) {
+    return false;
+  }
+  const previous = document.activeElement;
+  const keydown = (event) => {
+    if (event.key !== "Tab") {
+      return;
+    }
+    const focusable = focusableDescendants(node);
+    if (focusable.length === 0) {
+      event.preventDefault();
+      return;
+    }
+    const first = focusable[0];
+    const last = focusable[focusable.length - 1];
+    const outside = !node.contains(document.activeElement);
+    const [edge, wrapTo] = event.shiftKey ? [first, last] : [last, first];
+    if (outside || document.activeElement === edge) {
+      event.preventDefault();
+      wrapTo.focus();
+    }
+  };
+  document.addEventListener("keydown", keydown);
+  // Move focus into the node if it is somewhere else
+  if (!node.contains(document.activeElement)) {
+    focusableDescendants(node)[0]?.focus();
+  }
+  window.interpreter.observers[key] = {
+    disconnect() {
+      document.removeEventListener("keydown", keydown);
+      previous?.focus?.();
+    },
+  };
+  return true;
+}
+
+window.interpreter.observeScroll = function (id, key, callback) {
+  const node = nodes[id];
+  if (!node) {
+    return false;
+  }
+  const scroll = (event) => {
+    // Only scrolling the page or an ancestor of the node moves it
+    if (event.target.contains?.(node)) {
+      callback(true);
+    }
+  };
+  // Scroll events on elements do not bubble, so listen in the capture phase
+  document.addEventListener("scroll", scroll, true);
+  window.interpreter.observers[key] = {
+    disconnect() {
+      document.removeEventListener("scroll", scroll, true);
+    },
+  };
+  return true;
+}
+
+window.interpreter.observeContextMenu = function (id, key, callback) {
+  const node = nodes[id];
+  if (!node) {
+    return false;
+  }
+  const contextmenu = (event) => {
+    // The native menu replaces the menu of the webview
+    event.preventDefault();
+    event.stopPropagation();
+    callback(true);
+  };
+  node.addEventListener("contextmenu", contextmenu);
+  window.interpreter.observers[key] = {
+    disconnect() {
+      node.removeEventListener("contextmenu", contextmenu);
+    },
+  };
+  return true;
+}
+
+window.interpreter.observeBounds = function (id, key, callback) {
+  const node = nodes[id];
+  if (!node || typeof ResizeObserver === "undefined") {
+    return false;
+  }
+  let last = null;
+  const update = () => {
+    const rect = node.getBoundingClientRect();
+    const bounds = [rect.x, rect.y, rect.width, rect.height];
+    if (last === null || bounds.some((value, i) => value !== last[i])) {
+      last = bounds;
+      callback({
+        origin: [rect.x, rect.y],
+        size: [rect.width, rect.height],
+      });
+    }
+  };
+  // Moving the node without resizing it fires no event, so also check after the page changes
+  const resize = new ResizeObserver(update);
+  resize.observe(node);
+  resize.observe(document.documentElement);
+  const mutations = new MutationObserver(() => requestAnimationFrame(update));
+  mutations.observe(document.body, {
+    childList: true,
+    subtree: true,
+    attributes: true,
+  });
+  document.addEventListener("scroll", update, true);
+  window.addEventListener("resize", update);
+  update();
+  window.interpreter.observers[key] = {
+    disconnect() {
+      resize.disconnect();
+      mutations.disconnect();
+      document.removeEventListener("scroll", update, true);
+      window.removeEventListener("resize", update);
+    },
+  };
+  return true;
+}
+
+window.interpreter.observeNetwork = function (key, callback) {
+  // Only some browsers expose the connection type
+  const connection = navigator.connection;
+  const report = () =>
+    callback({
+      online: navigator.onLine,
+      connection: connection?.type ?? "unknown",
+    });
+  window.addEventListener("online", report);
+  window.addEventListener("offline", report);
+  connection?.addEventListener?.("change", report);
+  report();
+  window.interpreter.observers[key] = {
+    disconnect() {
+      window.removeEventListener("online", report);
+      window.removeEventListener("offline", report);
+      connection?.removeEventListener?.("change", report);
+    },
+  };
+  return true;
+}
+
+window.interpreter.observeBattery = function (key, callback) {
+  if (typeof navigator.getBattery !== "function") {
+    callback(null);
+    return false;
+  }
+  let battery = null;
+  let disconnected = false;
+  const report = () =>
+    callback({ level: battery.level, charging: battery.charging });
+  navigator.getBattery().then(
+    (manager) => {
+      // The observer may have been disconnected while the battery was resolved
+      if (disconnected) {
+        return;
+      }
+      battery = manager;
+      battery.addEventListener("levelchange", report);
+      battery.addEventListener("chargingchange", report);
+      report();
+    },
+    () => callback(null)
+  );
+  window.interpreter.observers[key] = {
+    disconnect() {
+      disconnected = true;
+      battery?.removeEventListener("levelchange", report);
+      battery?.removeEventListener("chargingchange", report);
+    },
+  };
+  return true;
+}
+
+window.interpreter.unobserve = function (key) {
+  const observer = window.interpreter.observers[key];
+  if (observer) {
+    observer.disconnect();
+    delete window.interpreter.observers[key];
+  }
+}
+
 window.interpreter.getClientRect = function (id) {
   const node = nodes[id];
   if (!node) {
@@ -271,6 +542,34 @@ window.interpreter.getClientRect = function (id) {
   };
 }
 
+/// Only print the element with this id, or the whole page if the id is null
+window.interpreter.setPrintTarget = function (id) {
+  document.querySelectorAll("[data-dioxus-print]").forEach((node) => node.removeAttribute("data-dioxus-print"));
+  let style = document.getElementById("dioxus-print-style");
+  if (id === null) {
+    if (style) {
+      style.remove();
+    }
+    return true;
+  }
+  const node = nodes[id];
+  if (!node || !node.setAttribute) {
+    return false;
+  }
+  node.setAttribute("data-dioxus-print", "");
+  if (!style) {
+    style = document.createElement("style");
+    style.id = "dioxus-print-style";
+    style.textContent = `@media print {
+  body * { visibility: hidden; }
+  [data-dioxus-print], [data-dioxus-print] * { visibility: visible; }
+  [data-dioxus-print] { position: absolute; left: 0; top: 0; }
+}`;
+    document.head.appendChild(style);
+  }
+  return true;
+}
+
 window.interpreter.scrollTo = function (id, behavior) {
   const node = nodes[id];
   if (!node) {
@@ -331,12 +630,45 @@ function get_mouse_data(event) {
   };
 }
 
+// Files larger than this are listed in the clipboard types, but their contents are not sent to the renderer
+const MAX_SERIALIZED_FILE_SIZE = 16 * 1024 * 1024;
+
+async function serializeFileList(fileList) {
+  const files = {};
+  for (let i = 0; i < fileList.length; i++) {
+    const file = fileList[i];
+    if (file.size > MAX_SERIALIZED_FILE_SIZE) {
+      continue;
+    }
+    files[file.name] = Array.from(new Uint8Array(await file.arrayBuffer()));
+  }
+  return { files };
+}
+
+async function serialize_clipboard_data(clipboardData) {
+  if (!clipboardData) {
+    return {};
+  }
+  const text = clipboardData.getData("text/plain");
+  const html = clipboardData.getData("text/html");
+  let files = null;
+  if (clipboardData.files && clipboardData.files.length > 0) {
+    files = await serializeFileList(clipboardData.files);
+  }
+  return {
+    types: Array.from(clipboardData.types),
+    text: text || null,
+    html: html || null,
+    files,
+  };
+}
+
 async function serialize_event(event) {
   switch (event.type) {
     case "copy":
     case "cut":
-    case "past": {
-      return {};
+    case "paste": {
+      return serialize_clipboard_data(event.clipboardData);
     }
     case "compositionend":
     case "compositionstart":
@@ -17,5 +17,7 @@ pub mod minimal_bindings {
     #[wasm_bindgen(module = "/src/common.js")]
     extern "C" {
         pub fn setAttributeInner(node: JsValue, name: &str, value: JsValue, ns: Option<&str>);
+
+        pub fn setTrustedTypesPolicy(policy: &JsValue);
     }
 }
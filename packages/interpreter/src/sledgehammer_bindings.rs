@@ -3,6 +3,8 @@ use js_sys::Function;
 #[cfg(feature = "web")]
 use sledgehammer_bindgen::bindgen;
 #[cfg(feature = "web")]
+use wasm_bindgen::JsValue;
+#[cfg(feature = "web")]
 use web_sys::Node;
 
 #[cfg(feature = "web")]
@@ -127,6 +129,9 @@ mod js {
     export function get_node(id) {
         return nodes[id];
     }
+    export function set_trusted_types_policy(policy) {
+        setTrustedTypesPolicy(policy);
+    }
     export function initialize(root, handler) {
         listeners.handler = handler;
         nodes = [root];
@@ -152,6 +157,9 @@ mod js {
         #[wasm_bindgen]
         pub fn get_node(id: u32) -> Node;
 
+        #[wasm_bindgen]
+        pub fn set_trusted_types_policy(policy: &JsValue);
+
         #[wasm_bindgen]
         pub fn initialize(root: Node, handler: &Function);
     }
@@ -177,6 +185,12 @@ mod js {
     fn insert_before(id: u32, n: u16) {
         "{nodes[$id$].before(...stack.splice(stack.length-$n$));}"
     }
+    fn move_after(id: u32, n: u16) {
+        "{moveAfter(nodes[$id$], stack.splice(stack.length-$n$));}"
+    }
+    fn move_before(id: u32, n: u16) {
+        "{moveBefore(nodes[$id$], stack.splice(stack.length-$n$));}"
+    }
     fn remove(id: u32) {
         "{node = nodes[$id$]; if (node !== undefined) { if (node.listening) { listeners.removeAllNonBubbling(node); } node.remove(); }}"
     }
@@ -216,7 +230,7 @@ mod js {
                         node.selected = false;
                         break;
                     case "dangerous_inner_html":
-                        node.innerHTML = "";
+                        node.textContent = "";
                         break;
                     default:
                         node.removeAttribute(field);
@@ -251,6 +265,12 @@ mod js {
     fn load_template(tmpl_id: u16, index: u16, id: u32) {
         "{node = templates[$tmpl_id$][$index$].cloneNode(true); nodes[$id$] = node; stack.push(node);}"
     }
+    fn before_mutations() {
+        "{beforeMutations();}"
+    }
+    fn after_mutations() {
+        "{afterMutations();}"
+    }
 }
 
 #[cfg(feature = "binary-protocol")]
@@ -293,6 +313,12 @@ pub mod binary_protocol {
         fn insert_before(id: u32, n: u16) {
             "{nodes[$id$].before(...stack.splice(stack.length-$n$));}"
         }
+        fn move_after(id: u32, n: u16) {
+            "{moveAfter(nodes[$id$], stack.splice(stack.length-$n$));}"
+        }
+        fn move_before(id: u32, n: u16) {
+            "{moveBefore(nodes[$id$], stack.splice(stack.length-$n$));}"
+        }
         fn remove(id: u32) {
             "{node = nodes[$id$]; if (node !== undefined) { if (node.listening) { listeners.removeAllNonBubbling(node); } node.remove(); }}"
         }
@@ -337,13 +363,17 @@ pub mod binary_protocol {
                     })
                 );
             } else {
-                listeners.create(event_name, node, bubbles, (event) => {
-                    handler(event, event_name, bubbles, config);
-                });
+                listeners.create(event_name, node, bubbles);
             }"#
         }
         fn remove_event_listener(event_name: &str<u8, evt>, id: u32, bubbles: u8) {
-            "{node = nodes[$id$]; node.listening -= 1; node.removeAttribute('data-dioxus-id'); listeners.remove(node, $event_name$, $bubbles$);}"
+            "{node = nodes[$id$]; node.listening -= 1; listeners.remove(node, $event_name$, $bubbles$ == 1); if (node.listening === 0) { node.removeAttribute('data-dioxus-id'); }}"
+        }
+        fn listen_at_root(event_name: &str<u8, evt>) {
+            "{listeners.create($event_name$, null, true);}"
+        }
+        fn unlisten_at_root(event_name: &str<u8, evt>) {
+            "{listeners.remove(null, $event_name$, true);}"
         }
         fn set_text(id: u32, text: &str) {
             "{nodes[$id$].textContent = $text$;}"
@@ -369,7 +399,7 @@ pub mod binary_protocol {
                             node.selected = false;
                             break;
                         case "dangerous_inner_html":
-                            node.innerHTML = "";
+                            node.textContent = "";
                             break;
                         default:
                             node.removeAttribute(field);
@@ -383,7 +413,7 @@ pub mod binary_protocol {
             }"#
         }
         fn assign_id(array: &[u8], id: u32) {
-            "{nodes[$id$] = LoadChild($array$);}"
+            "{node = LoadChild($array$); node.dioxusId = $id$; nodes[$id$] = node;}"
         }
         fn hydrate_text(array: &[u8], value: &str, id: u32) {
             r#"{
@@ -402,10 +432,16 @@ pub mod binary_protocol {
             "{els = stack.splice(stack.length - $n$); node = LoadChild($array$); node.replaceWith(...els);}"
         }
         fn load_template(tmpl_id: u16, index: u16, id: u32) {
-            "{node = templates[$tmpl_id$][$index$].cloneNode(true); nodes[$id$] = node; stack.push(node);}"
+            "{node = templates[$tmpl_id$][$index$].cloneNode(true); node.dioxusId = $id$; nodes[$id$] = node; stack.push(node);}"
         }
         fn add_templates(tmpl_id: u16, len: u16) {
             "{templates[$tmpl_id$] = stack.splice(stack.length-$len$);}"
         }
+        fn before_mutations() {
+            "{beforeMutations();}"
+        }
+        fn after_mutations() {
+            "{afterMutations();}"
+        }
     }
 }
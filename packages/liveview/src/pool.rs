@@ -339,6 +339,8 @@ fn apply_edits(
             ReplacePlaceholder { path, m } => channel.replace_placeholder(path, m as u16),
             InsertAfter { id, m } => channel.insert_after(id.0 as u32, m as u16),
             InsertBefore { id, m } => channel.insert_before(id.0 as u32, m as u16),
+            MoveAfter { id, m } => channel.move_after(id.0 as u32, m as u16),
+            MoveBefore { id, m } => channel.move_before(id.0 as u32, m as u16),
             SetAttribute {
                 name,
                 value,
@@ -163,7 +163,7 @@ impl DioxusState {
                     }
                     rdom.get_mut(old_node_id).unwrap().remove();
                 }
-                InsertAfter { id, m } => {
+                InsertAfter { id, m } | MoveAfter { id, m } => {
                     let new_nodes = self.stack.split_off(self.stack.len() - m);
                     let old_node_id = self.element_to_node_id(id);
                     for new in new_nodes.into_iter().rev() {
@@ -171,7 +171,7 @@ impl DioxusState {
                         node.insert_after(old_node_id);
                     }
                 }
-                InsertBefore { id, m } => {
+                InsertBefore { id, m } | MoveBefore { id, m } => {
                     let new_nodes = self.stack.split_off(self.stack.len() - m);
                     let old_node_id = self.element_to_node_id(id);
                     for new in new_nodes {
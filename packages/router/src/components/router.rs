@@ -1,7 +1,9 @@
 use dioxus::prelude::*;
 use std::{cell::RefCell, str::FromStr};
 
-use crate::{prelude::Outlet, routable::Routable, router_cfg::RouterConfig};
+use crate::{
+    preload::PreloadedData, prelude::Outlet, routable::Routable, router_cfg::RouterConfig,
+};
 
 /// The config for [`Router`].
 pub struct RouterConfigFactory<R: Routable> {
@@ -117,21 +119,29 @@ where
 {
     use crate::prelude::{outlet::OutletContext, RouterContext};
 
+    let preloaded = use_context_provider(cx, PreloadedData::default);
     use_context_provider(cx, || {
-        RouterContext::new(
-            (cx.props
-                .config
-                .config
-                .take()
-                .expect("use_context_provider ran twice"))(),
-            cx.schedule_update_any(),
-        )
+        let mut config = (cx
+            .props
+            .config
+            .config
+            .take()
+            .expect("use_context_provider ran twice"))();
+        let loaders = config.take_loaders();
+        let router = RouterContext::new(config, cx.schedule_update_any());
+        preloaded.start(cx, &router.current::<R>(), &loaders);
+        router
     });
     use_context_provider(cx, || OutletContext::<R> {
         current_level: 0,
         _marker: std::marker::PhantomData,
     });
 
+    // Don't render the routes until the data they need is loaded
+    if preloaded.is_loading() {
+        cx.suspend()?;
+    }
+
     render! {
         Outlet::<R> {}
     }
@@ -144,21 +154,29 @@ where
     <R as FromStr>::Err: std::fmt::Display,
     R: serde::Serialize + serde::de::DeserializeOwned,
 {
+    let preloaded = use_context_provider(cx, PreloadedData::default);
     use_context_provider(cx, || {
-        RouterContext::new(
-            (cx.props
-                .config
-                .config
-                .take()
-                .expect("use_context_provider ran twice"))(),
-            cx.schedule_update_any(),
-        )
+        let mut config = (cx
+            .props
+            .config
+            .config
+            .take()
+            .expect("use_context_provider ran twice"))();
+        let loaders = config.take_loaders();
+        let router = RouterContext::new(config, cx.schedule_update_any());
+        preloaded.start(cx, &router.current::<R>(), &loaders);
+        router
     });
     use_context_provider(cx, || OutletContext::<R> {
         current_level: 0,
         _marker: std::marker::PhantomData,
     });
 
+    // Don't render the routes until the data they need is loaded
+    if preloaded.is_loading() {
+        cx.suspend()?;
+    }
+
     render! {
         Outlet::<R> {}
     }
@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use dioxus::prelude::ScopeState;
+
+use crate::preload::PreloadedData;
+
+/// A hook that provides access to the data a loader registered with
+/// [`RouterConfig::loader`](crate::prelude::RouterConfig::loader) fetched for the initial route.
+///
+/// The router runs the loaders of the initial route and all of its parents in parallel before
+/// it renders anything, so nested routes don't have to wait for each other to mount before they
+/// can start fetching.
+///
+/// # Return values
+/// - None, when no loader for `T` matched the initial route, or when not called inside a
+///   [`Router`](crate::prelude::Router).
+/// - Otherwise the loaded value.
+///
+/// # Example
+/// ```rust
+/// # use std::rc::Rc;
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+/// }
+///
+/// struct Greeting(String);
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     render! {
+///         Router::<Route> {
+///             config: || RouterConfig::default().loader(|route: &Route| match route {
+///                 Route::Index {} => Some(async { Greeting("Hello".to_string()) }),
+///             })
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Index(cx: Scope) -> Element {
+///     let greeting: Option<Rc<Greeting>> = use_preloaded(cx);
+///     render! {
+///         h1 { "{greeting.unwrap().0}" }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_preloaded<T: 'static>(cx: &ScopeState) -> Option<Rc<T>> {
+    let data = cx.use_hook(|| cx.consume_context::<PreloadedData>());
+    data.as_ref()?.get::<T>()
+}
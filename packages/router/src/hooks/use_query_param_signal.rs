@@ -0,0 +1,148 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc, str::FromStr};
+
+use dioxus::prelude::ScopeState;
+use dioxus_signals::{use_effect, use_signal, Signal};
+
+use crate::prelude::*;
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that binds a signal to a query parameter of the current route.
+///
+/// The signal starts with the value of the parameter in the current url, or [`None`] if the parameter is missing or
+/// can't be parsed. Writing to the signal replaces the current url with one where the parameter is updated, and
+/// setting it to [`None`] removes the parameter. When the url changes, for example when the user goes back in the
+/// history or reloads the page, the signal is updated to match. This keeps UI state like the selected tab or an open
+/// dialog in the url on every history provider.
+///
+/// The route must keep the parameter when it is parsed and turned back into a string, so it needs a query segment
+/// for it, like `#[route("/settings?:tab")]`.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`] component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/?:tab")]
+///     Settings { tab: String },
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     render! {
+///         Router::<Route> {
+///             config: || RouterConfig::default().history(MemoryHistory::with_initial_path(Route::Settings { tab: "2".to_string() }))
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Settings(cx: Scope, tab: String) -> Element {
+///     let selected = use_query_param_signal::<u8>(cx, "tab");
+///     let current = selected.read().unwrap_or(0);
+///
+///     render! {
+///         button { onclick: move |_| selected.set(Some(1)), "Profile" }
+///         button { onclick: move |_| selected.set(Some(2)), "Privacy" }
+///         p { "Selected tab: {current}" }
+///     }
+/// }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # let _ = vdom.rebuild();
+/// # assert!(dioxus_ssr::render(&vdom).ends_with("<p>Selected tab: 2</p>"));
+/// ```
+#[must_use]
+pub fn use_query_param_signal<T>(cx: &ScopeState, name: &'static str) -> Signal<Option<T>>
+where
+    T: FromStr + Display + PartialEq + Clone + 'static,
+{
+    let router = use_router_internal(cx)
+        .as_ref()
+        .expect("`use_query_param_signal` must have access to a parent router");
+
+    let from_url = parse_query_param::<T>(&router.current_route_string(), name);
+    let signal = use_signal(cx, || from_url.clone());
+
+    // The last value that the url and the signal agreed on. Comparing against it tells which side changed
+    let synced = cx.use_hook(|| Rc::new(RefCell::new(from_url.clone())));
+
+    // The url changed, through a link or the history buttons of the browser
+    if *synced.borrow() != from_url {
+        *synced.borrow_mut() = from_url.clone();
+        if *signal.peek() != from_url {
+            signal.set(from_url);
+        }
+    }
+
+    // The signal changed, so write it to the url
+    let router = router.clone();
+    let synced = synced.clone();
+    use_effect(cx, move || {
+        let value = signal.read().clone();
+        if *synced.borrow() == value {
+            return;
+        }
+        *synced.borrow_mut() = value.clone();
+
+        let url = with_query_param(
+            &router.current_route_string(),
+            name,
+            value.map(|value| value.to_string()).as_deref(),
+        );
+        match router.route_from_str(&url) {
+            Ok(route) => {
+                router.replace(IntoRoutable::Route(route));
+            }
+            Err(err) => {
+                tracing::error!("Failed to write the query parameter `{name}` to the url: {err}")
+            }
+        }
+    });
+
+    signal
+}
+
+fn parse_query_param<T: FromStr>(route: &str, name: &str) -> Option<T> {
+    let (path, _) = split_hash(route);
+    let (_, query) = path.split_once('?')?;
+    let value = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)?
+        .1;
+    urlencoding::decode(value).ok()?.parse().ok()
+}
+
+fn with_query_param(route: &str, name: &str, value: Option<&str>) -> String {
+    let (path, hash) = split_hash(route);
+    let (path, query) = path.split_once('?').unwrap_or((path, ""));
+
+    let mut pairs: Vec<String> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(name))
+        .map(str::to_string)
+        .collect();
+    if let Some(value) = value {
+        pairs.push(format!("{name}={}", urlencoding::encode(value)));
+    }
+
+    let mut url = path.to_string();
+    if !pairs.is_empty() {
+        url.push('?');
+        url.push_str(&pairs.join("&"));
+    }
+    url.push_str(hash);
+    url
+}
+
+/// Split a route into the part before the hash fragment and the fragment, including the `#`
+fn split_hash(route: &str) -> (&str, &str) {
+    match route.find('#') {
+        Some(index) => route.split_at(index),
+        None => (route, ""),
+    }
+}
@@ -39,6 +39,8 @@ mod contexts {
 
 mod router_cfg;
 
+mod preload;
+
 mod history;
 
 /// Hooks for interacting with the router in components.
@@ -51,6 +53,12 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_preloaded;
+    pub use use_preloaded::*;
+
+    mod use_query_param_signal;
+    pub use use_query_param_signal::*;
 }
 
 pub use hooks::router;
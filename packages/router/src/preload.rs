@@ -0,0 +1,145 @@
+//! Runs the loaders registered with [`RouterConfig::loader`](crate::prelude::RouterConfig::loader) for the initial route.
+
+use std::{
+    any::{Any, TypeId},
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+};
+
+use dioxus::prelude::ScopeState;
+use futures_util::future::join_all;
+
+use crate::routable::Routable;
+
+type LoaderFuture = Pin<Box<dyn Future<Output = Rc<dyn Any>>>>;
+
+/// A type-erased loader registered with [`RouterConfig::loader`](crate::prelude::RouterConfig::loader).
+pub(crate) struct RouteLoader<R> {
+    type_id: TypeId,
+    type_name: &'static str,
+    load: Box<dyn Fn(&R) -> Option<LoaderFuture>>,
+}
+
+impl<R: Routable> RouteLoader<R> {
+    pub(crate) fn new<T, F>(load: impl Fn(&R) -> Option<F> + 'static) -> Self
+    where
+        T: 'static,
+        F: Future<Output = T> + 'static,
+    {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            load: Box::new(move |route| {
+                let future = load(route)?;
+                Some(Box::pin(async move { Rc::new(future.await) as Rc<dyn Any> }) as LoaderFuture)
+            }),
+        }
+    }
+}
+
+/// The data loaded for the initial route. The router provides this as a context.
+#[derive(Clone, Default)]
+pub(crate) struct PreloadedData {
+    values: Rc<RefCell<HashMap<TypeId, Rc<dyn Any>>>>,
+    loading: Rc<Cell<bool>>,
+}
+
+impl PreloadedData {
+    pub(crate) fn get<T: 'static>(&self) -> Option<Rc<T>> {
+        self.values
+            .borrow()
+            .get(&TypeId::of::<T>())?
+            .clone()
+            .downcast()
+            .ok()
+    }
+
+    pub(crate) fn is_loading(&self) -> bool {
+        self.loading.get()
+    }
+
+    /// Start every loader that applies to `route` or one of its parents in parallel, and rerun the router scope once all of them finished.
+    pub(crate) fn start<R: Routable>(
+        &self,
+        cx: &ScopeState,
+        route: &R,
+        loaders: &[RouteLoader<R>],
+    ) {
+        // Walk from the leaf to the root, so if loaders of the same type match several levels the most specific one wins
+        let mut pending = Vec::new();
+        let mut current = Some(route.clone());
+        while let Some(route) = current {
+            for loader in loaders {
+                if let Some(future) = (loader.load)(&route) {
+                    let (type_id, type_name) = (loader.type_id, loader.type_name);
+                    pending.push(async move {
+                        let start = now_ms();
+                        let value = future.await;
+                        (type_id, type_name, value, now_ms() - start)
+                    });
+                }
+            }
+            current = route.parent();
+        }
+
+        if pending.is_empty() {
+            return;
+        }
+
+        self.loading.set(true);
+        let data = self.clone();
+        let update = cx.schedule_update();
+        #[cfg(debug_assertions)]
+        let route = route.to_string();
+        cx.spawn(async move {
+            #[cfg(debug_assertions)]
+            let start = now_ms();
+            let results = join_all(pending).await;
+
+            #[cfg(debug_assertions)]
+            {
+                let total = now_ms() - start;
+                let sequential: f64 = results.iter().map(|(_, _, _, elapsed)| elapsed).sum();
+                let loaders: Vec<_> = results
+                    .iter()
+                    .map(|(_, name, _, elapsed)| format!("{name}: {elapsed:.1}ms"))
+                    .collect();
+                tracing::info!(
+                    "Preloaded {} loaders for {route} in {total:.1}ms ({sequential:.1}ms if run one after another): {}",
+                    results.len(),
+                    loaders.join(", ")
+                );
+            }
+
+            let mut values = data.values.borrow_mut();
+            for (type_id, _, value, _) in results {
+                values.entry(type_id).or_insert(value);
+            }
+            drop(values);
+            data.loading.set(false);
+            update();
+        });
+    }
+}
+
+/// Milliseconds since an arbitrary point in time. Only used to time loaders.
+fn now_ms() -> f64 {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        js_sys::Date::now()
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+            .unwrap_or_default()
+    }
+    #[cfg(all(target_arch = "wasm32", not(feature = "web")))]
+    {
+        0.0
+    }
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use crate::contexts::router::RoutingCallback;
 use crate::history::HistoryProvider;
+use crate::preload::RouteLoader;
 use crate::routable::Routable;
 use dioxus::prelude::*;
 
@@ -28,6 +29,7 @@ pub struct RouterConfig<R: Routable> {
     pub(crate) failure_external_navigation: fn(Scope) -> Element,
     pub(crate) history: Option<Box<dyn AnyHistoryProvider>>,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) loaders: Vec<RouteLoader<R>>,
 }
 
 #[cfg(feature = "serde")]
@@ -41,6 +43,7 @@ where
             failure_external_navigation: FailureExternalNavigation::<R>,
             history: None,
             on_update: None,
+            loaders: Vec::new(),
         }
     }
 }
@@ -77,6 +80,7 @@ where
             failure_external_navigation: FailureExternalNavigation,
             history: None,
             on_update: None,
+            loaders: Vec::new(),
         }
     }
 }
@@ -133,6 +137,30 @@ where
         }
     }
 
+    /// A loader that fetches data for the initial route before the router renders it.
+    ///
+    /// When the app starts, the router calls every loader with the initial route and each of its
+    /// [parents](Routable::parent). All the futures that are returned run in parallel, and the
+    /// router suspends until they are done. That way, nested routes don't wait for their parents to
+    /// mount before they can start loading. Components read the results with [`use_preloaded`].
+    ///
+    /// Return [`None`] for routes the loader doesn't apply to. If several routes in the chain
+    /// return a value of the same type, the most specific route wins.
+    ///
+    /// In debug builds, the time each loader took is logged once they are all done.
+    pub fn loader<T, F>(mut self, loader: impl Fn(&R) -> Option<F> + 'static) -> Self
+    where
+        T: 'static,
+        F: std::future::Future<Output = T> + 'static,
+    {
+        self.loaders.push(RouteLoader::new(loader));
+        self
+    }
+
+    pub(crate) fn take_loaders(&mut self) -> Vec<RouteLoader<R>> {
+        std::mem::take(&mut self.loaders)
+    }
+
     /// The [`HistoryProvider`] the router should use.
     ///
     /// Defaults to a default [`MemoryHistory`].
@@ -1,2 +1,3 @@
 mod link;
 mod outlet;
+mod query_param;
@@ -0,0 +1,70 @@
+#![allow(unused)]
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+fn prepare(path: impl Into<String>) -> VirtualDom {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { path: path.into() });
+    let _ = vdom.rebuild();
+    return vdom;
+
+    #[derive(Routable, Clone)]
+    enum Route {
+        #[route("/?:tab")]
+        Index { tab: String },
+    }
+
+    #[derive(Debug, Props, PartialEq)]
+    struct AppProps {
+        path: String,
+    }
+
+    #[component]
+    fn App(cx: Scope<AppProps>) -> Element {
+        render! {
+            Router::<Route> {
+                config: {
+                    let path = cx.props.path.parse().unwrap();
+                    move || RouterConfig::default().history(MemoryHistory::with_initial_path(path))
+                }
+            }
+        }
+    }
+
+    #[component]
+    fn Index(cx: Scope, tab: String) -> Element {
+        let selected = use_query_param_signal::<u8>(cx, "tab");
+        let selected = match *selected.read() {
+            Some(tab) => tab.to_string(),
+            None => "none".to_string(),
+        };
+
+        render! {
+            p { "{selected}" }
+        }
+    }
+}
+
+#[test]
+fn present() {
+    let vdom = prepare("/?tab=2");
+    let html = dioxus_ssr::render(&vdom);
+
+    assert_eq!(html, "<p>2</p>");
+}
+
+#[test]
+fn missing() {
+    let vdom = prepare("/");
+    let html = dioxus_ssr::render(&vdom);
+
+    assert_eq!(html, "<p>none</p>");
+}
+
+#[test]
+fn unparsable() {
+    let vdom = prepare("/?tab=general");
+    let html = dioxus_ssr::render(&vdom);
+
+    assert_eq!(html, "<p>none</p>");
+}
@@ -0,0 +1,196 @@
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use dioxus_core::prelude::*;
+
+use crate::{get_effect_stack, CopyValue, Effect, ReadOnlySignal, Signal};
+
+/// The state of a future computed by [`use_async_memo`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AsyncState<T, E> {
+    /// The future is still running.
+    Pending,
+    /// The future finished successfully.
+    Ready(T),
+    /// The future failed.
+    Err(E),
+}
+
+impl<T, E> AsyncState<T, E> {
+    /// Returns true if the future is still running.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, AsyncState::Pending)
+    }
+
+    /// Get the value of the future if it finished successfully.
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            AsyncState::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the error of the future if it failed.
+    pub fn err(&self) -> Option<&E> {
+        match self {
+            AsyncState::Err(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for AsyncState<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => AsyncState::Ready(value),
+            Err(error) => AsyncState::Err(error),
+        }
+    }
+}
+
+/// Creates a new async memo. The future returned by `f` will be run immediately and again whenever any signal read by `f` or by the future changes.
+///
+/// When the memo re-runs, the future that was still running is dropped, so a slow stale result can never overwrite a newer one.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// async fn search(query: String) -> Result<Vec<String>, String> {
+///     Ok(vec![format!("Result for {query}")])
+/// }
+///
+/// fn App(cx: Scope) -> Element {
+///     let query = use_signal(cx, String::new);
+///     let results = use_async_memo(cx, move || search(query.value()));
+///
+///     render! {
+///         input { oninput: move |evt| query.set(evt.value.clone()) }
+///         if let Some(results) = results.read().ready() {
+///             for result in results.iter() {
+///                 p { "{result}" }
+///             }
+///         } else if let Some(error) = results.read().err() {
+///             "Search failed: {error}"
+///         } else {
+///             "Searching..."
+///         }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_async_memo<T, E, F>(
+    cx: &ScopeState,
+    f: impl FnMut() -> F + 'static,
+) -> ReadOnlySignal<AsyncState<T, E>>
+where
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    *cx.use_hook(|| async_memo(f))
+}
+
+/// Creates a new async memo. The future returned by `f` will be run immediately and again whenever any signal read by `f` or by the future changes.
+///
+/// The memo will be owned by the current component and its future is dropped when the component is dropped.
+pub fn async_memo<T, E, F>(mut f: impl FnMut() -> F + 'static) -> ReadOnlySignal<AsyncState<T, E>>
+where
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    let state = Signal::new(AsyncState::Pending);
+    let running = Rc::new(RunningFuture::default());
+    let effect = Effect {
+        source: current_scope_id().expect("in a virtual dom"),
+        callback: CopyValue::invalid(),
+        effect_stack: get_effect_stack(),
+    };
+
+    effect.callback.value.set(Box::new({
+        let running = running.clone();
+        move || {
+            let generation = running.generation.get() + 1;
+            running.generation.set(generation);
+            let future = f();
+            let current = running.generation.clone();
+            running.replace(Box::pin(async move {
+                let result = future.await;
+                // The future may finish in the same poll that made it stale
+                if current.get() == generation {
+                    state.set(result.into());
+                }
+            }));
+            if !state.peek().is_pending() {
+                state.set(AsyncState::Pending);
+            }
+        }
+    }));
+    effect.try_run();
+
+    // A single task polls whichever future is current, so re-running the memo never spawns or removes tasks
+    spawn(MemoTask { effect, running });
+
+    ReadOnlySignal::new(state)
+}
+
+#[derive(Default)]
+struct RunningFuture {
+    future: RefCell<Option<Pin<Box<dyn Future<Output = ()>>>>>,
+    generation: Rc<Cell<u64>>,
+    waker: RefCell<Option<Waker>>,
+}
+
+impl RunningFuture {
+    fn replace(&self, future: Pin<Box<dyn Future<Output = ()>>>) {
+        let stale = self.future.borrow_mut().replace(future);
+        drop(stale);
+        let waker = self.waker.borrow_mut().take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+struct MemoTask {
+    effect: Effect,
+    running: Rc<RunningFuture>,
+}
+
+impl Future for MemoTask {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        *self.running.waker.borrow_mut() = Some(cx.waker().clone());
+
+        loop {
+            // Take the future out while it is polled, so the effect can replace it if the future writes to a signal it read
+            let future = self.running.future.borrow_mut().take();
+            let mut future = match future {
+                Some(future) => future,
+                None => return Poll::Pending,
+            };
+
+            // Any signal read by the future re-runs the memo
+            {
+                self.effect.effect_stack.effects.write().push(self.effect);
+            }
+            let poll = future.as_mut().poll(cx);
+            {
+                self.effect.effect_stack.effects.write().pop();
+            }
+
+            if poll.is_pending() {
+                let mut current = self.running.future.borrow_mut();
+                if current.is_none() {
+                    *current = Some(future);
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use std::{cell::RefCell, fmt::Display, future::Future, pin::Pin, rc::Rc, time::Duration};
+
+use dioxus_core::prelude::*;
+
+use crate::{CopyValue, ReadOnlySignal, Signal};
+
+/// Whether the signals an [`Autosave`] watches have been saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveStatus {
+    /// Every watched signal is saved.
+    Saved,
+    /// Some signals are being saved right now.
+    Saving,
+    /// The last save failed. The signals that failed to save are saved again on the next flush.
+    Error(String),
+}
+
+impl Default for SaveStatus {
+    fn default() -> Self {
+        Self::Saved
+    }
+}
+
+/// Something that happened to the app that can make an [`Autosave`] flush.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AutosaveTrigger {
+    /// The window lost focus.
+    Blur,
+    /// The page or window was hidden.
+    Hidden,
+    /// The app is about to quit.
+    BeforeQuit,
+    /// The [`AutosaveConfig::interval`] passed.
+    Interval,
+}
+
+/// Configures when an [`Autosave`] flushes. By default it flushes on every [`AutosaveTrigger`] and every 30 seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutosaveConfig {
+    /// How often to flush. `None` disables the interval.
+    pub interval: Option<Duration>,
+    /// Flush when the window loses focus.
+    pub on_blur: bool,
+    /// Flush when the page or window is hidden.
+    pub on_hidden: bool,
+    /// Flush before the app quits.
+    pub on_before_quit: bool,
+}
+
+impl Default for AutosaveConfig {
+    fn default() -> Self {
+        Self {
+            interval: Some(Duration::from_secs(30)),
+            on_blur: true,
+            on_hidden: true,
+            on_before_quit: true,
+        }
+    }
+}
+
+impl AutosaveConfig {
+    /// Set how often to flush. `None` disables the interval.
+    pub fn with_interval(mut self, interval: impl Into<Option<Duration>>) -> Self {
+        self.interval = interval.into();
+        self
+    }
+
+    /// Set whether to flush when the window loses focus.
+    pub fn with_on_blur(mut self, on_blur: bool) -> Self {
+        self.on_blur = on_blur;
+        self
+    }
+
+    /// Set whether to flush when the page or window is hidden.
+    pub fn with_on_hidden(mut self, on_hidden: bool) -> Self {
+        self.on_hidden = on_hidden;
+        self
+    }
+
+    /// Set whether to flush before the app quits.
+    pub fn with_on_before_quit(mut self, on_before_quit: bool) -> Self {
+        self.on_before_quit = on_before_quit;
+        self
+    }
+
+    /// Returns true if the trigger should flush.
+    pub fn flushes_on(&self, trigger: AutosaveTrigger) -> bool {
+        match trigger {
+            AutosaveTrigger::Blur => self.on_blur,
+            AutosaveTrigger::Hidden => self.on_hidden,
+            AutosaveTrigger::BeforeQuit => self.on_before_quit,
+            AutosaveTrigger::Interval => self.interval.is_some(),
+        }
+    }
+}
+
+type SaveFuture = Pin<Box<dyn Future<Output = Result<(), String>>>>;
+
+enum Check {
+    Clean,
+    Dirty(SaveFuture),
+    Dropped,
+}
+
+struct AutosaveState {
+    config: AutosaveConfig,
+    runtime: Rc<Runtime>,
+    targets: Vec<Box<dyn FnMut() -> Check>>,
+    // The number of edits in progress. Triggers wait until every edit is finished
+    editing: usize,
+    // A trigger fired while an edit or flush was in progress
+    pending: bool,
+    flushing: usize,
+}
+
+/// Saves signals that changed when the app loses focus, is hidden, is about to quit, or on an interval.
+///
+/// Renderers create an autosave and connect it to their lifecycle events with their `use_autosave` hook. The autosave is provided as context, so components below it can get it with [`Autosave::current`] to watch signals or render the [`SaveStatus`].
+///
+/// ```rust, ignore
+/// let autosave = use_autosave(cx, AutosaveConfig::default());
+/// let document = use_signal(cx, String::new);
+/// cx.use_hook(|| {
+///     autosave.watch(document, |text| async move { std::fs::write("draft.txt", text) })
+/// });
+///
+/// let status = match &*autosave.status().read() {
+///     SaveStatus::Saved => "Saved".to_string(),
+///     SaveStatus::Saving => "Saving...".to_string(),
+///     SaveStatus::Error(err) => format!("Failed to save: {err}"),
+/// };
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub struct Autosave {
+    state: CopyValue<AutosaveState>,
+    status: Signal<SaveStatus>,
+}
+
+impl Autosave {
+    /// Create an autosave in the current component and provide it as context to its children.
+    ///
+    /// The autosave does not listen to any lifecycle events by itself. Call [`Autosave::trigger`] when they happen, or use the `use_autosave` hook of your renderer which does that for you.
+    pub fn new(config: AutosaveConfig) -> Self {
+        let autosave = Self {
+            state: CopyValue::new(AutosaveState {
+                config,
+                runtime: Runtime::current().expect("in a virtual dom"),
+                targets: Vec::new(),
+                editing: 0,
+                pending: false,
+                flushing: 0,
+            }),
+            status: Signal::new(SaveStatus::Saved),
+        };
+        provide_context(autosave);
+        autosave
+    }
+
+    /// Get the closest autosave of the current component.
+    pub fn current() -> Option<Self> {
+        consume_context()
+    }
+
+    /// Get the configuration of the autosave.
+    pub fn config(&self) -> AutosaveConfig {
+        self.state.read().config
+    }
+
+    /// Get the save status as a signal. Components that read it rerender when it changes.
+    pub fn status(&self) -> ReadOnlySignal<SaveStatus> {
+        ReadOnlySignal::new(self.status)
+    }
+
+    /// Save the signal with `save` whenever the autosave flushes and the value changed since it was last saved.
+    ///
+    /// If saving fails, the value is saved again on the next flush. The signal stops being watched when it is dropped.
+    pub fn watch<T, F, E>(&self, signal: Signal<T>, mut save: impl FnMut(T) -> F + 'static)
+    where
+        T: Clone + PartialEq + 'static,
+        F: Future<Output = Result<(), E>> + 'static,
+        E: Display,
+    {
+        struct Saved<T> {
+            value: T,
+            saving: bool,
+        }
+
+        let saved = Rc::new(RefCell::new(Saved {
+            value: signal.peek().clone(),
+            saving: false,
+        }));
+
+        self.state.write().targets.push(Box::new(move || {
+            let value = match signal.inner.try_read() {
+                Ok(data) => data.value.clone(),
+                Err(_) => return Check::Dropped,
+            };
+            let mut state = saved.borrow_mut();
+            // Wait for the last save of this signal to finish so saves can't finish out of order
+            if state.saving || state.value == value {
+                return Check::Clean;
+            }
+            state.saving = true;
+            drop(state);
+
+            let future = save(value.clone());
+            let saved = saved.clone();
+            Check::Dirty(Box::pin(async move {
+                let result = future.await.map_err(|err| err.to_string());
+                let mut state = saved.borrow_mut();
+                state.saving = false;
+                if result.is_ok() {
+                    state.value = value;
+                }
+                result
+            }))
+        }));
+    }
+
+    /// Start an edit. Triggers that fire while an edit is in progress are delayed until every [`EditGuard`] is dropped, so the autosave never saves a half finished edit.
+    ///
+    /// ```rust, ignore
+    /// let edit = autosave.edit();
+    /// document.write().replace_range(selection, &replacement);
+    /// cursor.set(selection.start + replacement.len());
+    /// drop(edit);
+    /// ```
+    pub fn edit(&self) -> EditGuard {
+        self.state.write().editing += 1;
+        EditGuard { autosave: *self }
+    }
+
+    /// Tell the autosave that a lifecycle event happened. This starts a flush in the background if the [`AutosaveConfig`] flushes on the trigger.
+    pub fn trigger(&self, trigger: AutosaveTrigger) {
+        let flushes = self
+            .state
+            .try_read()
+            .map_or(false, |state| state.config.flushes_on(trigger));
+        if flushes {
+            self.request_flush();
+        }
+    }
+
+    /// Save every watched signal that changed since it was last saved, and wait for the saves to finish.
+    ///
+    /// Unlike [`Autosave::trigger`], this saves even while an edit is in progress. Returns the first error any save failed with.
+    pub async fn flush(self) -> Result<(), String> {
+        // Check the targets without holding the state, so saving can use the autosave
+        let targets = match self.state.try_write() {
+            Ok(mut state) => std::mem::take(&mut state.targets),
+            Err(_) => return Ok(()),
+        };
+        let mut saves = Vec::new();
+        let mut watched = Vec::with_capacity(targets.len());
+        for mut target in targets {
+            match target() {
+                Check::Clean => watched.push(target),
+                Check::Dirty(save) => {
+                    saves.push(save);
+                    watched.push(target);
+                }
+                Check::Dropped => {}
+            }
+        }
+        {
+            let mut state = self.state.write();
+            // Keep signals that started being watched while the targets were checked
+            watched.append(&mut state.targets);
+            state.targets = watched;
+            if saves.is_empty() {
+                return Ok(());
+            }
+            state.flushing += 1;
+        }
+
+        self.status.set(SaveStatus::Saving);
+        let mut result = Ok(());
+        for save in saves {
+            if let Err(err) = save.await {
+                if result.is_ok() {
+                    result = Err(err);
+                }
+            }
+        }
+
+        let mut state = match self.state.try_write() {
+            Ok(state) => state,
+            Err(_) => return result,
+        };
+        state.flushing -= 1;
+        let done = state.flushing == 0;
+        let pending = done && state.editing == 0 && std::mem::take(&mut state.pending);
+        drop(state);
+        if done {
+            self.status.set(match &result {
+                Ok(()) => SaveStatus::Saved,
+                Err(err) => SaveStatus::Error(err.clone()),
+            });
+        }
+        if pending {
+            self.request_flush();
+        }
+
+        result
+    }
+
+    fn request_flush(&self) {
+        let mut state = self.state.write();
+        if state.editing > 0 || state.flushing > 0 {
+            state.pending = true;
+            return;
+        }
+        let runtime = state.runtime.clone();
+        drop(state);
+
+        let autosave = *self;
+        // Triggers come from event listeners outside of the virtual dom, so enter the runtime to spawn the flush
+        RuntimeGuard::with(runtime, None, || {
+            spawn_at(
+                async move {
+                    if let Err(err) = autosave.flush().await {
+                        tracing::error!("Failed to autosave: {err}");
+                    }
+                },
+                ScopeId::ROOT,
+            )
+        });
+    }
+}
+
+/// An edit in progress, created with [`Autosave::edit`]. Triggers that fire while the guard is alive flush once it is dropped.
+pub struct EditGuard {
+    autosave: Autosave,
+}
+
+impl Drop for EditGuard {
+    fn drop(&mut self) {
+        let mut state = match self.autosave.state.try_write() {
+            Ok(state) => state,
+            Err(_) => return,
+        };
+        state.editing -= 1;
+        let pending = state.editing == 0 && std::mem::take(&mut state.pending);
+        drop(state);
+        if pending {
+            self.autosave.request_flush();
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use std::{collections::HashMap, hash::Hash};
+
+use dioxus_core::prelude::*;
+
+use crate::{get_effect_stack, CopyValue, Effect, EffectStack, Signal};
+
+/// The number of results a [`Cached`] keeps by default before it starts evicting the least recently used ones.
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// Creates a new Cached function. The cached function remembers the result of `f` for each argument it is called with, so expensive per-item work (formatting, parsing) is not repeated every render.
+///
+/// Any signals read inside of `f` are tracked. When one of them changes, every cached result is thrown away and the components that called the cached function rerun.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     let currency = use_signal(cx, || "USD".to_string());
+///     // The prices are only formatted once per value until the currency changes
+///     let format_price = use_cached(cx, move |cents: &u64| {
+///         format!("{}.{:02} {}", cents / 100, cents % 100, currency)
+///     });
+///
+///     render! {
+///         for cents in [199, 2500, 199] {
+///             p { "{format_price.call(cents)}" }
+///         }
+///     }
+/// }
+/// ```
+#[track_caller]
+#[must_use]
+pub fn use_cached<A, T>(cx: &ScopeState, f: impl FnMut(&A) -> T + 'static) -> Cached<A, T>
+where
+    A: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+{
+    use_cached_with_capacity(cx, DEFAULT_CACHE_CAPACITY, f)
+}
+
+/// Creates a new Cached function that keeps at most `capacity` results. See [`use_cached`] for more information.
+#[track_caller]
+#[must_use]
+pub fn use_cached_with_capacity<A, T>(
+    cx: &ScopeState,
+    capacity: usize,
+    f: impl FnMut(&A) -> T + 'static,
+) -> Cached<A, T>
+where
+    A: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+{
+    let mut f = Some(f);
+    let mut cached = *cx.use_hook(|| Cached::new(capacity, f.take().unwrap()));
+    // Always call the latest closure so it sees the latest props
+    if let Some(f) = f {
+        cached.compute.set(Box::new(f));
+    }
+    cached
+}
+
+/// A function that caches its results per argument in a bounded LRU cache. Created with [`use_cached`].
+pub struct Cached<A: 'static, T: 'static> {
+    entries: CopyValue<CacheEntries<A, T>>,
+    compute: CopyValue<Box<dyn FnMut(&A) -> T>>,
+    // Subscribed to by every scope that calls the function and notified when the cache is invalidated
+    readers: Signal<()>,
+    effect: Effect,
+    effect_stack: EffectStack,
+}
+
+struct CacheEntries<A, T> {
+    values: HashMap<A, (T, u64)>,
+    last_used: u64,
+    capacity: usize,
+}
+
+impl<A, T> Cached<A, T>
+where
+    A: Hash + Eq + Clone + 'static,
+    T: Clone + 'static,
+{
+    /// Create a new Cached function. The cache will be owned by the current component.
+    pub fn new(capacity: usize, f: impl FnMut(&A) -> T + 'static) -> Self {
+        let entries = CopyValue::new(CacheEntries {
+            values: HashMap::new(),
+            last_used: 0,
+            capacity,
+        });
+        let readers = Signal::new(());
+        // The effect is never run directly. It only runs when a signal read while computing a result changes
+        let effect = Effect {
+            source: current_scope_id().expect("in a virtual dom"),
+            callback: CopyValue::new(Box::new(move || {
+                entries.write().values.clear();
+                readers.set(());
+            })),
+            effect_stack: get_effect_stack(),
+        };
+        Self {
+            entries,
+            compute: CopyValue::new(Box::new(f)),
+            readers,
+            effect,
+            effect_stack: get_effect_stack(),
+        }
+    }
+
+    /// Call the function. If there is a cached result for the argument it is cloned and returned, otherwise the result is computed and cached.
+    ///
+    /// This will subscribe the current scope to the cache being invalidated.
+    #[track_caller]
+    pub fn call(&self, arg: A) -> T {
+        self.readers.read();
+
+        {
+            let mut entries = self.entries.write();
+            let CacheEntries {
+                values, last_used, ..
+            } = &mut *entries;
+            if let Some((value, used)) = values.get_mut(&arg) {
+                *last_used += 1;
+                *used = *last_used;
+                return value.clone();
+            }
+        }
+
+        // Track any signals the function reads so the cache is invalidated when they change
+        self.effect_stack.effects.write().push(self.effect);
+        let value = {
+            let mut compute = self.compute.write();
+            compute(&arg)
+        };
+        self.effect_stack.effects.write().pop();
+
+        let mut entries = self.entries.write();
+        entries.last_used += 1;
+        let used = entries.last_used;
+        entries.values.insert(arg, (value.clone(), used));
+        if entries.values.len() > entries.capacity {
+            let oldest = entries
+                .values
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(arg, _)| arg.clone());
+            if let Some(oldest) = oldest {
+                entries.values.remove(&oldest);
+            }
+        }
+
+        value
+    }
+
+    /// Throw away every cached result and rerun the components that called the function.
+    pub fn invalidate(&self) {
+        self.entries.write().values.clear();
+        self.readers.set(());
+    }
+
+    /// Get the number of cached results.
+    pub fn len(&self) -> usize {
+        self.entries.read().values.len()
+    }
+
+    /// Returns true if there are no cached results.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<A: 'static, T: 'static> Copy for Cached<A, T> {}
+
+impl<A: 'static, T: 'static> Clone for Cached<A, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: 'static, T: 'static> PartialEq for Cached<A, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
@@ -2,6 +2,7 @@ use crate::rt::CopyValue;
 use crate::signal::{ReadOnlySignal, Signal, Write};
 use generational_box::GenerationalRef;
 use generational_box::GenerationalRefMut;
+use generational_box::UnsyncStorage;
 
 use std::{
     fmt::{Debug, Display},
@@ -38,7 +39,7 @@ macro_rules! read_impls {
 
         impl<T: 'static> $ty<Vec<T>> {
             /// Read a value from the inner vector.
-            pub fn get(&self, index: usize) -> Option<GenerationalRef<T>> {
+            pub fn get(&self, index: usize) -> Option<GenerationalRef<T, UnsyncStorage>> {
                 GenerationalRef::filter_map(self.read(), |v| v.get(index))
             }
         }
@@ -53,7 +54,7 @@ macro_rules! read_impls {
             }
 
             /// Attempts to read the inner value of the Option.
-            pub fn as_ref(&self) -> Option<GenerationalRef<T>> {
+            pub fn as_ref(&self) -> Option<GenerationalRef<T, UnsyncStorage>> {
                 GenerationalRef::filter_map(self.read(), |v| v.as_ref())
             }
         }
@@ -182,12 +183,15 @@ macro_rules! write_impls {
             }
 
             /// Gets the value out of the Option, or inserts the given value if the Option is empty.
-            pub fn get_or_insert(&self, default: T) -> GenerationalRef<T> {
+            pub fn get_or_insert(&self, default: T) -> GenerationalRef<T, UnsyncStorage> {
                 self.get_or_insert_with(|| default)
             }
 
             /// Gets the value out of the Option, or inserts the value returned by the given function if the Option is empty.
-            pub fn get_or_insert_with(&self, default: impl FnOnce() -> T) -> GenerationalRef<T> {
+            pub fn get_or_insert_with(
+                &self,
+                default: impl FnOnce() -> T,
+            ) -> GenerationalRef<T, UnsyncStorage> {
                 let borrow = self.read();
                 if borrow.is_none() {
                     drop(borrow);
@@ -238,14 +242,14 @@ impl<T: Clone + 'static> IntoIterator for CopyValue<Vec<T>> {
 
 impl<T: 'static> CopyValue<Vec<T>> {
     /// Write to an element in the inner vector.
-    pub fn get_mut(&self, index: usize) -> Option<GenerationalRefMut<T>> {
+    pub fn get_mut(&self, index: usize) -> Option<GenerationalRefMut<T, UnsyncStorage>> {
         GenerationalRefMut::filter_map(self.write(), |v| v.get_mut(index))
     }
 }
 
 impl<T: 'static> CopyValue<Option<T>> {
     /// Deref the inner value mutably.
-    pub fn as_mut(&self) -> Option<GenerationalRefMut<T>> {
+    pub fn as_mut(&self) -> Option<GenerationalRefMut<T, UnsyncStorage>> {
         GenerationalRefMut::filter_map(self.write(), |v| v.as_mut())
     }
 }
@@ -10,7 +10,25 @@ pub use effect::*;
 mod impls;
 mod selector;
 pub use selector::*;
+mod async_memo;
+pub use async_memo::*;
 pub(crate) mod signal;
 pub use signal::*;
 mod dependency;
 pub use dependency::*;
+mod vec;
+pub use vec::*;
+mod map;
+pub use map::*;
+mod cached;
+pub use cached::*;
+mod read;
+pub use read::*;
+mod autosave;
+pub use autosave::*;
+#[cfg(feature = "signal-profiling")]
+pub mod profile;
+
+// Signals keep their subscribers in `Rc`s, so they always use `UnsyncStorage`. The other storages are re-exported for
+// code that stores its own values next to signals and wants to follow `DefaultStorage`
+pub use generational_box::{DefaultStorage, SyncStorage, UnsyncStorage};
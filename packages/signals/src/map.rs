@@ -0,0 +1,280 @@
+use std::{collections::HashMap, hash::Hash};
+
+use dioxus_core::{prelude::current_scope_id, ScopeId, ScopeState};
+use generational_box::{GenerationalRef, GenerationalRefMut, UnsyncStorage};
+
+use crate::{vec::NotifyOnDrop, CopyValue, Signal};
+
+/// Creates a new SignalMap. A SignalMap is a map that tracks which keys each component or effect reads, and only notifies the readers of the keys that changed.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     let users = use_signal_map(cx, || {
+///         let mut users = std::collections::HashMap::new();
+///         users.insert(1, "Alice".to_string());
+///         users.insert(2, "Bob".to_string());
+///         users
+///     });
+///
+///     render! {
+///         User { users: users, id: 1 }
+///         User { users: users, id: 2 }
+///     }
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope, users: SignalMap<u32, String>, id: u32) -> Element {
+///     // This only subscribes to this user. Inserting or removing other users will not rerun this component
+///     let name = users.get(id).map(|name| name.clone()).unwrap_or_default();
+///
+///     render! { "{name}" }
+/// }
+/// ```
+#[track_caller]
+#[must_use]
+pub fn use_signal_map<K: Hash + Eq + Clone + 'static, V: 'static>(
+    cx: &ScopeState,
+    f: impl FnOnce() -> HashMap<K, V>,
+) -> SignalMap<K, V> {
+    *cx.use_hook(|| SignalMap::new(f()))
+}
+
+/// A map with fine-grained subscriptions. Reading a key with [`SignalMap::get`] only subscribes to that key, while reading or iterating the whole map subscribes to every change.
+pub struct SignalMap<K: 'static, V: 'static> {
+    values: CopyValue<HashMap<K, V>>,
+    // Notified on any change to the map
+    all: Signal<()>,
+    // Notified when a single key changes
+    keys: CopyValue<KeyTrackers<K>>,
+}
+
+/// Trackers are created the first time a key is read, even if the key is not in the map yet, so readers are notified when it is inserted.
+/// Trackers that nothing is subscribed to are recycled, so reading or removing many different keys doesn't grow the map of trackers forever.
+struct KeyTrackers<K> {
+    trackers: HashMap<K, Signal<()>>,
+    // Trackers that were released and can be reused for another key
+    spare: Vec<Signal<()>>,
+    // Release every unused tracker once this many trackers are in use
+    sweep_at: usize,
+}
+
+const MIN_SWEEP_AT: usize = 32;
+
+impl<K: Hash + Eq> KeyTrackers<K> {
+    fn release(&mut self, key: &K) {
+        if let Some(tracker) = self.trackers.remove(key) {
+            self.spare.push(tracker);
+        }
+    }
+
+    fn sweep(&mut self) {
+        let spare = &mut self.spare;
+        self.trackers.retain(|_, tracker| {
+            let used = tracker.has_subscribers();
+            if !used {
+                spare.push(*tracker);
+            }
+            used
+        });
+        self.sweep_at = (self.trackers.len() * 2).max(MIN_SWEEP_AT);
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, V: 'static> SignalMap<K, V> {
+    /// Create a new SignalMap. The map will be owned by the current component.
+    #[track_caller]
+    pub fn new(values: HashMap<K, V>) -> Self {
+        Self::new_in_scope(values, current_scope_id().expect("in a virtual dom"))
+    }
+
+    /// Create a new SignalMap with a custom owner scope. The map will be dropped when the owner scope is dropped instead of the current scope.
+    pub fn new_in_scope(values: HashMap<K, V>, owner: ScopeId) -> Self {
+        Self {
+            values: CopyValue::new_in_scope(values, owner),
+            all: Signal::new_in_scope((), owner),
+            keys: CopyValue::new_in_scope(
+                KeyTrackers {
+                    trackers: HashMap::new(),
+                    spare: Vec::new(),
+                    sweep_at: MIN_SWEEP_AT,
+                },
+                owner,
+            ),
+        }
+    }
+
+    /// Get the scope the map was created in.
+    pub fn origin_scope(&self) -> ScopeId {
+        self.values.origin_scope()
+    }
+
+    /// Get a reference to the value for `key`. This will only subscribe the current scope to that key.
+    #[track_caller]
+    pub fn get(&self, key: &K) -> Option<GenerationalRef<V, UnsyncStorage>> {
+        self.track(key);
+        self.peek_at(key)
+    }
+
+    /// Returns true if the map contains `key`. This will only subscribe the current scope to that key.
+    #[track_caller]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.track(key);
+        self.values.read().contains_key(key)
+    }
+
+    /// Get a reference to the value for `key` without subscribing to it.
+    pub fn peek_at(&self, key: &K) -> Option<GenerationalRef<V, UnsyncStorage>> {
+        GenerationalRef::filter_map(self.values.read(), |values| values.get(key))
+    }
+
+    /// Get the number of entries in the map. This will subscribe the current scope to every change in the map.
+    #[track_caller]
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns true if the map has no entries. This will subscribe the current scope to every change in the map.
+    #[track_caller]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a reference to the whole map. This will subscribe the current scope to every change in the map.
+    #[track_caller]
+    pub fn read(&self) -> GenerationalRef<HashMap<K, V>, UnsyncStorage> {
+        self.all.read();
+        self.values.read()
+    }
+
+    /// Get a reference to the whole map without subscribing to it.
+    pub fn peek(&self) -> GenerationalRef<HashMap<K, V>, UnsyncStorage> {
+        self.values.read()
+    }
+
+    /// Get a mutable reference to the value for `key`. Only readers of that key (and of the whole map) will be notified when the reference is dropped.
+    #[track_caller]
+    pub fn write(&self, key: &K) -> Option<SignalMapWrite<V>> {
+        let tracker = self.existing_key(key);
+        let write =
+            GenerationalRefMut::filter_map(self.values.write(), |values| values.get_mut(key))?;
+        let mut signals = vec![self.all];
+        signals.extend(tracker);
+        Some(SignalMapWrite {
+            write,
+            _notify: NotifyOnDrop { signals },
+        })
+    }
+
+    /// Run a closure with a mutable reference to the value for `key`. Panics if the key is not in the map.
+    #[track_caller]
+    pub fn with_mut<O>(&self, key: &K, f: impl FnOnce(&mut V) -> O) -> O {
+        let mut write = self.write(key).expect("key not in map");
+        f(&mut *write)
+    }
+
+    /// Insert a value into the map, returning the old value if there was one. Only readers of `key` (and of the whole map) will be notified.
+    #[track_caller]
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let tracker = self.existing_key(&key);
+        let old = self.values.write().insert(key.clone(), value);
+        self.notify(&key, tracker);
+        old
+    }
+
+    /// Remove a value from the map. Only readers of `key` (and of the whole map) will be notified.
+    #[track_caller]
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let old = self.values.write().remove(key)?;
+        self.notify(key, self.existing_key(key));
+        Some(old)
+    }
+
+    /// Remove every entry from the map.
+    #[track_caller]
+    pub fn clear(&self) {
+        self.values.write().clear();
+        let mut signals = vec![self.all];
+        signals.extend(self.keys.read().trackers.values().copied());
+        drop(NotifyOnDrop { signals });
+        self.keys.write().sweep();
+    }
+
+    fn existing_key(&self, key: &K) -> Option<Signal<()>> {
+        self.keys.read().trackers.get(key).copied()
+    }
+
+    fn key(&self, key: &K) -> Signal<()> {
+        if let Some(tracker) = self.existing_key(key) {
+            return tracker;
+        }
+        let mut keys = self.keys.write();
+        if keys.trackers.len() >= keys.sweep_at {
+            keys.sweep();
+        }
+        let tracker = keys
+            .spare
+            .pop()
+            .unwrap_or_else(|| Signal::new_in_scope((), self.origin_scope()));
+        keys.trackers.insert(key.clone(), tracker);
+        tracker
+    }
+
+    // Subscribe the current scope or effect to `key`
+    fn track(&self, key: &K) {
+        let tracker = self.key(key);
+        tracker.read();
+        // Nothing subscribed if the key was read outside of a component or effect
+        if !tracker.has_subscribers() {
+            self.keys.write().release(key);
+        }
+    }
+
+    fn notify(&self, key: &K, tracker: Option<Signal<()>>) {
+        let mut signals = vec![self.all];
+        signals.extend(tracker);
+        drop(NotifyOnDrop { signals });
+        // Effects are unsubscribed when they are notified, and may not read the key again
+        if tracker.map_or(false, |tracker| !tracker.has_subscribers()) {
+            self.keys.write().release(key);
+        }
+    }
+}
+
+impl<K: 'static, V: 'static> Copy for SignalMap<K, V> {}
+
+impl<K: 'static, V: 'static> Clone for SignalMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: 'static, V: 'static> PartialEq for SignalMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+/// A mutable reference to a value in a [`SignalMap`]. The readers of the key will be notified when this is dropped.
+pub struct SignalMapWrite<V: 'static> {
+    write: GenerationalRefMut<V, UnsyncStorage>,
+    // This must be declared after `write` so the borrow is released before subscribers are notified
+    _notify: NotifyOnDrop,
+}
+
+impl<V: 'static> std::ops::Deref for SignalMapWrite<V> {
+    type Target = V;
+
+    fn deref(&self) -> &Self::Target {
+        &self.write
+    }
+}
+
+impl<V: 'static> std::ops::DerefMut for SignalMapWrite<V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.write
+    }
+}
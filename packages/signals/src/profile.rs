@@ -0,0 +1,171 @@
+//! Read, write and notification counters for signals and the scopes that use them. Enable the `signal-profiling` feature to collect them.
+//!
+//! ```rust, ignore
+//! // After interacting with your app for a while, print the signals that caused the most rerenders
+//! println!("{}", dioxus_signals::profile::report());
+//! ```
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    panic::Location,
+};
+
+use dioxus_core::ScopeId;
+use generational_box::GenerationalBoxId;
+
+thread_local! {
+    static PROFILE: RefCell<Profile> = RefCell::new(Profile::default());
+}
+
+#[derive(Default)]
+struct Profile {
+    signals: HashMap<GenerationalBoxId, SignalProfile>,
+    scopes: HashMap<ScopeId, ScopeProfile>,
+}
+
+impl Profile {
+    fn signal(
+        &mut self,
+        id: GenerationalBoxId,
+        created_at: Option<&'static Location<'static>>,
+    ) -> &mut SignalProfile {
+        self.signals.entry(id).or_insert_with(|| SignalProfile {
+            id,
+            created_at,
+            reads: 0,
+            writes: 0,
+            notifications: 0,
+        })
+    }
+
+    fn scope(&mut self, scope: ScopeId) -> &mut ScopeProfile {
+        self.scopes.entry(scope).or_insert_with(|| ScopeProfile {
+            scope,
+            reads: 0,
+            writes: 0,
+            notifications: 0,
+        })
+    }
+}
+
+/// The counters collected for a single signal.
+#[derive(Debug, Clone)]
+pub struct SignalProfile {
+    /// The id of the signal's storage.
+    pub id: GenerationalBoxId,
+    /// Where the signal was created. This is only available in debug builds.
+    pub created_at: Option<&'static Location<'static>>,
+    /// The number of times the signal was read with subscriptions.
+    pub reads: usize,
+    /// The number of times the signal was written to.
+    pub writes: usize,
+    /// The number of scopes the signal marked as dirty.
+    pub notifications: usize,
+}
+
+/// The counters collected for a single scope.
+#[derive(Debug, Clone)]
+pub struct ScopeProfile {
+    /// The id of the scope.
+    pub scope: ScopeId,
+    /// The number of signal reads that happened while the scope was running.
+    pub reads: usize,
+    /// The number of signal writes that happened while the scope was running.
+    pub writes: usize,
+    /// The number of times a signal write marked the scope as dirty.
+    pub notifications: usize,
+}
+
+/// A snapshot of every counter collected on the current thread. Both lists are sorted with the most notifications first.
+#[derive(Debug, Clone, Default)]
+pub struct ProfileReport {
+    /// The counters for each signal.
+    pub signals: Vec<SignalProfile>,
+    /// The counters for each scope.
+    pub scopes: Vec<ScopeProfile>,
+}
+
+impl Display for ProfileReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "signals:")?;
+        for signal in &self.signals {
+            write!(
+                f,
+                "  {:?}: {} reads, {} writes, {} notifications",
+                signal.id, signal.reads, signal.writes, signal.notifications
+            )?;
+            if let Some(created_at) = signal.created_at {
+                write!(f, " (created at {created_at})")?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "scopes:")?;
+        for scope in &self.scopes {
+            writeln!(
+                f,
+                "  {:?}: {} reads, {} writes, {} notifications",
+                scope.scope, scope.reads, scope.writes, scope.notifications
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Get a snapshot of the counters collected on the current thread.
+pub fn report() -> ProfileReport {
+    PROFILE.with(|profile| {
+        let profile = profile.borrow();
+        let mut signals: Vec<_> = profile.signals.values().cloned().collect();
+        signals.sort_by(|a, b| b.notifications.cmp(&a.notifications));
+        let mut scopes: Vec<_> = profile.scopes.values().cloned().collect();
+        scopes.sort_by(|a, b| b.notifications.cmp(&a.notifications));
+        ProfileReport { signals, scopes }
+    })
+}
+
+/// Reset every counter collected on the current thread.
+pub fn reset() {
+    PROFILE.with(|profile| *profile.borrow_mut() = Profile::default());
+}
+
+pub(crate) fn record_read(
+    id: GenerationalBoxId,
+    created_at: Option<&'static Location<'static>>,
+    scope: Option<ScopeId>,
+) {
+    PROFILE.with(|profile| {
+        let mut profile = profile.borrow_mut();
+        profile.signal(id, created_at).reads += 1;
+        if let Some(scope) = scope {
+            profile.scope(scope).reads += 1;
+        }
+    })
+}
+
+pub(crate) fn record_write(
+    id: GenerationalBoxId,
+    created_at: Option<&'static Location<'static>>,
+    scope: Option<ScopeId>,
+) {
+    PROFILE.with(|profile| {
+        let mut profile = profile.borrow_mut();
+        profile.signal(id, created_at).writes += 1;
+        if let Some(scope) = scope {
+            profile.scope(scope).writes += 1;
+        }
+    })
+}
+
+pub(crate) fn record_notification(
+    id: GenerationalBoxId,
+    created_at: Option<&'static Location<'static>>,
+    scope: ScopeId,
+) {
+    PROFILE.with(|profile| {
+        let mut profile = profile.borrow_mut();
+        profile.signal(id, created_at).notifications += 1;
+        profile.scope(scope).notifications += 1;
+    })
+}
@@ -0,0 +1,54 @@
+use generational_box::{GenerationalRef, UnsyncStorage};
+
+use crate::{CopyValue, ReadOnlySignal, Signal};
+
+/// A value that can be read like a signal. This lets APIs accept any reactive value without caring which signal type it is.
+pub trait Readable {
+    /// The type of the value.
+    type Target: ?Sized + 'static;
+
+    /// Get the current value. For signals, this will subscribe the current scope or effect to the value.
+    fn read(&self) -> GenerationalRef<Self::Target, UnsyncStorage>;
+
+    /// Get the current value without subscribing to it.
+    fn peek(&self) -> GenerationalRef<Self::Target, UnsyncStorage>;
+}
+
+impl<T: 'static> Readable for Signal<T> {
+    type Target = T;
+
+    #[track_caller]
+    fn read(&self) -> GenerationalRef<T, UnsyncStorage> {
+        Signal::read(self)
+    }
+
+    fn peek(&self) -> GenerationalRef<T, UnsyncStorage> {
+        Signal::peek(self)
+    }
+}
+
+impl<T: 'static> Readable for ReadOnlySignal<T> {
+    type Target = T;
+
+    #[track_caller]
+    fn read(&self) -> GenerationalRef<T, UnsyncStorage> {
+        ReadOnlySignal::read(self)
+    }
+
+    fn peek(&self) -> GenerationalRef<T, UnsyncStorage> {
+        ReadOnlySignal::peek(self)
+    }
+}
+
+impl<T: 'static> Readable for CopyValue<T> {
+    type Target = T;
+
+    #[track_caller]
+    fn read(&self) -> GenerationalRef<T, UnsyncStorage> {
+        CopyValue::read(self)
+    }
+
+    fn peek(&self) -> GenerationalRef<T, UnsyncStorage> {
+        CopyValue::read(self)
+    }
+}
@@ -6,22 +6,23 @@ use dioxus_core::prelude::*;
 use dioxus_core::ScopeId;
 
 use generational_box::{
-    BorrowError, BorrowMutError, GenerationalBox, GenerationalRef, GenerationalRefMut, Owner, Store,
+    BorrowError, BorrowMutError, GenerationalBox, GenerationalBoxId, GenerationalRef,
+    GenerationalRefMut, Owner, Store, UnsyncStorage,
 };
 
 use crate::Effect;
 
-fn current_store() -> Store {
+fn current_store() -> Store<UnsyncStorage> {
     match consume_context() {
         Some(rt) => rt,
         None => {
-            let store = Store::default();
+            let store = Store::new();
             provide_root_context(store).expect("in a virtual dom")
         }
     }
 }
 
-fn current_owner() -> Rc<Owner> {
+fn current_owner() -> Rc<Owner<UnsyncStorage>> {
     match Effect::current() {
         // If we are inside of an effect, we should use the owner of the effect as the owner of the value.
         Some(effect) => {
@@ -39,7 +40,7 @@ fn current_owner() -> Rc<Owner> {
     }
 }
 
-fn owner_in_scope(scope: ScopeId) -> Rc<Owner> {
+fn owner_in_scope(scope: ScopeId) -> Rc<Owner<UnsyncStorage>> {
     match consume_context_from_scope(scope) {
         Some(rt) => rt,
         None => {
@@ -49,11 +50,35 @@ fn owner_in_scope(scope: ScopeId) -> Rc<Owner> {
     }
 }
 
+/// Inspect and release the state owned by a scope. This is mainly useful for debugging and for frameworks that need to release the state of a subtree before unmounting it.
+pub trait ScopeOwnedState {
+    /// Get the ids of every value (signals, effects, and copy values) owned by the scope.
+    fn owned_state(&self) -> Vec<GenerationalBoxId>;
+
+    /// Drop every value owned by the scope now instead of waiting for the scope to be dropped. Any handles to those values will become invalid.
+    fn dispose_state(&self);
+}
+
+impl ScopeOwnedState for ScopeId {
+    fn owned_state(&self) -> Vec<GenerationalBoxId> {
+        match has_context_in_scope::<Rc<Owner<UnsyncStorage>>>(*self) {
+            Some(owner) => owner.owned(),
+            None => Vec::new(),
+        }
+    }
+
+    fn dispose_state(&self) {
+        if let Some(owner) = has_context_in_scope::<Rc<Owner<UnsyncStorage>>>(*self) {
+            owner.dispose();
+        }
+    }
+}
+
 /// CopyValue is a wrapper around a value to make the value mutable and Copy.
 ///
 /// It is internally backed by [`generational_box::GenerationalBox`].
 pub struct CopyValue<T: 'static> {
-    pub(crate) value: GenerationalBox<T>,
+    pub(crate) value: GenerationalBox<T, UnsyncStorage>,
     origin_scope: ScopeId,
 }
 
@@ -133,27 +158,32 @@ impl<T: 'static> CopyValue<T> {
         self.origin_scope
     }
 
+    /// Run `hook` when the value is dropped, right after the owning scope releases it. See [`GenerationalBox::on_dispose`].
+    pub fn on_dispose(&self, hook: impl FnOnce() + Send + 'static) {
+        self.value.on_dispose(hook)
+    }
+
     /// Try to read the value. If the value has been dropped, this will return None.
     #[track_caller]
-    pub fn try_read(&self) -> Result<GenerationalRef<T>, BorrowError> {
+    pub fn try_read(&self) -> Result<GenerationalRef<T, UnsyncStorage>, BorrowError> {
         self.value.try_read()
     }
 
     /// Read the value. If the value has been dropped, this will panic.
     #[track_caller]
-    pub fn read(&self) -> GenerationalRef<T> {
+    pub fn read(&self) -> GenerationalRef<T, UnsyncStorage> {
         self.value.read()
     }
 
     /// Try to write the value. If the value has been dropped, this will return None.
     #[track_caller]
-    pub fn try_write(&self) -> Result<GenerationalRefMut<T>, BorrowMutError> {
+    pub fn try_write(&self) -> Result<GenerationalRefMut<T, UnsyncStorage>, BorrowMutError> {
         self.value.try_write()
     }
 
     /// Write the value. If the value has been dropped, this will panic.
     #[track_caller]
-    pub fn write(&self) -> GenerationalRefMut<T> {
+    pub fn write(&self) -> GenerationalRefMut<T, UnsyncStorage> {
         self.value.write()
     }
 
@@ -189,7 +219,7 @@ impl<T: 'static> PartialEq for CopyValue<T> {
 }
 
 impl<T> Deref for CopyValue<T> {
-    type Target = dyn Fn() -> GenerationalRef<T>;
+    type Target = dyn Fn() -> GenerationalRef<T, UnsyncStorage>;
 
     fn deref(&self) -> &Self::Target {
         // https://github.com/dtolnay/case-studies/tree/master/callable-types
@@ -7,10 +7,13 @@ use std::{
 };
 
 use dioxus_core::{
-    prelude::{current_scope_id, has_context, provide_context, schedule_update_any},
+    prelude::{
+        current_scope_id, has_context, provide_context, schedule_update_any,
+        schedule_urgent_update_any,
+    },
     ScopeId, ScopeState,
 };
-use generational_box::{GenerationalRef, GenerationalRefMut};
+use generational_box::{GenerationalRef, GenerationalRefMut, UnsyncStorage};
 
 use crate::{get_effect_stack, CopyValue, Effect, EffectStack};
 
@@ -203,12 +206,30 @@ impl<T: 'static> Signal<T> {
         self.inner.origin_scope()
     }
 
+    /// Run `hook` when the signal is dropped, like closing a file or aborting a task that feeds the signal.
+    pub fn on_dispose(&self, hook: impl FnOnce() + Send + 'static) {
+        self.inner.on_dispose(hook)
+    }
+
+    /// Mark the signal as urgent. Components that read an urgent signal are rendered before other dirty components when it changes.
+    ///
+    /// Renderers with a frame budget render large updates across several frames. Marking signals that hold input, like the text of a search field, as urgent keeps the input responsive while the rest of the update is rendered.
+    pub fn mark_urgent(&self) {
+        self.inner.write().update_any = schedule_urgent_update_any().expect("in a virtual dom");
+    }
+
     /// Get the current value of the signal. This will subscribe the current scope to the signal.  If you would like to read the signal without subscribing to it, you can use [`Self::peek`] instead.
     ///
     /// If the signal has been dropped, this will panic.
     #[track_caller]
-    pub fn read(&self) -> GenerationalRef<T> {
+    pub fn read(&self) -> GenerationalRef<T, UnsyncStorage> {
         let inner = self.inner.read();
+        #[cfg(feature = "signal-profiling")]
+        crate::profile::record_read(
+            self.inner.value.id(),
+            self.inner.value.created_at(),
+            current_scope_id(),
+        );
         if let Some(effect) = inner.effect_stack.current() {
             let mut effect_subscribers = inner.effect_subscribers.borrow_mut();
             if !effect_subscribers.contains(&effect) {
@@ -234,10 +255,18 @@ impl<T: 'static> Signal<T> {
         GenerationalRef::map(inner, |v| &v.value)
     }
 
+    /// Returns true if any component or effect is subscribed to the signal.
+    pub(crate) fn has_subscribers(&self) -> bool {
+        let inner = self.inner.read();
+        let has_subscribers =
+            !inner.subscribers.borrow().is_empty() || !inner.effect_subscribers.borrow().is_empty();
+        has_subscribers
+    }
+
     /// Get the current value of the signal. **Unlike read, this will not subscribe the current scope to the signal which can cause parts of your UI to not update.**
     ///
     /// If the signal has been dropped, this will panic.
-    pub fn peek(&self) -> GenerationalRef<T> {
+    pub fn peek(&self) -> GenerationalRef<T, UnsyncStorage> {
         let inner = self.inner.read();
         GenerationalRef::map(inner, |v| &v.value)
     }
@@ -248,6 +277,12 @@ impl<T: 'static> Signal<T> {
     #[track_caller]
     pub fn write(&self) -> Write<T> {
         let inner = self.inner.write();
+        #[cfg(feature = "signal-profiling")]
+        crate::profile::record_write(
+            self.inner.value.id(),
+            self.inner.value.created_at(),
+            current_scope_id(),
+        );
         let borrow = GenerationalRefMut::map(inner, |v| &mut v.value);
         Write {
             write: borrow,
@@ -264,6 +299,12 @@ impl<T: 'static> Signal<T> {
                     self.inner.value,
                     scope_id
                 );
+                #[cfg(feature = "signal-profiling")]
+                crate::profile::record_notification(
+                    self.inner.value.id(),
+                    self.inner.value.created_at(),
+                    scope_id,
+                );
                 (inner.update_any)(scope_id);
             }
         }
@@ -329,7 +370,7 @@ impl<T: 'static> PartialEq for Signal<T> {
 }
 
 impl<T> Deref for Signal<T> {
-    type Target = dyn Fn() -> GenerationalRef<T>;
+    type Target = dyn Fn() -> GenerationalRef<T, UnsyncStorage>;
 
     fn deref(&self) -> &Self::Target {
         // https://github.com/dtolnay/case-studies/tree/master/callable-types
@@ -373,7 +414,7 @@ impl<T: 'static> Drop for SignalSubscriberDrop<T> {
 
 /// A mutable reference to a signal's value.
 pub struct Write<T: 'static, I: 'static = T> {
-    write: GenerationalRefMut<T>,
+    write: GenerationalRefMut<T, UnsyncStorage>,
     signal: SignalSubscriberDrop<I>,
 }
 
@@ -432,14 +473,14 @@ impl<T: 'static> ReadOnlySignal<T> {
     ///
     /// If the signal has been dropped, this will panic.
     #[track_caller]
-    pub fn read(&self) -> GenerationalRef<T> {
+    pub fn read(&self) -> GenerationalRef<T, UnsyncStorage> {
         self.inner.read()
     }
 
     /// Get the current value of the signal. **Unlike read, this will not subscribe the current scope to the signal which can cause parts of your UI to not update.**
     ///
     /// If the signal has been dropped, this will panic.
-    pub fn peek(&self) -> GenerationalRef<T> {
+    pub fn peek(&self) -> GenerationalRef<T, UnsyncStorage> {
         self.inner.peek()
     }
 
@@ -464,7 +505,7 @@ impl<T: 'static> PartialEq for ReadOnlySignal<T> {
 }
 
 impl<T> Deref for ReadOnlySignal<T> {
-    type Target = dyn Fn() -> GenerationalRef<T>;
+    type Target = dyn Fn() -> GenerationalRef<T, UnsyncStorage>;
 
     fn deref(&self) -> &Self::Target {
         // https://github.com/dtolnay/case-studies/tree/master/callable-types
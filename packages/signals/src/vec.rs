@@ -0,0 +1,292 @@
+use dioxus_core::{prelude::current_scope_id, ScopeId, ScopeState};
+use generational_box::{GenerationalRef, GenerationalRefMut, UnsyncStorage};
+
+use crate::{CopyValue, Signal};
+
+/// Creates a new SignalVec. A SignalVec is a list that tracks which rows each component or effect reads, and only notifies the readers of the rows that changed.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     let rows = use_signal_vec(cx, || vec![0; 100]);
+///
+///     render! {
+///         // Only the length is read here, so this component only reruns when rows are added or removed
+///         for index in 0..rows.len() {
+///             Row { rows: rows, index: index }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Row(cx: Scope, rows: SignalVec<i32>, index: usize) -> Element {
+///     let rows = *rows;
+///     let index = *index;
+///     // This only subscribes to this row. Writing to another row will not rerun this component
+///     let value = rows.get(index).map(|value| *value).unwrap_or_default();
+///
+///     render! {
+///         button {
+///             onclick: move |_| rows.with_mut(index, |value| *value += 1),
+///             "{value}"
+///         }
+///     }
+/// }
+/// ```
+#[track_caller]
+#[must_use]
+pub fn use_signal_vec<T: 'static>(cx: &ScopeState, f: impl FnOnce() -> Vec<T>) -> SignalVec<T> {
+    *cx.use_hook(|| SignalVec::new(f()))
+}
+
+/// A list with fine-grained subscriptions. Reading a single row with [`SignalVec::get`] only subscribes to that row, reading the length only subscribes to structural changes, and reading the whole list subscribes to every change.
+pub struct SignalVec<T: 'static> {
+    values: CopyValue<Vec<T>>,
+    // Notified when the length of the list changes
+    structure: Signal<()>,
+    // Notified on any change to the list
+    all: Signal<()>,
+    // One tracker per index. Trackers are kept around when the list shrinks so they can be reused when it grows again
+    rows: CopyValue<Vec<Signal<()>>>,
+}
+
+impl<T: 'static> SignalVec<T> {
+    /// Create a new SignalVec. The list will be owned by the current component.
+    #[track_caller]
+    pub fn new(values: Vec<T>) -> Self {
+        Self::new_in_scope(values, current_scope_id().expect("in a virtual dom"))
+    }
+
+    /// Create a new SignalVec with a custom owner scope. The list will be dropped when the owner scope is dropped instead of the current scope.
+    pub fn new_in_scope(values: Vec<T>, owner: ScopeId) -> Self {
+        let rows = (0..values.len())
+            .map(|_| Signal::new_in_scope((), owner))
+            .collect();
+        Self {
+            values: CopyValue::new_in_scope(values, owner),
+            structure: Signal::new_in_scope((), owner),
+            all: Signal::new_in_scope((), owner),
+            rows: CopyValue::new_in_scope(rows, owner),
+        }
+    }
+
+    /// Get the scope the list was created in.
+    pub fn origin_scope(&self) -> ScopeId {
+        self.values.origin_scope()
+    }
+
+    /// Get the number of rows in the list. This will subscribe the current scope to rows being added or removed.
+    #[track_caller]
+    pub fn len(&self) -> usize {
+        self.structure.read();
+        self.values.read().len()
+    }
+
+    /// Returns true if the list has no rows. This will subscribe the current scope to rows being added or removed.
+    #[track_caller]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a reference to the row at `index`. This will only subscribe the current scope to that row.
+    ///
+    /// If the index is out of bounds, this subscribes to rows being added instead and returns None.
+    #[track_caller]
+    pub fn get(&self, index: usize) -> Option<GenerationalRef<T, UnsyncStorage>> {
+        match self.row(index) {
+            Some(row) => {
+                row.read();
+            }
+            None => {
+                self.structure.read();
+            }
+        }
+        self.peek_at(index)
+    }
+
+    /// Get a reference to the row at `index` without subscribing to it.
+    pub fn peek_at(&self, index: usize) -> Option<GenerationalRef<T, UnsyncStorage>> {
+        GenerationalRef::filter_map(self.values.read(), |values| values.get(index))
+    }
+
+    /// Get a reference to the whole list. This will subscribe the current scope to every change in the list.
+    #[track_caller]
+    pub fn read(&self) -> GenerationalRef<Vec<T>, UnsyncStorage> {
+        self.all.read();
+        self.values.read()
+    }
+
+    /// Get a reference to the whole list without subscribing to it.
+    pub fn peek(&self) -> GenerationalRef<Vec<T>, UnsyncStorage> {
+        self.values.read()
+    }
+
+    /// Get a mutable reference to the row at `index`. Only readers of that row (and of the whole list) will be notified when the reference is dropped.
+    #[track_caller]
+    pub fn write(&self, index: usize) -> Option<SignalVecWrite<T>> {
+        let row = self.row(index)?;
+        let write =
+            GenerationalRefMut::filter_map(self.values.write(), |values| values.get_mut(index))?;
+        Some(SignalVecWrite {
+            write,
+            _notify: NotifyOnDrop {
+                signals: vec![row, self.all],
+            },
+        })
+    }
+
+    /// Set the row at `index`. Panics if the index is out of bounds.
+    #[track_caller]
+    pub fn set(&self, index: usize, value: T) {
+        *self.write(index).expect("index out of bounds") = value;
+    }
+
+    /// Run a closure with a mutable reference to the row at `index`. Panics if the index is out of bounds.
+    #[track_caller]
+    pub fn with_mut<O>(&self, index: usize, f: impl FnOnce(&mut T) -> O) -> O {
+        let mut write = self.write(index).expect("index out of bounds");
+        f(&mut *write)
+    }
+
+    /// Add a row to the end of the list.
+    #[track_caller]
+    pub fn push(&self, value: T) {
+        let len = {
+            let mut values = self.values.write();
+            values.push(value);
+            values.len()
+        };
+        self.ensure_rows(len);
+        self.notify_structure(len..len);
+    }
+
+    /// Remove the last row from the list.
+    #[track_caller]
+    pub fn pop(&self) -> Option<T> {
+        let (value, len) = {
+            let mut values = self.values.write();
+            let value = values.pop()?;
+            (value, values.len())
+        };
+        self.notify_structure(len..len + 1);
+        Some(value)
+    }
+
+    /// Insert a row at `index`, shifting every row after it. Rows at or after `index` will be notified.
+    #[track_caller]
+    pub fn insert(&self, index: usize, value: T) {
+        let len = {
+            let mut values = self.values.write();
+            values.insert(index, value);
+            values.len()
+        };
+        self.ensure_rows(len);
+        self.notify_structure(index..len);
+    }
+
+    /// Remove the row at `index`, shifting every row after it. Rows at or after `index` will be notified.
+    #[track_caller]
+    pub fn remove(&self, index: usize) -> T {
+        let (value, len) = {
+            let mut values = self.values.write();
+            let value = values.remove(index);
+            (value, values.len())
+        };
+        self.notify_structure(index..len + 1);
+        value
+    }
+
+    /// Swap two rows. Only the two rows will be notified.
+    #[track_caller]
+    pub fn swap(&self, a: usize, b: usize) {
+        self.values.write().swap(a, b);
+        let rows = self.rows.read();
+        let signals = vec![rows[a], rows[b], self.all];
+        drop(rows);
+        drop(NotifyOnDrop { signals });
+    }
+
+    /// Remove every row from the list.
+    #[track_caller]
+    pub fn clear(&self) {
+        let len = {
+            let mut values = self.values.write();
+            let len = values.len();
+            values.clear();
+            len
+        };
+        self.notify_structure(0..len);
+    }
+
+    fn row(&self, index: usize) -> Option<Signal<()>> {
+        if index < self.values.read().len() {
+            self.rows.read().get(index).copied()
+        } else {
+            None
+        }
+    }
+
+    fn ensure_rows(&self, len: usize) {
+        let owner = self.origin_scope();
+        let mut rows = self.rows.write();
+        while rows.len() < len {
+            rows.push(Signal::new_in_scope((), owner));
+        }
+    }
+
+    fn notify_structure(&self, changed_rows: std::ops::Range<usize>) {
+        let mut signals = vec![self.structure, self.all];
+        signals.extend_from_slice(&self.rows.read()[changed_rows]);
+        drop(NotifyOnDrop { signals });
+    }
+}
+
+impl<T: 'static> Copy for SignalVec<T> {}
+
+impl<T: 'static> Clone for SignalVec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> PartialEq for SignalVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+pub(crate) struct NotifyOnDrop {
+    pub(crate) signals: Vec<Signal<()>>,
+}
+
+impl Drop for NotifyOnDrop {
+    fn drop(&mut self) {
+        for signal in &self.signals {
+            signal.set(());
+        }
+    }
+}
+
+/// A mutable reference to a row in a [`SignalVec`]. The readers of the row will be notified when this is dropped.
+pub struct SignalVecWrite<T: 'static> {
+    write: GenerationalRefMut<T, UnsyncStorage>,
+    // This must be declared after `write` so the borrow is released before subscribers are notified
+    _notify: NotifyOnDrop,
+}
+
+impl<T: 'static> std::ops::Deref for SignalVecWrite<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.write
+    }
+}
+
+impl<T: 'static> std::ops::DerefMut for SignalVecWrite<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.write
+    }
+}
@@ -0,0 +1,71 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::cell::Cell;
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+
+#[tokio::test]
+async fn async_memos_rerun() {
+    let mut dom = VirtualDom::new(|cx| {
+        let mut count = use_signal(cx, || 0);
+        let doubled = use_async_memo(cx, move || {
+            let count = count.value();
+            async move { Ok::<_, ()>(count * 2) }
+        });
+
+        if cx.generation() == 0 {
+            assert_eq!(*doubled.read(), AsyncState::Ready(0));
+            count += 1;
+            assert!(doubled.read().is_pending());
+        } else {
+            assert_eq!(*doubled.read(), AsyncState::Ready(2));
+        }
+
+        render! {
+            div {}
+        }
+    });
+
+    let _ = dom.rebuild().santize();
+    dom.wait_for_work().await;
+    let _ = dom.render_immediate();
+}
+
+#[tokio::test]
+async fn async_memos_drop_stale_futures() {
+    thread_local! {
+        static DROPPED: Cell<usize> = Cell::new(0);
+    }
+
+    struct DropCounter;
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            DROPPED.with(|dropped| dropped.set(dropped.get() + 1));
+        }
+    }
+
+    let mut dom = VirtualDom::new(|cx| {
+        let mut count = use_signal(cx, || 0);
+        let memo = use_async_memo(cx, move || {
+            let _ = count.value();
+            let counter = DropCounter;
+            async move {
+                let _counter = counter;
+                std::future::pending::<Result<(), ()>>().await
+            }
+        });
+
+        if cx.generation() == 0 {
+            count += 1;
+            assert_eq!(DROPPED.with(Cell::get), 1);
+            assert!(memo.read().is_pending());
+        }
+
+        render! {
+            div {}
+        }
+    });
+
+    let _ = dom.rebuild().santize();
+}
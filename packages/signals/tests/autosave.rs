@@ -0,0 +1,39 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::rc::Rc;
+use std::time::Duration;
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+
+#[tokio::test]
+async fn autosave_waits_for_edits_and_saves_changed_signals() {
+    let saved = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let saved = cx.props.clone();
+            let autosave = *cx.use_hook(|| Autosave::new(AutosaveConfig::default()));
+            let text = use_signal(cx, String::new);
+            cx.use_hook(move || {
+                autosave.watch(text, move |value: String| {
+                    saved.borrow_mut().push(value);
+                    async { Ok::<_, String>(()) }
+                });
+
+                // The trigger waits until the edit is finished
+                let edit = autosave.edit();
+                text.set("hello".to_string());
+                autosave.trigger(AutosaveTrigger::Blur);
+                text.write().push_str(" world");
+                drop(edit);
+            });
+
+            render! { div {} }
+        },
+        saved.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+    let _ = tokio::time::timeout(Duration::from_millis(100), dom.wait_for_work()).await;
+
+    assert_eq!(*saved.borrow(), vec!["hello world".to_string()]);
+}
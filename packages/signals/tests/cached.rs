@@ -0,0 +1,44 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use dioxus_signals::*;
+
+#[test]
+fn cached_results_are_reused_until_a_signal_changes() {
+    let computations = Rc::new(RefCell::new(0));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let multiplier = use_signal(cx, || 2);
+            let computations = cx.props.clone();
+            let double = use_cached(cx, move |value: &usize| {
+                *computations.borrow_mut() += 1;
+                value * multiplier.value()
+            });
+
+            assert_eq!(double.call(1), multiplier.value());
+            assert_eq!(double.call(2), 2 * multiplier.value());
+            assert_eq!(double.call(1), multiplier.value());
+
+            if cx.generation() == 1 {
+                multiplier.set(3);
+            }
+
+            render! { div {} }
+        },
+        computations.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+    assert_eq!(*computations.borrow(), 2);
+
+    // Rerunning the component reuses the cached results
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate();
+    assert_eq!(*computations.borrow(), 2);
+
+    // Changing the multiplier invalidates the cache and reruns the component
+    dom.render_immediate();
+    assert_eq!(*computations.borrow(), 4);
+}
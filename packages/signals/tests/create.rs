@@ -82,3 +82,23 @@ fn drop_signals() {
         Signal::new("hello world".to_string())
     }
 }
+
+#[test]
+fn dispose_scope_state() {
+    let mut dom = VirtualDom::new(|cx| {
+        let value = cx.use_hook(|| CopyValue::new(0));
+
+        if cx.generation() == 0 {
+            assert_eq!(cx.scope_id().owned_state().len(), 1);
+            cx.scope_id().dispose_state();
+            assert!(cx.scope_id().owned_state().is_empty());
+            assert!(value.try_read().is_err());
+        }
+
+        render! {
+            "hello world"
+        }
+    });
+
+    let _ = dom.rebuild().santize();
+}
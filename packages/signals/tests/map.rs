@@ -0,0 +1,132 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::{ElementId, RuntimeGuard};
+use dioxus_signals::*;
+
+#[test]
+fn inserting_a_key_only_reruns_readers_of_that_key() {
+    #[derive(Default)]
+    struct RunCounter {
+        parent: usize,
+        children: HashMap<u32, usize>,
+    }
+
+    let counter = Rc::new(RefCell::new(RunCounter::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let users = use_signal_map(cx, || {
+                let mut users = HashMap::new();
+                users.insert(1, "Alice".to_string());
+                users.insert(2, "Bob".to_string());
+                users
+            });
+
+            cx.props.borrow_mut().parent += 1;
+
+            if cx.generation() == 1 {
+                users.insert(1, "Carol".to_string());
+                users.insert(3, "Dave".to_string());
+            }
+
+            render! {
+                for id in 1..=2 {
+                    Child {
+                        users: users,
+                        id: id,
+                        counter: cx.props.clone()
+                    }
+                }
+            }
+        },
+        counter.clone(),
+    );
+
+    #[derive(Props, Clone)]
+    struct ChildProps {
+        users: SignalMap<u32, String>,
+        id: u32,
+        counter: Rc<RefCell<RunCounter>>,
+    }
+
+    impl PartialEq for ChildProps {
+        fn eq(&self, other: &Self) -> bool {
+            self.users == other.users && self.id == other.id
+        }
+    }
+
+    fn Child(cx: Scope<ChildProps>) -> Element {
+        *cx.props
+            .counter
+            .borrow_mut()
+            .children
+            .entry(cx.props.id)
+            .or_default() += 1;
+
+        let name = cx.props.users.get(&cx.props.id).map(|name| name.clone());
+
+        render! {
+            "{name:?}"
+        }
+    }
+
+    let _ = dom.rebuild().santize();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate();
+    dom.render_immediate();
+
+    let current_counter = counter.borrow();
+    assert_eq!(current_counter.parent, 2);
+    assert_eq!(current_counter.children[&1], 2);
+    assert_eq!(current_counter.children[&2], 1);
+}
+
+#[test]
+fn unused_key_trackers_are_recycled() {
+    let stash = Rc::new(Cell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let map = use_signal_map(cx, HashMap::<u32, u32>::new);
+            cx.props.set(Some(map));
+            render! { "" }
+        },
+        stash.clone(),
+    );
+    let _ = dom.rebuild().santize();
+    let map = stash.get().unwrap();
+
+    RuntimeGuard::with(dom.runtime(), Some(ScopeId::ROOT), || {
+        let owned = ScopeId::ROOT.owned_state().len();
+
+        // Reading absent keys outside of a component doesn't subscribe anything, so every key reuses the same tracker
+        for key in 0..100 {
+            assert!(map.get(&key).is_none());
+            assert!(!map.contains_key(&key));
+        }
+        assert_eq!(ScopeId::ROOT.owned_state().len(), owned + 1);
+
+        // Once the only effect reading a key stops reading it, removing the key releases the tracker for the next key
+        map.insert(1, 1);
+        let read_first = Rc::new(Cell::new(true));
+        Effect::new({
+            let read_first = read_first.clone();
+            move || {
+                if read_first.get() {
+                    map.get(&1);
+                }
+            }
+        });
+        read_first.set(false);
+        assert_eq!(map.remove(&1), Some(1));
+        Effect::new(move || {
+            map.get(&2);
+        });
+
+        // One spare tracker, and one callback for each effect
+        assert_eq!(ScopeId::ROOT.owned_state().len(), owned + 3);
+    });
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "signal-profiling")]
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::cell::Cell;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::RuntimeGuard;
+use dioxus_signals::*;
+
+#[test]
+fn reads_and_writes_are_counted() {
+    let stash = Rc::new(Cell::new(None));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let signal = use_signal(cx, || 0);
+            cx.props.set(Some(signal));
+            let value = *signal.read();
+            render! { "{value}" }
+        },
+        stash.clone(),
+    );
+    let _ = dom.rebuild().santize();
+    let signal = stash.get().unwrap();
+    profile::reset();
+
+    RuntimeGuard::with(dom.runtime(), Some(ScopeId::ROOT), || signal.set(1));
+    dom.render_immediate();
+
+    let report = profile::report();
+    assert_eq!(report.signals.len(), 1);
+    let signal_profile = &report.signals[0];
+    assert_eq!(signal_profile.reads, 1);
+    assert_eq!(signal_profile.writes, 1);
+    assert!(signal_profile.notifications > 0);
+
+    let scope_profile = report
+        .scopes
+        .iter()
+        .find(|scope| scope.scope == ScopeId::ROOT)
+        .unwrap();
+    assert_eq!(scope_profile.reads, 1);
+    assert_eq!(scope_profile.writes, 1);
+    assert_eq!(scope_profile.notifications, signal_profile.notifications);
+    assert!(report.to_string().contains("1 reads, 1 writes"));
+}
@@ -0,0 +1,125 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use dioxus_signals::*;
+
+#[derive(Default)]
+struct RunCounter {
+    parent: usize,
+    children: HashMap<usize, usize>,
+}
+
+#[derive(Props, Clone)]
+struct ChildProps {
+    rows: SignalVec<usize>,
+    index: usize,
+    counter: Rc<RefCell<RunCounter>>,
+}
+
+impl PartialEq for ChildProps {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows && self.index == other.index
+    }
+}
+
+fn Child(cx: Scope<ChildProps>) -> Element {
+    *cx.props
+        .counter
+        .borrow_mut()
+        .children
+        .entry(cx.props.index)
+        .or_default() += 1;
+
+    let value = cx.props.rows.get(cx.props.index).map(|value| *value);
+
+    render! {
+        "{value:?}"
+    }
+}
+
+#[test]
+fn writing_a_row_only_reruns_that_row() {
+    let counter = Rc::new(RefCell::new(RunCounter::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let rows = use_signal_vec(cx, || (0..10).collect::<Vec<usize>>());
+
+            cx.props.borrow_mut().parent += 1;
+
+            if cx.generation() == 1 {
+                rows.set(3, 100);
+            }
+
+            render! {
+                for index in 0..rows.len() {
+                    Child {
+                        rows: rows,
+                        index: index,
+                        counter: cx.props.clone()
+                    }
+                }
+            }
+        },
+        counter.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate();
+    dom.render_immediate();
+
+    let current_counter = counter.borrow();
+    assert_eq!(current_counter.parent, 2);
+    for (index, rerun_count) in current_counter.children.iter() {
+        if *index == 3 {
+            assert_eq!(rerun_count, &2);
+        } else {
+            assert_eq!(rerun_count, &1);
+        }
+    }
+}
+
+#[test]
+fn pushing_reruns_length_readers() {
+    let counter = Rc::new(RefCell::new(RunCounter::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let rows = use_signal_vec(cx, || (0..5).collect::<Vec<usize>>());
+
+            cx.props.borrow_mut().parent += 1;
+
+            if cx.generation() == 1 {
+                rows.push(5);
+            }
+
+            render! {
+                for index in 0..rows.len() {
+                    Child {
+                        rows: rows,
+                        index: index,
+                        counter: cx.props.clone()
+                    }
+                }
+            }
+        },
+        counter.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate();
+    dom.render_immediate();
+
+    let current_counter = counter.borrow();
+    // The parent reads the length, so the push reruns it one more time
+    assert_eq!(current_counter.parent, 3);
+    assert_eq!(current_counter.children.len(), 6);
+    for (index, rerun_count) in current_counter.children.iter() {
+        assert_eq!(rerun_count, &1);
+    }
+}
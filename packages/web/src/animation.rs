@@ -0,0 +1,25 @@
+use dioxus_core::ScopeState;
+use dioxus_html::animation::AnimationFrameProvider;
+use std::rc::Rc;
+use wasm_bindgen::{prelude::Closure, JsCast};
+
+/// Provides the WebAnimationFrameProvider through [`cx.provide_context`].
+pub(crate) fn init_animation_frame(cx: &ScopeState) {
+    let provider: Rc<dyn AnimationFrameProvider> = Rc::new(WebAnimationFrameProvider);
+    cx.provide_context(provider);
+}
+
+/// Schedules frames with `requestAnimationFrame`, so animations are synced with the display and paused in background tabs.
+pub struct WebAnimationFrameProvider;
+
+impl AnimationFrameProvider for WebAnimationFrameProvider {
+    fn request_frame(&self, callback: Box<dyn FnOnce(f64)>) {
+        let callback = Closure::once_into_js(callback);
+        if let Err(err) = web_sys::window()
+            .unwrap()
+            .request_animation_frame(callback.unchecked_ref())
+        {
+            tracing::error!("Failed to request an animation frame: {err:?}");
+        }
+    }
+}
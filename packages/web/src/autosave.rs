@@ -0,0 +1,92 @@
+//! Flush an [`Autosave`] when the page loses focus, is hidden, or is closed.
+
+use crate::listener::Listener;
+use dioxus_core::ScopeState;
+use dioxus_signals::{Autosave, AutosaveConfig, AutosaveTrigger};
+use wasm_bindgen::{prelude::Closure, JsCast};
+
+/// Create an [`Autosave`] that flushes when the page loses focus, is hidden, is closed, and on the interval of the config.
+///
+/// Browsers do not wait for async work when the page is closed, so the flush that starts when the page is closed may not finish. Saving when the page is hidden is more reliable, because browsers hide the page before they close it.
+///
+/// ```rust, ignore
+/// let autosave = use_autosave(cx, AutosaveConfig::default());
+/// let document = use_signal(cx, String::new);
+/// cx.use_hook(|| {
+///     autosave.watch(document, |text| async move { save_to_server(text).await })
+/// });
+/// ```
+pub fn use_autosave(cx: &ScopeState, config: AutosaveConfig) -> Autosave {
+    let (autosave, _, _) = cx.use_hook(|| {
+        let autosave = Autosave::new(config);
+
+        let mut listeners = Vec::new();
+        if let Some(window) = web_sys::window() {
+            if let Some(document) = window.document() {
+                let hidden = document.clone();
+                listeners.push(Listener::new(
+                    document.unchecked_into(),
+                    "visibilitychange",
+                    move || {
+                        if hidden.hidden() {
+                            autosave.trigger(AutosaveTrigger::Hidden);
+                        }
+                    },
+                ));
+            }
+            listeners.push(Listener::new(
+                window.clone().unchecked_into(),
+                "blur",
+                move || autosave.trigger(AutosaveTrigger::Blur),
+            ));
+            listeners.push(Listener::new(
+                window.unchecked_into(),
+                "pagehide",
+                move || autosave.trigger(AutosaveTrigger::BeforeQuit),
+            ));
+        }
+
+        let interval = config.interval.map(|interval| {
+            Interval::new(
+                interval.as_millis().min(i32::MAX as u128) as i32,
+                move || autosave.trigger(AutosaveTrigger::Interval),
+            )
+        });
+
+        (autosave, listeners, interval)
+    });
+
+    *autosave
+}
+
+/// A `setInterval` timer that is cleared when dropped.
+struct Interval {
+    handle: Option<i32>,
+    _callback: Closure<dyn FnMut()>,
+}
+
+impl Interval {
+    fn new(millis: i32, callback: impl FnMut() + 'static) -> Self {
+        let callback = Closure::<dyn FnMut()>::new(callback);
+        let handle = web_sys::window().and_then(|window| {
+            window
+                .set_interval_with_callback_and_timeout_and_arguments_0(
+                    callback.as_ref().unchecked_ref(),
+                    millis,
+                )
+                .ok()
+        });
+        Self {
+            handle,
+            _callback: callback,
+        }
+    }
+}
+
+impl Drop for Interval {
+    fn drop(&mut self) {
+        if let (Some(window), Some(handle)) = (web_sys::window(), self.handle) {
+            window.clear_interval_with_handle(handle);
+        }
+    }
+}
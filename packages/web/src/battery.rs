@@ -0,0 +1,83 @@
+use crate::listener::Listener;
+use dioxus_core::ScopeState;
+use dioxus_html::battery::{BatteryProvider, BatteryStatus};
+use js_sys::{Function, Promise, Reflect};
+use std::{any::Any, cell::RefCell, rc::Rc};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// Provides the WebBatteryProvider through [`cx.provide_context`].
+pub(crate) fn init_battery(cx: &ScopeState) {
+    let provider: Rc<dyn BatteryProvider> = Rc::new(WebBatteryProvider);
+    cx.provide_context(provider);
+}
+
+/// Reads the battery with `navigator.getBattery()`, and listens for its `levelchange` and `chargingchange` events.
+///
+/// Browsers without the battery status API report no battery.
+pub struct WebBatteryProvider;
+
+impl BatteryProvider for WebBatteryProvider {
+    fn subscribe(&self, on_change: Box<dyn FnMut(Option<BatteryStatus>)>) -> Box<dyn Any> {
+        let on_change = Rc::new(RefCell::new(on_change));
+        // The listeners are only added once the battery is resolved, so the subscription owns them through a shared list
+        let listeners = Rc::new(RefCell::new(Vec::new()));
+
+        let battery = match get_battery() {
+            Some(battery) => battery,
+            None => {
+                (on_change.borrow_mut())(None);
+                return Box::new(listeners);
+            }
+        };
+
+        let subscription = Rc::downgrade(&listeners);
+        wasm_bindgen_futures::spawn_local(async move {
+            let manager = JsFuture::from(battery).await;
+            // The subscription may have been dropped while the battery was resolved
+            let listeners = match subscription.upgrade() {
+                Some(listeners) => listeners,
+                None => return,
+            };
+            let manager = match manager {
+                Ok(manager) => manager,
+                Err(err) => {
+                    tracing::error!("Failed to read the battery: {err:?}");
+                    (on_change.borrow_mut())(None);
+                    return;
+                }
+            };
+
+            let report = {
+                let manager = manager.clone();
+                move || (on_change.borrow_mut())(battery_status(&manager))
+            };
+            report();
+            let target: web_sys::EventTarget = manager.unchecked_into();
+            let mut listeners = listeners.borrow_mut();
+            listeners.push(Listener::new(target.clone(), "levelchange", report.clone()));
+            listeners.push(Listener::new(target, "chargingchange", report));
+        });
+
+        Box::new(listeners)
+    }
+}
+
+fn get_battery() -> Option<Promise> {
+    let navigator = web_sys::window()?.navigator();
+    let get_battery: Function = Reflect::get(&navigator, &JsValue::from_str("getBattery"))
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    get_battery.call0(&navigator).ok()?.dyn_into().ok()
+}
+
+fn battery_status(manager: &JsValue) -> Option<BatteryStatus> {
+    let level = Reflect::get(manager, &JsValue::from_str("level"))
+        .ok()?
+        .as_f64()?;
+    let charging = Reflect::get(manager, &JsValue::from_str("charging"))
+        .ok()?
+        .as_bool()?;
+    Some(BatteryStatus { level, charging })
+}
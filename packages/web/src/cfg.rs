@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 ///  Configuration for the WebSys renderer for the Dioxus VirtualDOM.
 ///
 /// This struct helps configure the specifics of hydration and render destination for WebSys.
@@ -13,6 +15,9 @@ pub struct Config {
     pub(crate) root: ConfigRoot,
     pub(crate) cached_strings: Vec<String>,
     pub(crate) default_panic_hook: bool,
+    pub(crate) trusted_types_policy: Option<String>,
+    pub(crate) csp_nonce: Option<String>,
+    pub(crate) frame_budget: Option<Duration>,
 }
 
 impl Default for Config {
@@ -23,6 +28,9 @@ impl Default for Config {
             root: ConfigRoot::RootName("main".to_string()),
             cached_strings: Vec::new(),
             default_panic_hook: true,
+            trusted_types_policy: None,
+            csp_nonce: None,
+            frame_budget: None,
         }
     }
 }
@@ -81,6 +89,42 @@ impl Config {
         self.default_panic_hook = f;
         self
     }
+
+    /// Create a [Trusted Types](https://developer.mozilla.org/en-US/docs/Web/API/Trusted_Types_API) policy with this name, so the app can run on pages with the `require-trusted-types-for 'script'` directive. The name has to be allowed by the `trusted-types` directive of the page.
+    ///
+    /// The policy passes values through unchanged. It is only used for HTML set with `dangerous_inner_html` and for scripts run with `eval`, which you are already trusting.
+    pub fn with_trusted_types_policy(mut self, name: impl Into<String>) -> Self {
+        self.trusted_types_policy = Some(name.into());
+        self
+    }
+
+    /// Set the nonce from the `script-src` directive of the page's content security policy.
+    ///
+    /// `eval` normally runs scripts with the `Function` constructor, which pages without `'unsafe-eval'` block. With a nonce, scripts run in script elements with the nonce instead.
+    ///
+    /// ```rust, ignore
+    /// // The server generates a new nonce for every response and renders it into a data attribute of the body
+    /// let body = web_sys::window().unwrap().document().unwrap().body().unwrap();
+    /// let nonce = body.dataset().get("nonce").unwrap();
+    /// dioxus_web::launch_cfg(
+    ///     App,
+    ///     Config::new()
+    ///         .with_trusted_types_policy("dioxus")
+    ///         .with_csp_nonce(nonce),
+    /// );
+    /// ```
+    pub fn with_csp_nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.csp_nonce = Some(nonce.into());
+        self
+    }
+
+    /// Render large updates across several frames, spending at most `budget` rendering before the browser handles input again.
+    ///
+    /// By default the whole update is rendered at once, which can block the page while thousands of components render. Components that read signals marked with `Signal::mark_urgent` are rendered first. A budget around 8ms leaves time for the browser to paint at 60fps.
+    pub fn with_frame_budget(mut self, budget: Duration) -> Self {
+        self.frame_budget = Some(budget);
+        self
+    }
 }
 
 pub(crate) enum ConfigRoot {
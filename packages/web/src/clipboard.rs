@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use dioxus_core::ScopeState;
+use dioxus_html::clipboard::{ClipboardError, ClipboardProvider};
+use js_sys::{Promise, Reflect};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+/// Provides the WebClipboardProvider through [`cx.provide_context`].
+pub(crate) fn init_clipboard(cx: &ScopeState) {
+    let provider: Rc<dyn ClipboardProvider> = Rc::new(WebClipboardProvider);
+    cx.provide_context(provider);
+}
+
+/// Reads and writes the clipboard with `navigator.clipboard`.
+///
+/// Browsers only allow this in secure contexts, and most only while the page is focused or in response to a user action.
+pub struct WebClipboardProvider;
+
+#[async_trait(?Send)]
+impl ClipboardProvider for WebClipboardProvider {
+    async fn read_text(&self) -> Result<String, ClipboardError> {
+        let text = JsFuture::from(read_text().map_err(clipboard_error)?)
+            .await
+            .map_err(clipboard_error)?;
+        text.as_string().ok_or(ClipboardError::Empty)
+    }
+
+    async fn write_text(&self, text: String) -> Result<(), ClipboardError> {
+        JsFuture::from(write_text(&text).map_err(clipboard_error)?)
+            .await
+            .map_err(clipboard_error)?;
+        Ok(())
+    }
+}
+
+// web-sys only exposes navigator.clipboard behind `--cfg=web_sys_unstable_apis`
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = readText, catch)]
+    fn read_text() -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(js_namespace = ["navigator", "clipboard"], js_name = writeText, catch)]
+    fn write_text(text: &str) -> Result<Promise, JsValue>;
+}
+
+fn clipboard_error(err: JsValue) -> ClipboardError {
+    let field = |name: &str| {
+        Reflect::get(&err, &JsValue::from_str(name))
+            .ok()
+            .and_then(|value| value.as_string())
+    };
+    let message = field("message").unwrap_or_else(|| format!("{err:?}"));
+    match field("name").as_deref() {
+        Some("NotAllowedError") | Some("SecurityError") => ClipboardError::Denied(message),
+        // navigator.clipboard is undefined outside of secure contexts
+        Some("TypeError") => ClipboardError::Unsupported,
+        _ => ClipboardError::Other(message),
+    }
+}
@@ -0,0 +1,38 @@
+let policy = null;
+
+// Create the Trusted Types policy the renderer uses for HTML and scripts it injects, or return the policy that was
+// already created. Returns null in browsers without Trusted Types
+export function createTrustedTypesPolicy(name) {
+  if (!policy && window.trustedTypes) {
+    policy = window.trustedTypes.createPolicy(name, {
+      createHTML: (html) => html,
+      createScript: (script) => script,
+    });
+  }
+  return policy;
+}
+
+let nextEval = 0;
+
+// Run code as the body of a function that takes the dioxus object, in a script element with the nonce of the page.
+// This works on pages whose content security policy blocks the Function constructor
+export function evalWithNonce(code, dioxus, nonce) {
+  const id = nextEval++;
+  const evals = (window.dioxusEvals = window.dioxusEvals || {});
+  evals[id] = { dioxus };
+
+  const source = `window.dioxusEvals[${id}].result = (function (dioxus) {${code}\n})(window.dioxusEvals[${id}].dioxus);`;
+  const script = document.createElement("script");
+  script.nonce = nonce;
+  script.text = policy ? policy.createScript(source) : source;
+  document.head.appendChild(script);
+  script.remove();
+
+  const slot = evals[id];
+  delete evals[id];
+  // Errors thrown by the script are reported to the window instead of the code that added it
+  if (!("result" in slot)) {
+    throw new Error("The script failed or was blocked by the content security policy");
+  }
+  return slot.result;
+}
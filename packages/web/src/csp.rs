@@ -0,0 +1,22 @@
+//! Support for pages with a strict content security policy.
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(module = "/src/csp.js")]
+extern "C" {
+    /// Create the Trusted Types policy used for HTML set with `dangerous_inner_html` and scripts run with `eval`. Returns null in browsers without Trusted Types.
+    #[wasm_bindgen(js_name = createTrustedTypesPolicy, catch)]
+    pub(crate) fn create_trusted_types_policy(name: &str) -> Result<JsValue, JsValue>;
+}
+
+#[cfg(feature = "eval")]
+#[wasm_bindgen(module = "/src/csp.js")]
+extern "C" {
+    /// Run code in a script element with the nonce of the page.
+    #[wasm_bindgen(js_name = evalWithNonce, catch)]
+    pub(crate) fn eval_with_nonce(
+        code: &str,
+        dioxus: &JsValue,
+        nonce: &str,
+    ) -> Result<JsValue, JsValue>;
+}
@@ -196,6 +196,7 @@ impl WebsysDom {
         #[cfg(feature = "mounted")]
         // we need to apply the mount events last, so we collect them here
         let mut to_mount = Vec::new();
+        i.before_mutations();
         for edit in &edits {
             match edit {
                 AppendChildren { id, m } => i.append_children(id.0 as u32, *m as u16),
@@ -218,6 +219,8 @@ impl WebsysDom {
                 }
                 InsertAfter { id, m } => i.insert_after(id.0 as u32, *m as u16),
                 InsertBefore { id, m } => i.insert_before(id.0 as u32, *m as u16),
+                MoveAfter { id, m } => i.move_after(id.0 as u32, *m as u16),
+                MoveBefore { id, m } => i.move_before(id.0 as u32, *m as u16),
                 SetAttribute {
                     name,
                     value,
@@ -267,6 +270,7 @@ impl WebsysDom {
                 PushRoot { id } => i.push_root(id.0 as u32),
             }
         }
+        i.after_mutations();
         edits.clear();
         i.flush();
 
@@ -8,15 +8,23 @@ use wasm_bindgen::prelude::*;
 
 /// Provides the WebEvalProvider through [`cx.provide_context`].
 pub fn init_eval(cx: &ScopeState) {
-    let provider: Rc<dyn EvalProvider> = Rc::new(WebEvalProvider {});
+    init_eval_with_nonce(cx, None);
+}
+
+/// Provides a WebEvalProvider that runs JavaScript in script elements with the content security policy `nonce` of the page through [`cx.provide_context`].
+pub fn init_eval_with_nonce(cx: &ScopeState, nonce: Option<String>) {
+    let provider: Rc<dyn EvalProvider> = Rc::new(WebEvalProvider { nonce });
     cx.provide_context(provider);
 }
 
 /// Reprents the web-target's provider of evaluators.
-pub struct WebEvalProvider;
+pub struct WebEvalProvider {
+    nonce: Option<String>,
+}
 impl EvalProvider for WebEvalProvider {
     fn new_evaluator(&self, js: String) -> Result<Rc<dyn Evaluator>, EvalError> {
-        WebEvaluator::new(js).map(|eval| Rc::new(eval) as Rc<dyn Evaluator + 'static>)
+        WebEvaluator::new_with_nonce(js, self.nonce.as_deref())
+            .map(|eval| Rc::new(eval) as Rc<dyn Evaluator + 'static>)
     }
 }
 
@@ -38,6 +46,11 @@ pub struct WebEvaluator {
 impl WebEvaluator {
     /// Creates a new evaluator for web-based targets.
     pub fn new(js: String) -> Result<Self, EvalError> {
+        Self::new_with_nonce(js, None)
+    }
+
+    /// Creates a new evaluator that runs the JavaScript in a script element with the content security policy nonce of the page. Without a nonce, the JavaScript runs with the `Function` constructor.
+    pub fn new_with_nonce(js: String, nonce: Option<&str>) -> Result<Self, EvalError> {
         let (channel_sender, channel_receiver) = async_channel::unbounded();
 
         // This Rc cloning mess hurts but it seems to work..
@@ -57,7 +70,11 @@ impl WebEvaluator {
         // Wrap the evaluated JS in a promise so that wasm can continue running (send/receive data from js)
         let code = PROMISE_WRAPPER.replace("{JS_CODE}", &js);
 
-        let result = match Function::new_with_args("dioxus", &code).call1(&JsValue::NULL, &dioxus) {
+        let result = match nonce {
+            Some(nonce) => crate::csp::eval_with_nonce(&code, dioxus.as_ref(), nonce),
+            None => Function::new_with_args("dioxus", &code).call1(&JsValue::NULL, &dioxus),
+        };
+        let result = match result {
             Ok(result) => {
                 if let Ok(stringified) = js_sys::JSON::stringify(&result) {
                     if !stringified.is_undefined() && stringified.is_valid_utf16() {
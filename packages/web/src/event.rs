@@ -5,8 +5,9 @@ use dioxus_html::{
         InteractionElementOffset, InteractionLocation, ModifiersInteraction, PointerInteraction,
     },
     prelude::FormValue,
-    DragData, FileEngine, FormData, HasDragData, HasFileData, HasFormData, HasImageData,
-    HasMouseData, HtmlEventConverter, ImageData, MountedData, PlatformEventData, ScrollData,
+    DragData, FileEngine, FormData, HasClipboardData, HasDragData, HasFileData, HasFormData,
+    HasImageData, HasMouseData, HtmlEventConverter, ImageData, MountedData, PlatformEventData,
+    ScrollData,
 };
 use js_sys::Array;
 use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
@@ -35,7 +36,7 @@ impl HtmlEventConverter for WebEventConverter {
         &self,
         event: &dioxus_html::PlatformEventData,
     ) -> dioxus_html::ClipboardData {
-        downcast_event(event).raw.clone().into()
+        dioxus_html::ClipboardData::new(WebClipboardData::new(downcast_event(event).raw.clone()))
     }
 
     #[inline(always)]
@@ -461,6 +462,73 @@ impl HasFileData for WebFormData {
     }
 }
 
+struct WebClipboardData {
+    raw: Event,
+}
+
+impl WebClipboardData {
+    fn new(raw: Event) -> Self {
+        Self { raw }
+    }
+
+    fn data_transfer(&self) -> Option<web_sys::DataTransfer> {
+        self.raw
+            .dyn_ref::<web_sys::ClipboardEvent>()?
+            .clipboard_data()
+    }
+
+    fn data(&self, format: &str) -> Option<String> {
+        self.data_transfer()?
+            .get_data(format)
+            .ok()
+            .filter(|data| !data.is_empty())
+    }
+}
+
+impl HasClipboardData for WebClipboardData {
+    fn types(&self) -> Vec<String> {
+        self.data_transfer()
+            .map(|data| {
+                data.types()
+                    .iter()
+                    .filter_map(|ty| ty.as_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn text(&self) -> Option<String> {
+        self.data("text/plain")
+    }
+
+    fn html(&self) -> Option<String> {
+        self.data("text/html")
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        &self.raw as &dyn std::any::Any
+    }
+}
+
+impl HasFileData for WebClipboardData {
+    fn files(&self) -> Option<std::sync::Arc<dyn FileEngine>> {
+        #[cfg(not(feature = "file_engine"))]
+        let files = None;
+        #[cfg(feature = "file_engine")]
+        let files = self
+            .data_transfer()
+            .and_then(|data| data.files())
+            .filter(|files| files.length() > 0)
+            .and_then(|files| {
+                #[allow(clippy::arc_with_non_send_sync)]
+                crate::file_engine::WebFileEngine::new(files)
+                    .map(|f| std::sync::Arc::new(f) as std::sync::Arc<dyn dioxus_html::FileEngine>)
+            });
+
+        files
+    }
+}
+
 struct WebDragData {
     element: Element,
     raw: MouseEvent,
@@ -0,0 +1,237 @@
+use crate::file_engine::WebFileEngine;
+use async_trait::async_trait;
+use dioxus_core::ScopeState;
+use dioxus_html::{
+    file_dialog::{FileDialogError, FileDialogOptions, FileDialogProvider, FileFilter},
+    FileEngine,
+};
+use futures_channel::oneshot;
+use js_sys::{Array, Promise, Reflect, Uint8Array};
+use serde::Serialize;
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, HtmlAnchorElement, HtmlInputElement, Url};
+
+/// Provides the WebFileDialogProvider through [`cx.provide_context`].
+pub(crate) fn init_file_dialog(cx: &ScopeState) {
+    let provider: Rc<dyn FileDialogProvider> = Rc::new(WebFileDialogProvider);
+    cx.provide_context(provider);
+}
+
+/// Picks files with a hidden `<input type="file">`, and saves files with the File System Access API.
+///
+/// Browsers only open file dialogs in response to a user action like a click. Browsers without the File System Access API download saved files instead, and browsers that don't fire the `cancel` event on file inputs never finish picking if the dialog is cancelled.
+pub struct WebFileDialogProvider;
+
+#[async_trait(?Send)]
+impl FileDialogProvider for WebFileDialogProvider {
+    async fn pick_files(
+        &self,
+        options: FileDialogOptions,
+        multiple: bool,
+    ) -> Option<Arc<dyn FileEngine>> {
+        pick(&options, multiple, false).await
+    }
+
+    async fn pick_folder(&self, options: FileDialogOptions) -> Option<Arc<dyn FileEngine>> {
+        pick(&options, true, true).await
+    }
+
+    async fn save_file(
+        &self,
+        options: FileDialogOptions,
+        contents: Vec<u8>,
+    ) -> Result<Option<String>, FileDialogError> {
+        let window = web_sys::window().ok_or(FileDialogError::Unsupported)?;
+        let file_name = options
+            .file_name
+            .clone()
+            .unwrap_or_else(|| "download".to_string());
+
+        if Reflect::has(&window, &JsValue::from_str("showSaveFilePicker")).unwrap_or(false) {
+            save_with_picker(&options, file_name, &contents).await
+        } else {
+            download(&file_name, &contents).map_err(write_error)?;
+            Ok(Some(file_name))
+        }
+    }
+}
+
+async fn pick(
+    options: &FileDialogOptions,
+    multiple: bool,
+    folder: bool,
+) -> Option<Arc<dyn FileEngine>> {
+    let document = web_sys::window()?.document()?;
+    let input: HtmlInputElement = document.create_element("input").ok()?.dyn_into().ok()?;
+    input.set_type("file");
+    input.set_multiple(multiple);
+    input.set_accept(&accept(&options.filters));
+    if folder {
+        input.set_attribute("webkitdirectory", "").ok()?;
+    }
+
+    // Resolves with true once files are picked, or false if the dialog was cancelled
+    let (sender, receiver) = oneshot::channel();
+    let sender = Rc::new(RefCell::new(Some(sender)));
+    let listener = |picked: bool| {
+        let sender = sender.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            if let Some(sender) = sender.borrow_mut().take() {
+                _ = sender.send(picked);
+            }
+        })
+    };
+    let on_change = listener(true);
+    let on_cancel = listener(false);
+    input
+        .add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref())
+        .ok()?;
+    input
+        .add_event_listener_with_callback("cancel", on_cancel.as_ref().unchecked_ref())
+        .ok()?;
+    input.click();
+
+    if !receiver.await.unwrap_or(false) {
+        return None;
+    }
+    let files = input.files()?;
+    if files.length() == 0 {
+        return None;
+    }
+    WebFileEngine::new(files).map(|engine| Arc::new(engine) as Arc<dyn FileEngine>)
+}
+
+/// The value of the `accept` attribute that only allows the extensions of the filters.
+fn accept(filters: &[FileFilter]) -> String {
+    filters
+        .iter()
+        .flat_map(|filter| filter.extensions.iter())
+        .map(|extension| format!(".{extension}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveFilePickerOptions {
+    suggested_name: String,
+    types: Vec<SaveFilePickerType>,
+}
+
+#[derive(Serialize)]
+struct SaveFilePickerType {
+    description: String,
+    accept: HashMap<&'static str, Vec<String>>,
+}
+
+async fn save_with_picker(
+    options: &FileDialogOptions,
+    file_name: String,
+    contents: &[u8],
+) -> Result<Option<String>, FileDialogError> {
+    let picker_options = SaveFilePickerOptions {
+        suggested_name: file_name,
+        types: options
+            .filters
+            .iter()
+            .map(|filter| SaveFilePickerType {
+                description: filter.name.clone(),
+                accept: std::iter::once((
+                    "application/octet-stream",
+                    filter
+                        .extensions
+                        .iter()
+                        .map(|extension| format!(".{extension}"))
+                        .collect(),
+                ))
+                .collect(),
+            })
+            .collect(),
+    };
+    let picker_options = picker_options
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|err| FileDialogError::Write(err.to_string()))?;
+
+    let handle =
+        match JsFuture::from(show_save_file_picker(&picker_options).map_err(write_error)?).await {
+            Ok(handle) => handle.unchecked_into::<FileSystemFileHandle>(),
+            Err(err) if error_name(&err).as_deref() == Some("AbortError") => return Ok(None),
+            Err(err) => return Err(write_error(err)),
+        };
+
+    let writable = JsFuture::from(handle.create_writable().map_err(write_error)?)
+        .await
+        .map_err(write_error)?
+        .unchecked_into::<FileSystemWritableFileStream>();
+    JsFuture::from(
+        writable
+            .write(&Uint8Array::from(contents))
+            .map_err(write_error)?,
+    )
+    .await
+    .map_err(write_error)?;
+    JsFuture::from(writable.close().map_err(write_error)?)
+        .await
+        .map_err(write_error)?;
+
+    Ok(Some(handle.name()))
+}
+
+/// Save the file by downloading it from an object url.
+fn download(file_name: &str, contents: &[u8]) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or(JsValue::UNDEFINED)?;
+    let document = window.document().ok_or(JsValue::UNDEFINED)?;
+
+    let blob = Blob::new_with_u8_array_sequence(&Array::of1(&Uint8Array::from(contents)))?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+    let anchor: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    // Some browsers cancel the download if the url is revoked before it started
+    let revoke = Closure::once_into_js(move || {
+        _ = Url::revoke_object_url(&url);
+    });
+    window.set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), 0)?;
+    Ok(())
+}
+
+// web-sys only exposes the File System Access API behind `--cfg=web_sys_unstable_apis`
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = showSaveFilePicker, catch)]
+    fn show_save_file_picker(options: &JsValue) -> Result<Promise, JsValue>;
+
+    type FileSystemFileHandle;
+
+    #[wasm_bindgen(method, getter)]
+    fn name(this: &FileSystemFileHandle) -> String;
+
+    #[wasm_bindgen(method, js_name = createWritable, catch)]
+    fn create_writable(this: &FileSystemFileHandle) -> Result<Promise, JsValue>;
+
+    type FileSystemWritableFileStream;
+
+    #[wasm_bindgen(method, catch)]
+    fn write(this: &FileSystemWritableFileStream, data: &Uint8Array) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn close(this: &FileSystemWritableFileStream) -> Result<Promise, JsValue>;
+}
+
+fn error_name(err: &JsValue) -> Option<String> {
+    Reflect::get(err, &JsValue::from_str("name"))
+        .ok()
+        .and_then(|name| name.as_string())
+}
+
+fn write_error(err: JsValue) -> FileDialogError {
+    let message = Reflect::get(&err, &JsValue::from_str("message"))
+        .ok()
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| format!("{err:?}"));
+    FileDialogError::Write(message)
+}
@@ -0,0 +1,46 @@
+use dioxus_core::ScopeState;
+use dioxus_html::{hotkeys::HotkeyProvider, KeyboardData};
+use std::{any::Any, rc::Rc};
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlElement, KeyboardEvent};
+
+use crate::listener::Listener;
+
+/// Provides the WebHotkeyProvider through [`cx.provide_context`].
+pub(crate) fn init_hotkeys(cx: &ScopeState) {
+    let provider: Rc<dyn HotkeyProvider> = Rc::new(WebHotkeyProvider);
+    cx.provide_context(provider);
+}
+
+/// Listens for `keydown` events on the window, and prevents the default action of key presses a hotkey handled.
+pub struct WebHotkeyProvider;
+
+impl HotkeyProvider for WebHotkeyProvider {
+    fn subscribe(
+        &self,
+        mut on_keydown: Box<dyn FnMut(KeyboardData, bool) -> bool>,
+    ) -> Box<dyn Any> {
+        let window: EventTarget = web_sys::window().unwrap().unchecked_into();
+        Box::new(Listener::with_event(window, "keydown", move |event| {
+            let event: KeyboardEvent = event.unchecked_into();
+            let in_editable = match event.target() {
+                Some(target) => is_editable(&target),
+                None => false,
+            };
+            if on_keydown(KeyboardData::from(event.clone()), in_editable) {
+                event.prevent_default();
+            }
+        }))
+    }
+}
+
+/// Whether typing in the element inserts text, so plain hotkeys should not run.
+fn is_editable(target: &EventTarget) -> bool {
+    match target.dyn_ref::<HtmlElement>() {
+        Some(element) => {
+            element.is_content_editable()
+                || matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        }
+        None => false,
+    }
+}
@@ -66,19 +66,51 @@ use futures_util::{
     pin_mut, FutureExt, StreamExt,
 };
 
+mod animation;
+pub use animation::WebAnimationFrameProvider;
+mod autosave;
+pub use autosave::use_autosave;
+mod battery;
+pub use battery::WebBatteryProvider;
 mod cache;
 mod cfg;
+mod clipboard;
+pub use clipboard::WebClipboardProvider;
+mod csp;
 mod dom;
 #[cfg(feature = "eval")]
 mod eval;
 mod event;
 pub use event::*;
 #[cfg(feature = "file_engine")]
+mod file_dialog;
+#[cfg(feature = "file_engine")]
+pub use file_dialog::WebFileDialogProvider;
+#[cfg(feature = "file_engine")]
 mod file_engine;
 #[cfg(all(feature = "hot_reload", debug_assertions))]
 mod hot_reload;
+mod hotkeys;
+pub use hotkeys::WebHotkeyProvider;
+mod listener;
+mod network;
+pub use network::WebNetworkStatusProvider;
+mod notification;
+pub use notification::{
+    cancel_notification, schedule_notification, use_notification_events, WebNotificationProvider,
+};
 #[cfg(feature = "hydrate")]
 mod rehydrate;
+mod storage;
+pub use storage::{use_local_storage, use_session_storage};
+mod text_metrics;
+pub use text_metrics::{measure_text, use_text_metrics};
+mod theme;
+pub use theme::{use_accent_color, use_color_scheme};
+mod window_size;
+pub use window_size::WebWindowSizeProvider;
+mod worker;
+pub use worker::{use_worker, UseWorker, WorkerError, WorkerSource};
 
 // Currently disabled since it actually slows down immediate rendering
 // todo: only schedule non-immediate renders through ric/raf
@@ -181,13 +213,36 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
 
     let mut dom = VirtualDom::new_with_props(root, root_props);
 
+    if let Some(name) = &cfg.trusted_types_policy {
+        match csp::create_trusted_types_policy(name) {
+            // The interpreter and the template bindings each have their own copy of the code that sets HTML
+            Ok(policy) => {
+                dioxus_interpreter_js::set_trusted_types_policy(&policy);
+                dioxus_interpreter_js::minimal_bindings::setTrustedTypesPolicy(&policy);
+            }
+            Err(err) => {
+                tracing::error!("Failed to create the trusted types policy {name:?}: {err:?}");
+            }
+        }
+    }
+
     #[cfg(feature = "eval")]
     {
         // Eval
         let cx = dom.base_scope();
-        eval::init_eval(cx);
+        eval::init_eval_with_nonce(cx, cfg.csp_nonce.clone());
     }
 
+    animation::init_animation_frame(dom.base_scope());
+    clipboard::init_clipboard(dom.base_scope());
+    hotkeys::init_hotkeys(dom.base_scope());
+    window_size::init_window_size(dom.base_scope());
+    network::init_network_status(dom.base_scope());
+    battery::init_battery(dom.base_scope());
+    notification::init_notifications(dom.base_scope());
+    #[cfg(feature = "file_engine")]
+    file_dialog::init_file_dialog(dom.base_scope());
+
     #[cfg(feature = "panic_hook")]
     if cfg.default_panic_hook {
         console_error_panic_hook::set_once();
@@ -205,6 +260,8 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
 
     let (tx, mut rx) = futures_channel::mpsc::unbounded();
 
+    let frame_budget = cfg.frame_budget;
+
     #[cfg(feature = "hydrate")]
     let should_hydrate = cfg.hydrate;
     #[cfg(not(feature = "hydrate"))]
@@ -298,12 +355,35 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         // let deadline = work_loop.wait_for_idle_time().await;
 
         // run the virtualdom work phase until the frame deadline is reached
-        let edits = dom.render_immediate();
+        let edits = match frame_budget {
+            Some(budget) => {
+                let performance = web_sys::window().and_then(|window| window.performance());
+                let now = move || performance.as_ref().map_or(0.0, |p| p.now());
+                let deadline = now() + budget.as_secs_f64() * 1000.0;
+                dom.render_with_budget(|| now() >= deadline)
+            }
+            None => dom.render_immediate(),
+        };
 
         // wait for the animation frame to fire so we can apply our changes
         // work_loop.wait_for_raf().await;
 
         websys_dom.load_templates(&edits.templates);
         websys_dom.apply_edits(edits.edits);
+
+        // Let the browser handle input and paint before rendering the rest of the update
+        if frame_budget.is_some() && dom.has_dirty_scopes() {
+            yield_to_browser().await;
+        }
     }
 }
+
+/// Wait for a task of the browser's event loop, so it can handle events and paint.
+async fn yield_to_browser() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0);
+        }
+    });
+    _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
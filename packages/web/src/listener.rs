@@ -0,0 +1,50 @@
+//! Event listeners that are removed when their handle is dropped.
+
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{Event, EventTarget};
+
+/// An event listener that is removed when dropped.
+pub(crate) struct Listener {
+    target: EventTarget,
+    event: &'static str,
+    callback: Closure<dyn FnMut(Event)>,
+}
+
+impl Listener {
+    /// Listen for `event` on `target`, for callbacks that don't need to read the event.
+    pub(crate) fn new(
+        target: EventTarget,
+        event: &'static str,
+        mut callback: impl FnMut() + 'static,
+    ) -> Self {
+        Self::with_event(target, event, move |_| callback())
+    }
+
+    /// Listen for `event` on `target` and pass each event to `callback`.
+    pub(crate) fn with_event(
+        target: EventTarget,
+        event: &'static str,
+        callback: impl FnMut(Event) + 'static,
+    ) -> Self {
+        let callback = Closure::<dyn FnMut(Event)>::new(callback);
+        if let Err(err) =
+            target.add_event_listener_with_callback(event, callback.as_ref().unchecked_ref())
+        {
+            tracing::error!("Failed to listen for {event} events: {err:?}");
+        }
+        Self {
+            target,
+            event,
+            callback,
+        }
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        _ = self.target.remove_event_listener_with_callback(
+            self.event,
+            self.callback.as_ref().unchecked_ref(),
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use crate::listener::Listener;
+use dioxus_core::ScopeState;
+use dioxus_html::network::{ConnectionType, NetworkStatus, NetworkStatusProvider};
+use js_sys::Reflect;
+use std::{any::Any, cell::RefCell, rc::Rc};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::EventTarget;
+
+/// Provides the WebNetworkStatusProvider through [`cx.provide_context`].
+pub(crate) fn init_network_status(cx: &ScopeState) {
+    let provider: Rc<dyn NetworkStatusProvider> = Rc::new(WebNetworkStatusProvider);
+    cx.provide_context(provider);
+}
+
+/// Reads `navigator.onLine` and `navigator.connection`, and listens for the `online`, `offline` and connection `change` events.
+pub struct WebNetworkStatusProvider;
+
+impl NetworkStatusProvider for WebNetworkStatusProvider {
+    fn status(&self) -> NetworkStatus {
+        current_status()
+    }
+
+    fn subscribe(&self, on_change: Box<dyn FnMut(NetworkStatus)>) -> Box<dyn Any> {
+        let window = web_sys::window().unwrap();
+        let on_change = Rc::new(RefCell::new(on_change));
+        let report = move || (on_change.borrow_mut())(current_status());
+
+        let mut listeners = vec![
+            Listener::new(window.clone().unchecked_into(), "online", report.clone()),
+            Listener::new(window.unchecked_into(), "offline", report.clone()),
+        ];
+        // Only some browsers expose the connection type
+        if let Some(connection) = connection() {
+            listeners.push(Listener::new(connection, "change", report));
+        }
+        Box::new(listeners)
+    }
+}
+
+fn connection() -> Option<EventTarget> {
+    let navigator = web_sys::window()?.navigator();
+    let connection = Reflect::get(&navigator, &JsValue::from_str("connection")).ok()?;
+    connection.dyn_into().ok()
+}
+
+fn current_status() -> NetworkStatus {
+    let online = web_sys::window()
+        .map(|window| window.navigator().on_line())
+        .unwrap_or(true);
+    let connection = connection()
+        .and_then(|connection| Reflect::get(&connection, &JsValue::from_str("type")).ok())
+        .and_then(|kind| kind.as_string())
+        .map(|kind| ConnectionType::from_web(&kind))
+        .unwrap_or_default();
+    NetworkStatus { online, connection }
+}
@@ -0,0 +1,512 @@
+//! Local notifications shown right away or scheduled to be shown at a later time with the `Notification` API.
+//!
+//! Scheduled notifications are saved to local storage so they survive a reload. In browsers that support notification triggers, notifications are handed to the service worker of the page, which shows them even while the page is closed. Other browsers show them with a timer while the page is open, and notifications that came due while it was closed are shown the next time it loads.
+
+use dioxus_core::ScopeState;
+use dioxus_html::notification::{
+    NotificationContent, NotificationEvent, NotificationEventKind, NotificationId,
+    NotificationProvider, ScheduleAt,
+};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{
+    MessageEvent, Notification, NotificationOptions, NotificationPermission,
+    ServiceWorkerRegistration,
+};
+
+const STORAGE_KEY: &str = "dioxus-notifications";
+const CLICK_PARAM: &str = "dioxus-notification-click";
+// setTimeout overflows for longer delays, so long timers are split up
+const MAX_TIMEOUT: u64 = i32::MAX as u64;
+
+/// A notification that has been scheduled, but not delivered yet.
+#[derive(Serialize, Deserialize, Clone)]
+struct Scheduled {
+    id: u64,
+    /// Milliseconds since the unix epoch
+    at: u64,
+    title: String,
+    body: Option<String>,
+    icon: Option<String>,
+    data: Option<String>,
+    /// The service worker shows this notification, even if the page is closed
+    system: bool,
+}
+
+type NotificationHandler = Box<dyn FnMut(&NotificationEvent)>;
+
+#[derive(Default)]
+struct Scheduler {
+    scheduled: RefCell<Vec<Scheduled>>,
+    timers: RefCell<FxHashMap<u64, i32>>,
+    handlers: RefCell<FxHashMap<usize, NotificationHandler>>,
+    next_handler: Cell<usize>,
+    // Events that happened before any component listened for them
+    missed: RefCell<Vec<NotificationEvent>>,
+    /// The callbacks of notifications that were shown right away. Their events only go to their callback.
+    shown: RefCell<FxHashMap<u64, Box<dyn FnMut(NotificationEvent)>>>,
+    last_id: Cell<u64>,
+    // Keeps the listener for clicks forwarded by the service worker alive
+    message_listener: RefCell<Option<Closure<dyn FnMut(MessageEvent)>>>,
+}
+
+thread_local! {
+    static SCHEDULER: Scheduler = Scheduler::default();
+    static LOADED: Cell<bool> = Cell::new(false);
+}
+
+/// Schedule a local notification to be shown at a later time.
+///
+/// This asks the user for permission to show notifications if they have not decided yet. Browsers only show that prompt in response to a user action like a click.
+///
+/// The notification is saved to local storage, so it is still shown if the page reloads before it is due. If the browser supports notification triggers and the page has an active service worker, the service worker shows it, even while the page is closed. Otherwise a notification that comes due while the page is closed is shown the next time the page loads.
+///
+/// Use [`use_notification_events`] to find out when the notification was shown or clicked. To get clicks on notifications shown by the service worker, forward them from your service worker:
+///
+/// ```js
+/// self.addEventListener("notificationclick", (event) => {
+///   const { dioxusNotification, data } = event.notification.data ?? {};
+///   event.notification.close();
+///   event.waitUntil(clients.matchAll({ type: "window" }).then((windows) => {
+///     if (windows.length) {
+///       windows[0].postMessage({ dioxusNotificationClick: dioxusNotification, data });
+///       return windows[0].focus();
+///     }
+///     return clients.openWindow(`/?dioxus-notification-click=${dioxusNotification}`);
+///   }));
+/// });
+/// ```
+pub fn schedule_notification(
+    at: impl Into<ScheduleAt>,
+    content: NotificationContent,
+) -> NotificationId {
+    load();
+    request_permission();
+
+    let now = now_millis();
+    SCHEDULER.with(|scheduler| {
+        let id = scheduler.next_id();
+
+        let mut notification = Scheduled {
+            id,
+            at: at.into().unix_millis(now),
+            title: content.title,
+            body: content.body,
+            icon: content.icon,
+            data: content.data,
+            system: false,
+        };
+        notification.system = schedule_with_service_worker(&notification);
+
+        start_timer(&notification);
+        scheduler.scheduled.borrow_mut().push(notification);
+        scheduler.save();
+
+        NotificationId(id)
+    })
+}
+
+/// Cancel a notification created with [`schedule_notification`] that has not been shown yet.
+///
+/// Notifications that were handed to the service worker are only canceled while the page is open. After that, the browser shows them anyway.
+pub fn cancel_notification(id: NotificationId) {
+    load();
+    SCHEDULER.with(|scheduler| {
+        scheduler
+            .scheduled
+            .borrow_mut()
+            .retain(|notification| notification.id != id.0);
+        if let Some(timer) = scheduler.timers.borrow_mut().remove(&id.0) {
+            if let Some(window) = web_sys::window() {
+                window.clear_timeout_with_handle(timer);
+            }
+        }
+        scheduler.save();
+    });
+}
+
+/// Listen for [`NotificationEvent`]s of notifications created with [`schedule_notification`].
+///
+/// Events of notifications that were shown while the page was closed, or before any component listened, are passed to the first handler that is added.
+///
+/// ```rust, ignore
+/// use_notification_events(cx, |event| {
+///     if event.kind == NotificationEventKind::Clicked {
+///         log::info!("Opened from notification {:?}", event.data);
+///     }
+/// });
+/// ```
+pub fn use_notification_events(cx: &ScopeState, handler: impl FnMut(&NotificationEvent) + 'static) {
+    cx.use_hook(|| {
+        load();
+        let mut handler: NotificationHandler = Box::new(handler);
+        SCHEDULER.with(|scheduler| {
+            for event in scheduler.missed.borrow_mut().drain(..) {
+                handler(&event);
+            }
+            let id = scheduler.next_handler.get();
+            scheduler.next_handler.set(id + 1);
+            scheduler.handlers.borrow_mut().insert(id, handler);
+            Handler(id)
+        })
+    });
+
+    struct Handler(usize);
+    impl Drop for Handler {
+        fn drop(&mut self) {
+            SCHEDULER.with(|scheduler| scheduler.handlers.borrow_mut().remove(&self.0));
+        }
+    }
+}
+
+/// Provides the WebNotificationProvider through [`cx.provide_context`].
+pub(crate) fn init_notifications(cx: &ScopeState) {
+    let provider: Rc<dyn NotificationProvider> = Rc::new(WebNotificationProvider);
+    cx.provide_context(provider);
+}
+
+/// Shows the notifications of `use_notification` with `new Notification()`.
+///
+/// This asks the user for permission to show notifications if they have not decided yet, and shows the notification once it is granted. Browsers only show notifications with action buttons from a service worker, so the actions are not shown.
+pub struct WebNotificationProvider;
+
+impl NotificationProvider for WebNotificationProvider {
+    fn show(
+        &self,
+        content: NotificationContent,
+        on_event: Box<dyn FnMut(NotificationEvent)>,
+    ) -> NotificationId {
+        load();
+        let notification = SCHEDULER.with(|scheduler| {
+            let id = scheduler.next_id();
+            scheduler.shown.borrow_mut().insert(id, on_event);
+            Scheduled {
+                id,
+                at: now_millis(),
+                title: content.title,
+                body: content.body,
+                icon: content.icon,
+                data: content.data,
+                system: false,
+            }
+        });
+        let id = NotificationId(notification.id);
+
+        match Notification::permission() {
+            NotificationPermission::Default => {
+                let Ok(permission) = Notification::request_permission() else {
+                    return id;
+                };
+                wasm_bindgen_futures::spawn_local(async move {
+                    _ = wasm_bindgen_futures::JsFuture::from(permission).await;
+                    deliver(&notification);
+                });
+            }
+            _ => deliver(&notification),
+        }
+
+        id
+    }
+}
+
+impl Scheduler {
+    fn next_id(&self) -> u64 {
+        let id = now_millis().max(self.last_id.get() + 1);
+        self.last_id.set(id);
+        id
+    }
+
+    fn emit(&self, event: NotificationEvent) {
+        let callback = self.shown.borrow_mut().remove(&event.id.0);
+        if let Some(mut callback) = callback {
+            // Nothing happens to a notification after it is clicked
+            let done = event.kind != NotificationEventKind::Delivered;
+            let id = event.id.0;
+            callback(event);
+            if !done {
+                self.shown.borrow_mut().insert(id, callback);
+            }
+            return;
+        }
+
+        if event.kind == NotificationEventKind::Delivered {
+            self.scheduled
+                .borrow_mut()
+                .retain(|notification| notification.id != event.id.0);
+            self.timers.borrow_mut().remove(&event.id.0);
+            self.save();
+        }
+
+        let mut handlers = self.handlers.borrow_mut();
+        if handlers.is_empty() {
+            self.missed.borrow_mut().push(event);
+            return;
+        }
+        for handler in handlers.values_mut() {
+            handler(&event);
+        }
+    }
+
+    fn save(&self) {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        let json = serde_json::to_string(&*self.scheduled.borrow());
+        if let (Some(storage), Ok(json)) = (storage, json) {
+            if let Err(err) = storage.set_item(STORAGE_KEY, &json) {
+                tracing::error!("Failed to save scheduled notifications: {err:?}");
+            }
+        }
+    }
+}
+
+/// Load the notifications that were scheduled before the page was reloaded and start their timers. This only does anything the first time it is called.
+fn load() {
+    if LOADED.with(|loaded| loaded.replace(true)) {
+        return;
+    }
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let saved: Vec<Scheduled> = window
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    SCHEDULER.with(|scheduler| {
+        scheduler
+            .last_id
+            .set(saved.iter().map(|saved| saved.id).max().unwrap_or(0));
+
+        let now = now_millis();
+        for notification in saved {
+            if notification.system && notification.at <= now {
+                // The service worker already showed it while the page was closed
+                scheduler.missed.borrow_mut().push(NotificationEvent {
+                    id: NotificationId(notification.id),
+                    kind: NotificationEventKind::Delivered,
+                    data: notification.data,
+                    action: None,
+                });
+            } else {
+                start_timer(&notification);
+                scheduler.scheduled.borrow_mut().push(notification);
+            }
+        }
+        scheduler.save();
+
+        // The service worker opened the page because a notification was clicked
+        let clicked = window
+            .location()
+            .search()
+            .ok()
+            .and_then(|search| web_sys::UrlSearchParams::new_with_str(&search).ok())
+            .and_then(|params| params.get(CLICK_PARAM))
+            .and_then(|id| id.parse().ok());
+        if let Some(id) = clicked {
+            scheduler.missed.borrow_mut().push(NotificationEvent {
+                id: NotificationId(id),
+                kind: NotificationEventKind::Clicked,
+                data: None,
+                action: None,
+            });
+        }
+
+        // The service worker forwards clicks while the page is open
+        if let Ok(container) = js_sys::Reflect::get(&window.navigator(), &"serviceWorker".into()) {
+            if let Some(container) = container.dyn_ref::<web_sys::EventTarget>() {
+                let listener = Closure::<dyn FnMut(MessageEvent)>::new(|message: MessageEvent| {
+                    let message = message.data();
+                    let id = js_sys::Reflect::get(&message, &"dioxusNotificationClick".into())
+                        .ok()
+                        .and_then(|id| id.as_f64());
+                    let data = js_sys::Reflect::get(&message, &"data".into())
+                        .ok()
+                        .and_then(|data| data.as_string());
+                    if let Some(id) = id {
+                        SCHEDULER.with(|scheduler| {
+                            scheduler.emit(NotificationEvent {
+                                id: NotificationId(id as u64),
+                                kind: NotificationEventKind::Clicked,
+                                data,
+                                action: None,
+                            })
+                        });
+                    }
+                });
+                _ = container
+                    .add_event_listener_with_callback("message", listener.as_ref().unchecked_ref());
+                *scheduler.message_listener.borrow_mut() = Some(listener);
+            }
+        }
+    });
+}
+
+fn now_millis() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+fn request_permission() {
+    if Notification::permission() == NotificationPermission::Default {
+        _ = Notification::request_permission();
+    }
+}
+
+fn options(notification: &Scheduled) -> NotificationOptions {
+    let options = js_sys::Object::new();
+    let set = |key: &str, value: JsValue| {
+        _ = js_sys::Reflect::set(&options, &key.into(), &value);
+    };
+    if let Some(body) = &notification.body {
+        set("body", body.into());
+    }
+    if let Some(icon) = &notification.icon {
+        set("icon", icon.into());
+    }
+    set("tag", notification.id.to_string().into());
+    options.unchecked_into()
+}
+
+/// Hand the notification to the service worker with a timestamp trigger, so it is shown even if the page is closed. Returns false if the browser does not support notification triggers or the page has no active service worker.
+fn schedule_with_service_worker(notification: &Scheduled) -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+    let global = js_sys::global();
+    let trigger = js_sys::Reflect::get(&global, &"TimestampTrigger".into())
+        .ok()
+        .and_then(|trigger| trigger.dyn_into::<js_sys::Function>().ok());
+    let controller = js_sys::Reflect::get(&window.navigator(), &"serviceWorker".into())
+        .ok()
+        .filter(|container| !container.is_undefined())
+        .and_then(|container| js_sys::Reflect::get(&container, &"controller".into()).ok())
+        .filter(|controller| !controller.is_null() && !controller.is_undefined());
+    let (Some(trigger), Some(_)) = (trigger, controller) else {
+        return false;
+    };
+    let Ok(trigger) = js_sys::Reflect::construct(
+        &trigger,
+        &js_sys::Array::of1(&(notification.at as f64).into()),
+    ) else {
+        return false;
+    };
+
+    let options = options(notification);
+    let data = js_sys::Object::new();
+    _ = js_sys::Reflect::set(
+        &data,
+        &"dioxusNotification".into(),
+        &(notification.id as f64).into(),
+    );
+    if let Some(user_data) = &notification.data {
+        _ = js_sys::Reflect::set(&data, &"data".into(), &user_data.into());
+    }
+    _ = js_sys::Reflect::set(&options, &"data".into(), &data);
+    _ = js_sys::Reflect::set(&options, &"showTrigger".into(), &trigger);
+
+    let title = notification.title.clone();
+    let ready = js_sys::Reflect::get(&window.navigator(), &"serviceWorker".into())
+        .and_then(|container| js_sys::Reflect::get(&container, &"ready".into()))
+        .ok()
+        .and_then(|ready| ready.dyn_into::<js_sys::Promise>().ok());
+    let Some(ready) = ready else {
+        return false;
+    };
+    wasm_bindgen_futures::spawn_local(async move {
+        let registration = wasm_bindgen_futures::JsFuture::from(ready).await;
+        let shown = registration
+            .map(|registration| registration.unchecked_into::<ServiceWorkerRegistration>())
+            .and_then(|registration| registration.show_notification_with_options(&title, &options));
+        if let Err(err) = shown {
+            tracing::error!("Failed to schedule notification with the service worker: {err:?}");
+        }
+    });
+    true
+}
+
+/// Wait for the notification, show it if the service worker doesn't, and report what happened.
+fn start_timer(notification: &Scheduled) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let delay = notification
+        .at
+        .saturating_sub(now_millis())
+        .min(MAX_TIMEOUT);
+    let id = notification.id;
+    let notification = notification.clone();
+    let callback = Closure::once_into_js(move || {
+        if notification.at > now_millis() {
+            // The delay was longer than a single timeout
+            start_timer(&notification);
+            return;
+        }
+
+        deliver(&notification);
+    });
+
+    match window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        callback.unchecked_ref(),
+        delay as i32,
+    ) {
+        Ok(timer) => {
+            SCHEDULER.with(|scheduler| scheduler.timers.borrow_mut().insert(id, timer));
+        }
+        Err(err) => tracing::error!("Failed to start a notification timer: {err:?}"),
+    }
+}
+
+/// Show the notification if the service worker doesn't, and report that it was delivered.
+fn deliver(notification: &Scheduled) {
+    if !notification.system {
+        show(notification);
+    }
+    SCHEDULER.with(|scheduler| {
+        scheduler.emit(NotificationEvent {
+            id: NotificationId(notification.id),
+            kind: NotificationEventKind::Delivered,
+            data: notification.data.clone(),
+            action: None,
+        })
+    });
+}
+
+fn show(notification: &Scheduled) {
+    if Notification::permission() != NotificationPermission::Granted {
+        tracing::warn!(
+            "Not showing notification {:?} because permission was not granted",
+            notification.title
+        );
+        return;
+    }
+    let shown = match Notification::new_with_options(&notification.title, &options(notification)) {
+        Ok(shown) => shown,
+        Err(err) => {
+            tracing::error!("Failed to show notification: {err:?}");
+            return;
+        }
+    };
+
+    let id = NotificationId(notification.id);
+    let data = notification.data.clone();
+    let onclick = Closure::<dyn FnMut()>::new(move || {
+        SCHEDULER.with(|scheduler| {
+            scheduler.emit(NotificationEvent {
+                id,
+                kind: NotificationEventKind::Clicked,
+                data: data.clone(),
+                action: None,
+            })
+        });
+    });
+    shown.set_onclick(Some(onclick.as_ref().unchecked_ref()));
+    // The handler lives as long as the notification, which the browser owns
+    onclick.forget();
+}
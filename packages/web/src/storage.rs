@@ -0,0 +1,124 @@
+//! Signals that are saved to `localStorage` or `sessionStorage`.
+
+use crate::listener::Listener;
+use dioxus_core::ScopeState;
+use dioxus_signals::{Effect, Signal};
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::JsCast;
+use web_sys::Storage;
+
+/// Create a signal that is saved to `localStorage` under `key` as JSON. The value is kept when the page is reloaded or closed.
+///
+/// The signal starts with the stored value, or the value of `init` if nothing is stored or the stored value can't be deserialized. When another tab changes the key, the signal is updated to match.
+///
+/// If storage is not available, like in private browsing in some browsers or when the code runs outside of a browser during server side rendering, the signal starts with the value of `init` and is not saved.
+///
+/// ```rust, ignore
+/// let theme = use_local_storage(cx, "theme", || "light".to_string());
+/// ```
+pub fn use_local_storage<T>(
+    cx: &ScopeState,
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + PartialEq + 'static,
+{
+    use_storage(cx, StorageKind::Local, key.to_string(), init)
+}
+
+/// Create a signal that is saved to `sessionStorage` under `key` as JSON. The value is kept when the page is reloaded, but not when the tab is closed.
+///
+/// See [`use_local_storage`] for how the signal is loaded and what happens if storage is not available.
+pub fn use_session_storage<T>(
+    cx: &ScopeState,
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + PartialEq + 'static,
+{
+    use_storage(cx, StorageKind::Session, key.to_string(), init)
+}
+
+#[derive(Clone, Copy)]
+enum StorageKind {
+    Local,
+    Session,
+}
+
+impl StorageKind {
+    fn storage(self) -> Option<Storage> {
+        // web-sys panics outside of wasm, so server side rendering uses the initial value
+        if !cfg!(target_arch = "wasm32") {
+            return None;
+        }
+        let window = web_sys::window()?;
+        match self {
+            StorageKind::Local => window.local_storage(),
+            StorageKind::Session => window.session_storage(),
+        }
+        .ok()
+        .flatten()
+    }
+}
+
+fn use_storage<T>(
+    cx: &ScopeState,
+    kind: StorageKind,
+    key: String,
+    init: impl FnOnce() -> T,
+) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + PartialEq + 'static,
+{
+    let (signal, _, _) = cx.use_hook(|| {
+        let storage = kind.storage();
+        let stored = storage.as_ref().and_then(|storage| load(storage, &key));
+        let signal = Signal::new(stored.unwrap_or_else(init));
+
+        let storage = match storage {
+            Some(storage) => storage,
+            None => return (signal, None, None),
+        };
+
+        // Save the value whenever the signal changes
+        let effect = Effect::new({
+            let storage = storage.clone();
+            let key = key.clone();
+            move || {
+                let value = signal.read();
+                match serde_json::to_string(&*value) {
+                    Ok(json) => {
+                        if storage.get_item(&key).ok().flatten().as_deref() != Some(json.as_str()) {
+                            if let Err(err) = storage.set_item(&key, &json) {
+                                tracing::error!("Failed to save {key} to storage: {err:?}");
+                            }
+                        }
+                    }
+                    Err(err) => tracing::error!("Failed to serialize {key}: {err}"),
+                }
+            }
+        });
+
+        // Other tabs fire a storage event when they change the value
+        let listener = web_sys::window().map(|window| {
+            Listener::new(window.unchecked_into(), "storage", move || {
+                if let Some(new) = load(&storage, &key) {
+                    if *signal.peek() != new {
+                        signal.set(new);
+                    }
+                }
+            })
+        });
+
+        (signal, Some(effect), listener)
+    });
+
+    *signal
+}
+
+fn load<T: DeserializeOwned>(storage: &Storage, key: &str) -> Option<T> {
+    let json = storage.get_item(key).ok()??;
+    serde_json::from_str(&json).ok()
+}
@@ -0,0 +1,99 @@
+//! Measure text with the canvas `measureText` API.
+
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus_core::ScopeState;
+use dioxus_html::text_metrics::{Font, TextMetrics, TextMetricsCache};
+use dioxus_signals::{ReadOnlySignal, Signal};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+thread_local! {
+    static CONTEXT: Option<CanvasRenderingContext2d> = create_context();
+    static CACHE: TextMetricsCache = TextMetricsCache::new(1024);
+}
+
+/// Measure the size of a single line of text rendered in the given font.
+///
+/// If the font is a web font that has not loaded yet, this waits for it to load so the measurement matches what the
+/// page will show. Returns `None` if the browser does not support canvas text measurement.
+pub async fn measure_text(font: &Font, text: &str) -> Option<TextMetrics> {
+    if let Some(metrics) = CACHE.with(|cache| cache.get(font, text)) {
+        return Some(metrics);
+    }
+
+    let css = font.to_string();
+    if let Some(fonts) = web_sys::window()
+        .and_then(|window| window.document())
+        .map(|document| document.fonts())
+    {
+        if let Ok(loading) = fonts.load_with_text(&css, text) {
+            _ = JsFuture::from(loading).await;
+        }
+    }
+
+    let metrics = CONTEXT.with(|context| {
+        let context = context.as_ref()?;
+        context.set_font(&css);
+        let measured = context.measure_text(text).ok()?;
+        Some(TextMetrics {
+            width: measured.width() as f32,
+            ascent: measured.font_bounding_box_ascent() as f32,
+            descent: measured.font_bounding_box_descent() as f32,
+        })
+    })?;
+    CACHE.with(|cache| cache.insert(font, text, metrics));
+    Some(metrics)
+}
+
+/// Get the size of a single line of text as a signal. The text is measured again when the font or text changes.
+///
+/// The signal is `None` until the first measurement finishes.
+///
+/// ```rust, ignore
+/// let metrics = use_text_metrics(cx, Font::new("sans-serif", 12.0), label.clone());
+/// let width = metrics.read().map(|metrics| metrics.width).unwrap_or_default();
+/// ```
+pub fn use_text_metrics(
+    cx: &ScopeState,
+    font: Font,
+    text: impl Into<String>,
+) -> ReadOnlySignal<Option<TextMetrics>> {
+    let text = text.into();
+    let (metrics, measuring) = cx.use_hook(|| {
+        (
+            Signal::new(None),
+            Rc::new(RefCell::new(None::<(Font, String)>)),
+        )
+    });
+    let metrics = *metrics;
+
+    let input = (font, text);
+    if measuring.borrow().as_ref() != Some(&input) {
+        *measuring.borrow_mut() = Some(input.clone());
+        let measuring = measuring.clone();
+        cx.spawn(async move {
+            let new = measure_text(&input.0, &input.1).await;
+            // Skip the result if the font or text changed while the font was loading
+            if measuring.borrow().as_ref() != Some(&input) {
+                return;
+            }
+            if *metrics.peek() != new {
+                metrics.set(new);
+            }
+        });
+    }
+
+    ReadOnlySignal::new(metrics)
+}
+
+fn create_context() -> Option<CanvasRenderingContext2d> {
+    let canvas: HtmlCanvasElement = web_sys::window()?
+        .document()?
+        .create_element("canvas")
+        .ok()?
+        .dyn_into()
+        .ok()?;
+    canvas.get_context("2d").ok()??.dyn_into().ok()
+}
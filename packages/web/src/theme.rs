@@ -0,0 +1,122 @@
+//! The color scheme and accent color the user picked in their browser or OS settings.
+
+use dioxus_core::ScopeState;
+use dioxus_html::theme::{AccentColor, ColorScheme};
+use dioxus_signals::{ReadOnlySignal, Signal};
+use wasm_bindgen::JsCast;
+use web_sys::{EventTarget, HtmlElement, MediaQueryList};
+
+use crate::listener::Listener;
+
+/// Get the color scheme of the page as a signal. The signal follows the `prefers-color-scheme` media query, so it is updated when the user switches between light and dark mode.
+///
+/// ```rust, ignore
+/// let scheme = use_color_scheme(cx);
+/// let background = if scheme.read().is_dark() { "black" } else { "white" };
+/// ```
+pub fn use_color_scheme(cx: &ScopeState) -> ReadOnlySignal<ColorScheme> {
+    let (scheme, _) = cx.use_hook(|| {
+        let query = dark_mode_query();
+        let scheme = Signal::new(current_color_scheme(query.as_ref()));
+        let listener = query.map(|query| {
+            let target: EventTarget = query.clone().unchecked_into();
+            Listener::new(target, "change", move || {
+                let new = current_color_scheme(Some(&query));
+                if *scheme.peek() != new {
+                    scheme.set(new);
+                }
+            })
+        });
+        (scheme, listener)
+    });
+
+    ReadOnlySignal::new(*scheme)
+}
+
+/// Get the accent color the user picked in their OS settings as a signal. This reads the CSS `AccentColor` system color.
+///
+/// Browsers do not tell us when the accent color changes, so it is read again whenever the color scheme changes or the page regains focus.
+///
+/// The signal is `None` in browsers that do not support the `AccentColor` system color.
+pub fn use_accent_color(cx: &ScopeState) -> ReadOnlySignal<Option<AccentColor>> {
+    let (accent, _) = cx.use_hook(|| {
+        let accent = Signal::new(current_accent_color());
+        let refresh = move || {
+            let new = current_accent_color();
+            if *accent.peek() != new {
+                accent.set(new);
+            }
+        };
+
+        let mut listeners = Vec::new();
+        if let Some(query) = dark_mode_query() {
+            listeners.push(Listener::new(query.unchecked_into(), "change", refresh));
+        }
+        if let Some(window) = web_sys::window() {
+            listeners.push(Listener::new(window.unchecked_into(), "focus", refresh));
+        }
+        (accent, listeners)
+    });
+
+    ReadOnlySignal::new(*accent)
+}
+
+fn dark_mode_query() -> Option<MediaQueryList> {
+    web_sys::window()?
+        .match_media("(prefers-color-scheme: dark)")
+        .ok()
+        .flatten()
+}
+
+fn current_color_scheme(query: Option<&MediaQueryList>) -> ColorScheme {
+    match query {
+        Some(query) if query.matches() => ColorScheme::Dark,
+        _ => ColorScheme::Light,
+    }
+}
+
+fn current_accent_color() -> Option<AccentColor> {
+    if !web_sys::css::supports_with_property_and_value("color", "AccentColor").unwrap_or(false) {
+        return None;
+    }
+
+    // The system color is only resolved in computed styles, so we read it from a hidden probe element
+    let window = web_sys::window()?;
+    let document = window.document()?;
+    let probe: HtmlElement = document.create_element("div").ok()?.dyn_into().ok()?;
+    let style = probe.style();
+    style.set_property("display", "none").ok()?;
+    style.set_property("color", "AccentColor").ok()?;
+    document.body()?.append_child(&probe).ok()?;
+    let color = window
+        .get_computed_style(&probe)
+        .ok()
+        .flatten()
+        .and_then(|style| style.get_property_value("color").ok());
+    probe.remove();
+
+    parse_rgb(&color?)
+}
+
+/// Parse a computed color like `rgb(0, 122, 255)`.
+fn parse_rgb(color: &str) -> Option<AccentColor> {
+    let channels = color
+        .trim()
+        .strip_prefix("rgba(")
+        .or_else(|| color.trim().strip_prefix("rgb("))?
+        .strip_suffix(')')?;
+    let mut channels = channels
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|channel| !channel.is_empty())
+        .map(|channel| {
+            channel
+                .parse::<f64>()
+                .ok()
+                .map(|c| c.round().clamp(0.0, 255.0) as u8)
+        });
+    Some(AccentColor {
+        red: channels.next()??,
+        green: channels.next()??,
+        blue: channels.next()??,
+    })
+}
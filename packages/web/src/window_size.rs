@@ -0,0 +1,40 @@
+use crate::listener::Listener;
+use dioxus_core::ScopeState;
+use dioxus_html::window_size::WindowSizeProvider;
+use std::{any::Any, rc::Rc};
+use wasm_bindgen::JsCast;
+
+/// Provides the WebWindowSizeProvider through [`cx.provide_context`].
+pub(crate) fn init_window_size(cx: &ScopeState) {
+    let provider: Rc<dyn WindowSizeProvider> = Rc::new(WebWindowSizeProvider);
+    cx.provide_context(provider);
+}
+
+/// Reads the size of the browser window from `innerWidth` and `innerHeight`, and listens for `resize` events on the window.
+pub struct WebWindowSizeProvider;
+
+impl WindowSizeProvider for WebWindowSizeProvider {
+    fn size(&self) -> (u32, u32) {
+        current_size()
+    }
+
+    fn subscribe(&self, mut on_resize: Box<dyn FnMut((u32, u32))>) -> Box<dyn Any> {
+        let window = web_sys::window().unwrap();
+        Box::new(Listener::new(
+            window.unchecked_into(),
+            "resize",
+            move || on_resize(current_size()),
+        ))
+    }
+}
+
+fn current_size() -> (u32, u32) {
+    let window = web_sys::window().unwrap();
+    let dimension = |value: Result<wasm_bindgen::JsValue, _>| {
+        value.ok().and_then(|value| value.as_f64()).unwrap_or(0.0) as u32
+    };
+    (
+        dimension(window.inner_width()),
+        dimension(window.inner_height()),
+    )
+}
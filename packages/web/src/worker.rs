@@ -0,0 +1,243 @@
+//! Run scripts in a dedicated web worker and talk to them with typed messages.
+
+use dioxus_core::ScopeState;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+    rc::Rc,
+};
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+use web_sys::{Blob, BlobPropertyBag, ErrorEvent, MessageEvent, Url, Worker, WorkerOptions};
+
+/// The script a worker created with [`use_worker`] runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerSource {
+    /// Load a classic script from a URL.
+    Url(String),
+    /// Load an ES module from a URL. The script can use `import`.
+    Module(String),
+    /// Run JavaScript source code, like the body of a `self.onmessage = ...` handler.
+    Script(String),
+}
+
+/// Spawn a dedicated web worker to run heavy computations without blocking rendering. The worker is terminated when the component is dropped.
+///
+/// Messages are serialized with serde and sent with `postMessage`, so the worker receives `Req` as a plain JavaScript object in `event.data`, and should `postMessage` a value that deserializes into `Res` back. [`UseWorker::request`] expects one response for every request.
+///
+/// Workers are only available in the browser. When the component is rendered on the server, sending a message returns [`WorkerError::Unsupported`].
+///
+/// ```rust, ignore
+/// let worker = use_worker::<u64, u64>(
+///     cx,
+///     WorkerSource::Script(
+///         "self.onmessage = (e) => { let n = e.data; let a = 0, b = 1; \
+///          for (let i = 0; i < n; i++) [a, b] = [b, a + b]; postMessage(a); };"
+///             .into(),
+///     ),
+/// );
+///
+/// render! {
+///     button {
+///         onclick: move |_| {
+///             to_owned![worker];
+///             async move {
+///                 if let Ok(fib) = worker.request(&40).await {
+///                     tracing::info!("fib(40) = {fib}");
+///                 }
+///             }
+///         },
+///         "Compute"
+///     }
+/// }
+/// ```
+pub fn use_worker<Req, Res>(cx: &ScopeState, source: WorkerSource) -> &UseWorker<Req, Res>
+where
+    Req: Serialize + 'static,
+    Res: DeserializeOwned + 'static,
+{
+    let (worker, _guard) = cx.use_hook(|| {
+        let (sender, receiver) = async_channel::unbounded();
+        let inner = if cfg!(target_arch = "wasm32") {
+            match WorkerInner::spawn::<Res>(&source, sender.clone()) {
+                Ok(inner) => Some(inner),
+                Err(err) => {
+                    tracing::error!("Failed to spawn web worker: {err}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let inner = Rc::new(inner);
+        let worker = UseWorker {
+            inner: inner.clone(),
+            receiver,
+            phantom: PhantomData,
+        };
+        (worker, TerminateOnDrop { inner, sender })
+    });
+    worker
+}
+
+/// A handle to a web worker created with [`use_worker`].
+pub struct UseWorker<Req, Res> {
+    inner: Rc<Option<WorkerInner>>,
+    receiver: async_channel::Receiver<Result<Res, WorkerError>>,
+    phantom: PhantomData<fn(Req)>,
+}
+
+impl<Req, Res> Clone for UseWorker<Req, Res> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            receiver: self.receiver.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<Req: Serialize, Res: DeserializeOwned> UseWorker<Req, Res> {
+    /// Send a message to the worker.
+    pub fn send(&self, message: &Req) -> Result<(), WorkerError> {
+        let inner = (*self.inner).as_ref().ok_or(WorkerError::Unsupported)?;
+        if self.receiver.is_closed() {
+            return Err(WorkerError::Terminated);
+        }
+        let message = message
+            .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+            .map_err(|err| WorkerError::Serialize(err.to_string()))?;
+        inner
+            .worker
+            .post_message(&message)
+            .map_err(|err| WorkerError::Serialize(format!("{err:?}")))
+    }
+
+    /// Wait for the next message from the worker. Errors thrown in the worker are received as [`WorkerError::Worker`].
+    pub async fn recv(&self) -> Result<Res, WorkerError> {
+        if self.inner.is_none() {
+            return Err(WorkerError::Unsupported);
+        }
+        self.receiver
+            .recv()
+            .await
+            .unwrap_or(Err(WorkerError::Terminated))
+    }
+
+    /// Send a message to the worker and wait for its response. Responses are matched to requests in the order they were sent.
+    pub async fn request(&self, message: &Req) -> Result<Res, WorkerError> {
+        self.send(message)?;
+        self.recv().await
+    }
+}
+
+struct WorkerInner {
+    worker: Worker,
+    object_url: Option<String>,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    _onerror: Closure<dyn FnMut(ErrorEvent)>,
+}
+
+impl WorkerInner {
+    fn spawn<Res: DeserializeOwned + 'static>(
+        source: &WorkerSource,
+        sender: async_channel::Sender<Result<Res, WorkerError>>,
+    ) -> Result<Self, WorkerError> {
+        let spawn_error = |err: JsValue| WorkerError::Spawn(format!("{err:?}"));
+
+        let mut object_url = None;
+        let worker = match source {
+            WorkerSource::Url(url) => Worker::new(url),
+            WorkerSource::Module(url) => {
+                let mut options = WorkerOptions::new();
+                options.type_(web_sys::WorkerType::Module);
+                Worker::new_with_options(url, &options)
+            }
+            WorkerSource::Script(code) => {
+                let parts = js_sys::Array::of1(&JsValue::from_str(code));
+                let blob = Blob::new_with_str_sequence_and_options(
+                    &parts,
+                    BlobPropertyBag::new().type_("text/javascript"),
+                )
+                .map_err(spawn_error)?;
+                let url = Url::create_object_url_with_blob(&blob).map_err(spawn_error)?;
+                let worker = Worker::new(&url);
+                object_url = Some(url);
+                worker
+            }
+        }
+        .map_err(spawn_error)?;
+
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new({
+            let sender = sender.clone();
+            move |event: MessageEvent| {
+                let message = serde_wasm_bindgen::from_value(event.data())
+                    .map_err(|err| WorkerError::Deserialize(err.to_string()));
+                _ = sender.try_send(message);
+            }
+        });
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        let onerror = Closure::<dyn FnMut(ErrorEvent)>::new(move |event: ErrorEvent| {
+            _ = sender.try_send(Err(WorkerError::Worker(event.message())));
+        });
+        worker.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+        Ok(Self {
+            worker,
+            object_url,
+            _onmessage: onmessage,
+            _onerror: onerror,
+        })
+    }
+}
+
+/// Terminates the worker when the component that created it is dropped, even if handles to it are still alive.
+struct TerminateOnDrop<Res> {
+    inner: Rc<Option<WorkerInner>>,
+    sender: async_channel::Sender<Result<Res, WorkerError>>,
+}
+
+impl<Res> Drop for TerminateOnDrop<Res> {
+    fn drop(&mut self) {
+        self.sender.close();
+        if let Some(inner) = &*self.inner {
+            inner.worker.terminate();
+            if let Some(url) = &inner.object_url {
+                _ = Url::revoke_object_url(url);
+            }
+        }
+    }
+}
+
+/// An error talking to a worker created with [`use_worker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorkerError {
+    /// Workers are not available, like when rendering on the server, or the worker failed to start.
+    Unsupported,
+    /// The worker could not be created.
+    Spawn(String),
+    /// The message could not be sent to the worker.
+    Serialize(String),
+    /// The message from the worker could not be deserialized.
+    Deserialize(String),
+    /// The worker threw an error.
+    Worker(String),
+    /// The worker was terminated because the component that created it was dropped.
+    Terminated,
+}
+
+impl Display for WorkerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkerError::Unsupported => write!(f, "web workers are not available"),
+            WorkerError::Spawn(err) => write!(f, "failed to spawn the worker: {err}"),
+            WorkerError::Serialize(err) => write!(f, "failed to send the message: {err}"),
+            WorkerError::Deserialize(err) => write!(f, "failed to read the response: {err}"),
+            WorkerError::Worker(err) => write!(f, "the worker threw an error: {err}"),
+            WorkerError::Terminated => write!(f, "the worker was terminated"),
+        }
+    }
+}
+
+impl std::error::Error for WorkerError {}